@@ -0,0 +1,89 @@
+// Accept Invitation Page JavaScript
+document.addEventListener('DOMContentLoaded', async function() {
+    const token = new URLSearchParams(window.location.search).get('token');
+    const acceptForm = document.getElementById('accept-form');
+    const emailInput = document.getElementById('email');
+    const passwordInput = document.getElementById('password');
+    const acceptBtn = document.getElementById('accept-btn');
+    const title = document.getElementById('invite-title');
+    const details = document.getElementById('invite-details');
+
+    function showMessage(message) {
+        details.textContent = message;
+    }
+
+    async function errorMessage(response, fallback) {
+        try {
+            const errorData = await response.json();
+            return errorData.message || fallback;
+        } catch (e) {
+            return response.statusText || fallback;
+        }
+    }
+
+    if (!token) {
+        showMessage('This invitation link is incomplete. Please use the link from your email.');
+        return;
+    }
+
+    // Look up who the invitation is for before asking for a password
+    try {
+        const response = await fetch('/api/invitations/preview', {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ token })
+        });
+
+        if (!response.ok) {
+            showMessage(await errorMessage(response, 'This invitation could not be found.'));
+            return;
+        }
+
+        const { invitation } = await response.json();
+
+        if (invitation.status !== 'pending') {
+            showMessage(`This invitation is ${invitation.status}. Ask your practice admin to send a new one.`);
+            return;
+        }
+
+        title.textContent = `Join ${invitation.practice_name}`;
+        showMessage(`You've been invited as: ${invitation.roles.join(', ').replace(/_/g, ' ')}`);
+        emailInput.value = invitation.email;
+        acceptForm.style.display = 'block';
+    } catch (error) {
+        showMessage('Network error: Please check your connection and try again');
+        console.error('Invitation lookup error:', error);
+        return;
+    }
+
+    acceptForm.addEventListener('submit', async function(e) {
+        e.preventDefault();
+        acceptBtn.disabled = true;
+
+        try {
+            const response = await fetch('/api/invitations/accept', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ token, password: passwordInput.value, use_cookie: true })
+            });
+
+            if (!response.ok) {
+                acceptBtn.disabled = false;
+                showMessage(await errorMessage(response, 'Could not accept the invitation'));
+                return;
+            }
+
+            const data = await response.json();
+            localStorage.setItem('tokenExpiresAt', data.expires_at);
+
+            showMessage('Welcome aboard! Redirecting to your dashboard...');
+            setTimeout(() => {
+                window.location.href = '/dashboard.html';
+            }, 1000);
+        } catch (error) {
+            acceptBtn.disabled = false;
+            showMessage('Network error: Please check your connection and try again');
+            console.error('Accept invitation error:', error);
+        }
+    });
+});
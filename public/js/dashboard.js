@@ -2,6 +2,8 @@
 document.addEventListener('DOMContentLoaded', async function() {
     // Check authentication first
     await checkAuthentication();
+    startIdleWatch();
+    loadTodaysSchedule();
     
     // User dropdown toggle
     const userMenu = document.querySelector('.user-menu');
@@ -36,23 +38,19 @@ document.addEventListener('DOMContentLoaded', async function() {
     signoutBtn.addEventListener('click', async function(e) {
         e.preventDefault();
         
-        // Always clear local data first
-        const token = localStorage.getItem('authToken') || sessionStorage.getItem('authToken');
+        const csrfToken = getCsrfToken();
         
-        // Clear stored authentication data immediately
-        localStorage.removeItem('authToken');
-        sessionStorage.removeItem('authToken');
-        localStorage.removeItem('userEmail');
-        sessionStorage.removeItem('userEmail');
+        // Clear stored authentication data immediately, including any left by older versions
+        clearStoredAuth();
         
         try {
-            if (token) {
+            if (csrfToken) {
                 // Try to notify the server, but don't wait for success
                 fetch('/api/auth/signout', {
                     method: 'POST',
                     headers: {
                         'Content-Type': 'application/json',
-                        'Authorization': `Bearer ${token}`
+                        'X-CSRF-Token': csrfToken
                     }
                 }).catch(error => {
                     console.log('Server signout failed, but local signout completed:', error);
@@ -72,50 +70,220 @@ document.addEventListener('DOMContentLoaded', async function() {
         }
     });
     
+    // Read the double-submit CSRF token the server set alongside the session cookies
+    function getCsrfToken() {
+        const match = document.cookie.match(/(?:^|;\s*)breeze_csrf_token=([^;]*)/);
+        return match ? decodeURIComponent(match[1]) : null;
+    }
+    
     // Authentication check function
     async function checkAuthentication() {
-        const token = localStorage.getItem('authToken') || sessionStorage.getItem('authToken');
-        
-        if (!token) {
-            // No token found, redirect to signin
-            console.log('No token found, redirecting to signin');
+        // The session cookies are HttpOnly, so the CSRF cookie stands in for "signed in"
+        if (!getCsrfToken()) {
+            console.log('No session found, redirecting to signin');
             window.location.href = '/signin.html';
             return;
         }
         
-        // Basic JWT token format check (should have 3 parts separated by dots)
-        const tokenParts = token.split('.');
-        if (tokenParts.length !== 3) {
-            console.log('Invalid token format, redirecting to signin');
+        const expiresAt = parseInt(localStorage.getItem('tokenExpiresAt'), 10);
+        const currentTime = Math.floor(Date.now() / 1000);
+        
+        if (!expiresAt || expiresAt < currentTime) {
+            if (await refreshSession()) {
+                console.log('Session expired, refreshed');
+                return;
+            }
+            console.log('Session expired, redirecting to signin');
             clearAuthData();
             return;
         }
         
+        console.log('Session appears valid and not expired');
+    }
+    
+    // Poll the server's idle timer without counting as activity, warning shortly before logoff
+    function startIdleWatch() {
+        let warned = false;
+        setInterval(async () => {
+            try {
+                const response = await fetch('/api/auth/session/idle');
+                if (response.status === 401) {
+                    clearAuthData();
+                    return;
+                }
+                if (!response.ok) {
+                    return;
+                }
+                const status = await response.json();
+                const remaining = status.seconds_remaining;
+                if (remaining !== null && remaining !== undefined && remaining <= 120) {
+                    if (!warned) {
+                        showNotification('You will be signed out soon due to inactivity', 'warning');
+                        warned = true;
+                    }
+                } else {
+                    warned = false;
+                }
+            } catch (error) {
+                console.error('Idle check error:', error);
+            }
+        }, 60000);
+    }
+    
+    // Fill "Today's Schedule" with the caller's appointments in their first practice
+    async function loadTodaysSchedule() {
+        const list = document.getElementById('appointment-list');
+        const start = new Date();
+        start.setHours(0, 0, 0, 0);
+        const end = new Date(start);
+        end.setDate(end.getDate() + 1);
+        document.getElementById('schedule-date').textContent = start.toLocaleDateString(undefined, {
+            weekday: 'long', year: 'numeric', month: 'long', day: 'numeric'
+        });
+        
         try {
-            // Decode the JWT payload to check expiration (basic check)
-            const payload = JSON.parse(atob(tokenParts[1]));
-            const currentTime = Math.floor(Date.now() / 1000);
+            const [meResponse, practicesResponse] = await Promise.all([
+                fetch('/api/auth/me'),
+                fetch('/api/practices')
+            ]);
+            if (!meResponse.ok || !practicesResponse.ok) {
+                throw new Error('Failed to load profile or practices');
+            }
+            const { user } = await meResponse.json();
+            const { practices } = await practicesResponse.json();
+            if (!practices.length) {
+                showScheduleMessage(list, 'You are not a member of any practice yet.');
+                return;
+            }
             
-            if (payload.exp && payload.exp < currentTime) {
-                console.log('Token expired, redirecting to signin');
-                clearAuthData();
+            const query = new URLSearchParams({
+                from: start.toISOString(),
+                to: end.toISOString(),
+                clinician_id: user.user_id
+            });
+            const response = await fetch(`/api/practices/${practices[0].id}/appointments?${query}`);
+            if (!response.ok) {
+                throw new Error(`Appointments request failed with ${response.status}`);
+            }
+            const { appointments } = await response.json();
+            if (!appointments.length) {
+                showScheduleMessage(list, 'No appointments today.');
                 return;
             }
+            list.replaceChildren(...appointments.map(renderAppointment));
+        } catch (error) {
+            console.error('Schedule load error:', error);
+            showScheduleMessage(list, 'Could not load today\'s appointments.');
+        }
+    }
+    
+    function showScheduleMessage(list, message) {
+        const empty = document.createElement('p');
+        empty.className = 'appointment-empty';
+        empty.textContent = message;
+        list.replaceChildren(empty);
+    }
+    
+    function renderAppointment(appointment) {
+        const startsAt = new Date(appointment.starts_at);
+        const endsAt = new Date(appointment.ends_at);
+        const now = new Date();
+        const open = appointment.status === 'scheduled' || appointment.status === 'checked_in';
+        const telehealth = appointment.location_kind === 'telehealth';
+        
+        const item = document.createElement('div');
+        item.className = 'appointment-item';
+        if (open && startsAt <= now && now < endsAt) {
+            item.classList.add('current');
+        }
+        if (appointment.status === 'cancelled') {
+            item.classList.add('cancelled');
+        }
+        
+        const time = document.createElement('div');
+        time.className = 'appointment-time';
+        const clock = document.createElement('span');
+        clock.className = 'time';
+        clock.textContent = startsAt.toLocaleTimeString(undefined, { hour: 'numeric', minute: '2-digit' });
+        const duration = document.createElement('span');
+        duration.className = 'duration';
+        duration.textContent = `${Math.round((endsAt - startsAt) / 60000)} min`;
+        time.append(clock, duration);
+        
+        const info = document.createElement('div');
+        info.className = 'appointment-info';
+        const name = document.createElement('h4');
+        const client = appointment.client;
+        name.textContent = client
+            ? `${client.preferred_name || client.first_name} ${client.last_name}`
+            : 'Client';
+        const details = document.createElement('p');
+        details.textContent = appointment.status === 'scheduled'
+            ? (appointment.location || (telehealth ? 'Video session' : 'In person'))
+            : appointment.status.replace('_', ' ');
+        const type = document.createElement('span');
+        type.className = `appointment-type ${telehealth ? 'telehealth' : 'in-person'}`;
+        type.textContent = telehealth ? 'Telehealth' : 'In-Person';
+        info.append(name, details, type);
+        
+        const actions = document.createElement('div');
+        actions.className = 'appointment-actions';
+        if (open && telehealth && appointment.telehealth_url) {
+            const join = document.createElement('button');
+            join.className = 'join-btn';
+            join.textContent = 'Join Call';
+            join.addEventListener('click', () => {
+                window.open(appointment.telehealth_url, '_blank', 'noopener');
+            });
+            actions.append(join);
+        }
+        
+        item.append(time, info, actions);
+        return item;
+    }
+    
+    // Exchange the refresh cookie for a new session
+    async function refreshSession() {
+        const csrfToken = getCsrfToken();
+        if (!csrfToken) {
+            return false;
+        }
+        
+        try {
+            const response = await fetch('/api/auth/refresh', {
+                method: 'POST',
+                headers: {
+                    'Content-Type': 'application/json',
+                    'X-CSRF-Token': csrfToken
+                },
+                body: JSON.stringify({})
+            });
             
-            console.log('Token appears valid and not expired');
+            if (!response.ok) {
+                return false;
+            }
             
+            const data = await response.json();
+            localStorage.setItem('tokenExpiresAt', data.expires_at);
+            return true;
         } catch (error) {
-            console.error('Token validation error:', error);
-            clearAuthData();
+            console.error('Session refresh error:', error);
+            return false;
         }
     }
     
-    // Helper function to clear auth data and redirect
-    function clearAuthData() {
+    function clearStoredAuth() {
         localStorage.removeItem('authToken');
+        localStorage.removeItem('refreshToken');
+        localStorage.removeItem('tokenExpiresAt');
         sessionStorage.removeItem('authToken');
         localStorage.removeItem('userEmail');
         sessionStorage.removeItem('userEmail');
+    }
+    
+    // Helper function to clear auth data and redirect
+    function clearAuthData() {
+        clearStoredAuth();
         window.location.href = '/signin.html';
     }
     
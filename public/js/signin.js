@@ -165,15 +165,15 @@ document.addEventListener('DOMContentLoaded', function() {
                 headers: {
                     'Content-Type': 'application/json'
                 },
-                body: JSON.stringify({ email, password })
+                body: JSON.stringify({ email, password, use_cookie: true })
             });
 
             if (response.ok) {
                 const data = await response.json();
                 
-                // Store the token
-                if (data.token) {
-                    localStorage.setItem('authToken', data.token);
+                // The session itself lives in HttpOnly cookies; only the expiry is kept here
+                if (data.csrf_token) {
+                    localStorage.setItem('tokenExpiresAt', data.expires_at);
                     
                     // Show success message
                     showGlobalSuccess('Sign in successful! Redirecting to dashboard...');
@@ -184,7 +184,7 @@ document.addEventListener('DOMContentLoaded', function() {
                     }, 1000);
                 } else {
                     setLoadingState(false);
-                    showGlobalError('Authentication failed: No token received');
+                    showGlobalError('Authentication failed: No session received');
                 }
             } else {
                 // Handle HTTP error responses
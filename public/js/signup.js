@@ -282,7 +282,10 @@ document.addEventListener('DOMContentLoaded', function() {
                 }, 2000);
             } else {
                 // Handle specific error cases
-                if (data.message) {
+                if (data.code === 'weak_password' && data.violations && data.violations.length) {
+                    showError(passwordInput, data.violations.map(v => v.message).join('<br>'));
+                    showGlobalError(data.message);
+                } else if (data.message) {
                     showGlobalError(data.message);
                 } else {
                     showGlobalError('Failed to create account. Please try again.');
@@ -0,0 +1,196 @@
+use poem::web::Data;
+use poem_openapi::{
+    OpenApi,
+    param::{Path, Query},
+    payload::Json,
+};
+
+use crate::{
+    api::ApiTags,
+    domain::error::http_response::AppHttpResponse,
+    routes::{
+        appointments::{
+            cancel_appointment_series::{
+                CancelAppointmentSeriesRequest, cancel_appointment_series_handler,
+            },
+            create_appointment::{CreateAppointmentRequest, create_appointment_handler},
+            create_appointment_series::{
+                CreateAppointmentSeriesRequest, create_appointment_series_handler,
+            },
+            get_appointment::get_appointment_handler,
+            get_appointment_series::get_appointment_series_handler,
+            list_appointments::{AppointmentListQuery, list_appointments_handler},
+            update_appointment::{UpdateAppointmentRequest, update_appointment_handler},
+        },
+        auth::guard::AuthenticatedUser,
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct AppointmentApi;
+
+#[OpenApi(tag = "ApiTags::Appointments")]
+impl AppointmentApi {
+    /// Appointments overlapping `[from, to)` (RFC 3339, at most 93 days apart), ordered by
+    /// start. `clinician_id`, `client_id` and `status` narrow the list.
+    #[oai(path = "/practices/:practice_id/appointments", method = "get")]
+    #[tracing::instrument(name = "list_appointments", skip_all, fields(req_id=%ctx.request_id))]
+    #[allow(clippy::too_many_arguments)]
+    async fn list_appointments(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        from: Query<Option<String>>,
+        to: Query<Option<String>>,
+        clinician_id: Query<Option<String>>,
+        client_id: Query<Option<String>>,
+        status: Query<Option<String>>,
+    ) -> AppHttpResponse {
+        let query = AppointmentListQuery {
+            from: from.0,
+            to: to.0,
+            clinician_id: clinician_id.0,
+            client_id: client_id.0,
+            status: status.0,
+        };
+
+        match list_appointments_handler(state, auth, &practice_id, query).await {
+            Ok(appointments) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "appointments": appointments })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    /// Fails with `clinician_double_booked` when it overlaps another of the clinician's
+    /// appointments that is not cancelled.
+    #[oai(path = "/practices/:practice_id/appointments", method = "post")]
+    #[tracing::instrument(name = "create_appointment", skip_all, fields(req_id=%ctx.request_id))]
+    async fn create_appointment(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<CreateAppointmentRequest>,
+    ) -> AppHttpResponse {
+        match create_appointment_handler(state, auth, &practice_id, payload).await {
+            Ok(appointment) => {
+                AppHttpResponse::Created(Json(serde_json::json!({ "appointment": appointment })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/appointments/:appointment_id",
+        method = "get"
+    )]
+    #[tracing::instrument(name = "get_appointment", skip_all, fields(req_id=%ctx.request_id))]
+    async fn get_appointment(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        appointment_id: Path<String>,
+    ) -> AppHttpResponse {
+        match get_appointment_handler(state, auth, &practice_id, &appointment_id).await {
+            Ok(appointment) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "appointment": appointment })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    /// Reschedules, reassigns or relocates a scheduled appointment, or moves it along
+    /// scheduled → checked_in → completed, or to no_show or cancelled. Changing one
+    /// occurrence of a series marks it as an exception.
+    #[oai(
+        path = "/practices/:practice_id/appointments/:appointment_id",
+        method = "patch"
+    )]
+    #[tracing::instrument(name = "update_appointment", skip_all, fields(req_id=%ctx.request_id))]
+    async fn update_appointment(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        appointment_id: Path<String>,
+        payload: Json<UpdateAppointmentRequest>,
+    ) -> AppHttpResponse {
+        match update_appointment_handler(state, auth, &practice_id, &appointment_id, payload).await
+        {
+            Ok(appointment) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "appointment": appointment })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    /// Books every occurrence of the rule at once, or none if any of them would double-book
+    /// the clinician.
+    #[oai(path = "/practices/:practice_id/appointment_series", method = "post")]
+    #[tracing::instrument(name = "create_appointment_series", skip_all, fields(req_id=%ctx.request_id))]
+    async fn create_appointment_series(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<CreateAppointmentSeriesRequest>,
+    ) -> AppHttpResponse {
+        match create_appointment_series_handler(state, auth, &practice_id, payload).await {
+            Ok(series) => AppHttpResponse::Created(Json(serde_json::json!({ "series": series }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/appointment_series/:series_id",
+        method = "get"
+    )]
+    #[tracing::instrument(name = "get_appointment_series", skip_all, fields(req_id=%ctx.request_id))]
+    async fn get_appointment_series(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        series_id: Path<String>,
+    ) -> AppHttpResponse {
+        match get_appointment_series_handler(state, auth, &practice_id, &series_id).await {
+            Ok(series) => AppHttpResponse::Ok(Json(serde_json::json!({ "series": series }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    /// Cancels the rest of a series; earlier occurrences and those already checked in or
+    /// closed are kept.
+    #[oai(
+        path = "/practices/:practice_id/appointment_series/:series_id/cancel",
+        method = "post"
+    )]
+    #[tracing::instrument(name = "cancel_appointment_series", skip_all, fields(req_id=%ctx.request_id))]
+    async fn cancel_appointment_series(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        series_id: Path<String>,
+        payload: Json<CancelAppointmentSeriesRequest>,
+    ) -> AppHttpResponse {
+        match cancel_appointment_series_handler(state, auth, &practice_id, &series_id, payload)
+            .await
+        {
+            Ok(series) => AppHttpResponse::Ok(Json(serde_json::json!({ "series": series }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use poem::web::Data;
+use poem_openapi::{
+    OpenApi,
+    param::{Path, Query},
+    payload::Json,
+};
+
+use crate::{
+    api::ApiTags,
+    domain::error::http_response::AppHttpResponse,
+    routes::{
+        audit::list_audit_log::{AuditLogQuery, list_audit_log_handler},
+        auth::guard::AuthenticatedUser,
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct AuditApi;
+
+#[OpenApi(tag = "ApiTags::Audit")]
+impl AuditApi {
+    /// Audit entries for a practice, newest first. Only owners and admins see entries;
+    /// everyone else gets an empty page.
+    #[oai(path = "/practices/:practice_id/audit", method = "get")]
+    #[tracing::instrument(name = "list_audit_log", skip_all, fields(req_id=%ctx.request_id))]
+    #[allow(clippy::too_many_arguments)]
+    async fn list_audit_log(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        table_name: Query<Option<String>>,
+        operation: Query<Option<String>>,
+        actor_user_id: Query<Option<String>>,
+        row_id: Query<Option<String>>,
+        from: Query<Option<String>>,
+        to: Query<Option<String>>,
+        cursor: Query<Option<i64>>,
+        limit: Query<Option<usize>>,
+    ) -> AppHttpResponse {
+        let query = AuditLogQuery {
+            table_name: table_name.0,
+            operation: operation.0,
+            actor_user_id: actor_user_id.0,
+            row_id: row_id.0,
+            from: from.0,
+            to: to.0,
+            cursor: cursor.0,
+            limit: limit.0,
+        };
+
+        match list_audit_log_handler(state, auth, &practice_id, query).await {
+            Ok(page) => AppHttpResponse::Ok(Json(serde_json::json!({
+                "entries": page.entries,
+                "next_cursor": page.next_cursor,
+            }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
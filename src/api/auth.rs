@@ -1,15 +1,36 @@
-use poem::web::Data;
-use poem_openapi::{OpenApi, payload::Json};
+use poem::{
+    Request,
+    web::{Data, cookie::CookieJar},
+};
+use poem_openapi::{OpenApi, param::Path, payload::Json};
 
 use crate::{
     domain::error::http_response::AppHttpResponse,
     routes::auth::{
         delete_user::{DeleteUserRequest, delete_user_handler},
-        guard::AuthenticatedUser,
+        forgot_password::{ForgotPasswordRequest, forgot_password_handler},
+        get_me::get_me_handler,
+        guard::{Aal1AuthenticatedUser, AuthenticatedUser, PassiveAuthenticatedUser},
+        list_sessions::list_sessions_handler,
+        magic_link::{MagicLinkRequest, magic_link_handler},
+        mfa_challenge::{MfaChallengeRequest, mfa_challenge_handler},
+        mfa_enroll::{MfaEnrollRequest, mfa_enroll_handler},
+        mfa_enroll_phone::{MfaEnrollPhoneRequest, mfa_enroll_phone_handler},
+        mfa_factors::mfa_factors_handler,
+        mfa_unenroll::mfa_unenroll_handler,
+        mfa_verify::{MfaVerifyRequest, mfa_verify_handler},
+        refresh::{RefreshRequest, refresh_handler},
+        reset_password::{ResetPasswordRequest, reset_password_handler},
         retrieve_user_id::{RetrieveUserIdRequest, retrieve_user_id_handler},
+        revoke_session::revoke_session_handler,
+        revoke_sessions::revoke_sessions_handler,
+        session_cookie::session_body,
+        session_idle::session_idle_handler,
         signin::{SigninRequest, signin_handler},
         signout::signout_handler,
         signup::{SignupRequest, signup_handler},
+        update_me::{UpdateMeRequest, update_me_handler},
+        verify_otp::{VerifyOtpRequest, verify_otp_handler},
     },
     state::AppState,
     utils::tracing::RequestContext,
@@ -18,12 +39,6 @@ use crate::{
 #[derive(Debug)]
 pub struct AppApi;
 
-// TODO: forgot email and reset password routes
-// TODO: update user details route
-// TODO: send invite route (requires practices api)
-// TODO: TOTP/MFA routes
-// TODO: SMS 2FA routes
-
 #[OpenApi]
 impl AppApi {
     #[oai(path = "/health", method = "get")]
@@ -39,11 +54,11 @@ impl AppApi {
     async fn delete_user(
         &self,
         ctx: RequestContext,
-        _auth: AuthenticatedUser,
+        auth: AuthenticatedUser,
         state: Data<&AppState>,
         payload: Json<DeleteUserRequest>,
     ) -> AppHttpResponse {
-        match delete_user_handler(state, payload).await {
+        match delete_user_handler(state, &ctx, auth, payload).await {
             Ok(()) => AppHttpResponse::Ok(Json(
                 serde_json::json!({ "message": "User deleted successfully" }),
             )),
@@ -51,16 +66,219 @@ impl AppApi {
         }
     }
 
+    #[oai(path = "/auth/forgot_password", method = "post")]
+    #[tracing::instrument(name = "forgot_password", skip_all, fields(req_id=%ctx.request_id))]
+    async fn forgot_password(
+        &self,
+        ctx: RequestContext,
+        state: Data<&AppState>,
+        payload: Json<ForgotPasswordRequest>,
+    ) -> AppHttpResponse {
+        match forgot_password_handler(state, payload).await {
+            Ok(response) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "message": response.message })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/magic_link", method = "post")]
+    #[tracing::instrument(name = "magic_link", skip_all, fields(req_id=%ctx.request_id))]
+    async fn magic_link(
+        &self,
+        ctx: RequestContext,
+        state: Data<&AppState>,
+        payload: Json<MagicLinkRequest>,
+    ) -> AppHttpResponse {
+        match magic_link_handler(state, payload).await {
+            Ok(response) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "message": response.message })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/me", method = "get")]
+    #[tracing::instrument(name = "get_me", skip_all, fields(req_id=%ctx.request_id))]
+    async fn get_me(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+    ) -> AppHttpResponse {
+        match get_me_handler(state, auth).await {
+            Ok(profile) => AppHttpResponse::Ok(Json(serde_json::json!({ "user": profile }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/me", method = "patch")]
+    #[tracing::instrument(name = "update_me", skip_all, fields(req_id=%ctx.request_id))]
+    async fn update_me(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        payload: Json<UpdateMeRequest>,
+    ) -> AppHttpResponse {
+        match update_me_handler(state, &ctx, auth, payload).await {
+            Ok(profile) => AppHttpResponse::Ok(Json(serde_json::json!({ "user": profile }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/mfa/challenge", method = "post")]
+    #[tracing::instrument(name = "mfa_challenge", skip_all, fields(req_id=%ctx.request_id))]
+    async fn mfa_challenge(
+        &self,
+        ctx: RequestContext,
+        caller: Aal1AuthenticatedUser,
+        state: Data<&AppState>,
+        payload: Json<MfaChallengeRequest>,
+    ) -> AppHttpResponse {
+        match mfa_challenge_handler(state, caller.user, payload).await {
+            Ok(challenge) => AppHttpResponse::Ok(Json(serde_json::json!({
+                "challenge_id": challenge.id,
+                "expires_at": challenge.expires_at,
+            }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/mfa/enroll", method = "post")]
+    #[tracing::instrument(name = "mfa_enroll", skip_all, fields(req_id=%ctx.request_id))]
+    async fn mfa_enroll(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        payload: Json<MfaEnrollRequest>,
+    ) -> AppHttpResponse {
+        match mfa_enroll_handler(state, auth, payload).await {
+            Ok(enrollment) => AppHttpResponse::Created(Json(serde_json::json!({
+                "factor_id": enrollment.factor_id,
+                "friendly_name": enrollment.friendly_name,
+                "uri": enrollment.uri,
+                "qr_code": enrollment.qr_code,
+                "secret": enrollment.secret,
+            }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/mfa/enroll/phone", method = "post")]
+    #[tracing::instrument(name = "mfa_enroll_phone", skip_all, fields(req_id=%ctx.request_id))]
+    async fn mfa_enroll_phone(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        payload: Json<MfaEnrollPhoneRequest>,
+    ) -> AppHttpResponse {
+        match mfa_enroll_phone_handler(state, auth, payload).await {
+            Ok(enrollment) => AppHttpResponse::Created(Json(serde_json::json!({
+                "factor_id": enrollment.factor_id,
+                "friendly_name": enrollment.friendly_name,
+                "phone": enrollment.phone,
+            }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/mfa/factors", method = "get")]
+    #[tracing::instrument(name = "mfa_factors", skip_all, fields(req_id=%ctx.request_id))]
+    async fn mfa_factors(
+        &self,
+        ctx: RequestContext,
+        caller: Aal1AuthenticatedUser,
+        state: Data<&AppState>,
+    ) -> AppHttpResponse {
+        match mfa_factors_handler(state, caller.user).await {
+            Ok(factors) => AppHttpResponse::Ok(Json(serde_json::json!({ "factors": factors }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/mfa/factors/:factor_id", method = "delete")]
+    #[tracing::instrument(name = "mfa_unenroll", skip_all, fields(req_id=%ctx.request_id))]
+    async fn mfa_unenroll(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        factor_id: Path<String>,
+    ) -> AppHttpResponse {
+        match mfa_unenroll_handler(state, auth, &factor_id).await {
+            Ok(()) => AppHttpResponse::Ok(Json(
+                serde_json::json!({ "message": "MFA factor removed successfully" }),
+            )),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/mfa/verify", method = "post")]
+    #[tracing::instrument(name = "mfa_verify", skip_all, fields(req_id=%ctx.request_id))]
+    async fn mfa_verify(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        caller: Aal1AuthenticatedUser,
+        state: Data<&AppState>,
+        payload: Json<MfaVerifyRequest>,
+    ) -> AppHttpResponse {
+        let use_cookie = caller.user.from_cookie;
+        match mfa_verify_handler(state, caller.user, payload).await {
+            Ok(session) => AppHttpResponse::Ok(Json(session_body(cookies, &session, use_cookie))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/refresh", method = "post")]
+    #[tracing::instrument(name = "refresh", skip_all, fields(req_id=%ctx.request_id))]
+    async fn refresh(
+        &self,
+        ctx: RequestContext,
+        req: &Request,
+        cookies: &CookieJar,
+        state: Data<&AppState>,
+        payload: Json<RefreshRequest>,
+    ) -> AppHttpResponse {
+        match refresh_handler(state, req, payload).await {
+            Ok(refreshed) => AppHttpResponse::Ok(Json(session_body(
+                cookies,
+                &refreshed.session,
+                refreshed.use_cookie,
+            ))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/reset_password", method = "post")]
+    #[tracing::instrument(name = "reset_password", skip_all, fields(req_id=%ctx.request_id))]
+    async fn reset_password(
+        &self,
+        ctx: RequestContext,
+        state: Data<&AppState>,
+        payload: Json<ResetPasswordRequest>,
+    ) -> AppHttpResponse {
+        match reset_password_handler(state, payload).await {
+            Ok(response) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "message": response.message })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
     #[oai(path = "/auth/retrieve_user_id", method = "post")]
     #[tracing::instrument(name = "retrieve_user_id", skip_all, fields(req_id=%ctx.request_id))]
     async fn retrieve_user_id(
         &self,
         ctx: RequestContext,
-        _auth: AuthenticatedUser,
+        auth: AuthenticatedUser,
         state: Data<&AppState>,
         payload: Json<RetrieveUserIdRequest>,
     ) -> AppHttpResponse {
-        match retrieve_user_id_handler(state, payload).await {
+        match retrieve_user_id_handler(state, &ctx, auth, payload).await {
             Ok(response) => {
                 AppHttpResponse::Ok(Json(serde_json::json!({ "user_id": response.user_id })))
             }
@@ -68,17 +286,87 @@ impl AppApi {
         }
     }
 
+    #[oai(path = "/auth/session/idle", method = "get")]
+    #[tracing::instrument(name = "session_idle", skip_all, fields(req_id=%ctx.request_id))]
+    async fn session_idle(
+        &self,
+        ctx: RequestContext,
+        caller: PassiveAuthenticatedUser,
+    ) -> AppHttpResponse {
+        match session_idle_handler(caller).await {
+            Ok(status) => AppHttpResponse::Ok(Json(serde_json::json!({
+                "idle_timeout_secs": status.as_ref().map(|s| s.idle_timeout_secs),
+                "seconds_remaining": status.as_ref().map(|s| s.seconds_remaining),
+                "last_seen_at": status.as_ref().map(|s| s.last_seen_at),
+            }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/sessions", method = "get")]
+    #[tracing::instrument(name = "list_sessions", skip_all, fields(req_id=%ctx.request_id))]
+    async fn list_sessions(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+    ) -> AppHttpResponse {
+        match list_sessions_handler(state, auth).await {
+            Ok(sessions) => AppHttpResponse::Ok(Json(serde_json::json!({ "sessions": sessions }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/sessions", method = "delete")]
+    #[tracing::instrument(name = "revoke_sessions", skip_all, fields(req_id=%ctx.request_id))]
+    async fn revoke_sessions(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+    ) -> AppHttpResponse {
+        match revoke_sessions_handler(state, &ctx, cookies, auth).await {
+            Ok(()) => AppHttpResponse::Ok(Json(
+                serde_json::json!({ "message": "All sessions revoked successfully" }),
+            )),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/sessions/:session_id", method = "delete")]
+    #[tracing::instrument(name = "revoke_session", skip_all, fields(req_id=%ctx.request_id))]
+    async fn revoke_session(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        session_id: Path<String>,
+    ) -> AppHttpResponse {
+        match revoke_session_handler(state, &ctx, cookies, auth, &session_id).await {
+            Ok(()) => AppHttpResponse::Ok(Json(
+                serde_json::json!({ "message": "Session revoked successfully" }),
+            )),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
     #[oai(path = "/auth/signin", method = "post")]
     #[tracing::instrument(name = "signin", skip_all, fields(req_id=%ctx.request_id))]
     async fn signin(
         &self,
         ctx: RequestContext,
+        cookies: &CookieJar,
         state: Data<&AppState>,
         payload: Json<SigninRequest>,
     ) -> AppHttpResponse {
-        match signin_handler(state, payload).await {
-            Ok(response) => {
-                AppHttpResponse::Ok(Json(serde_json::json!({ "token": response.token })))
+        let use_cookie = payload.use_cookie;
+        match signin_handler(state, &ctx, payload).await {
+            Ok(signed_in) => {
+                let mut body = session_body(cookies, &signed_in.session, use_cookie);
+                body["mfa_required"] = signed_in.mfa_required.into();
+                AppHttpResponse::Ok(Json(body))
             }
             Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
         }
@@ -89,10 +377,11 @@ impl AppApi {
     async fn signout(
         &self,
         ctx: RequestContext,
-        auth: AuthenticatedUser,
+        cookies: &CookieJar,
+        caller: Aal1AuthenticatedUser,
         state: Data<&AppState>,
     ) -> AppHttpResponse {
-        match signout_handler(state, auth).await {
+        match signout_handler(state, &ctx, cookies, caller.user).await {
             Ok(()) => AppHttpResponse::Ok(Json(
                 serde_json::json!({ "message": "User signed out successfully" }),
             )),
@@ -115,4 +404,24 @@ impl AppApi {
             Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
         }
     }
+
+    #[oai(path = "/auth/verify_otp", method = "post")]
+    #[tracing::instrument(name = "verify_otp", skip_all, fields(req_id=%ctx.request_id))]
+    async fn verify_otp(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        state: Data<&AppState>,
+        payload: Json<VerifyOtpRequest>,
+    ) -> AppHttpResponse {
+        let use_cookie = payload.use_cookie;
+        match verify_otp_handler(state, &ctx, payload).await {
+            Ok(signed_in) => {
+                let mut body = session_body(cookies, &signed_in.session, use_cookie);
+                body["mfa_required"] = signed_in.mfa_required.into();
+                AppHttpResponse::Ok(Json(body))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
 }
@@ -0,0 +1,150 @@
+use poem::web::Data;
+use poem_openapi::{
+    OpenApi,
+    param::{Path, Query},
+    payload::Json,
+};
+
+use crate::{
+    api::ApiTags,
+    domain::error::http_response::AppHttpResponse,
+    routes::{
+        auth::guard::AuthenticatedUser,
+        clients::{
+            assign_client::{AssignClientRequest, assign_client_handler},
+            create_client::{CreateClientRequest, create_client_handler},
+            delete_client::delete_client_handler,
+            get_client::get_client_handler,
+            search_clients::{ClientSearchQuery, search_clients_handler},
+            update_client::{UpdateClientRequest, update_client_handler},
+        },
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct ClientApi;
+
+#[OpenApi(tag = "ApiTags::Clients")]
+impl ClientApi {
+    /// Clients matching every filter given: each word of `name` must start a first, last or
+    /// preferred name, and `date_of_birth` (`YYYY-MM-DD`), `mrn` and `status` match exactly.
+    #[oai(path = "/practices/:practice_id/clients", method = "get")]
+    #[tracing::instrument(name = "search_clients", skip_all, fields(req_id=%ctx.request_id))]
+    #[allow(clippy::too_many_arguments)]
+    async fn search_clients(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        name: Query<Option<String>>,
+        date_of_birth: Query<Option<String>>,
+        mrn: Query<Option<String>>,
+        status: Query<Option<String>>,
+    ) -> AppHttpResponse {
+        let query = ClientSearchQuery {
+            name: name.0,
+            date_of_birth: date_of_birth.0,
+            mrn: mrn.0,
+            status: status.0,
+        };
+
+        match search_clients_handler(state, auth, &practice_id, query).await {
+            Ok(clients) => AppHttpResponse::Ok(Json(serde_json::json!({ "clients": clients }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/clients", method = "post")]
+    #[tracing::instrument(name = "create_client", skip_all, fields(req_id=%ctx.request_id))]
+    async fn create_client(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<CreateClientRequest>,
+    ) -> AppHttpResponse {
+        match create_client_handler(state, auth, &practice_id, payload).await {
+            Ok(client) => AppHttpResponse::Created(Json(serde_json::json!({ "client": client }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/clients/:client_id", method = "get")]
+    #[tracing::instrument(name = "get_client", skip_all, fields(req_id=%ctx.request_id))]
+    async fn get_client(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        client_id: Path<String>,
+    ) -> AppHttpResponse {
+        match get_client_handler(state, auth, &practice_id, &client_id).await {
+            Ok(client) => AppHttpResponse::Ok(Json(serde_json::json!({ "client": client }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    /// Also discharges (`"status": "discharged"`) and readmits (`"status": "active"`).
+    #[oai(path = "/practices/:practice_id/clients/:client_id", method = "patch")]
+    #[tracing::instrument(name = "update_client", skip_all, fields(req_id=%ctx.request_id))]
+    async fn update_client(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        client_id: Path<String>,
+        payload: Json<UpdateClientRequest>,
+    ) -> AppHttpResponse {
+        match update_client_handler(state, auth, &practice_id, &client_id, payload).await {
+            Ok(client) => AppHttpResponse::Ok(Json(serde_json::json!({ "client": client }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    /// Owners and admins only; discharge a client instead to keep their record.
+    #[oai(path = "/practices/:practice_id/clients/:client_id", method = "delete")]
+    #[tracing::instrument(name = "delete_client", skip_all, fields(req_id=%ctx.request_id))]
+    async fn delete_client(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        client_id: Path<String>,
+    ) -> AppHttpResponse {
+        match delete_client_handler(state, auth, &practice_id, &client_id).await {
+            Ok(()) => AppHttpResponse::Ok(Json(
+                serde_json::json!({ "message": "Client deleted successfully" }),
+            )),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    /// Owners, admins and clinical supervisors only. Clinicians see the clients they are
+    /// the primary or supervising clinician for, and those assigned to their teams.
+    #[oai(
+        path = "/practices/:practice_id/clients/:client_id/assignment",
+        method = "put"
+    )]
+    #[tracing::instrument(name = "assign_client", skip_all, fields(req_id=%ctx.request_id))]
+    async fn assign_client(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        client_id: Path<String>,
+        payload: Json<AssignClientRequest>,
+    ) -> AppHttpResponse {
+        match assign_client_handler(state, auth, &practice_id, &client_id, payload).await {
+            Ok(client) => AppHttpResponse::Ok(Json(serde_json::json!({ "client": client }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
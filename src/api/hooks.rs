@@ -0,0 +1,30 @@
+use poem::{http::HeaderMap, web::Data};
+use poem_openapi::{OpenApi, payload::Json};
+
+use crate::{
+    api::ApiTags, domain::error::http_response::AppHttpResponse,
+    routes::hooks::send_sms::send_sms_hook_handler, state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct HookApi;
+
+#[OpenApi(tag = "ApiTags::Hooks")]
+impl HookApi {
+    /// Supabase Auth calls this to text phone-factor codes; it signs the raw body.
+    #[oai(path = "/hooks/send_sms", method = "post")]
+    #[tracing::instrument(name = "send_sms_hook", skip_all, fields(req_id=%ctx.request_id))]
+    async fn send_sms(
+        &self,
+        ctx: RequestContext,
+        state: Data<&AppState>,
+        headers: &HeaderMap,
+        body: Vec<u8>,
+    ) -> AppHttpResponse {
+        match send_sms_hook_handler(state, headers, &body).await {
+            Ok(()) => AppHttpResponse::Ok(Json(serde_json::json!({}))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use poem::web::{Data, cookie::CookieJar};
+use poem_openapi::{OpenApi, param::Path, payload::Json};
+
+use crate::{
+    api::ApiTags,
+    domain::error::http_response::AppHttpResponse,
+    routes::{
+        auth::{
+            authorization::{OwnerOrAdmin, RequirePracticeRole},
+            session_cookie::session_body,
+        },
+        invitations::{
+            accept_invitation::{AcceptInvitationRequest, accept_invitation_handler},
+            create_invitation::{CreateInvitationRequest, create_invitation_handler},
+            list_invitations::list_invitations_handler,
+            preview_invitation::{PreviewInvitationRequest, preview_invitation_handler},
+            resend_invitation::resend_invitation_handler,
+            revoke_invitation::revoke_invitation_handler,
+        },
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct InvitationApi;
+
+#[OpenApi(tag = "ApiTags::Invitations")]
+impl InvitationApi {
+    #[oai(path = "/practices/:practice_id/invitations", method = "get")]
+    #[tracing::instrument(name = "list_invitations", skip_all, fields(req_id=%ctx.request_id))]
+    async fn list_invitations(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+    ) -> AppHttpResponse {
+        match list_invitations_handler(state, access.into_user(), &practice_id).await {
+            Ok(invitations) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "invitations": invitations })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/invitations", method = "post")]
+    #[tracing::instrument(name = "create_invitation", skip_all, fields(req_id=%ctx.request_id))]
+    async fn create_invitation(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<CreateInvitationRequest>,
+    ) -> AppHttpResponse {
+        match create_invitation_handler(state, access.into_user(), &practice_id, payload).await {
+            Ok(invitation) => {
+                AppHttpResponse::Created(Json(serde_json::json!({ "invitation": invitation })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/invitations/:invitation_id/resend",
+        method = "post"
+    )]
+    #[tracing::instrument(name = "resend_invitation", skip_all, fields(req_id=%ctx.request_id))]
+    async fn resend_invitation(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        invitation_id: Path<String>,
+    ) -> AppHttpResponse {
+        match resend_invitation_handler(state, access.into_user(), &practice_id, &invitation_id)
+            .await
+        {
+            Ok(invitation) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "invitation": invitation })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/invitations/:invitation_id",
+        method = "delete"
+    )]
+    #[tracing::instrument(name = "revoke_invitation", skip_all, fields(req_id=%ctx.request_id))]
+    async fn revoke_invitation(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        invitation_id: Path<String>,
+    ) -> AppHttpResponse {
+        match revoke_invitation_handler(state, access.into_user(), &practice_id, &invitation_id)
+            .await
+        {
+            Ok(invitation) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "invitation": invitation })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/invitations/preview", method = "post")]
+    #[tracing::instrument(name = "preview_invitation", skip_all, fields(req_id=%ctx.request_id))]
+    async fn preview_invitation(
+        &self,
+        ctx: RequestContext,
+        state: Data<&AppState>,
+        payload: Json<PreviewInvitationRequest>,
+    ) -> AppHttpResponse {
+        match preview_invitation_handler(state, payload).await {
+            Ok(invitation) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "invitation": invitation })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/invitations/accept", method = "post")]
+    #[tracing::instrument(name = "accept_invitation", skip_all, fields(req_id=%ctx.request_id))]
+    async fn accept_invitation(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        state: Data<&AppState>,
+        payload: Json<AcceptInvitationRequest>,
+    ) -> AppHttpResponse {
+        let use_cookie = payload.use_cookie;
+        match accept_invitation_handler(state, &ctx, payload).await {
+            Ok(accepted) => {
+                let mut body = session_body(cookies, &accepted.session, use_cookie);
+                body["practice_id"] = accepted.practice_id.into();
+                AppHttpResponse::Ok(Json(body))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
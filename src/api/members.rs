@@ -0,0 +1,126 @@
+use poem::web::Data;
+use poem_openapi::{OpenApi, param::Path, payload::Json};
+
+use crate::{
+    api::ApiTags,
+    domain::error::http_response::AppHttpResponse,
+    routes::{
+        auth::{
+            authorization::{OwnerOrAdmin, RequirePracticeRole},
+            guard::AuthenticatedUser,
+        },
+        members::{
+            add_member_role::{AddMemberRoleRequest, add_member_role_handler},
+            list_members::list_members_handler,
+            remove_member_role::remove_member_role_handler,
+            revoke_member_sessions::revoke_member_sessions_handler,
+            update_membership::{UpdateMembershipRequest, update_membership_handler},
+        },
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct MemberApi;
+
+#[OpenApi(tag = "ApiTags::Members")]
+impl MemberApi {
+    #[oai(path = "/practices/:practice_id/members", method = "get")]
+    #[tracing::instrument(name = "list_members", skip_all, fields(req_id=%ctx.request_id))]
+    async fn list_members(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+    ) -> AppHttpResponse {
+        match list_members_handler(state, auth, &practice_id).await {
+            Ok(members) => AppHttpResponse::Ok(Json(serde_json::json!({ "members": members }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/members/:membership_id",
+        method = "patch"
+    )]
+    #[tracing::instrument(name = "update_membership", skip_all, fields(req_id=%ctx.request_id))]
+    async fn update_membership(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        membership_id: Path<String>,
+        payload: Json<UpdateMembershipRequest>,
+    ) -> AppHttpResponse {
+        match update_membership_handler(state, auth, &practice_id, &membership_id, payload).await {
+            Ok(member) => AppHttpResponse::Ok(Json(serde_json::json!({ "member": member }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/members/:membership_id/roles",
+        method = "post"
+    )]
+    #[tracing::instrument(name = "add_member_role", skip_all, fields(req_id=%ctx.request_id))]
+    async fn add_member_role(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        membership_id: Path<String>,
+        payload: Json<AddMemberRoleRequest>,
+    ) -> AppHttpResponse {
+        match add_member_role_handler(state, auth, &practice_id, &membership_id, payload).await {
+            Ok(member) => AppHttpResponse::Created(Json(serde_json::json!({ "member": member }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/members/:membership_id/roles/:role",
+        method = "delete"
+    )]
+    #[tracing::instrument(name = "remove_member_role", skip_all, fields(req_id=%ctx.request_id))]
+    async fn remove_member_role(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        membership_id: Path<String>,
+        role: Path<String>,
+    ) -> AppHttpResponse {
+        match remove_member_role_handler(state, auth, &practice_id, &membership_id, &role).await {
+            Ok(member) => AppHttpResponse::Ok(Json(serde_json::json!({ "member": member }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/members/:membership_id/sessions",
+        method = "delete"
+    )]
+    #[tracing::instrument(name = "revoke_member_sessions", skip_all, fields(req_id=%ctx.request_id))]
+    async fn revoke_member_sessions(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        membership_id: Path<String>,
+    ) -> AppHttpResponse {
+        match revoke_member_sessions_handler(state, &ctx, access, &practice_id, &membership_id)
+            .await
+        {
+            Ok(()) => AppHttpResponse::Ok(Json(serde_json::json!({
+                "message": "Member sessions revoked successfully"
+            }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
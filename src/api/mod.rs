@@ -1 +1,34 @@
+use poem_openapi::Tags;
+
+pub mod appointments;
+pub mod audit;
 pub mod auth;
+pub mod clients;
+pub mod hooks;
+pub mod invitations;
+pub mod members;
+pub mod practices;
+pub mod sso;
+pub mod teams;
+
+#[derive(Tags)]
+pub enum ApiTags {
+    /// Appointments, recurring series and clinician schedules
+    Appointments,
+    /// Practice audit trail
+    Audit,
+    /// Clients of a practice and their demographics
+    Clients,
+    /// Callbacks from Supabase Auth
+    Hooks,
+    /// Staff invitations and acceptance
+    Invitations,
+    /// Practice memberships and role assignments
+    Members,
+    /// Practices the caller belongs to
+    Practices,
+    /// OIDC single sign-on and each practice's identity providers
+    Sso,
+    /// Teams within a practice and their rosters
+    Teams,
+}
@@ -0,0 +1,147 @@
+use poem::web::Data;
+use poem_openapi::{OpenApi, param::Path, payload::Json};
+
+use crate::{
+    api::ApiTags,
+    domain::error::http_response::AppHttpResponse,
+    routes::{
+        auth::{
+            authorization::{OwnerOrAdmin, RequirePracticeRole},
+            guard::AuthenticatedUser,
+        },
+        practices::{
+            create_practice::{CreatePracticeRequest, create_practice_handler},
+            delete_practice::delete_practice_handler,
+            get_practice::get_practice_handler,
+            list_practices::list_practices_handler,
+            rename_practice::{RenamePracticeRequest, rename_practice_handler},
+            set_idle_timeout::{SetIdleTimeoutRequest, set_idle_timeout_handler},
+            set_passwordless_signin::{
+                SetPasswordlessSigninRequest, set_passwordless_signin_handler,
+            },
+        },
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct PracticeApi;
+
+#[OpenApi(tag = "ApiTags::Practices")]
+impl PracticeApi {
+    #[oai(path = "/practices", method = "get")]
+    #[tracing::instrument(name = "list_practices", skip_all, fields(req_id=%ctx.request_id))]
+    async fn list_practices(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+    ) -> AppHttpResponse {
+        match list_practices_handler(state, auth).await {
+            Ok(practices) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "practices": practices })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices", method = "post")]
+    #[tracing::instrument(name = "create_practice", skip_all, fields(req_id=%ctx.request_id))]
+    async fn create_practice(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        payload: Json<CreatePracticeRequest>,
+    ) -> AppHttpResponse {
+        match create_practice_handler(state, auth, payload).await {
+            Ok(practice) => {
+                AppHttpResponse::Created(Json(serde_json::json!({ "practice": practice })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id", method = "get")]
+    #[tracing::instrument(name = "get_practice", skip_all, fields(req_id=%ctx.request_id))]
+    async fn get_practice(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+    ) -> AppHttpResponse {
+        match get_practice_handler(state, auth, &practice_id).await {
+            Ok(practice) => AppHttpResponse::Ok(Json(serde_json::json!({ "practice": practice }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id", method = "patch")]
+    #[tracing::instrument(name = "rename_practice", skip_all, fields(req_id=%ctx.request_id))]
+    async fn rename_practice(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<RenamePracticeRequest>,
+    ) -> AppHttpResponse {
+        match rename_practice_handler(state, auth, &practice_id, payload).await {
+            Ok(practice) => AppHttpResponse::Ok(Json(serde_json::json!({ "practice": practice }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id", method = "delete")]
+    #[tracing::instrument(name = "delete_practice", skip_all, fields(req_id=%ctx.request_id))]
+    async fn delete_practice(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+    ) -> AppHttpResponse {
+        match delete_practice_handler(state, auth, &practice_id).await {
+            Ok(()) => AppHttpResponse::Ok(Json(
+                serde_json::json!({ "message": "Practice deleted successfully" }),
+            )),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/idle_timeout", method = "put")]
+    #[tracing::instrument(name = "set_idle_timeout", skip_all, fields(req_id=%ctx.request_id))]
+    async fn set_idle_timeout(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<SetIdleTimeoutRequest>,
+    ) -> AppHttpResponse {
+        match set_idle_timeout_handler(state, access.into_user(), &practice_id, payload).await {
+            Ok(practice) => AppHttpResponse::Ok(Json(serde_json::json!({ "practice": practice }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/passwordless_signin", method = "put")]
+    #[tracing::instrument(name = "set_passwordless_signin", skip_all, fields(req_id=%ctx.request_id))]
+    async fn set_passwordless_signin(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<SetPasswordlessSigninRequest>,
+    ) -> AppHttpResponse {
+        match set_passwordless_signin_handler(state, access.into_user(), &practice_id, payload)
+            .await
+        {
+            Ok(practice) => AppHttpResponse::Ok(Json(serde_json::json!({ "practice": practice }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
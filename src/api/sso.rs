@@ -0,0 +1,207 @@
+use poem::web::{Data, cookie::CookieJar};
+use poem_openapi::{
+    OpenApi,
+    param::{Path, Query},
+    payload::Json,
+};
+
+use crate::{
+    api::ApiTags,
+    domain::error::http_response::AppHttpResponse,
+    routes::{
+        auth::{
+            authorization::{OwnerOrAdmin, RequirePracticeRole},
+            guard::AuthenticatedUser,
+            session_cookie::session_body,
+        },
+        sso::{
+            approve_identity_provider::approve_identity_provider_handler,
+            create_identity_provider::{
+                CreateIdentityProviderRequest, create_identity_provider_handler,
+            },
+            delete_identity_provider::delete_identity_provider_handler,
+            link_identity::{LinkIdentityRequest, link_identity_handler},
+            list_identity_providers::list_identity_providers_handler,
+            sso_callback::{SsoCallbackOutcome, SsoCallbackParams, sso_callback_handler},
+            start_sso::start_sso_handler,
+        },
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct SsoApi;
+
+#[OpenApi(tag = "ApiTags::Sso")]
+impl SsoApi {
+    #[oai(path = "/auth/sso/callback", method = "get")]
+    #[tracing::instrument(name = "sso_callback", skip_all, fields(req_id=%ctx.request_id))]
+    #[allow(clippy::too_many_arguments)]
+    async fn sso_callback(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        state: Data<&AppState>,
+        code: Query<Option<String>>,
+        #[oai(name = "state")] sso_state: Query<Option<String>>,
+        error: Query<Option<String>>,
+        error_description: Query<Option<String>>,
+    ) -> AppHttpResponse {
+        let params = SsoCallbackParams {
+            code: code.0,
+            state: sso_state.0,
+            error: error.0,
+            error_description: error_description.0,
+        };
+        match sso_callback_handler(state, &ctx, cookies, params).await {
+            // The browser arrives by redirect, so the session can only travel as cookies.
+            Ok(SsoCallbackOutcome::SignedIn {
+                session,
+                redirect_to,
+            }) => {
+                session_body(cookies, &session, true);
+                AppHttpResponse::Found(redirect_to)
+            }
+            Ok(SsoCallbackOutcome::Link { redirect_to }) => AppHttpResponse::Found(redirect_to),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/sso/:provider_id/start", method = "get")]
+    #[tracing::instrument(name = "start_sso", skip_all, fields(req_id=%ctx.request_id))]
+    async fn start_sso(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        state: Data<&AppState>,
+        provider_id: Path<String>,
+        redirect_to: Query<Option<String>>,
+    ) -> AppHttpResponse {
+        match start_sso_handler(state, cookies, &provider_id, redirect_to.0, false).await {
+            Ok(url) => AppHttpResponse::Found(url),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/sso/:provider_id/link", method = "post")]
+    #[tracing::instrument(name = "start_sso_link", skip_all, fields(req_id=%ctx.request_id))]
+    async fn start_sso_link(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        _auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        provider_id: Path<String>,
+        redirect_to: Query<Option<String>>,
+    ) -> AppHttpResponse {
+        match start_sso_handler(state, cookies, &provider_id, redirect_to.0, true).await {
+            Ok(url) => AppHttpResponse::Ok(Json(serde_json::json!({ "authorization_url": url }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/auth/sso/link", method = "post")]
+    #[tracing::instrument(name = "link_identity", skip_all, fields(req_id=%ctx.request_id))]
+    async fn link_identity(
+        &self,
+        ctx: RequestContext,
+        cookies: &CookieJar,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        payload: Json<LinkIdentityRequest>,
+    ) -> AppHttpResponse {
+        match link_identity_handler(state, &ctx, cookies, auth, payload).await {
+            Ok(()) => AppHttpResponse::Ok(Json(
+                serde_json::json!({ "message": "Identity linked successfully" }),
+            )),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/identity_providers", method = "get")]
+    #[tracing::instrument(name = "list_identity_providers", skip_all, fields(req_id=%ctx.request_id))]
+    async fn list_identity_providers(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+    ) -> AppHttpResponse {
+        match list_identity_providers_handler(state, access.into_user(), &practice_id).await {
+            Ok(providers) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "identity_providers": providers })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/identity_providers", method = "post")]
+    #[tracing::instrument(name = "create_identity_provider", skip_all, fields(req_id=%ctx.request_id))]
+    async fn create_identity_provider(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<CreateIdentityProviderRequest>,
+    ) -> AppHttpResponse {
+        match create_identity_provider_handler(state, access.into_user(), &practice_id, payload)
+            .await
+        {
+            Ok(provider) => {
+                AppHttpResponse::Created(Json(serde_json::json!({ "identity_provider": provider })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/identity_providers/:provider_id/approve",
+        method = "post"
+    )]
+    #[tracing::instrument(name = "approve_identity_provider", skip_all, fields(req_id=%ctx.request_id))]
+    async fn approve_identity_provider(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        provider_id: Path<String>,
+    ) -> AppHttpResponse {
+        match approve_identity_provider_handler(state, auth, &practice_id, &provider_id).await {
+            Ok(provider) => {
+                AppHttpResponse::Ok(Json(serde_json::json!({ "identity_provider": provider })))
+            }
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/identity_providers/:provider_id",
+        method = "delete"
+    )]
+    #[tracing::instrument(name = "delete_identity_provider", skip_all, fields(req_id=%ctx.request_id))]
+    async fn delete_identity_provider(
+        &self,
+        ctx: RequestContext,
+        access: RequirePracticeRole<OwnerOrAdmin>,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        provider_id: Path<String>,
+    ) -> AppHttpResponse {
+        match delete_identity_provider_handler(
+            state,
+            access.into_user(),
+            &practice_id,
+            &provider_id,
+        )
+        .await
+        {
+            Ok(()) => AppHttpResponse::Ok(Json(
+                serde_json::json!({ "message": "Identity provider deleted successfully" }),
+            )),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use poem::web::Data;
+use poem_openapi::{OpenApi, param::Path, payload::Json};
+
+use crate::{
+    api::ApiTags,
+    domain::error::http_response::AppHttpResponse,
+    routes::{
+        auth::guard::AuthenticatedUser,
+        teams::{
+            add_team_member::{AddTeamMemberRequest, add_team_member_handler},
+            create_team::{CreateTeamRequest, create_team_handler},
+            delete_team::delete_team_handler,
+            get_team::get_team_handler,
+            list_teams::list_teams_handler,
+            remove_team_member::remove_team_member_handler,
+            rename_team::{RenameTeamRequest, rename_team_handler},
+        },
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Debug)]
+pub struct TeamApi;
+
+#[OpenApi(tag = "ApiTags::Teams")]
+impl TeamApi {
+    #[oai(path = "/practices/:practice_id/teams", method = "get")]
+    #[tracing::instrument(name = "list_teams", skip_all, fields(req_id=%ctx.request_id))]
+    async fn list_teams(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+    ) -> AppHttpResponse {
+        match list_teams_handler(state, auth, &practice_id).await {
+            Ok(teams) => AppHttpResponse::Ok(Json(serde_json::json!({ "teams": teams }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/teams", method = "post")]
+    #[tracing::instrument(name = "create_team", skip_all, fields(req_id=%ctx.request_id))]
+    async fn create_team(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        payload: Json<CreateTeamRequest>,
+    ) -> AppHttpResponse {
+        match create_team_handler(state, auth, &practice_id, payload).await {
+            Ok(team) => AppHttpResponse::Created(Json(serde_json::json!({ "team": team }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/teams/:team_id", method = "get")]
+    #[tracing::instrument(name = "get_team", skip_all, fields(req_id=%ctx.request_id))]
+    async fn get_team(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        team_id: Path<String>,
+    ) -> AppHttpResponse {
+        match get_team_handler(state, auth, &practice_id, &team_id).await {
+            Ok(team) => AppHttpResponse::Ok(Json(serde_json::json!({ "team": team }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/teams/:team_id", method = "patch")]
+    #[tracing::instrument(name = "rename_team", skip_all, fields(req_id=%ctx.request_id))]
+    async fn rename_team(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        team_id: Path<String>,
+        payload: Json<RenameTeamRequest>,
+    ) -> AppHttpResponse {
+        match rename_team_handler(state, auth, &practice_id, &team_id, payload).await {
+            Ok(team) => AppHttpResponse::Ok(Json(serde_json::json!({ "team": team }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(path = "/practices/:practice_id/teams/:team_id", method = "delete")]
+    #[tracing::instrument(name = "delete_team", skip_all, fields(req_id=%ctx.request_id))]
+    async fn delete_team(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        team_id: Path<String>,
+    ) -> AppHttpResponse {
+        match delete_team_handler(state, auth, &practice_id, &team_id).await {
+            Ok(()) => AppHttpResponse::Ok(Json(
+                serde_json::json!({ "message": "Team deleted successfully" }),
+            )),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/teams/:team_id/members",
+        method = "post"
+    )]
+    #[tracing::instrument(name = "add_team_member", skip_all, fields(req_id=%ctx.request_id))]
+    async fn add_team_member(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        team_id: Path<String>,
+        payload: Json<AddTeamMemberRequest>,
+    ) -> AppHttpResponse {
+        match add_team_member_handler(state, auth, &practice_id, &team_id, payload).await {
+            Ok(team) => AppHttpResponse::Created(Json(serde_json::json!({ "team": team }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+
+    #[oai(
+        path = "/practices/:practice_id/teams/:team_id/members/:user_id",
+        method = "delete"
+    )]
+    #[tracing::instrument(name = "remove_team_member", skip_all, fields(req_id=%ctx.request_id))]
+    async fn remove_team_member(
+        &self,
+        ctx: RequestContext,
+        auth: AuthenticatedUser,
+        state: Data<&AppState>,
+        practice_id: Path<String>,
+        team_id: Path<String>,
+        user_id: Path<String>,
+    ) -> AppHttpResponse {
+        match remove_team_member_handler(state, auth, &practice_id, &team_id, &user_id).await {
+            Ok(team) => AppHttpResponse::Ok(Json(serde_json::json!({ "team": team }))),
+            Err(e) => AppHttpResponse::from_app_error(e, &ctx.request_id),
+        }
+    }
+}
@@ -2,8 +2,13 @@ use color_eyre::eyre::Result;
 use thiserror::Error;
 use tracing_error::SpanTrace;
 
+use crate::domain::types::password_policy::PasswordViolation;
+
 #[derive(Debug, Error)]
 pub enum AuthError {
+    /// GoTrue rejected the email and password; the only signin failure the lockout counts.
+    #[error("Invalid login credentials")]
+    InvalidCredentials,
     #[error("Sign-in failed: {0}")]
     SignInError(String),
     #[error("Failed to sign out: {0}")]
@@ -24,6 +29,197 @@ pub enum AuthError {
     InvalidToken,
     #[error("Token expired")]
     ExpiredToken,
+    #[error("Password recovery failed: {0}")]
+    PasswordRecoveryError(String),
+    #[error("Recovery token has expired")]
+    ExpiredRecoveryToken,
+    #[error("Invalid recovery token")]
+    InvalidRecoveryToken,
+    #[error("MFA request failed: {0}")]
+    MfaError(String),
+    #[error("MFA factor not found")]
+    FactorNotFound,
+    #[error("Invalid MFA code")]
+    InvalidMfaCode,
+    #[error("Multi-factor authentication required")]
+    MfaRequired,
+    #[error("Failed to refresh session: {0}")]
+    RefreshError(String),
+    #[error("Invalid refresh token")]
+    InvalidRefreshToken,
+    #[error("You do not have a practice role that allows this action")]
+    InsufficientRole,
+    #[error("Too many failed sign-in attempts; try again in {retry_after_secs} seconds")]
+    AccountLocked { retry_after_secs: u64 },
+    #[error("Login attempt tracking failed: {0}")]
+    LoginAttemptError(String),
+    #[error("Missing or invalid CSRF token")]
+    CsrfTokenMismatch,
+    #[error("Session ended after a period of inactivity; sign in again")]
+    SessionIdleTimeout,
+    #[error("Session activity tracking failed: {0}")]
+    SessionActivityError(String),
+    #[error("Session has been revoked; sign in again")]
+    SessionRevoked,
+    #[error("Session not found")]
+    SessionNotFound,
+    #[error("Failed to revoke session: {0}")]
+    RevokeSessionError(String),
+    #[error("Current password is incorrect")]
+    InvalidCurrentPassword,
+    /// Supabase only changes the password of a session opened in the last 24 hours.
+    #[error("Sign in again to change your password")]
+    ReauthenticationRequired,
+    #[error("Profile request failed: {0}")]
+    ProfileError(String),
+    #[error("One-time password request failed: {0}")]
+    OtpError(String),
+    #[error("Invalid or expired sign-in code")]
+    InvalidOtp,
+    #[error("Passwordless sign-in is disabled by one of your practices")]
+    PasswordlessSigninDisabled,
+    #[error("A code was texted recently; try again in {retry_after_secs} seconds")]
+    SmsResendThrottled { retry_after_secs: u64 },
+}
+
+#[derive(Debug, Error)]
+pub enum AppointmentError {
+    #[error("Appointment not found")]
+    AppointmentNotFound,
+    #[error("Appointment series not found")]
+    SeriesNotFound,
+    #[error("The clinician already has an appointment at that time")]
+    ClinicianDoubleBooked,
+    #[error("You do not have permission to manage this appointment")]
+    AppointmentForbidden,
+    #[error("Invalid appointment participant: {0}")]
+    InvalidParticipant(String),
+    #[error("An appointment that is {from} cannot become {to}")]
+    InvalidStatusTransition { from: String, to: String },
+    #[error("Only scheduled appointments can be rescheduled or moved")]
+    AppointmentNotScheduled,
+    #[error("Appointment request failed: {0}")]
+    AppointmentRequestError(String),
+}
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("Audit log request failed: {0}")]
+    AuditRequestError(String),
+}
+
+#[derive(Debug, Error)]
+pub enum PracticeError {
+    #[error("Practice not found")]
+    PracticeNotFound,
+    #[error("You do not have permission to modify this practice")]
+    PracticeForbidden,
+    #[error("Practice request failed: {0}")]
+    PracticeRequestError(String),
+}
+
+#[derive(Debug, Error)]
+pub enum InvitationError {
+    #[error("Invitation not found")]
+    InvitationNotFound,
+    #[error("Invitation has expired")]
+    InvitationExpired,
+    #[error("Invitation has been revoked")]
+    InvitationRevoked,
+    #[error("Invitation has already been accepted")]
+    InvitationAlreadyAccepted,
+    #[error("An invitation is already pending for this email")]
+    InvitationPending,
+    #[error("This person is already a member of the practice")]
+    AlreadyMember,
+    #[error("You do not have permission to invite with these roles")]
+    CannotInvite,
+    #[error("Invitation request failed: {0}")]
+    InvitationRequestError(String),
+}
+
+#[derive(Debug, Error)]
+pub enum MembershipError {
+    #[error("Membership not found")]
+    MembershipNotFound,
+    #[error("Role is already assigned to this membership")]
+    RoleAlreadyAssigned,
+    #[error("Role is not assigned to this membership")]
+    RoleNotAssigned,
+    #[error("You do not have permission to manage this role")]
+    CannotManageRole,
+    #[error("You do not have permission to manage this membership")]
+    CannotManageMembership,
+    #[error("A practice must keep at least one active owner")]
+    LastOwner,
+    #[error("Membership request failed: {0}")]
+    MembershipRequestError(String),
+}
+
+#[derive(Debug, Error)]
+pub enum RateLimitError {
+    #[error("Too many requests; try again in {retry_after_secs} seconds")]
+    TooManyRequests { retry_after_secs: u64 },
+}
+
+#[derive(Debug, Error)]
+pub enum SmsError {
+    #[error("Failed to send text message: {0}")]
+    SendError(String),
+    #[error("Invalid webhook signature")]
+    InvalidHookSignature,
+}
+
+#[derive(Debug, Error)]
+pub enum SsoError {
+    #[error("Identity provider not found")]
+    ProviderNotFound,
+    #[error("This identity provider is waiting for a platform admin to approve it")]
+    ProviderNotApproved,
+    #[error("An account with this email already exists; sign in to it and link the provider")]
+    AccountExists,
+    #[error("Single sign-on login expired or was started in another browser")]
+    InvalidState,
+    #[error("Single sign-on failed: {0}")]
+    ProviderError(String),
+    #[error("This identity provider does not accept that email address")]
+    EmailNotAllowed,
+    #[error("Your membership in this practice has been deactivated")]
+    MembershipInactive,
+    #[error("Single sign-on request failed: {0}")]
+    SsoRequestError(String),
+}
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Client not found")]
+    ClientNotFound,
+    #[error("A client with this MRN already exists in the practice")]
+    MrnTaken,
+    #[error("You do not have permission to manage this client")]
+    ClientForbidden,
+    #[error("Invalid client assignment: {0}")]
+    InvalidAssignment(String),
+    #[error("Client request failed: {0}")]
+    ClientRequestError(String),
+}
+
+#[derive(Debug, Error)]
+pub enum TeamError {
+    #[error("Team not found")]
+    TeamNotFound,
+    #[error("A team with this name already exists in the practice")]
+    TeamNameTaken,
+    #[error("You do not have permission to manage this team")]
+    TeamForbidden,
+    #[error("User is not an active member of this practice")]
+    NotPracticeMember,
+    #[error("User is already on this team")]
+    AlreadyOnTeam,
+    #[error("User is not on this team")]
+    NotOnTeam,
+    #[error("Team request failed: {0}")]
+    TeamRequestError(String),
 }
 
 #[derive(Debug, Error)]
@@ -31,17 +227,70 @@ pub enum ValidationError {
     #[error("Invalid email format")]
     InvalidEmail,
     #[error("Password does not meet complexity requirements")]
-    WeakPassword,
+    WeakPassword(Vec<PasswordViolation>),
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+    #[error("Practice name must be between 1 and 120 characters")]
+    InvalidPracticeName,
+    #[error("Unknown role: {0}")]
+    InvalidRole(String),
+    #[error("Team name must be between 1 and 120 characters")]
+    InvalidTeamName,
+    #[error("NPI must be 10 digits with a valid check digit")]
+    InvalidNpi,
+    #[error("Invalid phone number")]
+    InvalidPhoneNumber,
+    #[error("Client names must be between 1 and 100 characters")]
+    InvalidClientName,
+    #[error("Date of birth must be a YYYY-MM-DD date between 1900 and today")]
+    InvalidDateOfBirth,
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("Invalid emergency contact: {0}")]
+    InvalidEmergencyContact(String),
+    #[error("MRN must be 1 to 20 letters, digits or hyphens")]
+    InvalidMrn,
+    #[error("Unknown client status: {0}")]
+    InvalidClientStatus(String),
+    #[error("Invalid recurrence rule: {0}")]
+    InvalidRecurrenceRule(String),
+    #[error("Invalid appointment time: {0}")]
+    InvalidAppointmentTime(String),
+    #[error("Invalid location: {0}")]
+    InvalidLocation(String),
+    #[error("Unknown appointment status: {0}")]
+    InvalidAppointmentStatus(String),
 }
 
 #[derive(Debug, Error)]
 pub enum AppError {
+    #[error(transparent)]
+    Appointment(#[from] AppointmentError),
+    #[error(transparent)]
+    Audit(#[from] AuditError),
     #[error(transparent)]
     Auth(#[from] AuthError),
     #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error(transparent)]
+    Invitation(#[from] InvitationError),
+    #[error(transparent)]
+    Membership(#[from] MembershipError),
+    #[error(transparent)]
+    Practice(#[from] PracticeError),
+    #[error(transparent)]
+    RateLimit(#[from] RateLimitError),
+    #[error(transparent)]
+    Sms(#[from] SmsError),
+    #[error(transparent)]
+    Sso(#[from] SsoError),
+    #[error(transparent)]
+    Team(#[from] TeamError),
+    #[error(transparent)]
     Validation(#[from] ValidationError),
+    /// A Postgres-only feature was called on an app running with `BACKEND=memory`.
+    #[error("{0} are not available with the memory backend")]
+    MemoryBackendUnsupported(&'static str),
     #[error("Internal server error")]
     Internal {
         #[source]
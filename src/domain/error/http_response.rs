@@ -2,13 +2,39 @@ use poem_openapi::{ApiResponse, Object, payload::Json};
 use serde::Serialize;
 use serde_json::Value;
 
-use crate::domain::error::app_error::{AppError, AuthError, ValidationError};
+use crate::domain::{
+    error::app_error::{
+        AppError, AppointmentError, AuditError, AuthError, ClientError, InvitationError,
+        MembershipError, PracticeError, RateLimitError, SmsError, SsoError, TeamError,
+        ValidationError,
+    },
+    types::password_policy::PasswordViolation,
+};
 
 #[derive(Object, Serialize, Debug)]
 pub struct ErrorBody {
     pub code: String,
     pub message: String,
     pub request_id: String,
+    /// Each password rule broken, on `weak_password`.
+    #[oai(skip_serializing_if_is_none)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub violations: Option<Vec<ViolationBody>>,
+}
+
+#[derive(Object, Serialize, Debug)]
+pub struct ViolationBody {
+    pub code: String,
+    pub message: String,
+}
+
+impl From<&PasswordViolation> for ViolationBody {
+    fn from(violation: &PasswordViolation) -> Self {
+        ViolationBody {
+            code: violation.code().to_string(),
+            message: violation.message(),
+        }
+    }
 }
 
 #[derive(ApiResponse, Debug)]
@@ -17,6 +43,8 @@ pub enum AppHttpResponse {
     Ok(Json<Value>),
     #[oai(status = 201)]
     Created(Json<Value>),
+    #[oai(status = 302)]
+    Found(#[oai(header = "Location")] String),
     #[oai(status = 400)]
     BadRequest(Json<ErrorBody>),
     #[oai(status = 401)]
@@ -27,8 +55,17 @@ pub enum AppHttpResponse {
     NotFound(Json<ErrorBody>),
     #[oai(status = 409)]
     Conflict(Json<ErrorBody>),
+    #[oai(status = 429)]
+    TooManyRequests(
+        Json<ErrorBody>,
+        /// Seconds until the request may be retried.
+        #[oai(header = "Retry-After")]
+        u64,
+    ),
     #[oai(status = 500)]
     InternalServerError(Json<ErrorBody>),
+    #[oai(status = 503)]
+    ServiceUnavailable(Json<ErrorBody>),
 }
 
 impl AppHttpResponse {
@@ -37,70 +74,512 @@ impl AppHttpResponse {
             code: code.to_string(),
             message: message.to_string(),
             request_id: request_id.to_string(),
+            violations: None,
         })
     }
 
     pub fn from_app_error(error: AppError, request_id: &str) -> Self {
         match error {
-            AppError::Auth(ae) => match ae {
-                AuthError::SignInError(msg) => {
-                    AppHttpResponse::Unauthorized(Self::body("sign_in_error", &msg, request_id))
+            AppError::Appointment(ae) => {
+                match ae {
+                    AppointmentError::AppointmentNotFound => AppHttpResponse::NotFound(Self::body(
+                        "appointment_not_found",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AppointmentError::SeriesNotFound => AppHttpResponse::NotFound(Self::body(
+                        "appointment_series_not_found",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AppointmentError::ClinicianDoubleBooked => AppHttpResponse::Conflict(
+                        Self::body("clinician_double_booked", &ae.to_string(), request_id),
+                    ),
+                    AppointmentError::AppointmentForbidden => AppHttpResponse::Forbidden(
+                        Self::body("appointment_forbidden", &ae.to_string(), request_id),
+                    ),
+                    AppointmentError::InvalidParticipant(_) => {
+                        AppHttpResponse::BadRequest(Self::body(
+                            "invalid_appointment_participant",
+                            &ae.to_string(),
+                            request_id,
+                        ))
+                    }
+                    AppointmentError::InvalidStatusTransition { .. } => AppHttpResponse::Conflict(
+                        Self::body("invalid_status_transition", &ae.to_string(), request_id),
+                    ),
+                    AppointmentError::AppointmentNotScheduled => AppHttpResponse::Conflict(
+                        Self::body("appointment_not_scheduled", &ae.to_string(), request_id),
+                    ),
+                    AppointmentError::AppointmentRequestError(msg) => {
+                        AppHttpResponse::InternalServerError(Self::body(
+                            "appointment_request_error",
+                            &msg,
+                            request_id,
+                        ))
+                    }
                 }
-                AuthError::SignOutError(msg) => {
-                    AppHttpResponse::Unauthorized(Self::body("sign_out_error", &msg, request_id))
+            }
+            AppError::Audit(ae) => match ae {
+                AuditError::AuditRequestError(msg) => AppHttpResponse::InternalServerError(
+                    Self::body("audit_request_error", &msg, request_id),
+                ),
+            },
+            AppError::Auth(ae) => {
+                match ae {
+                    AuthError::InvalidCredentials => AppHttpResponse::Unauthorized(Self::body(
+                        "sign_in_error",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::SignInError(msg) => {
+                        AppHttpResponse::Unauthorized(Self::body("sign_in_error", &msg, request_id))
+                    }
+                    AuthError::SignOutError(msg) => AppHttpResponse::Unauthorized(Self::body(
+                        "sign_out_error",
+                        &msg,
+                        request_id,
+                    )),
+                    AuthError::SignUpError(msg) => {
+                        AppHttpResponse::BadRequest(Self::body("sign_up_error", &msg, request_id))
+                    }
+                    AuthError::EmailAlreadyInUse => AppHttpResponse::Conflict(Self::body(
+                        "email_already_in_use",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::DeleteUserError(msg) => AppHttpResponse::InternalServerError(
+                        Self::body("delete_user_error", &msg, request_id),
+                    ),
+                    AuthError::RetrieveUserIdError(msg) => AppHttpResponse::InternalServerError(
+                        Self::body("retrieve_user_id_error", &msg, request_id),
+                    ),
+                    AuthError::UserNotFound => AppHttpResponse::NotFound(Self::body(
+                        "user_not_found",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::MissingToken => AppHttpResponse::Unauthorized(Self::body(
+                        "missing_token",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::InvalidToken => AppHttpResponse::Unauthorized(Self::body(
+                        "invalid_token",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::ExpiredToken => AppHttpResponse::Unauthorized(Self::body(
+                        "expired_token",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::PasswordRecoveryError(msg) => AppHttpResponse::BadRequest(
+                        Self::body("password_recovery_error", &msg, request_id),
+                    ),
+                    AuthError::ExpiredRecoveryToken => AppHttpResponse::Unauthorized(Self::body(
+                        "expired_recovery_token",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::InvalidRecoveryToken => AppHttpResponse::Unauthorized(Self::body(
+                        "invalid_recovery_token",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::MfaError(msg) => {
+                        AppHttpResponse::BadRequest(Self::body("mfa_error", &msg, request_id))
+                    }
+                    AuthError::FactorNotFound => AppHttpResponse::NotFound(Self::body(
+                        "factor_not_found",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::InvalidMfaCode => AppHttpResponse::Unauthorized(Self::body(
+                        "invalid_mfa_code",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::MfaRequired => AppHttpResponse::Forbidden(Self::body(
+                        "mfa_required",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::RefreshError(msg) => {
+                        AppHttpResponse::Unauthorized(Self::body("refresh_error", &msg, request_id))
+                    }
+                    AuthError::InvalidRefreshToken => AppHttpResponse::Unauthorized(Self::body(
+                        "invalid_refresh_token",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::InsufficientRole => AppHttpResponse::Forbidden(Self::body(
+                        "insufficient_role",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::AccountLocked { retry_after_secs } => {
+                        AppHttpResponse::TooManyRequests(
+                            Self::body("account_locked", &ae.to_string(), request_id),
+                            retry_after_secs,
+                        )
+                    }
+                    AuthError::LoginAttemptError(msg) => AppHttpResponse::InternalServerError(
+                        Self::body("login_attempt_error", &msg, request_id),
+                    ),
+                    AuthError::CsrfTokenMismatch => AppHttpResponse::Forbidden(Self::body(
+                        "csrf_token_mismatch",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::SessionIdleTimeout => AppHttpResponse::Unauthorized(Self::body(
+                        "session_idle_timeout",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::SessionActivityError(msg) => AppHttpResponse::InternalServerError(
+                        Self::body("session_activity_error", &msg, request_id),
+                    ),
+                    AuthError::SessionRevoked => AppHttpResponse::Unauthorized(Self::body(
+                        "session_revoked",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::SessionNotFound => AppHttpResponse::NotFound(Self::body(
+                        "session_not_found",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::RevokeSessionError(msg) => AppHttpResponse::InternalServerError(
+                        Self::body("revoke_session_error", &msg, request_id),
+                    ),
+                    AuthError::InvalidCurrentPassword => AppHttpResponse::Forbidden(Self::body(
+                        "invalid_current_password",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::ReauthenticationRequired => AppHttpResponse::Unauthorized(
+                        Self::body("reauthentication_required", &ae.to_string(), request_id),
+                    ),
+                    AuthError::ProfileError(msg) => AppHttpResponse::InternalServerError(
+                        Self::body("profile_error", &msg, request_id),
+                    ),
+                    AuthError::OtpError(msg) => {
+                        AppHttpResponse::BadRequest(Self::body("otp_error", &msg, request_id))
+                    }
+                    AuthError::InvalidOtp => AppHttpResponse::Unauthorized(Self::body(
+                        "invalid_otp",
+                        &ae.to_string(),
+                        request_id,
+                    )),
+                    AuthError::PasswordlessSigninDisabled => AppHttpResponse::Forbidden(
+                        Self::body("passwordless_signin_disabled", &ae.to_string(), request_id),
+                    ),
+                    AuthError::SmsResendThrottled { retry_after_secs } => {
+                        AppHttpResponse::TooManyRequests(
+                            Self::body("sms_resend_throttled", &ae.to_string(), request_id),
+                            retry_after_secs,
+                        )
+                    }
                 }
-                AuthError::SignUpError(msg) => {
-                    AppHttpResponse::BadRequest(Self::body("sign_up_error", &msg, request_id))
+            }
+            AppError::Client(ce) => match ce {
+                ClientError::ClientNotFound => AppHttpResponse::NotFound(Self::body(
+                    "client_not_found",
+                    &ce.to_string(),
+                    request_id,
+                )),
+                ClientError::MrnTaken => {
+                    AppHttpResponse::Conflict(Self::body("mrn_taken", &ce.to_string(), request_id))
                 }
-                AuthError::EmailAlreadyInUse => AppHttpResponse::Conflict(Self::body(
-                    "email_already_in_use",
-                    &ae.to_string(),
+                ClientError::ClientForbidden => AppHttpResponse::Forbidden(Self::body(
+                    "client_forbidden",
+                    &ce.to_string(),
+                    request_id,
+                )),
+                ClientError::InvalidAssignment(_) => AppHttpResponse::BadRequest(Self::body(
+                    "invalid_client_assignment",
+                    &ce.to_string(),
                     request_id,
                 )),
-                AuthError::DeleteUserError(msg) => AppHttpResponse::InternalServerError(
-                    Self::body("delete_user_error", &msg, request_id),
+                ClientError::ClientRequestError(msg) => AppHttpResponse::InternalServerError(
+                    Self::body("client_request_error", &msg, request_id),
                 ),
-                AuthError::RetrieveUserIdError(msg) => AppHttpResponse::InternalServerError(
-                    Self::body("retrieve_user_id_error", &msg, request_id),
+            },
+            AppError::Invitation(ie) => match ie {
+                InvitationError::InvitationNotFound => AppHttpResponse::NotFound(Self::body(
+                    "invitation_not_found",
+                    &ie.to_string(),
+                    request_id,
+                )),
+                InvitationError::InvitationExpired => AppHttpResponse::BadRequest(Self::body(
+                    "invitation_expired",
+                    &ie.to_string(),
+                    request_id,
+                )),
+                InvitationError::InvitationRevoked => AppHttpResponse::BadRequest(Self::body(
+                    "invitation_revoked",
+                    &ie.to_string(),
+                    request_id,
+                )),
+                InvitationError::InvitationAlreadyAccepted => AppHttpResponse::Conflict(
+                    Self::body("invitation_already_accepted", &ie.to_string(), request_id),
                 ),
-                AuthError::UserNotFound => AppHttpResponse::NotFound(Self::body(
-                    "user_not_found",
-                    &ae.to_string(),
+                InvitationError::InvitationPending => AppHttpResponse::Conflict(Self::body(
+                    "invitation_pending",
+                    &ie.to_string(),
+                    request_id,
+                )),
+                InvitationError::AlreadyMember => AppHttpResponse::Conflict(Self::body(
+                    "already_member",
+                    &ie.to_string(),
+                    request_id,
+                )),
+                InvitationError::CannotInvite => AppHttpResponse::Forbidden(Self::body(
+                    "cannot_invite",
+                    &ie.to_string(),
+                    request_id,
+                )),
+                InvitationError::InvitationRequestError(msg) => {
+                    AppHttpResponse::InternalServerError(Self::body(
+                        "invitation_request_error",
+                        &msg,
+                        request_id,
+                    ))
+                }
+            },
+            AppError::Membership(me) => match me {
+                MembershipError::MembershipNotFound => AppHttpResponse::NotFound(Self::body(
+                    "membership_not_found",
+                    &me.to_string(),
+                    request_id,
+                )),
+                MembershipError::RoleAlreadyAssigned => AppHttpResponse::Conflict(Self::body(
+                    "role_already_assigned",
+                    &me.to_string(),
+                    request_id,
+                )),
+                MembershipError::RoleNotAssigned => AppHttpResponse::NotFound(Self::body(
+                    "role_not_assigned",
+                    &me.to_string(),
+                    request_id,
+                )),
+                MembershipError::CannotManageRole => AppHttpResponse::Forbidden(Self::body(
+                    "cannot_manage_role",
+                    &me.to_string(),
+                    request_id,
+                )),
+                MembershipError::CannotManageMembership => AppHttpResponse::Forbidden(Self::body(
+                    "cannot_manage_membership",
+                    &me.to_string(),
+                    request_id,
+                )),
+                MembershipError::LastOwner => {
+                    AppHttpResponse::Conflict(Self::body("last_owner", &me.to_string(), request_id))
+                }
+                MembershipError::MembershipRequestError(msg) => {
+                    AppHttpResponse::InternalServerError(Self::body(
+                        "membership_request_error",
+                        &msg,
+                        request_id,
+                    ))
+                }
+            },
+            AppError::Practice(pe) => match pe {
+                PracticeError::PracticeNotFound => AppHttpResponse::NotFound(Self::body(
+                    "practice_not_found",
+                    &pe.to_string(),
                     request_id,
                 )),
-                AuthError::MissingToken => AppHttpResponse::Unauthorized(Self::body(
-                    "missing_token",
-                    &ae.to_string(),
+                PracticeError::PracticeForbidden => AppHttpResponse::Forbidden(Self::body(
+                    "practice_forbidden",
+                    &pe.to_string(),
                     request_id,
                 )),
-                AuthError::InvalidToken => AppHttpResponse::Unauthorized(Self::body(
-                    "invalid_token",
-                    &ae.to_string(),
+                PracticeError::PracticeRequestError(msg) => AppHttpResponse::InternalServerError(
+                    Self::body("practice_request_error", &msg, request_id),
+                ),
+            },
+            AppError::RateLimit(re) => match re {
+                RateLimitError::TooManyRequests { retry_after_secs } => {
+                    AppHttpResponse::TooManyRequests(
+                        Self::body("rate_limited", &re.to_string(), request_id),
+                        retry_after_secs,
+                    )
+                }
+            },
+            AppError::Sms(se) => match se {
+                SmsError::SendError(msg) => AppHttpResponse::InternalServerError(Self::body(
+                    "sms_send_error",
+                    &msg,
                     request_id,
                 )),
-                AuthError::ExpiredToken => AppHttpResponse::Unauthorized(Self::body(
-                    "expired_token",
-                    &ae.to_string(),
+                SmsError::InvalidHookSignature => AppHttpResponse::Unauthorized(Self::body(
+                    "invalid_hook_signature",
+                    &se.to_string(),
                     request_id,
                 )),
             },
-            AppError::Validation(ve) => match ve {
-                ValidationError::InvalidEmail => AppHttpResponse::BadRequest(Self::body(
-                    "invalid_email",
-                    &ve.to_string(),
+            AppError::Sso(se) => {
+                match se {
+                    SsoError::ProviderNotFound => AppHttpResponse::NotFound(Self::body(
+                        "identity_provider_not_found",
+                        &se.to_string(),
+                        request_id,
+                    )),
+                    SsoError::ProviderNotApproved => AppHttpResponse::Forbidden(Self::body(
+                        "identity_provider_not_approved",
+                        &se.to_string(),
+                        request_id,
+                    )),
+                    SsoError::AccountExists => AppHttpResponse::Conflict(Self::body(
+                        "sso_account_exists",
+                        &se.to_string(),
+                        request_id,
+                    )),
+                    SsoError::InvalidState => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_sso_state",
+                        &se.to_string(),
+                        request_id,
+                    )),
+                    SsoError::ProviderError(msg) => {
+                        AppHttpResponse::Unauthorized(Self::body("sso_error", &msg, request_id))
+                    }
+                    SsoError::EmailNotAllowed => AppHttpResponse::Forbidden(Self::body(
+                        "sso_email_not_allowed",
+                        &se.to_string(),
+                        request_id,
+                    )),
+                    SsoError::MembershipInactive => AppHttpResponse::Forbidden(Self::body(
+                        "sso_membership_inactive",
+                        &se.to_string(),
+                        request_id,
+                    )),
+                    SsoError::SsoRequestError(msg) => AppHttpResponse::InternalServerError(
+                        Self::body("sso_request_error", &msg, request_id),
+                    ),
+                }
+            }
+            AppError::Team(te) => match te {
+                TeamError::TeamNotFound => AppHttpResponse::NotFound(Self::body(
+                    "team_not_found",
+                    &te.to_string(),
+                    request_id,
+                )),
+                TeamError::TeamNameTaken => AppHttpResponse::Conflict(Self::body(
+                    "team_name_taken",
+                    &te.to_string(),
+                    request_id,
+                )),
+                TeamError::TeamForbidden => AppHttpResponse::Forbidden(Self::body(
+                    "team_forbidden",
+                    &te.to_string(),
                     request_id,
                 )),
-                ValidationError::WeakPassword => AppHttpResponse::BadRequest(Self::body(
-                    "weak_password",
-                    &ve.to_string(),
+                TeamError::NotPracticeMember => AppHttpResponse::BadRequest(Self::body(
+                    "not_practice_member",
+                    &te.to_string(),
                     request_id,
                 )),
-                ValidationError::InvalidInput(_) => AppHttpResponse::BadRequest(Self::body(
-                    "invalid_input",
-                    &ve.to_string(),
+                TeamError::AlreadyOnTeam => AppHttpResponse::Conflict(Self::body(
+                    "already_on_team",
+                    &te.to_string(),
                     request_id,
                 )),
+                TeamError::NotOnTeam => AppHttpResponse::NotFound(Self::body(
+                    "not_on_team",
+                    &te.to_string(),
+                    request_id,
+                )),
+                TeamError::TeamRequestError(msg) => AppHttpResponse::InternalServerError(
+                    Self::body("team_request_error", &msg, request_id),
+                ),
             },
+            AppError::Validation(ve) => {
+                match ve {
+                    ValidationError::InvalidEmail => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_email",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::WeakPassword(ref violations) => {
+                        let mut body = Self::body("weak_password", &ve.to_string(), request_id);
+                        body.violations = Some(violations.iter().map(Into::into).collect());
+                        AppHttpResponse::BadRequest(body)
+                    }
+                    ValidationError::InvalidInput(_) => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_input",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidPracticeName => AppHttpResponse::BadRequest(
+                        Self::body("invalid_practice_name", &ve.to_string(), request_id),
+                    ),
+                    ValidationError::InvalidRole(_) => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_role",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidTeamName => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_team_name",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidNpi => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_npi",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidPhoneNumber => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_phone_number",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidClientName => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_client_name",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidDateOfBirth => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_date_of_birth",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidAddress(_) => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_address",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidEmergencyContact(_) => AppHttpResponse::BadRequest(
+                        Self::body("invalid_emergency_contact", &ve.to_string(), request_id),
+                    ),
+                    ValidationError::InvalidMrn => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_mrn",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidClientStatus(_) => AppHttpResponse::BadRequest(
+                        Self::body("invalid_client_status", &ve.to_string(), request_id),
+                    ),
+                    ValidationError::InvalidRecurrenceRule(_) => AppHttpResponse::BadRequest(
+                        Self::body("invalid_recurrence_rule", &ve.to_string(), request_id),
+                    ),
+                    ValidationError::InvalidAppointmentTime(_) => AppHttpResponse::BadRequest(
+                        Self::body("invalid_appointment_time", &ve.to_string(), request_id),
+                    ),
+                    ValidationError::InvalidLocation(_) => AppHttpResponse::BadRequest(Self::body(
+                        "invalid_location",
+                        &ve.to_string(),
+                        request_id,
+                    )),
+                    ValidationError::InvalidAppointmentStatus(_) => AppHttpResponse::BadRequest(
+                        Self::body("invalid_appointment_status", &ve.to_string(), request_id),
+                    ),
+                }
+            }
+            AppError::MemoryBackendUnsupported(_) => AppHttpResponse::ServiceUnavailable(
+                Self::body("memory_backend_unsupported", &error.to_string(), request_id),
+            ),
             AppError::Internal { .. } => AppHttpResponse::InternalServerError(Self::body(
                 "internal_server_error",
                 "An internal server error occurred",
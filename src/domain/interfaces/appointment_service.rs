@@ -0,0 +1,67 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::appointment::{
+        Appointment, AppointmentFilter, AppointmentSeries, AppointmentUpdate, NewAppointment,
+        NewAppointmentSeries,
+    },
+};
+
+/// Appointments of a practice. Owners, admins, schedulers and clinical supervisors book for
+/// any clinician; clinicians book for themselves with clients in their caseload. A
+/// clinician sees their own appointments and those of clients they can see. RLS enforces
+/// all of it, and the database rejects any booking that overlaps another for the same
+/// clinician unless one of them is cancelled.
+#[async_trait::async_trait]
+pub trait AppointmentService {
+    /// Cancels every still-scheduled occurrence starting at or after `from` (RFC 3339) and
+    /// returns the series with all its occurrences.
+    async fn cancel_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        series_id: &str,
+        from: &str,
+        reason: Option<&str>,
+    ) -> AppResult<AppointmentSeries>;
+    async fn create_appointment(
+        &self,
+        token: &str,
+        practice_id: &str,
+        appointment: &NewAppointment,
+    ) -> AppResult<Appointment>;
+    /// Books every occurrence or, if any of them clashes, none.
+    async fn create_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        series: &NewAppointmentSeries,
+    ) -> AppResult<AppointmentSeries>;
+    async fn get_appointment(
+        &self,
+        token: &str,
+        practice_id: &str,
+        appointment_id: &str,
+    ) -> AppResult<Appointment>;
+    async fn get_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        series_id: &str,
+    ) -> AppResult<AppointmentSeries>;
+    /// Appointments overlapping the filter's range, ordered by start.
+    async fn list_appointments(
+        &self,
+        token: &str,
+        practice_id: &str,
+        filter: &AppointmentFilter,
+    ) -> AppResult<Vec<Appointment>>;
+    /// Checks the change against the appointment's current status first; see
+    /// [`AppointmentUpdate::check_against`].
+    async fn update_appointment(
+        &self,
+        token: &str,
+        practice_id: &str,
+        appointment_id: &str,
+        update: &AppointmentUpdate,
+    ) -> AppResult<Appointment>;
+}
@@ -0,0 +1,17 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::audit::{AuditFilter, AuditPage},
+};
+
+/// Reads the audit trail with the caller's token; the `audit_read_owner_admin` policy
+/// decides which entries are visible.
+#[async_trait::async_trait]
+pub trait AuditLogService {
+    /// Newest entries first, paginated by descending id.
+    async fn list_audit_log(
+        &self,
+        token: &str,
+        practice_id: &str,
+        filter: &AuditFilter,
+    ) -> AppResult<AuditPage>;
+}
@@ -0,0 +1,7 @@
+use crate::domain::{error::app_error::AppResult, types::audit::AuthAuditEvent};
+
+/// Append-only store for application-level audit events.
+#[async_trait::async_trait]
+pub trait AuditSink {
+    async fn record(&self, event: AuthAuditEvent) -> AppResult<()>;
+}
@@ -1,13 +1,59 @@
 use crate::domain::{
     error::app_error::AppResult,
-    types::{email::Email, password::Password},
+    types::{
+        email::Email,
+        mfa::{MfaChallenge, MfaFactor, PhoneEnrollment, TotpEnrollment},
+        otp::OtpVerification,
+        password::Password,
+        phone_number::PhoneNumber,
+        profile::{UserProfile, UserUpdate},
+        session::AuthSession,
+        sso::FederatedIdentity,
+    },
 };
 
 #[async_trait::async_trait]
 pub trait AuthService {
+    /// Starts verifying a factor. For a `phone` factor this texts a code, at most once per
+    /// [`SMS_RESEND_INTERVAL_SECS`](crate::domain::types::mfa::SMS_RESEND_INTERVAL_SECS).
+    async fn challenge_factor(&self, token: &str, factor_id: &str) -> AppResult<MfaChallenge>;
+    /// Creates a user whose email is already trusted (e.g. they followed an invite link).
+    async fn create_confirmed_user(&self, email: &Email, password: &Password) -> AppResult<()>;
     async fn delete_user(&self, user_id: &str) -> AppResult<()>;
+    async fn enroll_phone(
+        &self,
+        token: &str,
+        phone: &PhoneNumber,
+        friendly_name: Option<&str>,
+    ) -> AppResult<PhoneEnrollment>;
+    async fn enroll_totp(
+        &self,
+        token: &str,
+        friendly_name: Option<&str>,
+    ) -> AppResult<TotpEnrollment>;
+    async fn forgot_password(&self, email: &Email, redirect_to: Option<&str>) -> AppResult<()>;
+    async fn get_user(&self, token: &str) -> AppResult<UserProfile>;
+    async fn list_factors(&self, token: &str) -> AppResult<Vec<MfaFactor>>;
+    async fn refresh_session(&self, refresh_token: &str) -> AppResult<AuthSession>;
+    async fn reset_password(&self, recovery_token: &str, new_password: &Password) -> AppResult<()>;
     async fn retrieve_user_id(&self, email: &Email) -> AppResult<String>;
-    async fn signin(&self, email: &Email, password: &Password) -> AppResult<String>;
+    /// Ends one of the user's sessions with the service role, invalidating its refresh token.
+    async fn revoke_session(&self, user_id: &str, session_id: &str) -> AppResult<()>;
+    /// Ends every session the user has with the service role.
+    async fn revoke_user_sessions(&self, user_id: &str) -> AppResult<()>;
+    /// Emails an existing, confirmed user a sign-in link and code. Unknown addresses succeed
+    /// silently so the endpoint can't be used to probe for accounts.
+    async fn send_magic_link(&self, email: &Email, redirect_to: Option<&str>) -> AppResult<()>;
+    async fn signin(&self, email: &Email, password: &Password) -> AppResult<AuthSession>;
+    /// Opens a session for an identity an OIDC provider has vouched for. `user_id` is the
+    /// account the identity is already linked to; without one a confirmed user is created
+    /// for its email, and an email that already has an account is refused with
+    /// `SsoError::AccountExists` rather than signed in to.
+    async fn signin_with_identity(
+        &self,
+        identity: &FederatedIdentity,
+        user_id: Option<&str>,
+    ) -> AppResult<AuthSession>;
     async fn signout(&self, token: &str) -> AppResult<()>;
     async fn signup(
         &self,
@@ -15,4 +61,27 @@ pub trait AuthService {
         password: &Password,
         redirect_to: Option<&str>,
     ) -> AppResult<()>;
+    async fn unenroll_factor(&self, token: &str, factor_id: &str) -> AppResult<()>;
+    /// Updates the caller's account, `user_id` being the one `token` was issued to. A new
+    /// email only takes effect once confirmed, and `redirect_to` is where the confirmation
+    /// link lands.
+    async fn update_user(
+        &self,
+        token: &str,
+        user_id: &str,
+        update: &UserUpdate,
+        redirect_to: Option<&str>,
+    ) -> AppResult<UserProfile>;
+    /// Completes a challenge and returns a session elevated to AAL2.
+    async fn verify_factor(
+        &self,
+        token: &str,
+        factor_id: &str,
+        challenge_id: &str,
+        code: &str,
+    ) -> AppResult<AuthSession>;
+    /// Exchanges a magic-link token or emailed code for a session.
+    async fn verify_otp(&self, verification: &OtpVerification) -> AppResult<AuthSession>;
+    /// Checks a password without leaving a session behind.
+    async fn verify_password(&self, email: &Email, password: &Password) -> AppResult<()>;
 }
@@ -0,0 +1,48 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::client::{Client, ClientAssignment, ClientSearch, ClientUpdate, NewClient},
+};
+
+/// Clients of a practice. Clinicians read and edit their caseload; owners, admins, clinical
+/// supervisors, schedulers and billers reach every client. Owners, admins and clinical
+/// supervisors assign clients, and only owners and admins may delete one. RLS enforces all
+/// of it, so clients outside the caller's reach are reported as not found.
+#[async_trait::async_trait]
+pub trait ClientService {
+    /// Replaces the client's assignment; `None` fields are unassigned.
+    async fn assign_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+        assignment: &ClientAssignment,
+    ) -> AppResult<Client>;
+    async fn create_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client: &NewClient,
+    ) -> AppResult<Client>;
+    async fn delete_client(&self, token: &str, practice_id: &str, client_id: &str)
+    -> AppResult<()>;
+    async fn get_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+    ) -> AppResult<Client>;
+    /// At most 200 matches, ordered by last name, first name and date of birth.
+    async fn search_clients(
+        &self,
+        token: &str,
+        practice_id: &str,
+        search: &ClientSearch,
+    ) -> AppResult<Vec<Client>>;
+    async fn update_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+        update: &ClientUpdate,
+    ) -> AppResult<Client>;
+}
@@ -0,0 +1,7 @@
+use crate::domain::{error::app_error::AppResult, types::email::Email};
+
+/// Outgoing mail the app sends itself, as opposed to the auth emails Supabase delivers.
+#[async_trait::async_trait]
+pub trait EmailSender {
+    async fn send(&self, to: &Email, subject: &str, html_body: &str) -> AppResult<()>;
+}
@@ -0,0 +1,52 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::sso::{FederatedIdentity, IdentityProvider, NewIdentityProvider},
+};
+
+/// Per-practice OIDC provider configuration. Owners and admins manage it with their own
+/// token; the login flow reads it and links identities with the service role.
+#[async_trait::async_trait]
+pub trait IdentityProviderService {
+    async fn create_provider(
+        &self,
+        token: &str,
+        practice_id: &str,
+        provider: &NewIdentityProvider,
+    ) -> AppResult<IdentityProvider>;
+    async fn delete_provider(
+        &self,
+        token: &str,
+        practice_id: &str,
+        provider_id: &str,
+    ) -> AppResult<()>;
+    /// Marks the provider approved, letting it sign users in. Platform admins only; the
+    /// caller checks that.
+    async fn approve_provider(
+        &self,
+        practice_id: &str,
+        provider_id: &str,
+    ) -> AppResult<IdentityProvider>;
+    /// Loads a provider of a live practice, client secret included, for the login flow.
+    async fn get_provider(&self, provider_id: &str) -> AppResult<IdentityProvider>;
+    /// The account `identity` is linked to, if any.
+    async fn linked_user(
+        &self,
+        provider: &IdentityProvider,
+        identity: &FederatedIdentity,
+    ) -> AppResult<Option<String>>;
+    /// Records that `identity` signed in as `user_id` and, the first time, makes them a
+    /// member of the provider's practice with its default role. Only with `new_link` may
+    /// an identity that isn't linked yet be linked to `user_id`.
+    async fn link_identity(
+        &self,
+        provider: &IdentityProvider,
+        identity: &FederatedIdentity,
+        user_id: &str,
+        new_link: bool,
+    ) -> AppResult<()>;
+    async fn list_providers(
+        &self,
+        token: &str,
+        practice_id: &str,
+    ) -> AppResult<Vec<IdentityProvider>>;
+}
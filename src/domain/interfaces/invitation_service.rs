@@ -0,0 +1,40 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::{
+        email::Email,
+        invitation::{Invitation, InvitationPreview},
+        practice_role::PracticeRole,
+    },
+};
+
+/// Staff invitations. Callers pass the hash of the link token, never the token itself.
+#[async_trait::async_trait]
+pub trait InvitationService {
+    /// Joins the signed-in invitee to the practice with the invited roles.
+    async fn accept_invitation(&self, token: &str, token_hash: &str) -> AppResult<Invitation>;
+    async fn create_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        email: &Email,
+        roles: &[PracticeRole],
+        token_hash: &str,
+    ) -> AppResult<Invitation>;
+    /// Looks an invitation up by link token; needs no signed-in user.
+    async fn find_invitation(&self, token_hash: &str) -> AppResult<InvitationPreview>;
+    async fn list_invitations(&self, token: &str, practice_id: &str) -> AppResult<Vec<Invitation>>;
+    /// Swaps in a new token hash, invalidating the old link, and restarts the expiry window.
+    async fn resend_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        invitation_id: &str,
+        token_hash: &str,
+    ) -> AppResult<Invitation>;
+    async fn revoke_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        invitation_id: &str,
+    ) -> AppResult<Invitation>;
+}
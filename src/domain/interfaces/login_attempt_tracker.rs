@@ -0,0 +1,12 @@
+use crate::domain::{error::app_error::AppResult, types::email::Email};
+
+/// Throttles signin attempts per account and per client IP.
+#[async_trait::async_trait]
+pub trait LoginAttemptTracker {
+    /// Fails with `AuthError::AccountLocked` while the account or the client IP is locked.
+    async fn check(&self, email: &Email, client_ip: Option<&str>) -> AppResult<()>;
+    async fn record_failure(&self, email: &Email, client_ip: Option<&str>) -> AppResult<()>;
+    /// Clears the account's failures. The client IP's count is left to expire, so one valid
+    /// login can't reset a credential-stuffing run.
+    async fn record_success(&self, email: &Email) -> AppResult<()>;
+}
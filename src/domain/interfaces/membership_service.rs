@@ -0,0 +1,39 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::{
+        membership::{PracticeMember, PracticeRoleSet},
+        practice_role::PracticeRole,
+    },
+};
+
+/// Membership and role management. Who may change what is decided by the RLS policies and
+/// `private.can_manage_membership_roles`; implementations translate denials into
+/// `MembershipError`s.
+#[async_trait::async_trait]
+pub trait MembershipService {
+    async fn add_role(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        role: PracticeRole,
+    ) -> AppResult<PracticeMember>;
+    async fn list_members(&self, token: &str, practice_id: &str) -> AppResult<Vec<PracticeMember>>;
+    /// Active memberships of `user_id` in live practices, as far as the caller can see them:
+    /// all of their own, and other users' only in practices the caller owns or administers.
+    async fn list_user_roles(&self, token: &str, user_id: &str) -> AppResult<Vec<PracticeRoleSet>>;
+    async fn remove_role(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        role: PracticeRole,
+    ) -> AppResult<PracticeMember>;
+    async fn set_membership_active(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        is_active: bool,
+    ) -> AppResult<PracticeMember>;
+}
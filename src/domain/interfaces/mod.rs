@@ -1 +1,16 @@
+pub mod appointment_service;
+pub mod audit_log_service;
+pub mod audit_sink;
 pub mod auth_service;
+pub mod client_service;
+pub mod email_sender;
+pub mod identity_provider_service;
+pub mod invitation_service;
+pub mod login_attempt_tracker;
+pub mod membership_service;
+pub mod oidc_client;
+pub mod practice_service;
+pub mod session_activity_tracker;
+pub mod sms_gateway;
+pub mod team_service;
+pub mod token_verifier;
@@ -0,0 +1,24 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::sso::{FederatedIdentity, IdentityProvider, SsoLogin},
+};
+
+/// The relying-party side of the OIDC authorization-code flow with PKCE.
+#[async_trait::async_trait]
+pub trait OidcClient {
+    /// Where to send the browser to sign in with `provider`.
+    async fn authorization_url(
+        &self,
+        provider: &IdentityProvider,
+        login: &SsoLogin,
+        redirect_uri: &str,
+    ) -> AppResult<String>;
+    /// Redeems the authorization code and validates the returned ID token.
+    async fn exchange_code(
+        &self,
+        provider: &IdentityProvider,
+        login: &SsoLogin,
+        code: &str,
+        redirect_uri: &str,
+    ) -> AppResult<FederatedIdentity>;
+}
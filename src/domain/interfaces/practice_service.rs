@@ -0,0 +1,37 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::practice::{Practice, PracticeName},
+};
+
+/// Practice operations run with the caller's token, so RLS decides what each user may see
+/// or change.
+#[async_trait::async_trait]
+pub trait PracticeService {
+    /// Creates a practice and makes the caller its owner.
+    async fn create_practice(&self, token: &str, name: &PracticeName) -> AppResult<Practice>;
+    async fn delete_practice(&self, token: &str, practice_id: &str) -> AppResult<()>;
+    async fn get_practice(&self, token: &str, practice_id: &str) -> AppResult<Practice>;
+    async fn list_practices(&self, token: &str) -> AppResult<Vec<Practice>>;
+    async fn rename_practice(
+        &self,
+        token: &str,
+        practice_id: &str,
+        name: &PracticeName,
+    ) -> AppResult<Practice>;
+    async fn set_idle_timeout(
+        &self,
+        token: &str,
+        practice_id: &str,
+        idle_timeout_secs: Option<i64>,
+    ) -> AppResult<Practice>;
+    async fn set_passwordless_signin(
+        &self,
+        token: &str,
+        practice_id: &str,
+        allowed: bool,
+    ) -> AppResult<Practice>;
+    /// The shortest idle timeout among the caller's active practices, if any sets one.
+    async fn session_idle_timeout(&self, token: &str) -> AppResult<Option<i64>>;
+    /// False if any of the caller's active practices has turned passwordless signin off.
+    async fn passwordless_signin_allowed(&self, token: &str) -> AppResult<bool>;
+}
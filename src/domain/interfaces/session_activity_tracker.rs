@@ -0,0 +1,11 @@
+use crate::domain::{error::app_error::AppResult, types::session::SessionActivity};
+
+/// Stores when each session was last used, keyed by Supabase's `session_id`.
+#[async_trait::async_trait]
+pub trait SessionActivityTracker {
+    async fn get(&self, session_id: &str) -> AppResult<Option<SessionActivity>>;
+    /// Every record for the user, most recently used first, including revoked and idle ones.
+    async fn list_for_user(&self, user_id: &str) -> AppResult<Vec<SessionActivity>>;
+    async fn save(&self, activity: &SessionActivity) -> AppResult<()>;
+    async fn remove(&self, session_id: &str) -> AppResult<()>;
+}
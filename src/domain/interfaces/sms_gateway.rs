@@ -0,0 +1,7 @@
+use crate::domain::{error::app_error::AppResult, types::phone_number::PhoneNumber};
+
+/// Delivers text messages, such as the codes for SMS second factors.
+#[async_trait::async_trait]
+pub trait SmsGateway {
+    async fn send(&self, to: &PhoneNumber, body: &str) -> AppResult<()>;
+}
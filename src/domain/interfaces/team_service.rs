@@ -0,0 +1,37 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::team::{Team, TeamName},
+};
+
+/// Teams within a practice. Owners and admins manage teams; clinical supervisors may also
+/// manage rosters. RLS enforces both.
+#[async_trait::async_trait]
+pub trait TeamService {
+    /// Adds `user_id`, who must be an active member of the team's practice.
+    async fn add_team_member(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        user_id: &str,
+    ) -> AppResult<Team>;
+    async fn create_team(&self, token: &str, practice_id: &str, name: &TeamName)
+    -> AppResult<Team>;
+    async fn delete_team(&self, token: &str, practice_id: &str, team_id: &str) -> AppResult<()>;
+    async fn get_team(&self, token: &str, practice_id: &str, team_id: &str) -> AppResult<Team>;
+    async fn list_teams(&self, token: &str, practice_id: &str) -> AppResult<Vec<Team>>;
+    async fn remove_team_member(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        user_id: &str,
+    ) -> AppResult<Team>;
+    async fn rename_team(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        name: &TeamName,
+    ) -> AppResult<Team>;
+}
@@ -0,0 +1,7 @@
+use crate::domain::{error::app_error::AppResult, types::session::TokenClaims};
+
+/// Checks an access token's signature, issuer, audience and expiry.
+#[async_trait::async_trait]
+pub trait TokenVerifier {
+    async fn verify(&self, token: &str) -> AppResult<TokenClaims>;
+}
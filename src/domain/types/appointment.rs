@@ -0,0 +1,489 @@
+use serde::{Deserialize, Serialize};
+use time::{Date, Duration, OffsetDateTime, Time, format_description::well_known::Rfc3339};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AppointmentError, ValidationError},
+        types::recurrence_rule::RecurrenceRule,
+    },
+    utils::clock::parse_iso_date,
+};
+
+pub const MIN_APPOINTMENT_MINUTES: u32 = 5;
+pub const MAX_APPOINTMENT_MINUTES: u32 = 12 * 60;
+/// Longest window one range query may cover.
+pub const MAX_APPOINTMENT_RANGE_DAYS: i64 = 93;
+const MAX_LOCATION_CHARS: usize = 200;
+const MAX_TELEHEALTH_URL_CHARS: usize = 500;
+const MAX_CANCELLATION_REASON_CHARS: usize = 500;
+const MAX_TIMEZONE_CHARS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppointmentStatus {
+    Scheduled,
+    CheckedIn,
+    Completed,
+    NoShow,
+    Cancelled,
+}
+
+impl AppointmentStatus {
+    pub const ALL: [AppointmentStatus; 5] = [
+        AppointmentStatus::Scheduled,
+        AppointmentStatus::CheckedIn,
+        AppointmentStatus::Completed,
+        AppointmentStatus::NoShow,
+        AppointmentStatus::Cancelled,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppointmentStatus::Scheduled => "scheduled",
+            AppointmentStatus::CheckedIn => "checked_in",
+            AppointmentStatus::Completed => "completed",
+            AppointmentStatus::NoShow => "no_show",
+            AppointmentStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(code: &str) -> AppResult<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == code)
+            .ok_or_else(|| ValidationError::InvalidAppointmentStatus(code.to_string()).into())
+    }
+
+    /// Completed, no-show and cancelled appointments are closed for good.
+    pub fn can_become(&self, next: AppointmentStatus) -> bool {
+        use AppointmentStatus::*;
+        matches!(
+            (self, next),
+            (Scheduled, CheckedIn | Completed | NoShow | Cancelled)
+                | (CheckedIn, Completed | Cancelled)
+        )
+    }
+}
+
+impl std::fmt::Display for AppointmentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The client an appointment is with, as far as the caller may see them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppointmentClient {
+    pub id: String,
+    pub mrn: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub preferred_name: Option<String>,
+}
+
+/// A row of `public.appointments`, with its client embedded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Appointment {
+    pub id: String,
+    pub practice_id: String,
+    pub client_id: String,
+    pub clinician_id: String,
+    pub series_id: Option<String>,
+    /// A series occurrence that was moved or cancelled on its own.
+    pub is_exception: bool,
+    pub starts_at: String,
+    pub ends_at: String,
+    pub location_kind: String,
+    pub location: Option<String>,
+    pub telehealth_url: Option<String>,
+    pub status: AppointmentStatus,
+    pub status_changed_at: Option<String>,
+    pub cancellation_reason: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// `None` when the client is outside the caller's caseload.
+    #[serde(default)]
+    pub client: Option<AppointmentClient>,
+}
+
+/// A row of `public.appointment_series` with its occurrences.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppointmentSeries {
+    pub id: String,
+    pub practice_id: String,
+    pub client_id: String,
+    pub clinician_id: String,
+    pub rrule: String,
+    pub timezone: String,
+    pub start_date: String,
+    pub start_time: String,
+    pub duration_minutes: u32,
+    pub exdates: Vec<String>,
+    pub location_kind: String,
+    pub location: Option<String>,
+    pub telehealth_url: Option<String>,
+    pub cancelled_from: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    #[serde(default)]
+    pub appointments: Vec<Appointment>,
+}
+
+/// Where an appointment happens: a room or address, or a video link. Either detail may be
+/// filled in later.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppointmentLocation {
+    InPerson { location: Option<String> },
+    Telehealth { url: Option<String> },
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+impl AppointmentLocation {
+    #[tracing::instrument(name = "appointment_location_creation", skip_all)]
+    pub fn new(
+        kind: &str,
+        location: Option<String>,
+        telehealth_url: Option<String>,
+    ) -> AppResult<Self> {
+        let location = non_empty(location);
+        let telehealth_url = non_empty(telehealth_url);
+
+        match kind {
+            "in_person" => {
+                if telehealth_url.is_some() {
+                    return Err(ValidationError::InvalidLocation(
+                        "telehealth_url is only for telehealth appointments".to_string(),
+                    )
+                    .into());
+                }
+                if location.as_ref().is_some_and(|l| {
+                    l.chars().count() > MAX_LOCATION_CHARS || l.chars().any(char::is_control)
+                }) {
+                    return Err(ValidationError::InvalidLocation(format!(
+                        "location must be at most {MAX_LOCATION_CHARS} characters"
+                    ))
+                    .into());
+                }
+                Ok(AppointmentLocation::InPerson { location })
+            }
+            "telehealth" => {
+                if location.is_some() {
+                    return Err(ValidationError::InvalidLocation(
+                        "location is only for in-person appointments".to_string(),
+                    )
+                    .into());
+                }
+                if let Some(url) = &telehealth_url
+                    && (url.len() > MAX_TELEHEALTH_URL_CHARS
+                        || !reqwest::Url::parse(url).is_ok_and(|u| u.scheme() == "https"))
+                {
+                    return Err(ValidationError::InvalidLocation(
+                        "telehealth_url must be an https URL".to_string(),
+                    )
+                    .into());
+                }
+                Ok(AppointmentLocation::Telehealth {
+                    url: telehealth_url,
+                })
+            }
+            _ => Err(ValidationError::InvalidLocation(
+                "location_kind must be in_person or telehealth".to_string(),
+            )
+            .into()),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppointmentLocation::InPerson { .. } => "in_person",
+            AppointmentLocation::Telehealth { .. } => "telehealth",
+        }
+    }
+
+    pub fn location(&self) -> Option<&str> {
+        match self {
+            AppointmentLocation::InPerson { location } => location.as_deref(),
+            AppointmentLocation::Telehealth { .. } => None,
+        }
+    }
+
+    pub fn telehealth_url(&self) -> Option<&str> {
+        match self {
+            AppointmentLocation::InPerson { .. } => None,
+            AppointmentLocation::Telehealth { url } => url.as_deref(),
+        }
+    }
+}
+
+/// Parses an RFC 3339 timestamp for `field`.
+pub fn parse_timestamp(value: &str, field: &str) -> AppResult<OffsetDateTime> {
+    OffsetDateTime::parse(value.trim(), &Rfc3339).map_err(|_| {
+        ValidationError::InvalidAppointmentTime(format!(
+            "{field} must be an RFC 3339 timestamp such as 2026-11-02T09:00:00-05:00"
+        ))
+        .into()
+    })
+}
+
+fn check_duration(minutes: u32) -> AppResult<()> {
+    if !(MIN_APPOINTMENT_MINUTES..=MAX_APPOINTMENT_MINUTES).contains(&minutes) {
+        return Err(ValidationError::InvalidAppointmentTime(format!(
+            "duration_minutes must be between {MIN_APPOINTMENT_MINUTES} and {MAX_APPOINTMENT_MINUTES}"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// When an appointment starts and ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppointmentSlot {
+    starts_at: OffsetDateTime,
+    ends_at: OffsetDateTime,
+}
+
+impl AppointmentSlot {
+    #[tracing::instrument(name = "appointment_slot_creation", skip_all)]
+    pub fn new(starts_at: &str, duration_minutes: u32) -> AppResult<Self> {
+        let starts_at = parse_timestamp(starts_at, "starts_at")?;
+        check_duration(duration_minutes)?;
+
+        Ok(AppointmentSlot {
+            starts_at,
+            ends_at: starts_at + Duration::minutes(i64::from(duration_minutes)),
+        })
+    }
+
+    pub fn starts_at(&self) -> String {
+        format_timestamp(self.starts_at)
+    }
+
+    pub fn ends_at(&self) -> String {
+        format_timestamp(self.ends_at)
+    }
+}
+
+pub fn format_timestamp(at: OffsetDateTime) -> String {
+    at.format(&Rfc3339).unwrap_or_default()
+}
+
+/// A `[from, to)` window for listing appointments; anything overlapping it is included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppointmentRange {
+    from: OffsetDateTime,
+    to: OffsetDateTime,
+}
+
+impl AppointmentRange {
+    #[tracing::instrument(name = "appointment_range_creation", skip_all)]
+    pub fn new(from: &str, to: &str) -> AppResult<Self> {
+        let from = parse_timestamp(from, "from")?;
+        let to = parse_timestamp(to, "to")?;
+        if to <= from || to - from > Duration::days(MAX_APPOINTMENT_RANGE_DAYS) {
+            return Err(ValidationError::InvalidAppointmentTime(format!(
+                "to must be after from, and at most {MAX_APPOINTMENT_RANGE_DAYS} days later"
+            ))
+            .into());
+        }
+
+        Ok(AppointmentRange { from, to })
+    }
+
+    pub fn from(&self) -> String {
+        format_timestamp(self.from)
+    }
+
+    pub fn to(&self) -> String {
+        format_timestamp(self.to)
+    }
+}
+
+/// An IANA time zone name such as `America/Chicago`. Postgres has the final say on
+/// whether it exists.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimeZoneName {
+    inner: String,
+}
+
+impl AsRef<str> for TimeZoneName {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl TimeZoneName {
+    #[tracing::instrument(name = "time_zone_name_creation", skip_all)]
+    pub fn new(name: String) -> AppResult<Self> {
+        let trimmed = name.trim();
+
+        if trimmed.is_empty()
+            || trimmed.len() > MAX_TIMEZONE_CHARS
+            || !trimmed
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '+'))
+        {
+            return Err(ValidationError::InvalidAppointmentTime(
+                "timezone must be an IANA name such as America/Chicago".to_string(),
+            )
+            .into());
+        }
+
+        Ok(TimeZoneName {
+            inner: trimmed.to_string(),
+        })
+    }
+}
+
+/// Parses a `HH:MM` wall-clock time.
+pub fn parse_local_time(value: &str) -> AppResult<Time> {
+    value
+        .trim()
+        .split_once(':')
+        .filter(|(hour, minute)| hour.len() == 2 && minute.len() == 2)
+        .and_then(|(hour, minute)| Some((hour.parse().ok()?, minute.parse().ok()?)))
+        .and_then(|(hour, minute)| Time::from_hms(hour, minute, 0).ok())
+        .ok_or_else(|| {
+            ValidationError::InvalidAppointmentTime("start_time must be HH:MM".to_string()).into()
+        })
+}
+
+/// Parses a `YYYY-MM-DD` date for `field`.
+pub fn parse_local_date(value: &str, field: &str) -> AppResult<Date> {
+    parse_iso_date(value.trim()).ok_or_else(|| {
+        ValidationError::InvalidAppointmentTime(format!("{field} must be YYYY-MM-DD")).into()
+    })
+}
+
+pub fn check_cancellation_reason(reason: Option<String>) -> AppResult<Option<String>> {
+    let reason = non_empty(reason);
+    if reason
+        .as_ref()
+        .is_some_and(|r| r.chars().count() > MAX_CANCELLATION_REASON_CHARS)
+    {
+        return Err(ValidationError::InvalidInput(format!(
+            "cancellation_reason must be at most {MAX_CANCELLATION_REASON_CHARS} characters"
+        ))
+        .into());
+    }
+    Ok(reason)
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAppointment {
+    pub client_id: String,
+    pub clinician_id: String,
+    pub slot: AppointmentSlot,
+    pub location: AppointmentLocation,
+}
+
+/// A recurring series. Each occurrence starts at `start_time` in `timezone` on a date the
+/// rule yields from `start_date`; the database expands the same rule when it is stored.
+#[derive(Debug, Clone)]
+pub struct NewAppointmentSeries {
+    pub client_id: String,
+    pub clinician_id: String,
+    pub rule: RecurrenceRule,
+    pub timezone: TimeZoneName,
+    pub start_date: Date,
+    pub start_time: Time,
+    pub duration_minutes: u32,
+    pub exdates: Vec<Date>,
+    pub location: AppointmentLocation,
+}
+
+impl NewAppointmentSeries {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        client_id: String,
+        clinician_id: String,
+        rule: RecurrenceRule,
+        timezone: TimeZoneName,
+        start_date: Date,
+        start_time: Time,
+        duration_minutes: u32,
+        exdates: Vec<Date>,
+        location: AppointmentLocation,
+    ) -> AppResult<Self> {
+        check_duration(duration_minutes)?;
+        // Reports a rule that yields nothing, or too much, before it reaches the database.
+        rule.occurrences(start_date, &exdates)?;
+
+        Ok(NewAppointmentSeries {
+            client_id,
+            clinician_id,
+            rule,
+            timezone,
+            start_date,
+            start_time,
+            duration_minutes,
+            exdates,
+            location,
+        })
+    }
+}
+
+/// Changes to one appointment. `None` leaves a field alone.
+#[derive(Debug, Clone, Default)]
+pub struct AppointmentUpdate {
+    pub clinician_id: Option<String>,
+    pub slot: Option<AppointmentSlot>,
+    pub location: Option<AppointmentLocation>,
+    pub status: Option<AppointmentStatus>,
+    pub cancellation_reason: Option<String>,
+}
+
+impl AppointmentUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.clinician_id.is_none()
+            && self.slot.is_none()
+            && self.location.is_none()
+            && self.status.is_none()
+    }
+
+    /// Only scheduled appointments can be moved, and the status has to follow
+    /// [`AppointmentStatus::can_become`]. Setting the current status again is a no-op.
+    pub fn check_against(&self, current: &Appointment) -> AppResult<()> {
+        let moves = self.clinician_id.is_some() || self.slot.is_some() || self.location.is_some();
+        if moves && current.status != AppointmentStatus::Scheduled {
+            return Err(AppointmentError::AppointmentNotScheduled.into());
+        }
+
+        if let Some(next) = self.status
+            && next != current.status
+            && !current.status.can_become(next)
+        {
+            return Err(AppointmentError::InvalidStatusTransition {
+                from: current.status.to_string(),
+                to: next.to_string(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Whether this moves `current` away from what its series planned.
+    pub fn departs_from_series(&self, current: &Appointment) -> bool {
+        current.series_id.is_some()
+            && (self.clinician_id.is_some()
+                || self.slot.is_some()
+                || self.location.is_some()
+                || (self.status == Some(AppointmentStatus::Cancelled)
+                    && current.status != AppointmentStatus::Cancelled))
+    }
+}
+
+/// Filters for a range query; `range` is required so results stay bounded.
+#[derive(Debug, Clone)]
+pub struct AppointmentFilter {
+    pub range: AppointmentRange,
+    pub clinician_id: Option<String>,
+    pub client_id: Option<String>,
+    pub status: Option<AppointmentStatus>,
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::domain::error::app_error::{AppResult, ValidationError};
+
+/// Tables with `fn_audit_trigger` attached.
+pub const AUDITED_TABLES: &[&str] = &[
+    "appointment_series",
+    "appointments",
+    "clients",
+    "practice_membership_roles",
+    "practice_memberships",
+    "practices",
+    "team_members",
+    "teams",
+];
+
+pub const DEFAULT_AUDIT_PAGE_SIZE: usize = 50;
+pub const MAX_AUDIT_PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl AsRef<str> for AuditOperation {
+    fn as_ref(&self) -> &str {
+        match self {
+            AuditOperation::Insert => "INSERT",
+            AuditOperation::Update => "UPDATE",
+            AuditOperation::Delete => "DELETE",
+        }
+    }
+}
+
+impl AuditOperation {
+    pub fn parse(value: &str) -> AppResult<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "INSERT" => Ok(AuditOperation::Insert),
+            "UPDATE" => Ok(AuditOperation::Update),
+            "DELETE" => Ok(AuditOperation::Delete),
+            _ => Err(ValidationError::InvalidInput(
+                "operation must be one of INSERT, UPDATE or DELETE".to_string(),
+            )
+            .into()),
+        }
+    }
+}
+
+/// Validated audit log filters. Timestamps are passed through to Postgres, which rejects
+/// malformed values with `22007`.
+#[derive(Debug, Clone)]
+pub struct AuditFilter {
+    pub table_name: Option<String>,
+    pub operation: Option<AuditOperation>,
+    pub actor_user_id: Option<String>,
+    pub row_id: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Only entries with an id below this one are returned.
+    pub cursor: Option<i64>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub occurred_at: String,
+    pub table_name: String,
+    pub operation: String,
+    pub actor_user_id: Option<String>,
+    pub practice_id: Option<String>,
+    pub row_id: Option<String>,
+    pub before_data: Option<Value>,
+    pub after_data: Option<Value>,
+    /// Fields that differ between `before_data` and `after_data`.
+    #[serde(default, skip_deserializing)]
+    pub changes: Map<String, Value>,
+}
+
+impl AuditEntry {
+    pub fn with_changes(mut self) -> Self {
+        self.changes = diff(self.before_data.as_ref(), self.after_data.as_ref());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditPage {
+    pub entries: Vec<AuditEntry>,
+    /// Pass back as `cursor` to fetch the next (older) page; absent on the last page.
+    pub next_cursor: Option<i64>,
+}
+
+/// Field-level diff of two row snapshots as `{ field: { before, after } }`. A missing
+/// snapshot (INSERT/DELETE) counts as every field being null on that side.
+fn diff(before: Option<&Value>, after: Option<&Value>) -> Map<String, Value> {
+    let empty = Map::new();
+    let before = before.and_then(Value::as_object).unwrap_or(&empty);
+    let after = after.and_then(Value::as_object).unwrap_or(&empty);
+
+    let mut changes = Map::new();
+    for key in before.keys().chain(after.keys()) {
+        if changes.contains_key(key) {
+            continue;
+        }
+        let old = before.get(key).unwrap_or(&Value::Null);
+        let new = after.get(key).unwrap_or(&Value::Null);
+        if old != new {
+            changes.insert(key.clone(), json!({ "before": old, "after": new }));
+        }
+    }
+    changes
+}
+
+/// Authentication actions recorded in `auth_audit_events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthAuditAction {
+    Signin,
+    Signout,
+    DeleteUser,
+    RetrieveUserId,
+    RevokeSessions,
+    UpdateUser,
+    LinkIdentity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// One authentication action, for HIPAA access accounting.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthAuditEvent {
+    pub action: AuthAuditAction,
+    pub outcome: AuditOutcome,
+    pub actor_user_id: Option<String>,
+    /// The email, user id or session id the action was about.
+    pub target: Option<String>,
+    pub request_id: String,
+    pub client_ip: Option<String>,
+    /// Error message for failed actions.
+    pub reason: Option<String>,
+}
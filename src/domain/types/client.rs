@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    error::app_error::{AppResult, ValidationError},
+    types::{
+        date_of_birth::DateOfBirth, emergency_contact::EmergencyContact, phone_number::PhoneNumber,
+        postal_address::PostalAddress,
+    },
+};
+
+const MAX_CLIENT_NAME_CHARS: usize = 100;
+const MAX_MRN_CHARS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientStatus {
+    Active,
+    Discharged,
+}
+
+impl ClientStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClientStatus::Active => "active",
+            ClientStatus::Discharged => "discharged",
+        }
+    }
+
+    pub fn parse(code: &str) -> AppResult<Self> {
+        [ClientStatus::Active, ClientStatus::Discharged]
+            .into_iter()
+            .find(|status| status.as_str() == code)
+            .ok_or_else(|| ValidationError::InvalidClientStatus(code.to_string()).into())
+    }
+}
+
+/// A row of `public.clients`. Dates and timestamps are passed through as Postgres renders
+/// them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Client {
+    pub id: String,
+    pub practice_id: String,
+    pub mrn: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub preferred_name: Option<String>,
+    pub date_of_birth: String,
+    pub phone: Option<String>,
+    pub address_line1: Option<String>,
+    pub address_line2: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub postal_code: Option<String>,
+    pub emergency_contact_name: Option<String>,
+    pub emergency_contact_relationship: Option<String>,
+    pub emergency_contact_phone: Option<String>,
+    pub status: ClientStatus,
+    pub discharged_at: Option<String>,
+    pub primary_clinician_id: Option<String>,
+    pub supervising_clinician_id: Option<String>,
+    pub team_id: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A first, last or preferred name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientName {
+    inner: String,
+}
+
+impl AsRef<str> for ClientName {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl ClientName {
+    #[tracing::instrument(name = "client_name_creation", skip_all)]
+    pub fn new(name: String) -> AppResult<Self> {
+        let trimmed = name.trim();
+
+        if trimmed.is_empty()
+            || trimmed.chars().count() > MAX_CLIENT_NAME_CHARS
+            || trimmed.chars().any(char::is_control)
+        {
+            return Err(ValidationError::InvalidClientName.into());
+        }
+
+        Ok(ClientName {
+            inner: trimmed.to_string(),
+        })
+    }
+}
+
+/// A medical record number, unique within a practice. Stored upper-cased.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mrn {
+    inner: String,
+}
+
+impl AsRef<str> for Mrn {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl Mrn {
+    #[tracing::instrument(name = "mrn_creation", skip_all)]
+    pub fn new(mrn: String) -> AppResult<Self> {
+        let trimmed = mrn.trim();
+
+        if trimmed.is_empty()
+            || trimmed.len() > MAX_MRN_CHARS
+            || !trimmed
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(ValidationError::InvalidMrn.into());
+        }
+
+        Ok(Mrn {
+            inner: trimmed.to_ascii_uppercase(),
+        })
+    }
+}
+
+/// A client to add. Without an MRN the database assigns the next `C0000001`-style number.
+#[derive(Debug, Clone)]
+pub struct NewClient {
+    pub mrn: Option<Mrn>,
+    pub first_name: ClientName,
+    pub last_name: ClientName,
+    pub preferred_name: Option<ClientName>,
+    pub date_of_birth: DateOfBirth,
+    pub phone: Option<PhoneNumber>,
+    pub address: Option<PostalAddress>,
+    pub emergency_contact: Option<EmergencyContact>,
+}
+
+/// Changes to a client. `None` leaves a field alone; `Some(None)` clears an optional one.
+#[derive(Debug, Clone, Default)]
+pub struct ClientUpdate {
+    pub mrn: Option<Mrn>,
+    pub first_name: Option<ClientName>,
+    pub last_name: Option<ClientName>,
+    pub preferred_name: Option<Option<ClientName>>,
+    pub date_of_birth: Option<DateOfBirth>,
+    pub phone: Option<Option<PhoneNumber>>,
+    pub address: Option<Option<PostalAddress>>,
+    pub emergency_contact: Option<Option<EmergencyContact>>,
+    pub status: Option<ClientStatus>,
+}
+
+impl ClientUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.mrn.is_none()
+            && self.first_name.is_none()
+            && self.last_name.is_none()
+            && self.preferred_name.is_none()
+            && self.date_of_birth.is_none()
+            && self.phone.is_none()
+            && self.address.is_none()
+            && self.emergency_contact.is_none()
+            && self.status.is_none()
+    }
+}
+
+/// Who works with a client. Clinicians see the clients they are assigned to, directly or
+/// through a team they are on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientAssignment {
+    pub primary_clinician_id: Option<String>,
+    /// Must hold the `clinical_supervisor` role.
+    pub supervising_clinician_id: Option<String>,
+    pub team_id: Option<String>,
+}
+
+/// Filters for a client search; all given filters must match.
+#[derive(Debug, Clone, Default)]
+pub struct ClientSearch {
+    /// Each word must start the client's first, last or preferred name.
+    pub name: Option<String>,
+    pub date_of_birth: Option<DateOfBirth>,
+    pub mrn: Option<Mrn>,
+    pub status: Option<ClientStatus>,
+}
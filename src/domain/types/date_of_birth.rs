@@ -0,0 +1,33 @@
+use time::{Date, OffsetDateTime};
+
+use crate::{
+    domain::error::app_error::{AppResult, ValidationError},
+    utils::clock::{format_iso_date, parse_iso_date},
+};
+
+/// Earliest birth year accepted, matching the `clients.date_of_birth` check.
+const MIN_BIRTH_YEAR: i32 = 1900;
+
+/// A calendar date of birth, given as `YYYY-MM-DD`, that is neither in the future nor
+/// before 1900.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateOfBirth {
+    inner: Date,
+}
+
+impl std::fmt::Display for DateOfBirth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format_iso_date(self.inner))
+    }
+}
+
+impl DateOfBirth {
+    #[tracing::instrument(name = "date_of_birth_creation", skip_all)]
+    pub fn new(date: String) -> AppResult<Self> {
+        let date = parse_iso_date(date.trim())
+            .filter(|d| d.year() >= MIN_BIRTH_YEAR && *d <= OffsetDateTime::now_utc().date())
+            .ok_or(ValidationError::InvalidDateOfBirth)?;
+
+        Ok(DateOfBirth { inner: date })
+    }
+}
@@ -52,4 +52,9 @@ impl Email {
             .map_err(|_| ValidationError::InvalidEmail)?;
         Ok(email)
     }
+
+    /// Lowercased, as Supabase stores addresses; use for lookups and rate-limit keys.
+    pub fn normalized(&self) -> String {
+        self.inner.expose_secret().trim().to_lowercase()
+    }
 }
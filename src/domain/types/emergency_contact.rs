@@ -0,0 +1,67 @@
+use crate::domain::{
+    error::app_error::{AppResult, ValidationError},
+    types::phone_number::PhoneNumber,
+};
+
+const MAX_NAME_CHARS: usize = 200;
+const MAX_RELATIONSHIP_CHARS: usize = 100;
+
+/// Who to call about a client: a name and phone number, and optionally how they are related.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EmergencyContact {
+    name: String,
+    relationship: Option<String>,
+    phone: PhoneNumber,
+}
+
+fn text_field(value: &str, max_chars: usize) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()
+        && trimmed.chars().count() <= max_chars
+        && !trimmed.chars().any(char::is_control))
+    .then(|| trimmed.to_string())
+}
+
+impl EmergencyContact {
+    #[tracing::instrument(name = "emergency_contact_creation", skip_all)]
+    pub fn new(name: String, relationship: Option<String>, phone: String) -> AppResult<Self> {
+        let name = text_field(&name, MAX_NAME_CHARS).ok_or_else(|| {
+            ValidationError::InvalidEmergencyContact(format!(
+                "emergency_contact_name must be 1 to {MAX_NAME_CHARS} characters"
+            ))
+        })?;
+        let relationship = relationship
+            .filter(|r| !r.trim().is_empty())
+            .map(|r| {
+                text_field(&r, MAX_RELATIONSHIP_CHARS).ok_or_else(|| {
+                    ValidationError::InvalidEmergencyContact(format!(
+                        "emergency_contact_relationship must be at most {MAX_RELATIONSHIP_CHARS} characters"
+                    ))
+                })
+            })
+            .transpose()?;
+        let phone = PhoneNumber::new(phone).map_err(|_| {
+            ValidationError::InvalidEmergencyContact(
+                "emergency_contact_phone is not a valid phone number".to_string(),
+            )
+        })?;
+
+        Ok(EmergencyContact {
+            name,
+            relationship,
+            phone,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn relationship(&self) -> Option<&str> {
+        self.relationship.as_deref()
+    }
+
+    pub fn phone(&self) -> &PhoneNumber {
+        &self.phone
+    }
+}
@@ -0,0 +1,93 @@
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::domain::{
+    error::app_error::{AppResult, InvitationError},
+    types::practice_role::PracticeRole,
+};
+
+/// Lifecycle state computed by `public.status(practice_invitations)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvitationStatus {
+    Pending,
+    Accepted,
+    Revoked,
+    Expired,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invitation {
+    pub id: String,
+    pub practice_id: String,
+    pub email: String,
+    #[serde(rename(deserialize = "role_codes"))]
+    pub roles: Vec<PracticeRole>,
+    pub status: InvitationStatus,
+    pub expires_at: String,
+    pub accepted_at: Option<String>,
+    pub revoked_at: Option<String>,
+    pub created_at: String,
+}
+
+/// What the holder of an invite link may see before accepting it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvitationPreview {
+    pub id: String,
+    pub practice_id: String,
+    pub practice_name: String,
+    pub email: String,
+    #[serde(rename(deserialize = "role_codes"))]
+    pub roles: Vec<PracticeRole>,
+    pub status: InvitationStatus,
+    pub expires_at: String,
+}
+
+impl InvitationPreview {
+    /// Maps every non-pending state to the error the accept flow reports for it.
+    pub fn ensure_pending(&self) -> AppResult<()> {
+        match self.status {
+            InvitationStatus::Pending => Ok(()),
+            InvitationStatus::Accepted => Err(InvitationError::InvitationAlreadyAccepted.into()),
+            InvitationStatus::Revoked => Err(InvitationError::InvitationRevoked.into()),
+            InvitationStatus::Expired => Err(InvitationError::InvitationExpired.into()),
+        }
+    }
+}
+
+/// Single-use secret carried in the invite link. Only its SHA-256 hash is stored.
+#[derive(Debug, Clone)]
+pub struct InviteToken {
+    inner: String,
+}
+
+impl AsRef<str> for InviteToken {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl InviteToken {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::rng().fill_bytes(&mut bytes);
+        Self {
+            inner: bytes.iter().map(|b| format!("{b:02x}")).collect(),
+        }
+    }
+
+    #[tracing::instrument(name = "invite_token_creation", skip_all)]
+    pub fn new(token: String) -> AppResult<Self> {
+        let token = token.trim().to_ascii_lowercase();
+        if token.len() != 64 || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(InvitationError::InvitationNotFound.into());
+        }
+
+        Ok(Self { inner: token })
+    }
+
+    pub fn hash(&self) -> String {
+        format!("{:x}", Sha256::digest(self.inner.as_bytes()))
+    }
+}
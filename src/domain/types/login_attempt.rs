@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+/// How failed signins for one key (an account or a client IP) are throttled: the first
+/// `free_attempts` failures cost nothing, later ones lock the key for `base_delay_secs`
+/// doubling each time, and `max_failures` locks it for `lockout_secs`.
+#[derive(Debug, Clone, Copy)]
+pub struct LockoutPolicy {
+    pub free_attempts: u32,
+    pub max_failures: u32,
+    pub base_delay_secs: i64,
+    pub lockout_secs: i64,
+    /// Failures are forgotten once this long has passed since the last one.
+    pub window_secs: i64,
+}
+
+impl LockoutPolicy {
+    pub fn per_account() -> Self {
+        Self {
+            free_attempts: 3,
+            max_failures: 10,
+            base_delay_secs: 1,
+            lockout_secs: 15 * 60,
+            window_secs: 15 * 60,
+        }
+    }
+
+    /// Looser than [`Self::per_account`] because a clinic's staff often share one NAT address.
+    pub fn per_client_ip() -> Self {
+        Self {
+            free_attempts: 20,
+            max_failures: 100,
+            base_delay_secs: 1,
+            lockout_secs: 15 * 60,
+            window_secs: 15 * 60,
+        }
+    }
+}
+
+/// Failure count for one tracker key. Times are unix seconds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttemptState {
+    pub failures: u32,
+    pub last_failure_at: i64,
+    pub locked_until: i64,
+}
+
+impl AttemptState {
+    /// Seconds until the key may try again, if it is currently locked.
+    pub fn retry_after(&self, now: i64) -> Option<u64> {
+        (self.locked_until > now).then(|| (self.locked_until - now) as u64)
+    }
+
+    pub fn after_failure(&self, policy: &LockoutPolicy, now: i64) -> Self {
+        let failures = if now - self.last_failure_at > policy.window_secs {
+            1
+        } else {
+            self.failures.saturating_add(1)
+        };
+
+        let delay = if failures >= policy.max_failures {
+            policy.lockout_secs
+        } else if failures > policy.free_attempts {
+            let doublings = (failures - policy.free_attempts - 1).min(30);
+            (policy.base_delay_secs << doublings).min(policy.lockout_secs)
+        } else {
+            0
+        };
+
+        Self {
+            failures,
+            last_failure_at: now,
+            locked_until: now + delay,
+        }
+    }
+}
+
+/// Tracker keys for a signin attempt, each with the policy that applies to it.
+pub fn attempt_keys(
+    email: &str,
+    client_ip: Option<&str>,
+    account: LockoutPolicy,
+    client: LockoutPolicy,
+) -> Vec<(String, LockoutPolicy)> {
+    let mut keys = vec![(format!("email:{email}"), account)];
+    if let Some(ip) = client_ip {
+        keys.push((format!("ip:{ip}"), client));
+    }
+    keys
+}
@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+use crate::domain::types::practice_role::PracticeRole;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PracticeMember {
+    pub membership_id: String,
+    pub user_id: String,
+    pub is_active: bool,
+    pub created_at: String,
+    pub roles: Vec<PracticeRole>,
+}
+
+/// A user's active membership in one practice, with the roles held there.
+#[derive(Debug, Clone, Serialize)]
+pub struct PracticeRoleSet {
+    pub practice_id: String,
+    pub roles: Vec<PracticeRole>,
+}
+
+impl PracticeRoleSet {
+    pub fn has_any(&self, roles: &[PracticeRole]) -> bool {
+        self.roles.iter().any(|role| roles.contains(role))
+    }
+}
+
+impl PracticeMember {
+    pub fn has_role(&self, role: PracticeRole) -> bool {
+        self.roles.contains(&role)
+    }
+}
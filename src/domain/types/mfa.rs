@@ -0,0 +1,51 @@
+use serde::Serialize;
+
+/// A freshly enrolled TOTP factor. It stays `unverified` until the first successful challenge.
+#[derive(Debug, Clone, Serialize)]
+pub struct TotpEnrollment {
+    pub factor_id: String,
+    pub friendly_name: Option<String>,
+    pub uri: String,
+    pub qr_code: String,
+    pub secret: String,
+}
+
+/// A freshly enrolled SMS factor. Like TOTP, it is verified by completing a challenge, which
+/// texts a code to `phone`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhoneEnrollment {
+    pub factor_id: String,
+    pub friendly_name: Option<String>,
+    pub phone: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MfaFactor {
+    pub id: String,
+    pub factor_type: String,
+    pub friendly_name: Option<String>,
+    pub status: String,
+    /// Where codes are texted, for `phone` factors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+}
+
+impl MfaFactor {
+    pub fn is_verified(&self) -> bool {
+        self.status == "verified"
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MfaChallenge {
+    pub id: String,
+    pub expires_at: i64,
+}
+
+/// Minimum gap between SMS codes for one factor.
+pub const SMS_RESEND_INTERVAL_SECS: i64 = 30;
+
+/// The text message carrying an SMS factor's code.
+pub fn sms_code_message(code: &str) -> String {
+    format!("Your BreezeEHR verification code is {code}. It expires in 5 minutes.")
+}
@@ -1,2 +1,24 @@
+pub mod appointment;
+pub mod audit;
+pub mod client;
+pub mod date_of_birth;
 pub mod email;
+pub mod emergency_contact;
+pub mod invitation;
+pub mod login_attempt;
+pub mod membership;
+pub mod mfa;
+pub mod npi;
+pub mod otp;
 pub mod password;
+pub mod password_policy;
+pub mod phone_number;
+pub mod postal_address;
+pub mod practice;
+pub mod practice_role;
+pub mod profile;
+pub mod rate_limit;
+pub mod recurrence_rule;
+pub mod session;
+pub mod sso;
+pub mod team;
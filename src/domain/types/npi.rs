@@ -0,0 +1,49 @@
+use crate::domain::error::app_error::{AppResult, ValidationError};
+
+/// The ISO issuer prefix CMS prepends before applying the Luhn check to an NPI.
+const NPI_LUHN_PREFIX: &str = "80840";
+
+/// A National Provider Identifier: ten digits, the last a Luhn check digit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Npi {
+    inner: String,
+}
+
+impl AsRef<str> for Npi {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+fn luhn_valid(digits: &str) -> bool {
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, d)| match (i % 2, d * 2) {
+            (0, _) => d,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+impl Npi {
+    #[tracing::instrument(name = "npi_creation", skip_all)]
+    pub fn new(npi: String) -> AppResult<Self> {
+        let trimmed = npi.trim();
+
+        if trimmed.len() != 10
+            || !trimmed.chars().all(|c| c.is_ascii_digit())
+            || !luhn_valid(&format!("{NPI_LUHN_PREFIX}{trimmed}"))
+        {
+            return Err(ValidationError::InvalidNpi.into());
+        }
+
+        Ok(Npi {
+            inner: trimmed.to_string(),
+        })
+    }
+}
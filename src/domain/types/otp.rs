@@ -0,0 +1,10 @@
+use crate::domain::types::email::Email;
+
+/// What a user brings back from a magic-link email.
+#[derive(Debug, Clone)]
+pub enum OtpVerification {
+    /// The code from the email, typed in along with the address it was sent to.
+    Code { email: Email, code: String },
+    /// The `token` query parameter of the emailed link.
+    TokenHash(String),
+}
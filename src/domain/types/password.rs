@@ -1,9 +1,10 @@
 use std::hash::{Hash, Hasher};
 
+use crate::domain::{
+    error::app_error::{AppResult, ValidationError},
+    types::{email::Email, password_policy::PasswordPolicy},
+};
 use secrecy::{ExposeSecret, SecretString};
-use validator::Validate;
-
-use crate::domain::error::app_error::{AppResult, ValidationError};
 
 #[derive(Debug, Clone)]
 pub struct Password {
@@ -30,44 +31,29 @@ impl Hash for Password {
 
 impl Eq for Password {}
 
-impl Validate for Password {
-    #[tracing::instrument(name = "password_validation", skip_all)]
-    fn validate(&self) -> std::result::Result<(), validator::ValidationErrors> {
-        let password = self.inner.expose_secret();
-
-        // Password must be at least 8 characters long
-        if password.len() < 8 {
-            return Err(validator::ValidationErrors::new());
-        }
-
-        // Must contain at least 1 number
-        if !password.chars().any(|c| c.is_ascii_digit()) {
-            return Err(validator::ValidationErrors::new());
-        }
-
-        // Must contain at least 1 uppercase letter
-        if !password.chars().any(|c| c.is_ascii_uppercase()) {
-            return Err(validator::ValidationErrors::new());
-        }
-
-        // Must contain at least 1 special character (non-alphanumeric)
-        if !password.chars().any(|c| !c.is_alphanumeric()) {
-            return Err(validator::ValidationErrors::new());
+impl Password {
+    /// A password being chosen, so it must satisfy `policy`. `email` is the account's
+    /// address, when known, for the no-email rule.
+    #[tracing::instrument(name = "password_creation", skip_all)]
+    pub fn new(
+        password: String,
+        policy: &PasswordPolicy,
+        email: Option<&Email>,
+    ) -> AppResult<Self> {
+        let violations = policy.check(&password, email);
+        if !violations.is_empty() {
+            return Err(ValidationError::WeakPassword(violations).into());
         }
-
-        Ok(())
+        Ok(Password {
+            inner: SecretString::from(password),
+        })
     }
-}
 
-impl Password {
-    #[tracing::instrument(name = "password_creation", skip_all)]
-    pub fn new(password: String) -> AppResult<Self> {
-        let password = Password {
+    /// A password offered to prove who someone is. The policy doesn't apply, since it may
+    /// have been set before the policy last changed.
+    pub fn existing(password: String) -> Self {
+        Password {
             inner: SecretString::from(password),
-        };
-        password
-            .validate()
-            .map_err(|_| ValidationError::WeakPassword)?;
-        Ok(password)
+        }
     }
 }
@@ -0,0 +1,173 @@
+use std::{collections::HashSet, sync::Arc};
+
+use sha1::{Digest, Sha1};
+
+use crate::domain::types::email::Email;
+
+/// Email local parts shorter than this are too common to be worth rejecting.
+const MIN_EMAIL_MATCH_LEN: usize = 3;
+
+/// Characters GoTrue counts as symbols for `password_requirements`.
+const GOTRUE_SYMBOLS: &str = "!@#$%^&*()_+-=[]{};'\\:\"|<>?,./`~";
+
+/// Rules a newly chosen password must meet. Lengths count bytes and the character classes
+/// are ASCII-only, as in GoTrue, and the defaults match `supabase/config.toml`. GoTrue can
+/// still refuse a password we accepted if the project's Auth settings are stricter, such as
+/// with leaked-password protection on.
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    /// Supabase hashes with bcrypt, which ignores everything past 72 bytes.
+    pub max_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Reject passwords containing the account's email address or its local part.
+    pub forbid_email: bool,
+    /// SHA-1 digests of known-breached passwords, as in the Pwned Passwords download.
+    pub breached: Arc<HashSet<[u8; 20]>>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 72,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            forbid_email: true,
+            breached: Arc::new(HashSet::new()),
+        }
+    }
+}
+
+/// One rule a password broke, reported back so the form can show each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordViolation {
+    TooShort { min: usize },
+    TooLong { max: usize },
+    MissingLowercase,
+    MissingUppercase,
+    MissingDigit,
+    MissingSymbol,
+    ContainsEmail,
+    Breached,
+}
+
+impl PasswordViolation {
+    pub fn code(&self) -> &'static str {
+        match self {
+            PasswordViolation::TooShort { .. } => "too_short",
+            PasswordViolation::TooLong { .. } => "too_long",
+            PasswordViolation::MissingLowercase => "missing_lowercase",
+            PasswordViolation::MissingUppercase => "missing_uppercase",
+            PasswordViolation::MissingDigit => "missing_digit",
+            PasswordViolation::MissingSymbol => "missing_symbol",
+            PasswordViolation::ContainsEmail => "contains_email",
+            PasswordViolation::Breached => "breached",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            PasswordViolation::TooShort { min } => format!("Must be at least {min} characters"),
+            PasswordViolation::TooLong { max } => {
+                format!("Must be at most {max} bytes; accented letters and emoji count as several")
+            }
+            PasswordViolation::MissingLowercase => "Must contain a lowercase letter".to_string(),
+            PasswordViolation::MissingUppercase => "Must contain an uppercase letter".to_string(),
+            PasswordViolation::MissingDigit => "Must contain a number".to_string(),
+            PasswordViolation::MissingSymbol => "Must contain a symbol".to_string(),
+            PasswordViolation::ContainsEmail => "Must not contain your email address".to_string(),
+            PasswordViolation::Breached => {
+                "Appears in a known data breach; choose a different password".to_string()
+            }
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Parses a breached-password list: one hex SHA-1 digest per line, optionally followed by
+    /// `:count` as in the Pwned Passwords download. Blank lines and `#` comments are skipped.
+    pub fn parse_breached(list: &str) -> Result<HashSet<[u8; 20]>, String> {
+        list.lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| {
+                let hex = line.split(':').next().unwrap_or_default();
+                parse_sha1_hex(hex)
+                    .ok_or_else(|| format!("line {} is not a SHA-1 digest: '{hex}'", i + 1))
+            })
+            .collect()
+    }
+
+    /// Every rule `password` breaks, in a stable order; empty when it is acceptable.
+    pub fn check(&self, password: &str, email: Option<&Email>) -> Vec<PasswordViolation> {
+        let mut violations = Vec::new();
+
+        let length = password.len();
+        if length < self.min_length {
+            violations.push(PasswordViolation::TooShort {
+                min: self.min_length,
+            });
+        }
+        if length > self.max_length {
+            violations.push(PasswordViolation::TooLong {
+                max: self.max_length,
+            });
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_ascii_lowercase()) {
+            violations.push(PasswordViolation::MissingLowercase);
+        }
+        if self.require_uppercase && !password.chars().any(|c| c.is_ascii_uppercase()) {
+            violations.push(PasswordViolation::MissingUppercase);
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push(PasswordViolation::MissingDigit);
+        }
+        if self.require_symbol && !password.chars().any(|c| GOTRUE_SYMBOLS.contains(c)) {
+            violations.push(PasswordViolation::MissingSymbol);
+        }
+        if self.forbid_email
+            && let Some(email) = email
+            && contains_email(password, email)
+        {
+            violations.push(PasswordViolation::ContainsEmail);
+        }
+        if !self.breached.is_empty() && self.breached.contains(&sha1(password)) {
+            violations.push(PasswordViolation::Breached);
+        }
+
+        violations
+    }
+}
+
+fn contains_email(password: &str, email: &Email) -> bool {
+    let password = password.to_lowercase();
+    let address = email.normalized();
+    let local = address.split('@').next().unwrap_or_default();
+    // `jane+ehr@example.com` is still Jane.
+    let local = local.split('+').next().unwrap_or_default();
+
+    password.contains(&address)
+        || (local.chars().count() >= MIN_EMAIL_MATCH_LEN && password.contains(local))
+}
+
+fn sha1(password: &str) -> [u8; 20] {
+    Sha1::digest(password.as_bytes()).into()
+}
+
+fn parse_sha1_hex(hex: &str) -> Option<[u8; 20]> {
+    if hex.len() != 40 || !hex.is_ascii() {
+        return None;
+    }
+    let mut digest = [0u8; 20];
+    for (i, byte) in digest.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(digest)
+}
@@ -0,0 +1,45 @@
+use crate::domain::error::app_error::{AppResult, ValidationError};
+
+/// A phone number normalized to E.164 (`+15551234567`). Numbers without a country code are
+/// taken as North American.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PhoneNumber {
+    inner: String,
+}
+
+impl AsRef<str> for PhoneNumber {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl PhoneNumber {
+    #[tracing::instrument(name = "phone_number_creation", skip_all)]
+    pub fn new(phone: String) -> AppResult<Self> {
+        let trimmed = phone.trim();
+        let (international, rest) = match trimmed.strip_prefix('+') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+
+        // Allow the usual separators, but nothing else.
+        if !rest
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        {
+            return Err(ValidationError::InvalidPhoneNumber.into());
+        }
+        let digits: String = rest.chars().filter(char::is_ascii_digit).collect();
+
+        let normalized = match (international, digits.len()) {
+            (true, 8..=15) if !digits.starts_with('0') => format!("+{digits}"),
+            (false, 10) if !digits.starts_with(['0', '1']) => format!("+1{digits}"),
+            (false, 11) if digits.starts_with('1') && !digits[1..].starts_with(['0', '1']) => {
+                format!("+{digits}")
+            }
+            _ => return Err(ValidationError::InvalidPhoneNumber.into()),
+        };
+
+        Ok(PhoneNumber { inner: normalized })
+    }
+}
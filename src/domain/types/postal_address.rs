@@ -0,0 +1,109 @@
+use crate::domain::error::app_error::{AppResult, ValidationError};
+
+const MAX_LINE_CHARS: usize = 200;
+const MAX_CITY_CHARS: usize = 100;
+
+/// USPS codes for the states, DC and the inhabited territories.
+const US_STATES: &[&str] = &[
+    "AK", "AL", "AR", "AS", "AZ", "CA", "CO", "CT", "DC", "DE", "FL", "GA", "GU", "HI", "IA", "ID",
+    "IL", "IN", "KS", "KY", "LA", "MA", "MD", "ME", "MI", "MN", "MO", "MP", "MS", "MT", "NC", "ND",
+    "NE", "NH", "NJ", "NM", "NV", "NY", "OH", "OK", "OR", "PA", "PR", "RI", "SC", "SD", "TN", "TX",
+    "UT", "VA", "VI", "VT", "WA", "WI", "WV", "WY",
+];
+
+/// A US mailing address. The state is a USPS code and the ZIP is five digits, optionally
+/// followed by `-` and four more.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PostalAddress {
+    line1: String,
+    line2: Option<String>,
+    city: String,
+    state: String,
+    postal_code: String,
+}
+
+fn text_field(value: &str, max_chars: usize, field: &str) -> AppResult<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty()
+        || trimmed.chars().count() > max_chars
+        || trimmed.chars().any(char::is_control)
+    {
+        return Err(ValidationError::InvalidAddress(format!(
+            "{field} must be 1 to {max_chars} characters"
+        ))
+        .into());
+    }
+    Ok(trimmed.to_string())
+}
+
+fn valid_zip(zip: &str) -> bool {
+    let (five, plus_four) = match zip.split_once('-') {
+        Some((five, four)) => (five, Some(four)),
+        None => (zip, None),
+    };
+    five.len() == 5
+        && five.chars().all(|c| c.is_ascii_digit())
+        && plus_four.is_none_or(|four| four.len() == 4 && four.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl PostalAddress {
+    #[tracing::instrument(name = "postal_address_creation", skip_all)]
+    pub fn new(
+        line1: String,
+        line2: Option<String>,
+        city: String,
+        state: String,
+        postal_code: String,
+    ) -> AppResult<Self> {
+        let line1 = text_field(&line1, MAX_LINE_CHARS, "address_line1")?;
+        let line2 = line2
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| text_field(&line, MAX_LINE_CHARS, "address_line2"))
+            .transpose()?;
+        let city = text_field(&city, MAX_CITY_CHARS, "city")?;
+
+        let state = state.trim().to_ascii_uppercase();
+        if !US_STATES.contains(&state.as_str()) {
+            return Err(ValidationError::InvalidAddress(
+                "state must be a two-letter USPS code".to_string(),
+            )
+            .into());
+        }
+
+        let postal_code = postal_code.trim().to_string();
+        if !valid_zip(&postal_code) {
+            return Err(ValidationError::InvalidAddress(
+                "postal_code must be a ZIP or ZIP+4".to_string(),
+            )
+            .into());
+        }
+
+        Ok(PostalAddress {
+            line1,
+            line2,
+            city,
+            state,
+            postal_code,
+        })
+    }
+
+    pub fn line1(&self) -> &str {
+        &self.line1
+    }
+
+    pub fn line2(&self) -> Option<&str> {
+        self.line2.as_deref()
+    }
+
+    pub fn city(&self) -> &str {
+        &self.city
+    }
+
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn postal_code(&self) -> &str {
+        &self.postal_code
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::app_error::{AppResult, ValidationError};
+
+const MAX_PRACTICE_NAME_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Practice {
+    pub id: String,
+    pub name: String,
+    /// Seconds of inactivity before members' sessions are signed out; `None` uses the
+    /// app-wide default.
+    #[serde(default)]
+    pub idle_timeout_secs: Option<i64>,
+    /// Whether members may sign in with a magic link or emailed code.
+    #[serde(default = "default_passwordless_signin")]
+    pub passwordless_signin: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn default_passwordless_signin() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PracticeName {
+    inner: String,
+}
+
+impl AsRef<str> for PracticeName {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl PracticeName {
+    #[tracing::instrument(name = "practice_name_creation", skip_all)]
+    pub fn new(name: String) -> AppResult<Self> {
+        let trimmed = name.trim();
+
+        if trimmed.is_empty()
+            || trimmed.chars().count() > MAX_PRACTICE_NAME_CHARS
+            || trimmed.chars().any(char::is_control)
+        {
+            return Err(ValidationError::InvalidPracticeName.into());
+        }
+
+        Ok(PracticeName {
+            inner: trimmed.to_string(),
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::app_error::{AppResult, ValidationError};
+
+/// Role codes seeded into `public.practice_roles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PracticeRole {
+    Owner,
+    Admin,
+    Biller,
+    Scheduler,
+    ClinicalSupervisor,
+    Clinician,
+}
+
+impl PracticeRole {
+    pub const ALL: [PracticeRole; 6] = [
+        PracticeRole::Owner,
+        PracticeRole::Admin,
+        PracticeRole::Biller,
+        PracticeRole::Scheduler,
+        PracticeRole::ClinicalSupervisor,
+        PracticeRole::Clinician,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PracticeRole::Owner => "owner",
+            PracticeRole::Admin => "admin",
+            PracticeRole::Biller => "biller",
+            PracticeRole::Scheduler => "scheduler",
+            PracticeRole::ClinicalSupervisor => "clinical_supervisor",
+            PracticeRole::Clinician => "clinician",
+        }
+    }
+
+    pub fn parse(code: &str) -> AppResult<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|role| role.as_str() == code)
+            .ok_or_else(|| ValidationError::InvalidRole(code.to_string()).into())
+    }
+}
+
+impl std::fmt::Display for PracticeRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
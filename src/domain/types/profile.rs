@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::types::{email::Email, password::Password};
+
+/// Profile fields kept under `profile` in the Supabase user's `app_metadata`, which only
+/// the service role can write. Validated by the API on the way in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileMetadata {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Professional credentials, e.g. `LCSW` or `PhD`.
+    #[serde(default)]
+    pub credentials: Vec<String>,
+    #[serde(default)]
+    pub npi: Option<String>,
+    /// E.164.
+    #[serde(default)]
+    pub phone: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserProfile {
+    pub user_id: String,
+    pub email: String,
+    /// A requested address that hasn't been confirmed yet; `email` stays in effect until then.
+    pub pending_email: Option<String>,
+    #[serde(flatten)]
+    pub metadata: ProfileMetadata,
+}
+
+/// Changes to apply to the caller's account; `None` leaves that part alone.
+#[derive(Debug, Clone, Default)]
+pub struct UserUpdate {
+    /// Takes effect once the new address is confirmed.
+    pub email: Option<Email>,
+    pub password: Option<Password>,
+    pub metadata: Option<ProfileMetadata>,
+}
@@ -0,0 +1,142 @@
+use poem::http::Method;
+
+/// A token bucket: `limit` requests of burst, refilled evenly over `window_secs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitPolicy {
+    pub limit: u32,
+    pub window_secs: u64,
+}
+
+impl RateLimitPolicy {
+    /// Parses `N/<window>` where the window is `30s`, `5m`, `1h` or plain seconds.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (limit, window) = spec
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| format!("rate limit '{spec}' must look like 60/1m"))?;
+
+        let limit = limit
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|l| *l > 0)
+            .ok_or_else(|| format!("rate limit '{spec}' needs a positive request count"))?;
+
+        let window = window.trim();
+        let (digits, unit) = match window.char_indices().last() {
+            Some((i, c)) if c.is_ascii_alphabetic() => (&window[..i], c),
+            _ => (window, 's'),
+        };
+        let scale = match unit {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            _ => return Err(format!("rate limit '{spec}' has an unknown window unit")),
+        };
+        let window_secs = digits
+            .parse::<u64>()
+            .ok()
+            .filter(|w| *w > 0)
+            .ok_or_else(|| format!("rate limit '{spec}' needs a positive window"))?
+            * scale;
+
+        Ok(Self { limit, window_secs })
+    }
+
+    /// Tokens regained per second.
+    pub fn refill_rate(&self) -> f64 {
+        self.limit as f64 / self.window_secs as f64
+    }
+}
+
+/// A policy for requests whose method and path match. Path segments written `*` match any
+/// single segment, so `/api/practices/*/invitations` covers every practice.
+#[derive(Debug, Clone)]
+pub struct RouteRateLimit {
+    pub method: Option<Method>,
+    pub path: String,
+    pub policy: RateLimitPolicy,
+}
+
+impl RouteRateLimit {
+    /// Parses `[METHOD ]/path=N/<window>`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (route, policy) = spec
+            .trim()
+            .rsplit_once('=')
+            .ok_or_else(|| format!("route rate limit '{spec}' must look like POST /path=5/1m"))?;
+
+        let (method, path) = match route.trim().split_once(' ') {
+            Some((method, path)) => (
+                Some(
+                    Method::from_bytes(method.trim().to_ascii_uppercase().as_bytes())
+                        .map_err(|_| format!("route rate limit '{spec}' has a bad method"))?,
+                ),
+                path.trim(),
+            ),
+            None => (None, route.trim()),
+        };
+        if !path.starts_with('/') {
+            return Err(format!("route rate limit '{spec}' needs an absolute path"));
+        }
+
+        Ok(Self {
+            method,
+            path: path.trim_end_matches('/').to_string(),
+            policy: RateLimitPolicy::parse(policy)?,
+        })
+    }
+
+    pub fn matches(&self, method: &Method, path: &str) -> bool {
+        if self.method.as_ref().is_some_and(|m| m != method) {
+            return false;
+        }
+
+        let mut pattern = self.path.split('/');
+        let mut actual = path.trim_end_matches('/').split('/');
+        loop {
+            match (pattern.next(), actual.next()) {
+                (None, None) => return true,
+                (Some(p), Some(a)) if p == "*" || p == a => continue,
+                _ => return false,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// Applies to callers without a valid access token, keyed by client IP.
+    pub anonymous: RateLimitPolicy,
+    /// Applies to signed-in callers, keyed by user id.
+    pub authenticated: RateLimitPolicy,
+    /// Checked in order; the first match replaces the default for that request.
+    pub routes: Vec<RouteRateLimit>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        let route = |spec: &str| RouteRateLimit::parse(spec).expect("valid default rate limit");
+        Self {
+            enabled: true,
+            anonymous: RateLimitPolicy {
+                limit: 60,
+                window_secs: 60,
+            },
+            authenticated: RateLimitPolicy {
+                limit: 600,
+                window_secs: 60,
+            },
+            routes: vec![
+                route("POST /api/auth/signup=10/1h"),
+                route("POST /api/auth/signin=20/1m"),
+                route("POST /api/auth/forgot_password=5/1h"),
+                route("POST /api/auth/magic_link=5/1h"),
+                route("POST /api/auth/verify_otp=20/1m"),
+                route("POST /api/auth/reset_password=10/1h"),
+                route("POST /api/auth/retrieve_user_id=30/1m"),
+            ],
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use time::{Date, Duration, Month, Weekday};
+
+use crate::{
+    domain::error::app_error::{AppResult, ValidationError},
+    utils::clock::{format_iso_date, parse_iso_date},
+};
+
+/// Most occurrences a single series may expand to.
+pub const MAX_SERIES_OCCURRENCES: usize = 200;
+/// How far past its first date a series may run.
+pub const MAX_SERIES_SPAN_DAYS: i64 = 2 * 366;
+const MAX_INTERVAL: u32 = 99;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceEnd {
+    Count(usize),
+    /// Inclusive.
+    Until(Date),
+}
+
+/// The subset of RFC 5545 `RRULE` a practice needs for recurring sessions: `FREQ` of
+/// `DAILY`, `WEEKLY` or `MONTHLY`, an optional `INTERVAL`, `BYDAY` for weekly rules, and
+/// exactly one of `COUNT` or `UNTIL` so every series ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    /// Monday first, without repeats. Empty means the weekday of the first date.
+    by_day: Vec<Weekday>,
+    end: RecurrenceEnd,
+}
+
+fn invalid(reason: impl Into<String>) -> ValidationError {
+    ValidationError::InvalidRecurrenceRule(reason.into())
+}
+
+fn span_exceeded() -> ValidationError {
+    invalid(format!(
+        "a series may run at most {MAX_SERIES_SPAN_DAYS} days"
+    ))
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Monday => "MO",
+        Weekday::Tuesday => "TU",
+        Weekday::Wednesday => "WE",
+        Weekday::Thursday => "TH",
+        Weekday::Friday => "FR",
+        Weekday::Saturday => "SA",
+        Weekday::Sunday => "SU",
+    }
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ]
+    .into_iter()
+    .find(|day| weekday_code(*day) == code)
+}
+
+/// `UNTIL` as `YYYYMMDD`, optionally followed by a `THHMMSSZ` time that is ignored since
+/// occurrences are whole local dates.
+fn parse_until(value: &str) -> Option<Date> {
+    let date = value.split('T').next()?;
+    if date.len() != 8 || !date.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    parse_iso_date(&format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..]))
+}
+
+impl std::fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FREQ={};INTERVAL={}",
+            self.frequency.as_str(),
+            self.interval
+        )?;
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        match self.end {
+            RecurrenceEnd::Count(count) => write!(f, ";COUNT={count}"),
+            RecurrenceEnd::Until(until) => {
+                write!(f, ";UNTIL={}", format_iso_date(until).replace('-', ""))
+            }
+        }
+    }
+}
+
+impl RecurrenceRule {
+    #[tracing::instrument(name = "recurrence_rule_creation", skip_all)]
+    pub fn new(rule: String) -> AppResult<Self> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = None;
+        let mut by_day = None;
+        let mut count = None;
+        let mut until = None;
+
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("'{part}' is not NAME=VALUE")))?;
+            let duplicate = match name.to_ascii_uppercase().as_str() {
+                "FREQ" => frequency
+                    .replace(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(invalid("FREQ must be DAILY, WEEKLY or MONTHLY").into()),
+                    })
+                    .is_some(),
+                "INTERVAL" => interval
+                    .replace(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|i| (1..=MAX_INTERVAL).contains(i))
+                            .ok_or_else(|| {
+                                invalid(format!("INTERVAL must be between 1 and {MAX_INTERVAL}"))
+                            })?,
+                    )
+                    .is_some(),
+                "BYDAY" => {
+                    let mut days = value
+                        .split(',')
+                        .map(|code| parse_weekday(&code.trim().to_ascii_uppercase()))
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| invalid("BYDAY must list days such as MO,WE,FR"))?;
+                    days.sort_by_key(|d| d.number_days_from_monday());
+                    days.dedup();
+                    by_day.replace(days).is_some()
+                }
+                "COUNT" => count
+                    .replace(
+                        value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|c| (1..=MAX_SERIES_OCCURRENCES).contains(c))
+                            .ok_or_else(|| {
+                                invalid(format!(
+                                    "COUNT must be between 1 and {MAX_SERIES_OCCURRENCES}"
+                                ))
+                            })?,
+                    )
+                    .is_some(),
+                "UNTIL" => until
+                    .replace(parse_until(value).ok_or_else(|| invalid("UNTIL must be YYYYMMDD"))?)
+                    .is_some(),
+                _ => return Err(invalid(format!("{name} is not supported")).into()),
+            };
+            if duplicate {
+                return Err(invalid(format!("{name} is given more than once")).into());
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| invalid("FREQ is required"))?;
+        let by_day = by_day.unwrap_or_default();
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err(invalid("BYDAY is only supported with FREQ=WEEKLY").into());
+        }
+        let end = match (count, until) {
+            (Some(count), None) => RecurrenceEnd::Count(count),
+            (None, Some(until)) => RecurrenceEnd::Until(until),
+            _ => return Err(invalid("exactly one of COUNT or UNTIL is required").into()),
+        };
+
+        Ok(RecurrenceRule {
+            frequency,
+            interval: interval.unwrap_or(1),
+            by_day,
+            end,
+        })
+    }
+
+    /// The local dates the rule yields from `start`, minus `exdates`. As in RFC 5545,
+    /// `COUNT` counts occurrences before exclusions are removed. Dates before `start` that
+    /// `BYDAY` would pick in the first week are skipped.
+    pub fn occurrences(&self, start: Date, exdates: &[Date]) -> AppResult<Vec<Date>> {
+        let horizon = start + Duration::days(MAX_SERIES_SPAN_DAYS);
+        if let RecurrenceEnd::Until(until) = self.end {
+            if until < start {
+                return Err(invalid("UNTIL is before the first date").into());
+            }
+            if until > horizon {
+                return Err(span_exceeded().into());
+            }
+        }
+
+        let mut dates = Vec::new();
+        let mut period = 0;
+        loop {
+            let (period_start, candidates) = self.period_dates(start, period);
+            period += 1;
+            if period_start > horizon {
+                // UNTIL is within the horizon, so only COUNT can still be short here.
+                return match self.end {
+                    RecurrenceEnd::Until(_) => Self::finish(dates, exdates),
+                    RecurrenceEnd::Count(_) => Err(span_exceeded().into()),
+                };
+            }
+            for date in candidates.into_iter().filter(|d| *d >= start) {
+                if matches!(self.end, RecurrenceEnd::Until(until) if date > until) {
+                    return Self::finish(dates, exdates);
+                }
+                if date > horizon {
+                    return Err(span_exceeded().into());
+                }
+                if dates.len() == MAX_SERIES_OCCURRENCES {
+                    return Err(invalid(format!(
+                        "a series may have at most {MAX_SERIES_OCCURRENCES} occurrences"
+                    ))
+                    .into());
+                }
+                dates.push(date);
+                if self.end == RecurrenceEnd::Count(dates.len()) {
+                    return Self::finish(dates, exdates);
+                }
+            }
+        }
+    }
+
+    fn finish(mut dates: Vec<Date>, exdates: &[Date]) -> AppResult<Vec<Date>> {
+        dates.retain(|d| !exdates.contains(d));
+        if dates.is_empty() {
+            return Err(invalid("the rule yields no dates").into());
+        }
+        Ok(dates)
+    }
+
+    /// The first day of the `period`th day, week or month, and the candidate dates in it in
+    /// order. A month that lacks the start's day of month has none, as in RFC 5545.
+    fn period_dates(&self, start: Date, period: u32) -> (Date, Vec<Date>) {
+        let step = i64::from(period) * i64::from(self.interval);
+        match self.frequency {
+            Frequency::Daily => {
+                let date = start + Duration::days(step);
+                (date, vec![date])
+            }
+            Frequency::Weekly => {
+                let week_start = start
+                    - Duration::days(i64::from(start.weekday().number_days_from_monday()))
+                    + Duration::weeks(step);
+                let dates = if self.by_day.is_empty() {
+                    vec![start + Duration::weeks(step)]
+                } else {
+                    self.by_day
+                        .iter()
+                        .map(|d| {
+                            week_start + Duration::days(i64::from(d.number_days_from_monday()))
+                        })
+                        .collect()
+                };
+                (week_start, dates)
+            }
+            Frequency::Monthly => {
+                let months = i64::from(u8::from(start.month()) - 1) + step;
+                let year = start.year() + (months / 12) as i32;
+                let month = Month::try_from((months % 12) as u8 + 1).unwrap_or(Month::January);
+                let first = Date::from_calendar_date(year, month, 1).unwrap_or(Date::MAX);
+                let dates = Date::from_calendar_date(year, month, start.day())
+                    .into_iter()
+                    .collect();
+                (first, dates)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> Date {
+        parse_iso_date(value).unwrap()
+    }
+
+    fn dates(values: &[&str]) -> Vec<Date> {
+        values.iter().map(|v| date(v)).collect()
+    }
+
+    fn expand(rule: &str, start: &str, exdates: &[&str]) -> AppResult<Vec<Date>> {
+        RecurrenceRule::new(rule.to_string())?.occurrences(date(start), &dates(exdates))
+    }
+
+    #[test]
+    fn daily_counts_consecutive_days_across_months() {
+        assert_eq!(
+            expand("FREQ=DAILY;COUNT=3", "2026-03-30", &[]).unwrap(),
+            dates(&["2026-03-30", "2026-03-31", "2026-04-01"])
+        );
+    }
+
+    #[test]
+    fn interval_skips_periods() {
+        assert_eq!(
+            expand("FREQ=DAILY;INTERVAL=2;UNTIL=20260405", "2026-03-30", &[]).unwrap(),
+            dates(&["2026-03-30", "2026-04-01", "2026-04-03", "2026-04-05"])
+        );
+        assert_eq!(
+            expand("FREQ=MONTHLY;INTERVAL=3;COUNT=3", "2026-11-15", &[]).unwrap(),
+            dates(&["2026-11-15", "2027-02-15", "2027-05-15"])
+        );
+    }
+
+    #[test]
+    fn monthly_skips_months_without_the_day() {
+        assert_eq!(
+            expand("FREQ=MONTHLY;COUNT=4", "2026-01-31", &[]).unwrap(),
+            dates(&["2026-01-31", "2026-03-31", "2026-05-31", "2026-07-31"])
+        );
+        assert_eq!(
+            expand("FREQ=MONTHLY;UNTIL=20260430", "2026-01-30", &[]).unwrap(),
+            dates(&["2026-01-30", "2026-03-30", "2026-04-30"])
+        );
+        assert_eq!(
+            expand("FREQ=MONTHLY;COUNT=2", "2027-01-29", &[]).unwrap(),
+            dates(&["2027-01-29", "2027-03-29"])
+        );
+        assert_eq!(
+            expand("FREQ=MONTHLY;COUNT=2", "2028-01-29", &[]).unwrap(),
+            dates(&["2028-01-29", "2028-02-29"])
+        );
+    }
+
+    #[test]
+    fn weekly_byday_picks_several_days_and_skips_those_before_start() {
+        // 2026-10-14 is a Wednesday, so that week's Monday is skipped.
+        assert_eq!(
+            expand(
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=FR,MO,WE;COUNT=5",
+                "2026-10-14",
+                &[]
+            )
+            .unwrap(),
+            dates(&[
+                "2026-10-14",
+                "2026-10-16",
+                "2026-10-26",
+                "2026-10-28",
+                "2026-10-30"
+            ])
+        );
+        assert_eq!(
+            expand("FREQ=WEEKLY;COUNT=2", "2026-10-14", &[]).unwrap(),
+            dates(&["2026-10-14", "2026-10-21"])
+        );
+    }
+
+    #[test]
+    fn until_is_inclusive_and_excludes_count() {
+        assert_eq!(
+            expand("FREQ=WEEKLY;UNTIL=20261028T235959Z", "2026-10-14", &[]).unwrap(),
+            dates(&["2026-10-14", "2026-10-21", "2026-10-28"])
+        );
+        assert!(RecurrenceRule::new("FREQ=DAILY;COUNT=2;UNTIL=20261020".to_string()).is_err());
+        assert!(RecurrenceRule::new("FREQ=DAILY".to_string()).is_err());
+        assert!(expand("FREQ=DAILY;UNTIL=20261001", "2026-10-14", &[]).is_err());
+        assert!(expand("FREQ=DAILY;UNTIL=20290101", "2026-10-14", &[]).is_err());
+        assert!(expand("FREQ=MONTHLY;COUNT=30", "2026-10-14", &[]).is_err());
+    }
+
+    #[test]
+    fn exdates_are_removed_after_count() {
+        assert_eq!(
+            expand(
+                "FREQ=DAILY;COUNT=3",
+                "2026-03-30",
+                &["2026-03-31", "2026-04-02"]
+            )
+            .unwrap(),
+            dates(&["2026-03-30", "2026-04-01"])
+        );
+        assert!(expand("FREQ=DAILY;COUNT=1", "2026-03-30", &["2026-03-30"]).is_err());
+    }
+
+    #[test]
+    fn display_normalizes_the_rule() {
+        let rule = RecurrenceRule::new("RRULE:freq=weekly;byday=we,mo,we;count=4".to_string());
+        assert_eq!(
+            rule.unwrap().to_string(),
+            "FREQ=WEEKLY;INTERVAL=1;BYDAY=MO,WE;COUNT=4"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+/// Tokens issued by a successful signin, MFA verification or refresh.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthSession {
+    pub user_id: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Lifetime of `access_token` in seconds.
+    pub expires_in: i64,
+    /// Unix timestamp at which `access_token` expires.
+    pub expires_at: i64,
+}
+
+/// Claims the API relies on from a verified Supabase access token.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenClaims {
+    pub sub: String,
+    /// `aal1` after a password signin, `aal2` once an MFA factor has been verified.
+    pub aal: Option<String>,
+    pub email: Option<String>,
+    /// Supabase's id for the signin session, shared by every token refreshed from it.
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub app_metadata: AppMetadata,
+}
+
+/// The part of GoTrue's `app_metadata` the API reads. Unlike `user_metadata` only the
+/// service role can write it, so it is safe to authorize on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppMetadata {
+    /// Operators of the deployment, who may look up and delete any account.
+    #[serde(default)]
+    pub platform_admin: bool,
+}
+
+/// Server-side record of a session's last use, for automatic idle logoff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionActivity {
+    pub session_id: String,
+    pub user_id: String,
+    /// Unix seconds.
+    pub created_at: i64,
+    /// Unix seconds.
+    pub last_seen_at: i64,
+    /// Fixed when the record is created, from the caller's practices or the app default.
+    pub idle_timeout_secs: i64,
+    /// Set once the session idles out, so a refreshed token for it stays rejected.
+    pub expired: bool,
+    /// Client IP and `User-Agent` of the most recent recorded request.
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Set when the user or a practice owner or admin ends the session remotely.
+    #[serde(default)]
+    pub revoked: bool,
+    /// The user has verified a second factor, so the session is refused until it reaches
+    /// AAL2.
+    #[serde(default)]
+    pub mfa_required: bool,
+}
+
+impl SessionActivity {
+    pub fn seconds_remaining(&self, now: i64) -> i64 {
+        (self.last_seen_at + self.idle_timeout_secs - now).max(0)
+    }
+
+    pub fn is_idle(&self, now: i64) -> bool {
+        self.expired || self.seconds_remaining(now) == 0
+    }
+
+    /// Still usable: neither revoked nor idled out.
+    pub fn is_active(&self, now: i64) -> bool {
+        !self.revoked && !self.is_idle(now)
+    }
+}
@@ -0,0 +1,169 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use rand::RngCore;
+use secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::domain::{
+    error::app_error::{AppResult, SsoError, ValidationError},
+    types::{email::Email, practice_role::PracticeRole},
+};
+
+/// An OpenID Connect provider (Google Workspace, Microsoft Entra…) a practice's staff sign
+/// in with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityProvider {
+    pub id: String,
+    pub practice_id: String,
+    /// Shown on the login button, e.g. "Google Workspace".
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    /// Only loaded for the login flow; never returned by the API.
+    #[serde(default, skip_serializing)]
+    pub client_secret: Option<SecretString>,
+    /// Role given to users the first time they sign in through this provider.
+    pub default_role: PracticeRole,
+    /// Email domains accepted from this provider; other addresses are refused.
+    pub email_domains: Vec<String>,
+    pub created_at: String,
+    /// When a platform admin approved the issuer and domains. Until then the provider
+    /// signs nobody in.
+    #[serde(default)]
+    pub approved_at: Option<String>,
+}
+
+impl IdentityProvider {
+    pub fn ensure_approved(&self) -> AppResult<()> {
+        if self.approved_at.is_some() {
+            Ok(())
+        } else {
+            Err(SsoError::ProviderNotApproved.into())
+        }
+    }
+
+    pub fn allows_email(&self, email: &Email) -> bool {
+        let email = email.normalized();
+        email
+            .rsplit_once('@')
+            .is_some_and(|(_, domain)| self.email_domains.iter().any(|d| d == domain))
+    }
+}
+
+/// A validated provider configuration, ready to be stored.
+#[derive(Debug, Clone)]
+pub struct NewIdentityProvider {
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: Option<SecretString>,
+    pub default_role: PracticeRole,
+    pub email_domains: Vec<String>,
+}
+
+impl NewIdentityProvider {
+    pub fn new(
+        name: String,
+        issuer: String,
+        client_id: String,
+        client_secret: Option<String>,
+        default_role: PracticeRole,
+        email_domains: Vec<String>,
+    ) -> AppResult<Self> {
+        let invalid = |msg: &str| ValidationError::InvalidInput(msg.to_string());
+
+        let name = name.trim().to_string();
+        if name.is_empty() || name.chars().count() > 120 {
+            return Err(invalid("name must be between 1 and 120 characters").into());
+        }
+
+        // Discovery documents are fetched from the issuer, so it has to be HTTPS outside
+        // local development.
+        let issuer = issuer.trim().trim_end_matches('/').to_string();
+        let url = reqwest::Url::parse(&issuer).map_err(|_| invalid("issuer must be a URL"))?;
+        let local = matches!(url.host_str(), Some("localhost" | "127.0.0.1"));
+        if url.scheme() != "https" && !(url.scheme() == "http" && local) {
+            return Err(invalid("issuer must be an https URL").into());
+        }
+
+        let client_id = client_id.trim().to_string();
+        if client_id.is_empty() {
+            return Err(invalid("client_id is required").into());
+        }
+
+        if default_role == PracticeRole::Owner {
+            return Err(invalid("default_role cannot be owner").into());
+        }
+
+        let email_domains: Vec<String> = email_domains
+            .iter()
+            .map(|d| d.trim().trim_start_matches('@').to_ascii_lowercase())
+            .filter(|d| !d.is_empty())
+            .collect();
+        if email_domains.is_empty() || email_domains.iter().any(|d| !d.contains('.')) {
+            return Err(invalid("email_domains must list at least one domain").into());
+        }
+
+        Ok(Self {
+            name,
+            issuer,
+            client_id,
+            client_secret: client_secret
+                .filter(|s| !s.is_empty())
+                .map(SecretString::from),
+            default_role,
+            email_domains,
+        })
+    }
+}
+
+/// Who the provider says signed in, taken from a validated ID token.
+#[derive(Debug, Clone)]
+pub struct FederatedIdentity {
+    pub issuer: String,
+    /// The provider's stable user id (`sub`).
+    pub subject: String,
+    pub email: Email,
+    pub name: Option<String>,
+}
+
+/// An authorization-code login in flight, kept by the browser between the start and
+/// callback requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsoLogin {
+    pub provider_id: String,
+    pub state: String,
+    pub nonce: String,
+    /// PKCE secret; only its SHA-256 goes to the provider until the code is exchanged.
+    pub code_verifier: String,
+    /// Path on this site to land on afterwards.
+    pub redirect_to: String,
+    /// Started by a signed-in user to link the identity to their account, rather than to
+    /// sign in with it.
+    #[serde(default)]
+    pub link: bool,
+}
+
+impl SsoLogin {
+    pub fn new(provider_id: String, redirect_to: String) -> Self {
+        Self {
+            provider_id,
+            state: random_string(),
+            nonce: random_string(),
+            code_verifier: random_string(),
+            redirect_to,
+            link: false,
+        }
+    }
+
+    /// S256 code challenge for [`Self::code_verifier`].
+    pub fn code_challenge(&self) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(self.code_verifier.as_bytes()))
+    }
+}
+
+fn random_string() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
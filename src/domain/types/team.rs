@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::error::app_error::{AppResult, ValidationError};
+
+const MAX_TEAM_NAME_CHARS: usize = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamMember {
+    pub user_id: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Team {
+    pub id: String,
+    pub practice_id: String,
+    pub name: String,
+    pub created_at: String,
+    #[serde(default, rename(deserialize = "team_members"))]
+    pub members: Vec<TeamMember>,
+}
+
+impl Team {
+    pub fn has_member(&self, user_id: &str) -> bool {
+        self.members.iter().any(|m| m.user_id == user_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TeamName {
+    inner: String,
+}
+
+impl AsRef<str> for TeamName {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl TeamName {
+    #[tracing::instrument(name = "team_name_creation", skip_all)]
+    pub fn new(name: String) -> AppResult<Self> {
+        let trimmed = name.trim();
+
+        if trimmed.is_empty()
+            || trimmed.chars().count() > MAX_TEAM_NAME_CHARS
+            || trimmed.chars().any(char::is_control)
+        {
+            return Err(ValidationError::InvalidTeamName.into());
+        }
+
+        Ok(TeamName {
+            inner: trimmed.to_string(),
+        })
+    }
+}
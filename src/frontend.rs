@@ -13,6 +13,10 @@ pub fn build_frontend_routes() -> Route {
         )
         .at("/signup", StaticFileEndpoint::new("public/signup.html"))
         .at("/signin", StaticFileEndpoint::new("public/signin.html"))
+        .at(
+            "/accept-invite",
+            StaticFileEndpoint::new("public/accept-invite.html"),
+        )
         .at(
             "/dashboard",
             StaticFileEndpoint::new("public/dashboard.html"),
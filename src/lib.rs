@@ -1,20 +1,66 @@
 use std::sync::Arc;
 
 use poem::{
-    EndpointExt, Route, Server,
+    EndpointExt, IntoEndpoint, Route, Server,
     http::Method,
     listener::TcpListener,
     middleware::{Cors, Tracing},
 };
 use poem_openapi::OpenApiService;
+use secrecy::SecretString;
 use tokio::sync::RwLock;
 
 use crate::{
-    api::auth::AppApi,
-    domain::error::app_error::{AppError, AppResult},
-    services::supabase_auth_service::SupabaseAuthService,
-    state::AppState,
-    utils::config::AppConfig,
+    api::{
+        appointments::AppointmentApi, audit::AuditApi, auth::AppApi, clients::ClientApi,
+        hooks::HookApi, invitations::InvitationApi, members::MemberApi, practices::PracticeApi,
+        sso::SsoApi, teams::TeamApi,
+    },
+    domain::{
+        error::app_error::{AppError, AppResult},
+        interfaces::{
+            audit_sink::AuditSink, auth_service::AuthService, email_sender::EmailSender,
+            identity_provider_service::IdentityProviderService,
+            login_attempt_tracker::LoginAttemptTracker, membership_service::MembershipService,
+            session_activity_tracker::SessionActivityTracker, sms_gateway::SmsGateway,
+        },
+        types::{password_policy::PasswordPolicy, rate_limit::RateLimitConfig},
+    },
+    routes::{auth::authorization::with_role_cache, rate_limit::RateLimit},
+    services::{
+        file_sms_gateway::FileSmsGateway,
+        http_oidc_client::HttpOidcClient,
+        in_memory_audit_sink::InMemoryAuditSink,
+        in_memory_auth_service::InMemoryAuthService,
+        in_memory_email_sender::InMemoryEmailSender,
+        in_memory_identity_provider_service::InMemoryIdentityProviderService,
+        in_memory_login_attempt_tracker::InMemoryLoginAttemptTracker,
+        in_memory_membership_service::InMemoryMembershipService,
+        in_memory_session_activity_tracker::InMemorySessionActivityTracker,
+        jwks_token_verifier::{JwksTokenVerifier, JwtSettings},
+        memory_backend_unsupported::MemoryBackendUnsupported,
+        smtp_email_sender::SmtpEmailSender,
+        supabase_appointment_service::SupabaseAppointmentService,
+        supabase_audit_log_service::SupabaseAuditLogService,
+        supabase_audit_sink::SupabaseAuditSink,
+        supabase_auth_service::SupabaseAuthService,
+        supabase_client_service::SupabaseClientService,
+        supabase_identity_provider_service::SupabaseIdentityProviderService,
+        supabase_invitation_service::SupabaseInvitationService,
+        supabase_login_attempt_tracker::SupabaseLoginAttemptTracker,
+        supabase_membership_service::SupabaseMembershipService,
+        supabase_practice_service::SupabasePracticeService,
+        supabase_session_activity_tracker::SupabaseSessionActivityTracker,
+        supabase_team_service::SupabaseTeamService,
+        twilio_sms_gateway::TwilioSmsGateway,
+    },
+    state::{
+        AppState, AppointmentServiceType, AuditLogServiceType, AuditSinkType, AuthServiceType,
+        ClientServiceType, EmailSenderType, IdentityProviderServiceType, InvitationServiceType,
+        LoginAttemptTrackerType, MembershipServiceType, PracticeServiceType,
+        SessionActivityTrackerType, SmsGatewayType, TeamServiceType,
+    },
+    utils::config::{AppConfig, Backend, LoginAttemptStore, SessionActivityStore, SmsDelivery},
 };
 
 pub mod api;
@@ -30,21 +76,344 @@ pub struct App {
     pub state: AppState,
 }
 
+/// Assembles an [`App`], using the services `config.backend` calls for unless overridden.
+pub struct AppBuilder {
+    config: AppConfig,
+    audit_sink: Option<AuditSinkType>,
+    auth_service: Option<AuthServiceType>,
+    email_sender: Option<EmailSenderType>,
+    identity_provider_service: Option<IdentityProviderServiceType>,
+    login_attempt_tracker: Option<LoginAttemptTrackerType>,
+    membership_service: Option<MembershipServiceType>,
+    session_activity_tracker: Option<SessionActivityTrackerType>,
+    sms_gateway: Option<SmsGatewayType>,
+}
+
+impl AppBuilder {
+    pub fn audit_sink<S: AuditSink + Send + Sync + 'static>(
+        mut self,
+        sink: Arc<RwLock<S>>,
+    ) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    pub fn auth_service<S: AuthService + Send + Sync + 'static>(
+        mut self,
+        service: Arc<RwLock<S>>,
+    ) -> Self {
+        self.auth_service = Some(service);
+        self
+    }
+
+    pub fn email_sender<S: EmailSender + Send + Sync + 'static>(
+        mut self,
+        sender: Arc<RwLock<S>>,
+    ) -> Self {
+        self.email_sender = Some(sender);
+        self
+    }
+
+    pub fn identity_provider_service<S: IdentityProviderService + Send + Sync + 'static>(
+        mut self,
+        service: Arc<RwLock<S>>,
+    ) -> Self {
+        self.identity_provider_service = Some(service);
+        self
+    }
+
+    pub fn login_attempt_tracker<T: LoginAttemptTracker + Send + Sync + 'static>(
+        mut self,
+        tracker: Arc<RwLock<T>>,
+    ) -> Self {
+        self.login_attempt_tracker = Some(tracker);
+        self
+    }
+
+    pub fn membership_service<S: MembershipService + Send + Sync + 'static>(
+        mut self,
+        service: Arc<RwLock<S>>,
+    ) -> Self {
+        self.membership_service = Some(service);
+        self
+    }
+
+    pub fn session_activity_tracker<T: SessionActivityTracker + Send + Sync + 'static>(
+        mut self,
+        tracker: Arc<RwLock<T>>,
+    ) -> Self {
+        self.session_activity_tracker = Some(tracker);
+        self
+    }
+
+    pub fn sms_gateway<G: SmsGateway + Send + Sync + 'static>(
+        mut self,
+        gateway: Arc<RwLock<G>>,
+    ) -> Self {
+        self.sms_gateway = Some(gateway);
+        self
+    }
+
+    pub fn send_sms_hook_secret(mut self, secret: SecretString) -> Self {
+        self.config.send_sms_hook_secret = secret;
+        self
+    }
+
+    pub fn session_idle_timeout_secs(mut self, secs: i64) -> Self {
+        self.config.session_idle_timeout_secs = secs;
+        self
+    }
+
+    pub fn password_policy(mut self, policy: PasswordPolicy) -> Self {
+        self.config.password_policy = policy;
+        self
+    }
+
+    pub fn rate_limits(mut self, rate_limits: RateLimitConfig) -> Self {
+        self.config.rate_limits = rate_limits;
+        self
+    }
+
+    pub fn build(self) -> App {
+        let config = self.config;
+        let memory = config.backend == Backend::Memory;
+
+        // Postgres-only features; the memory backend answers them with a 503 up front.
+        let appointment_service: AppointmentServiceType = if memory {
+            Arc::new(RwLock::new(MemoryBackendUnsupported))
+        } else {
+            Arc::new(RwLock::new(SupabaseAppointmentService::new(
+                config.supabase_url.clone(),
+                config.supabase_anon_key.clone(),
+            )))
+        };
+        let audit_log_service: AuditLogServiceType = if memory {
+            Arc::new(RwLock::new(MemoryBackendUnsupported))
+        } else {
+            Arc::new(RwLock::new(SupabaseAuditLogService::new(
+                config.supabase_url.clone(),
+                config.supabase_anon_key.clone(),
+            )))
+        };
+        let audit_sink = self.audit_sink.unwrap_or_else(|| -> AuditSinkType {
+            if memory {
+                Arc::new(RwLock::new(InMemoryAuditSink::default()))
+            } else {
+                Arc::new(RwLock::new(SupabaseAuditSink::new(
+                    config.supabase_url.clone(),
+                    config.supabase_service_role_key.clone(),
+                )))
+            }
+        });
+        let sms_gateway = self.sms_gateway.unwrap_or_else(|| -> SmsGatewayType {
+            match config.sms_delivery {
+                SmsDelivery::File => Arc::new(RwLock::new(FileSmsGateway::new(
+                    config.sms_outbox_path.clone(),
+                ))),
+                SmsDelivery::Twilio => Arc::new(RwLock::new(TwilioSmsGateway::new(
+                    config.twilio_api_url.clone(),
+                    config.twilio_account_sid.clone(),
+                    config.twilio_auth_token.clone(),
+                    config.twilio_from.clone(),
+                ))),
+            }
+        });
+        let auth_service = self.auth_service.unwrap_or_else(|| -> AuthServiceType {
+            if memory {
+                Arc::new(RwLock::new(
+                    InMemoryAuthService::new(
+                        config.jwt_issuer.clone(),
+                        config.jwt_audience.clone(),
+                        config.supabase_jwt_secret.clone(),
+                    )
+                    .with_auto_confirm(true)
+                    .with_sms_gateway(sms_gateway.clone()),
+                ))
+            } else {
+                Arc::new(RwLock::new(SupabaseAuthService::new(
+                    config.supabase_url.clone(),
+                    config.supabase_anon_key.clone(),
+                    config.supabase_service_role_key.clone(),
+                )))
+            }
+        });
+        let email_sender = self.email_sender.unwrap_or_else(|| -> EmailSenderType {
+            if memory {
+                Arc::new(RwLock::new(InMemoryEmailSender::default()))
+            } else {
+                Arc::new(RwLock::new(SmtpEmailSender::new(
+                    &config.smtp_host,
+                    config.smtp_port,
+                    config.email_from.clone(),
+                )))
+            }
+        });
+        // Shared so SSO first logins land in the same memberships the API reads.
+        let memory_memberships = Arc::new(RwLock::new(InMemoryMembershipService::default()));
+        let identity_provider_service =
+            self.identity_provider_service
+                .unwrap_or_else(|| -> IdentityProviderServiceType {
+                    if memory {
+                        Arc::new(RwLock::new(InMemoryIdentityProviderService::new(
+                            memory_memberships.clone(),
+                        )))
+                    } else {
+                        Arc::new(RwLock::new(SupabaseIdentityProviderService::new(
+                            config.supabase_url.clone(),
+                            config.supabase_anon_key.clone(),
+                            config.supabase_service_role_key.clone(),
+                        )))
+                    }
+                });
+        let invitation_service: InvitationServiceType = if memory {
+            Arc::new(RwLock::new(MemoryBackendUnsupported))
+        } else {
+            Arc::new(RwLock::new(SupabaseInvitationService::new(
+                config.supabase_url.clone(),
+                config.supabase_anon_key.clone(),
+            )))
+        };
+        let login_attempt_tracker =
+            self.login_attempt_tracker
+                .unwrap_or_else(|| -> LoginAttemptTrackerType {
+                    match config.login_attempt_store {
+                        LoginAttemptStore::Memory => {
+                            Arc::new(RwLock::new(InMemoryLoginAttemptTracker::default()))
+                        }
+                        LoginAttemptStore::Postgres => {
+                            Arc::new(RwLock::new(SupabaseLoginAttemptTracker::new(
+                                config.supabase_url.clone(),
+                                config.supabase_service_role_key.clone(),
+                            )))
+                        }
+                    }
+                });
+        let membership_service =
+            self.membership_service
+                .unwrap_or_else(|| -> MembershipServiceType {
+                    if memory {
+                        memory_memberships.clone()
+                    } else {
+                        Arc::new(RwLock::new(SupabaseMembershipService::new(
+                            config.supabase_url.clone(),
+                            config.supabase_anon_key.clone(),
+                        )))
+                    }
+                });
+        let oidc_client = Arc::new(RwLock::new(HttpOidcClient::default()));
+        let practice_service: PracticeServiceType = if memory {
+            Arc::new(RwLock::new(MemoryBackendUnsupported))
+        } else {
+            Arc::new(RwLock::new(SupabasePracticeService::new(
+                config.supabase_url.clone(),
+                config.supabase_anon_key.clone(),
+            )))
+        };
+        let session_activity_tracker =
+            self.session_activity_tracker
+                .unwrap_or_else(|| -> SessionActivityTrackerType {
+                    match config.session_activity_store {
+                        SessionActivityStore::Memory => {
+                            Arc::new(RwLock::new(InMemorySessionActivityTracker::default()))
+                        }
+                        SessionActivityStore::Postgres => {
+                            Arc::new(RwLock::new(SupabaseSessionActivityTracker::new(
+                                config.supabase_url.clone(),
+                                config.supabase_service_role_key.clone(),
+                            )))
+                        }
+                    }
+                });
+        let client_service: ClientServiceType = if memory {
+            Arc::new(RwLock::new(MemoryBackendUnsupported))
+        } else {
+            Arc::new(RwLock::new(SupabaseClientService::new(
+                config.supabase_url.clone(),
+                config.supabase_anon_key.clone(),
+            )))
+        };
+        let team_service: TeamServiceType = if memory {
+            Arc::new(RwLock::new(MemoryBackendUnsupported))
+        } else {
+            Arc::new(RwLock::new(SupabaseTeamService::new(
+                config.supabase_url.clone(),
+                config.supabase_anon_key.clone(),
+            )))
+        };
+        let token_verifier = Arc::new(RwLock::new(JwksTokenVerifier::new(JwtSettings {
+            // Memory-backend tokens are all HS256, so there is no key set to fetch.
+            jwks_url: (!memory).then(|| config.jwks_url.clone()),
+            issuer: config.jwt_issuer.clone(),
+            audience: config.jwt_audience.clone(),
+            hs256_secret: config
+                .jwt_hs256_fallback
+                .then(|| config.supabase_jwt_secret.clone()),
+        })));
+        let state = AppState {
+            appointment_service,
+            audit_log_service,
+            audit_sink,
+            auth_service,
+            client_service,
+            email_sender,
+            identity_provider_service,
+            invitation_service,
+            login_attempt_tracker,
+            membership_service,
+            oidc_client,
+            practice_service,
+            session_activity_tracker,
+            sms_gateway,
+            team_service,
+            token_verifier,
+            public_url: config.public_url.clone(),
+            session_idle_timeout_secs: config.session_idle_timeout_secs,
+            password_policy: config.password_policy.clone(),
+            send_sms_hook_secret: config.send_sms_hook_secret.clone(),
+            sso_cookie_secret: config.sso_cookie_secret.clone(),
+        };
+        App { config, state }
+    }
+}
+
 impl App {
     pub fn new(config: AppConfig) -> Self {
-        let auth_service = Arc::new(RwLock::new(SupabaseAuthService::new(
-            config.supabase_url.clone(),
-            config.supabase_anon_key.clone(),
-            config.supabase_service_role_key.clone(),
-        )));
-        let state = AppState::new(auth_service, config.supabase_jwt_secret.clone());
-        App { config, state }
+        Self::builder(config).build()
+    }
+
+    pub fn builder(config: AppConfig) -> AppBuilder {
+        AppBuilder {
+            config,
+            audit_sink: None,
+            auth_service: None,
+            email_sender: None,
+            identity_provider_service: None,
+            login_attempt_tracker: None,
+            membership_service: None,
+            session_activity_tracker: None,
+            sms_gateway: None,
+        }
     }
 
     pub async fn run(&self) -> AppResult<()> {
         // OpenAPI service - use HTTP since Caddy handles TLS
-        let api_service = OpenApiService::new(AppApi, "BreezeEHR API", "1.0")
-            .server(format!("http://{}", self.config.app_address));
+        let api_service = OpenApiService::new(
+            (
+                AppApi,
+                AppointmentApi,
+                AuditApi,
+                ClientApi,
+                HookApi,
+                InvitationApi,
+                MemberApi,
+                PracticeApi,
+                SsoApi,
+                TeamApi,
+            ),
+            "BreezeEHR API",
+            "1.0",
+        )
+        .server(format!("http://{}", self.config.app_address));
         let ui = api_service.swagger_ui();
 
         // CORS - allow Caddy's domains
@@ -57,17 +426,30 @@ impl App {
                 Method::GET,
                 Method::POST,
                 Method::PUT,
+                Method::PATCH,
                 Method::DELETE,
                 Method::OPTIONS,
             ])
-            .allow_headers(vec!["Authorization", "Content-Type"])
-            .expose_headers(vec!["Content-Length"])
+            .allow_headers(vec!["Authorization", "Content-Type", "X-CSRF-Token"])
+            .expose_headers(vec![
+                "Content-Length",
+                "RateLimit-Limit",
+                "RateLimit-Remaining",
+                "RateLimit-Reset",
+                "RateLimit-Policy",
+                "Retry-After",
+            ])
             .max_age(3600);
 
+        let api = api_service
+            .into_endpoint()
+            .with(RateLimit::new(self.config.rate_limits.clone()));
+
         let app = Route::new()
-            .nest("/api", api_service)
+            .nest("/api", api)
             .nest("/docs", ui)
             .nest("/", frontend::build_frontend_routes())
+            .around(with_role_cache)
             .with(Tracing)
             .with(cors)
             .data(self.state.clone());
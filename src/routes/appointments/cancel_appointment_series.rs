@@ -0,0 +1,54 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+use time::OffsetDateTime;
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::appointment::{
+            AppointmentSeries, check_cancellation_reason, format_timestamp, parse_timestamp,
+        },
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct CancelAppointmentSeriesRequest {
+    /// RFC 3339; occurrences starting at or after it are cancelled. Defaults to now.
+    pub from: Option<String>,
+    pub reason: Option<String>,
+}
+
+pub async fn cancel_appointment_series_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    series_id: &str,
+    payload: Json<CancelAppointmentSeriesRequest>,
+) -> AppResult<AppointmentSeries> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let series_id = uuid_param(series_id, "series_id")?;
+    let payload = payload.0;
+
+    let from = match &payload.from {
+        Some(from) => parse_timestamp(from, "from")?,
+        None => OffsetDateTime::now_utc(),
+    };
+    let reason = check_cancellation_reason(payload.reason)?;
+
+    let series = state
+        .appointment_service
+        .read()
+        .await
+        .cancel_appointment_series(
+            &auth.token,
+            &practice_id,
+            &series_id,
+            &format_timestamp(from),
+            reason.as_deref(),
+        )
+        .await?;
+
+    Ok(series)
+}
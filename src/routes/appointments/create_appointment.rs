@@ -0,0 +1,61 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::appointment::{Appointment, AppointmentLocation, AppointmentSlot, NewAppointment},
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct CreateAppointmentRequest {
+    pub client_id: String,
+    /// Defaults to the caller.
+    pub clinician_id: Option<String>,
+    /// RFC 3339, e.g. `2026-11-02T09:00:00-06:00`.
+    pub starts_at: String,
+    /// 5 to 720.
+    pub duration_minutes: u32,
+    /// `in_person` or `telehealth`.
+    pub location_kind: String,
+    /// Room or address, for in-person appointments.
+    pub location: Option<String>,
+    /// `https` video link, for telehealth appointments.
+    pub telehealth_url: Option<String>,
+}
+
+pub async fn create_appointment_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<CreateAppointmentRequest>,
+) -> AppResult<Appointment> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let payload = payload.0;
+
+    let appointment = NewAppointment {
+        client_id: uuid_param(&payload.client_id, "client_id")?,
+        clinician_id: match &payload.clinician_id {
+            Some(clinician_id) => uuid_param(clinician_id, "clinician_id")?,
+            None => auth.user_id.clone(),
+        },
+        slot: AppointmentSlot::new(&payload.starts_at, payload.duration_minutes)?,
+        location: AppointmentLocation::new(
+            &payload.location_kind,
+            payload.location,
+            payload.telehealth_url,
+        )?,
+    };
+
+    let appointment = state
+        .appointment_service
+        .read()
+        .await
+        .create_appointment(&auth.token, &practice_id, &appointment)
+        .await?;
+
+    Ok(appointment)
+}
@@ -0,0 +1,86 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::{
+            appointment::{
+                AppointmentLocation, AppointmentSeries, NewAppointmentSeries, TimeZoneName,
+                parse_local_date, parse_local_time,
+            },
+            recurrence_rule::RecurrenceRule,
+        },
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct CreateAppointmentSeriesRequest {
+    pub client_id: String,
+    /// Defaults to the caller.
+    pub clinician_id: Option<String>,
+    /// RFC 5545 `RRULE` with `FREQ` of `DAILY`, `WEEKLY` or `MONTHLY`, optional `INTERVAL`,
+    /// `BYDAY` for weekly rules, and `COUNT` or `UNTIL`, e.g.
+    /// `FREQ=WEEKLY;BYDAY=MO,TH;COUNT=12`.
+    pub rrule: String,
+    /// IANA time zone the occurrences keep their wall-clock time in, e.g. `America/Chicago`.
+    pub timezone: String,
+    /// `YYYY-MM-DD`; the series starts on the first date at or after it that the rule
+    /// picks.
+    pub start_date: String,
+    /// `HH:MM`.
+    pub start_time: String,
+    /// 5 to 720.
+    pub duration_minutes: u32,
+    /// `YYYY-MM-DD` dates the rule would pick but that should be skipped.
+    pub exdates: Option<Vec<String>>,
+    /// `in_person` or `telehealth`.
+    pub location_kind: String,
+    pub location: Option<String>,
+    pub telehealth_url: Option<String>,
+}
+
+pub async fn create_appointment_series_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<CreateAppointmentSeriesRequest>,
+) -> AppResult<AppointmentSeries> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let payload = payload.0;
+
+    let series = NewAppointmentSeries::new(
+        uuid_param(&payload.client_id, "client_id")?,
+        match &payload.clinician_id {
+            Some(clinician_id) => uuid_param(clinician_id, "clinician_id")?,
+            None => auth.user_id.clone(),
+        },
+        RecurrenceRule::new(payload.rrule)?,
+        TimeZoneName::new(payload.timezone)?,
+        parse_local_date(&payload.start_date, "start_date")?,
+        parse_local_time(&payload.start_time)?,
+        payload.duration_minutes,
+        payload
+            .exdates
+            .unwrap_or_default()
+            .iter()
+            .map(|date| parse_local_date(date, "exdates"))
+            .collect::<AppResult<_>>()?,
+        AppointmentLocation::new(
+            &payload.location_kind,
+            payload.location,
+            payload.telehealth_url,
+        )?,
+    )?;
+
+    let series = state
+        .appointment_service
+        .read()
+        .await
+        .create_appointment_series(&auth.token, &practice_id, &series)
+        .await?;
+
+    Ok(series)
+}
@@ -0,0 +1,26 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::appointment::Appointment},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn get_appointment_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    appointment_id: &str,
+) -> AppResult<Appointment> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let appointment_id = uuid_param(appointment_id, "appointment_id")?;
+
+    let appointment = state
+        .appointment_service
+        .read()
+        .await
+        .get_appointment(&auth.token, &practice_id, &appointment_id)
+        .await?;
+
+    Ok(appointment)
+}
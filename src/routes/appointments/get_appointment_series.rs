@@ -0,0 +1,26 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::appointment::AppointmentSeries},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn get_appointment_series_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    series_id: &str,
+) -> AppResult<AppointmentSeries> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let series_id = uuid_param(series_id, "series_id")?;
+
+    let series = state
+        .appointment_service
+        .read()
+        .await
+        .get_appointment_series(&auth.token, &practice_id, &series_id)
+        .await?;
+
+    Ok(series)
+}
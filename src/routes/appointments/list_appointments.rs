@@ -0,0 +1,66 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, ValidationError},
+        types::appointment::{Appointment, AppointmentFilter, AppointmentRange, AppointmentStatus},
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+/// Raw query string filters, validated into an [`AppointmentFilter`] by the handler.
+#[derive(Debug, Default)]
+pub struct AppointmentListQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub clinician_id: Option<String>,
+    pub client_id: Option<String>,
+    pub status: Option<String>,
+}
+
+impl AppointmentListQuery {
+    fn into_filter(self) -> AppResult<AppointmentFilter> {
+        let (Some(from), Some(to)) = (self.from, self.to) else {
+            return Err(
+                ValidationError::InvalidInput("from and to are required".to_string()).into(),
+            );
+        };
+
+        Ok(AppointmentFilter {
+            range: AppointmentRange::new(&from, &to)?,
+            clinician_id: self
+                .clinician_id
+                .map(|id| uuid_param(&id, "clinician_id"))
+                .transpose()?,
+            client_id: self
+                .client_id
+                .map(|id| uuid_param(&id, "client_id"))
+                .transpose()?,
+            status: self
+                .status
+                .as_deref()
+                .map(AppointmentStatus::parse)
+                .transpose()?,
+        })
+    }
+}
+
+pub async fn list_appointments_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    query: AppointmentListQuery,
+) -> AppResult<Vec<Appointment>> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let filter = query.into_filter()?;
+
+    let appointments = state
+        .appointment_service
+        .read()
+        .await
+        .list_appointments(&auth.token, &practice_id, &filter)
+        .await?;
+
+    Ok(appointments)
+}
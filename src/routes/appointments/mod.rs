@@ -0,0 +1,7 @@
+pub mod cancel_appointment_series;
+pub mod create_appointment;
+pub mod create_appointment_series;
+pub mod get_appointment;
+pub mod get_appointment_series;
+pub mod list_appointments;
+pub mod update_appointment;
@@ -0,0 +1,106 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, ValidationError},
+        types::appointment::{
+            Appointment, AppointmentLocation, AppointmentSlot, AppointmentStatus,
+            AppointmentUpdate, check_cancellation_reason,
+        },
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+/// Omitted fields are left alone. `starts_at` and `duration_minutes` go together, as do
+/// the location fields, which replace the location as a whole.
+#[derive(Object, Debug)]
+pub struct UpdateAppointmentRequest {
+    pub clinician_id: Option<String>,
+    /// RFC 3339.
+    pub starts_at: Option<String>,
+    pub duration_minutes: Option<u32>,
+    /// `in_person` or `telehealth`.
+    pub location_kind: Option<String>,
+    pub location: Option<String>,
+    pub telehealth_url: Option<String>,
+    /// `checked_in`, `completed`, `no_show` or `cancelled`.
+    pub status: Option<String>,
+    /// Only with `"status": "cancelled"`.
+    pub cancellation_reason: Option<String>,
+}
+
+impl UpdateAppointmentRequest {
+    fn into_update(self) -> AppResult<AppointmentUpdate> {
+        let slot = match (&self.starts_at, self.duration_minutes) {
+            (None, None) => None,
+            (Some(starts_at), Some(minutes)) => Some(AppointmentSlot::new(starts_at, minutes)?),
+            _ => {
+                return Err(ValidationError::InvalidInput(
+                    "starts_at and duration_minutes must be given together".to_string(),
+                )
+                .into());
+            }
+        };
+
+        let touches_location = self.location_kind.is_some()
+            || self.location.is_some()
+            || self.telehealth_url.is_some();
+        let location = touches_location
+            .then(|| {
+                let kind = self.location_kind.as_deref().ok_or_else(|| {
+                    ValidationError::InvalidLocation(
+                        "location_kind is required to change the location".to_string(),
+                    )
+                })?;
+                AppointmentLocation::new(kind, self.location.clone(), self.telehealth_url.clone())
+            })
+            .transpose()?;
+
+        let status = self
+            .status
+            .as_deref()
+            .map(AppointmentStatus::parse)
+            .transpose()?;
+        let cancellation_reason = check_cancellation_reason(self.cancellation_reason)?;
+        if cancellation_reason.is_some() && status != Some(AppointmentStatus::Cancelled) {
+            return Err(ValidationError::InvalidInput(
+                "cancellation_reason is only allowed when cancelling".to_string(),
+            )
+            .into());
+        }
+
+        Ok(AppointmentUpdate {
+            clinician_id: self
+                .clinician_id
+                .map(|id| uuid_param(&id, "clinician_id"))
+                .transpose()?,
+            slot,
+            location,
+            status,
+            cancellation_reason,
+        })
+    }
+}
+
+pub async fn update_appointment_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    appointment_id: &str,
+    payload: Json<UpdateAppointmentRequest>,
+) -> AppResult<Appointment> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let appointment_id = uuid_param(appointment_id, "appointment_id")?;
+    let update = payload.0.into_update()?;
+
+    let appointment = state
+        .appointment_service
+        .read()
+        .await
+        .update_appointment(&auth.token, &practice_id, &appointment_id, &update)
+        .await?;
+
+    Ok(appointment)
+}
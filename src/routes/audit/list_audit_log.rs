@@ -0,0 +1,90 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, ValidationError},
+        types::audit::{
+            AUDITED_TABLES, AuditFilter, AuditOperation, AuditPage, DEFAULT_AUDIT_PAGE_SIZE,
+            MAX_AUDIT_PAGE_SIZE,
+        },
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+/// Raw query string filters, validated into an [`AuditFilter`] by the handler.
+#[derive(Debug, Default)]
+pub struct AuditLogQuery {
+    pub table_name: Option<String>,
+    pub operation: Option<String>,
+    pub actor_user_id: Option<String>,
+    pub row_id: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub cursor: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+impl AuditLogQuery {
+    fn into_filter(self) -> AppResult<AuditFilter> {
+        if let Some(table_name) = &self.table_name
+            && !AUDITED_TABLES.contains(&table_name.as_str())
+        {
+            return Err(ValidationError::InvalidInput(format!(
+                "table_name must be one of {}",
+                AUDITED_TABLES.join(", ")
+            ))
+            .into());
+        }
+
+        let limit = self.limit.unwrap_or(DEFAULT_AUDIT_PAGE_SIZE);
+        if limit == 0 || limit > MAX_AUDIT_PAGE_SIZE {
+            return Err(ValidationError::InvalidInput(format!(
+                "limit must be between 1 and {MAX_AUDIT_PAGE_SIZE}"
+            ))
+            .into());
+        }
+
+        Ok(AuditFilter {
+            table_name: self.table_name,
+            operation: self
+                .operation
+                .as_deref()
+                .map(AuditOperation::parse)
+                .transpose()?,
+            actor_user_id: self
+                .actor_user_id
+                .as_deref()
+                .map(|id| uuid_param(id, "actor_user_id"))
+                .transpose()?,
+            row_id: self
+                .row_id
+                .as_deref()
+                .map(|id| uuid_param(id, "row_id"))
+                .transpose()?,
+            from: self.from,
+            to: self.to,
+            cursor: self.cursor,
+            limit,
+        })
+    }
+}
+
+pub async fn list_audit_log_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    query: AuditLogQuery,
+) -> AppResult<AuditPage> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let filter = query.into_filter()?;
+
+    let page = state
+        .audit_log_service
+        .read()
+        .await
+        .list_audit_log(&auth.token, &practice_id, &filter)
+        .await?;
+
+    Ok(page)
+}
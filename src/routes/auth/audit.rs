@@ -0,0 +1,38 @@
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::audit::{AuditOutcome, AuthAuditAction, AuthAuditEvent},
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+/// Records the outcome of an authentication action. A sink failure is logged rather than
+/// returned, so an audit store outage doesn't lock users out.
+pub async fn record_auth_event<T>(
+    state: &AppState,
+    ctx: &RequestContext,
+    action: AuthAuditAction,
+    actor_user_id: Option<String>,
+    target: Option<String>,
+    result: &AppResult<T>,
+) {
+    let (outcome, reason) = match result {
+        Ok(_) => (AuditOutcome::Success, None),
+        Err(e) => (AuditOutcome::Failure, Some(e.to_string())),
+    };
+
+    let event = AuthAuditEvent {
+        action,
+        outcome,
+        actor_user_id,
+        target,
+        request_id: ctx.request_id.clone(),
+        client_ip: ctx.client_ip.clone(),
+        reason,
+    };
+
+    if let Err(e) = state.audit_sink.read().await.record(event).await {
+        tracing::error!(error = %e, ?action, "Failed to record audit event");
+    }
+}
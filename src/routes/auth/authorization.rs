@@ -0,0 +1,148 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use poem::{Endpoint, FromRequest, IntoResponse, Request, RequestBody, Response};
+use tokio::sync::OnceCell;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        types::{membership::PracticeRoleSet, practice_role::PracticeRole},
+    },
+    routes::auth::guard::{AuthenticatedUser, reject},
+    state::AppState,
+};
+
+/// Per-request slot for the caller's roles, so several extractors (or a guard plus the
+/// handler) only load them once.
+#[derive(Clone, Default)]
+struct RoleCache(Arc<OnceCell<Arc<Vec<PracticeRoleSet>>>>);
+
+/// Middleware installing an empty [`RoleCache`] on every request.
+pub async fn with_role_cache<E: Endpoint>(ep: Arc<E>, mut req: Request) -> poem::Result<Response> {
+    req.extensions_mut().insert(RoleCache::default());
+    ep.call(req).await.map(IntoResponse::into_response)
+}
+
+/// The caller together with their active practice memberships and roles.
+#[derive(Clone)]
+pub struct PracticeRoles {
+    pub user: AuthenticatedUser,
+    memberships: Arc<Vec<PracticeRoleSet>>,
+}
+
+impl PracticeRoles {
+    pub fn memberships(&self) -> &[PracticeRoleSet] {
+        &self.memberships
+    }
+
+    /// Whether the caller holds any of `roles` in the given practice.
+    pub fn has_any_in(&self, practice_id: &str, roles: &[PracticeRole]) -> bool {
+        self.memberships
+            .iter()
+            .any(|m| m.practice_id == practice_id && m.has_any(roles))
+    }
+
+    /// Practices in which the caller holds any of `roles`.
+    pub fn practices_with_any(&self, roles: &[PracticeRole]) -> Vec<&str> {
+        self.memberships
+            .iter()
+            .filter(|m| m.has_any(roles))
+            .map(|m| m.practice_id.as_str())
+            .collect()
+    }
+
+    pub fn require_any_in(&self, practice_id: &str, roles: &[PracticeRole]) -> AppResult<()> {
+        if self.has_any_in(practice_id, roles) {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientRole.into())
+        }
+    }
+}
+
+impl<'a> FromRequest<'a> for PracticeRoles {
+    async fn from_request(req: &'a Request, body: &mut RequestBody) -> poem::Result<Self> {
+        let user = AuthenticatedUser::from_request(req, body).await?;
+
+        let state = req.data::<AppState>().ok_or_else(|| {
+            poem::Error::from_status(poem::http::StatusCode::INTERNAL_SERVER_ERROR)
+        })?;
+
+        let load = || async {
+            state
+                .membership_service
+                .read()
+                .await
+                .list_user_roles(&user.token, &user.user_id)
+                .await
+                .map(Arc::new)
+        };
+
+        let memberships = match req.extensions().get::<RoleCache>() {
+            Some(cache) => cache.0.get_or_try_init(load).await.cloned(),
+            None => load().await,
+        }
+        .map_err(|e| reject(req, e))?;
+
+        Ok(Self { user, memberships })
+    }
+}
+
+/// A set of roles, any one of which satisfies a [`RequirePracticeRole`] guard.
+pub trait RoleRequirement: Send + Sync {
+    const ROLES: &'static [PracticeRole];
+}
+
+pub struct Owner;
+
+impl RoleRequirement for Owner {
+    const ROLES: &'static [PracticeRole] = &[PracticeRole::Owner];
+}
+
+pub struct OwnerOrAdmin;
+
+impl RoleRequirement for OwnerOrAdmin {
+    const ROLES: &'static [PracticeRole] = &[PracticeRole::Owner, PracticeRole::Admin];
+}
+
+/// poem-openapi registers routes with their variables renamed to `param0`, `param1`…, so
+/// `raw_path_param("practice_id")` never matches; read the segment after `practices` instead.
+fn practice_id_from_path(path: &str) -> Option<&str> {
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == "practices")?;
+    segments.next().filter(|segment| !segment.is_empty())
+}
+
+/// Rejects with `403 insufficient_role` before the handler runs unless the caller holds one
+/// of `R::ROLES` in the practice named by the route's `:practice_id` segment.
+pub struct RequirePracticeRole<R: RoleRequirement> {
+    pub roles: PracticeRoles,
+    pub practice_id: String,
+    _requirement: PhantomData<R>,
+}
+
+impl<R: RoleRequirement> RequirePracticeRole<R> {
+    pub fn into_user(self) -> AuthenticatedUser {
+        self.roles.user
+    }
+}
+
+impl<'a, R: RoleRequirement> FromRequest<'a> for RequirePracticeRole<R> {
+    async fn from_request(req: &'a Request, body: &mut RequestBody) -> poem::Result<Self> {
+        let roles = PracticeRoles::from_request(req, body).await?;
+
+        let practice_id = practice_id_from_path(req.uri().path())
+            .ok_or_else(|| poem::Error::from_status(poem::http::StatusCode::INTERNAL_SERVER_ERROR))?
+            .to_string();
+
+        roles
+            .require_any_in(&practice_id, R::ROLES)
+            .map_err(|e| reject(req, e))?;
+
+        Ok(Self {
+            roles,
+            practice_id,
+            _requirement: PhantomData,
+        })
+    }
+}
@@ -1,23 +1,58 @@
 use poem::web::Data;
 use poem_openapi::{Object, payload::Json};
 
-use crate::{domain::error::app_error::AppResult, state::AppState};
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        types::audit::AuthAuditAction,
+    },
+    routes::auth::{audit::record_auth_event, guard::AuthenticatedUser},
+    state::AppState,
+    utils::tracing::RequestContext,
+};
 
 #[derive(Object, Debug)]
 pub struct DeleteUserRequest {
     pub user_id: String,
 }
 
+/// Accounts span practices, so practice roles don't reach them: users may delete
+/// themselves, and only platform admins may delete anyone else.
+fn authorize(user: &AuthenticatedUser, target_user_id: &str) -> AppResult<()> {
+    if target_user_id == user.user_id || user.platform_admin {
+        Ok(())
+    } else {
+        Err(AuthError::InsufficientRole.into())
+    }
+}
+
 pub async fn delete_user_handler(
     state: Data<&AppState>,
+    ctx: &RequestContext,
+    auth: AuthenticatedUser,
     payload: Json<DeleteUserRequest>,
 ) -> AppResult<()> {
-    state
-        .auth_service
-        .read()
-        .await
-        .delete_user(&payload.user_id)
-        .await?;
+    let result = match authorize(&auth, &payload.user_id) {
+        Ok(()) => {
+            state
+                .auth_service
+                .read()
+                .await
+                .delete_user(&payload.user_id)
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::DeleteUser,
+        Some(auth.user_id.clone()),
+        Some(payload.user_id.clone()),
+        &result,
+    )
+    .await;
 
-    Ok(())
+    result
 }
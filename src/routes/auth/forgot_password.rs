@@ -0,0 +1,40 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::email::Email},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+    pub redirect_to: Option<String>,
+}
+
+#[derive(Object, Debug)]
+pub struct ForgotPasswordResponse {
+    pub message: String,
+}
+
+pub async fn forgot_password_handler(
+    state: Data<&AppState>,
+    payload: Json<ForgotPasswordRequest>,
+) -> AppResult<ForgotPasswordResponse> {
+    let email = Email::new(payload.email.clone())?;
+
+    let redirect = payload.redirect_to.clone();
+
+    state
+        .auth_service
+        .read()
+        .await
+        .forgot_password(&email, redirect.as_deref())
+        .await?;
+
+    // Same message whether or not the account exists, so the endpoint can't be used to probe emails.
+    Ok(ForgotPasswordResponse {
+        message: "If an account exists for this email, a password reset link has been sent."
+            .to_string(),
+    })
+}
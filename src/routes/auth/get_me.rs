@@ -0,0 +1,21 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::profile::UserProfile},
+    routes::auth::guard::AuthenticatedUser,
+    state::AppState,
+};
+
+pub async fn get_me_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+) -> AppResult<UserProfile> {
+    let profile = state
+        .auth_service
+        .read()
+        .await
+        .get_user(&auth.token)
+        .await?;
+
+    Ok(profile)
+}
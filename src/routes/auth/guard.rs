@@ -1,50 +1,167 @@
-use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
-use poem::{FromRequest, Request, RequestBody};
-use secrecy::ExposeSecret;
-use serde::Deserialize;
+use poem::{FromRequest, IntoResponse, Request, RequestBody};
 
-use crate::state::AppState;
-
-#[derive(Deserialize, Debug)]
-struct SupabaseClaims {
-    pub sub: String,
-}
+use crate::{
+    domain::{
+        error::{
+            app_error::{AppError, AppResult, AuthError},
+            http_response::AppHttpResponse,
+        },
+        types::session::SessionActivity,
+    },
+    routes::auth::{
+        session_activity::{check_session_activity, has_verified_factor},
+        session_cookie::{ACCESS_COOKIE, cookie_value, verify_csrf},
+    },
+    state::AppState,
+};
 
+#[derive(Clone)]
 pub struct AuthenticatedUser {
     pub user_id: String,
     pub token: String,
+    /// Authenticator assurance level: `aal1` after a password signin, `aal2` once an MFA
+    /// factor has been verified for the session.
+    pub aal: String,
+    /// Supabase's signin session, when the token carries one.
+    pub session_id: Option<String>,
+    /// The token came from the session cookie rather than an `Authorization` header, so
+    /// reissued sessions should go back into cookies too.
+    pub from_cookie: bool,
+    /// From the token's `app_metadata`, which only the service role can set.
+    pub platform_admin: bool,
+}
+
+impl AuthenticatedUser {
+    /// Sensitive handlers call this to insist the session passed a second factor.
+    pub fn require_aal2(&self) -> AppResult<()> {
+        if self.aal == "aal2" {
+            Ok(())
+        } else {
+            Err(AuthError::MfaRequired.into())
+        }
+    }
+}
+
+// Rejections carry the usual `ErrorBody` so clients can tell an expired token (refresh and
+// retry) apart from a missing or invalid one (sign in again).
+pub(crate) fn reject(req: &Request, error: impl Into<AppError>) -> poem::Error {
+    let request_id = req
+        .header("x-request-id")
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    poem::Error::from_response(
+        AppHttpResponse::from_app_error(error.into(), &request_id).into_response(),
+    )
+}
+
+/// Verifies the caller's token and checks the session against its idle timeout, counting
+/// the request as activity when `touch` is set. Unless `allow_aal1` is set, a session below
+/// AAL2 is refused once its user has verified a factor.
+async fn authenticate(
+    req: &Request,
+    touch: bool,
+    allow_aal1: bool,
+) -> poem::Result<(AuthenticatedUser, Option<SessionActivity>)> {
+    let bearer = req
+        .headers()
+        .get(poem::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.strip_prefix("Bearer "));
+
+    // A bearer header wins; the cookie is only trusted alongside a matching CSRF token.
+    let (raw_token, from_cookie) = match bearer {
+        Some(token) => (token.to_string(), false),
+        None => {
+            let token = cookie_value(req.headers(), ACCESS_COOKIE)
+                .ok_or_else(|| reject(req, AuthError::MissingToken))?;
+            verify_csrf(req).map_err(|e| reject(req, e))?;
+            (token, true)
+        }
+    };
+
+    let state = req
+        .data::<AppState>()
+        .ok_or_else(|| poem::Error::from_status(poem::http::StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    let claims = state
+        .token_verifier
+        .read()
+        .await
+        .verify(&raw_token)
+        .await
+        .map_err(|e| reject(req, e))?;
+
+    // If the activity store is down we can't tell an idle or revoked session from a live
+    // one, so the request is refused too.
+    let activity = match &claims.session_id {
+        Some(session_id) => Some(
+            check_session_activity(state, req, &raw_token, &claims.sub, session_id, touch)
+                .await
+                .map_err(|e| {
+                    tracing::error!(error = %e, "Session activity check failed");
+                    reject(req, e)
+                })?,
+        ),
+        None => None,
+    };
+
+    if !allow_aal1 && claims.aal.as_deref() != Some("aal2") {
+        let mfa_required = match &activity {
+            Some(activity) => activity.mfa_required,
+            None => has_verified_factor(state, &raw_token)
+                .await
+                .map_err(|e| reject(req, e))?,
+        };
+        if mfa_required {
+            return Err(reject(req, AuthError::MfaRequired));
+        }
+    }
+
+    let user = AuthenticatedUser {
+        user_id: claims.sub,
+        token: raw_token,
+        aal: claims.aal.unwrap_or_else(|| "aal1".to_string()),
+        session_id: claims.session_id,
+        from_cookie,
+        platform_admin: claims.app_metadata.platform_admin,
+    };
+    Ok((user, activity))
 }
 
 // Poem runs this extractor automatically whenever a handler declares an `AuthenticatedUser`
 // parameter, so routes just add the argument and receive a validated Supabase user id.
 impl<'a> FromRequest<'a> for AuthenticatedUser {
     async fn from_request(req: &'a Request, _: &mut RequestBody) -> poem::Result<Self> {
-        let token = req
-            .headers()
-            .get(poem::http::header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .and_then(|s| s.strip_prefix("Bearer "))
-            .ok_or_else(|| poem::Error::from_status(poem::http::StatusCode::UNAUTHORIZED))?;
-
-        let raw_token = token.to_string();
-
-        let state = req.data::<AppState>().ok_or_else(|| {
-            poem::Error::from_status(poem::http::StatusCode::INTERNAL_SERVER_ERROR)
-        })?;
+        authenticate(req, true, false).await.map(|(user, _)| user)
+    }
+}
 
-        let secret = state.supabase_jwt_secret.expose_secret();
-        let decoding_key = DecodingKey::from_base64_secret(secret)
-            .unwrap_or_else(|_| DecodingKey::from_secret(secret.as_bytes()));
+/// Authenticates like [`AuthenticatedUser`] but also admits an AAL1 session whose user has
+/// verified a factor, for the routes that step such a session up to AAL2 or end it.
+pub struct Aal1AuthenticatedUser {
+    pub user: AuthenticatedUser,
+}
 
-        let mut validation = Validation::new(Algorithm::HS256);
-        validation.validate_aud = false;
+impl<'a> FromRequest<'a> for Aal1AuthenticatedUser {
+    async fn from_request(req: &'a Request, _: &mut RequestBody) -> poem::Result<Self> {
+        let (user, _) = authenticate(req, true, true).await?;
+        Ok(Self { user })
+    }
+}
 
-        let data = decode::<SupabaseClaims>(&raw_token, &decoding_key, &validation)
-            .map_err(|_| poem::Error::from_status(poem::http::StatusCode::UNAUTHORIZED))?;
+/// Authenticates like [`AuthenticatedUser`] but doesn't count the request as activity, so
+/// polling the session's status can't keep it alive. Sessions still waiting on their second
+/// factor may poll too.
+pub struct PassiveAuthenticatedUser {
+    pub user: AuthenticatedUser,
+    /// `None` for tokens without a `session_id`.
+    pub activity: Option<SessionActivity>,
+}
 
-        Ok(Self {
-            user_id: data.claims.sub,
-            token: raw_token,
-        })
+impl<'a> FromRequest<'a> for PassiveAuthenticatedUser {
+    async fn from_request(req: &'a Request, _: &mut RequestBody) -> poem::Result<Self> {
+        let (user, activity) = authenticate(req, false, true).await?;
+        Ok(Self { user, activity })
     }
 }
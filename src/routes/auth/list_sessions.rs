@@ -0,0 +1,55 @@
+use poem::web::Data;
+use serde::Serialize;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::session::SessionActivity},
+    routes::auth::guard::AuthenticatedUser,
+    state::AppState,
+    utils::clock::unix_now,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ActiveSession {
+    pub session_id: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    /// Unix seconds.
+    pub created_at: i64,
+    /// Unix seconds.
+    pub last_seen_at: i64,
+    /// This is the session making the request.
+    pub current: bool,
+}
+
+impl ActiveSession {
+    fn new(activity: SessionActivity, current_session_id: Option<&str>) -> Self {
+        Self {
+            current: current_session_id == Some(activity.session_id.as_str()),
+            session_id: activity.session_id,
+            ip_address: activity.ip_address,
+            user_agent: activity.user_agent,
+            created_at: activity.created_at,
+            last_seen_at: activity.last_seen_at,
+        }
+    }
+}
+
+/// The caller's sessions that are neither revoked nor idle, most recently used first.
+pub async fn list_sessions_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+) -> AppResult<Vec<ActiveSession>> {
+    let now = unix_now();
+    let sessions = state
+        .session_activity_tracker
+        .read()
+        .await
+        .list_for_user(&auth.user_id)
+        .await?;
+
+    Ok(sessions
+        .into_iter()
+        .filter(|s| s.is_active(now))
+        .map(|s| ActiveSession::new(s, auth.session_id.as_deref()))
+        .collect())
+}
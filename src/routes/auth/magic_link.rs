@@ -0,0 +1,38 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::email::Email},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct MagicLinkRequest {
+    pub email: String,
+    pub redirect_to: Option<String>,
+}
+
+#[derive(Object, Debug)]
+pub struct MagicLinkResponse {
+    pub message: String,
+}
+
+pub async fn magic_link_handler(
+    state: Data<&AppState>,
+    payload: Json<MagicLinkRequest>,
+) -> AppResult<MagicLinkResponse> {
+    let email = Email::new(payload.email.clone())?;
+
+    state
+        .auth_service
+        .read()
+        .await
+        .send_magic_link(&email, payload.redirect_to.as_deref())
+        .await?;
+
+    // As with password resets, unknown emails get the same answer as real ones.
+    Ok(MagicLinkResponse {
+        message: "If an account exists for this email, a signin link and code have been sent."
+            .to_string(),
+    })
+}
@@ -0,0 +1,28 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::mfa::MfaChallenge},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct MfaChallengeRequest {
+    pub factor_id: String,
+}
+
+pub async fn mfa_challenge_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    payload: Json<MfaChallengeRequest>,
+) -> AppResult<MfaChallenge> {
+    let challenge = state
+        .auth_service
+        .read()
+        .await
+        .challenge_factor(&auth.token, &uuid_param(&payload.factor_id, "factor_id")?)
+        .await?;
+
+    Ok(challenge)
+}
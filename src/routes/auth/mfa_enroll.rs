@@ -0,0 +1,30 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::mfa::TotpEnrollment},
+    routes::auth::guard::AuthenticatedUser,
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct MfaEnrollRequest {
+    pub friendly_name: Option<String>,
+}
+
+pub async fn mfa_enroll_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    payload: Json<MfaEnrollRequest>,
+) -> AppResult<TotpEnrollment> {
+    let friendly_name = payload.friendly_name.clone();
+
+    let enrollment = state
+        .auth_service
+        .read()
+        .await
+        .enroll_totp(&auth.token, friendly_name.as_deref())
+        .await?;
+
+    Ok(enrollment)
+}
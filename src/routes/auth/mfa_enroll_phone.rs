@@ -0,0 +1,36 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::{mfa::PhoneEnrollment, phone_number::PhoneNumber},
+    },
+    routes::auth::guard::AuthenticatedUser,
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct MfaEnrollPhoneRequest {
+    /// E.164, e.g. `+15555550123`. Codes are texted here once the factor is challenged.
+    pub phone: String,
+    pub friendly_name: Option<String>,
+}
+
+pub async fn mfa_enroll_phone_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    payload: Json<MfaEnrollPhoneRequest>,
+) -> AppResult<PhoneEnrollment> {
+    let phone = PhoneNumber::new(payload.phone.clone())?;
+    let friendly_name = payload.friendly_name.clone();
+
+    let enrollment = state
+        .auth_service
+        .read()
+        .await
+        .enroll_phone(&auth.token, &phone, friendly_name.as_deref())
+        .await?;
+
+    Ok(enrollment)
+}
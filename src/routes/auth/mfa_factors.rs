@@ -0,0 +1,21 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::mfa::MfaFactor},
+    routes::auth::guard::AuthenticatedUser,
+    state::AppState,
+};
+
+pub async fn mfa_factors_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+) -> AppResult<Vec<MfaFactor>> {
+    let factors = state
+        .auth_service
+        .read()
+        .await
+        .list_factors(&auth.token)
+        .await?;
+
+    Ok(factors)
+}
@@ -0,0 +1,45 @@
+use poem::web::Data;
+
+use crate::{
+    domain::error::app_error::{AppResult, AuthError},
+    routes::{
+        auth::{
+            guard::AuthenticatedUser,
+            session_activity::{has_verified_factor, set_mfa_required},
+        },
+        params::uuid_param,
+    },
+    state::AppState,
+};
+
+pub async fn mfa_unenroll_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    factor_id: &str,
+) -> AppResult<()> {
+    let factor_id = uuid_param(factor_id, "factor_id")?;
+    let auth_service = state.auth_service.read().await;
+
+    let factor = auth_service
+        .list_factors(&auth.token)
+        .await?
+        .into_iter()
+        .find(|factor| factor.id == factor_id)
+        .ok_or(AuthError::FactorNotFound)?;
+
+    // Removing a verified factor downgrades the account, so it takes an AAL2 session to do it.
+    if factor.is_verified() {
+        auth.require_aal2()?;
+    }
+
+    auth_service
+        .unenroll_factor(&auth.token, &factor_id)
+        .await?;
+    drop(auth_service);
+
+    if factor.is_verified() && !has_verified_factor(&state, &auth.token).await? {
+        set_mfa_required(&state, &auth.user_id, false).await?;
+    }
+
+    Ok(())
+}
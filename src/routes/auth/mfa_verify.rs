@@ -0,0 +1,49 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, ValidationError},
+        types::session::AuthSession,
+    },
+    routes::{
+        auth::{guard::AuthenticatedUser, session_activity::set_mfa_required},
+        params::uuid_param,
+    },
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct MfaVerifyRequest {
+    pub factor_id: String,
+    pub challenge_id: String,
+    pub code: String,
+}
+
+pub async fn mfa_verify_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    payload: Json<MfaVerifyRequest>,
+) -> AppResult<AuthSession> {
+    let code = payload.code.trim();
+    if code.len() != 6 || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ValidationError::InvalidInput("MFA code must be 6 digits".to_string()).into());
+    }
+
+    let session = state
+        .auth_service
+        .read()
+        .await
+        .verify_factor(
+            &auth.token,
+            &uuid_param(&payload.factor_id, "factor_id")?,
+            &payload.challenge_id,
+            code,
+        )
+        .await?;
+
+    // The user's other sessions, still at AAL1, now have to step up too.
+    set_mfa_required(&state, &auth.user_id, true).await?;
+
+    Ok(session)
+}
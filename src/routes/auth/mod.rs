@@ -1,6 +1,27 @@
+pub mod audit;
+pub mod authorization;
 pub mod delete_user;
+pub mod forgot_password;
+pub mod get_me;
 pub mod guard;
+pub mod list_sessions;
+pub mod magic_link;
+pub mod mfa_challenge;
+pub mod mfa_enroll;
+pub mod mfa_enroll_phone;
+pub mod mfa_factors;
+pub mod mfa_unenroll;
+pub mod mfa_verify;
+pub mod refresh;
+pub mod reset_password;
 pub mod retrieve_user_id;
+pub mod revoke_session;
+pub mod revoke_sessions;
+pub mod session_activity;
+pub mod session_cookie;
+pub mod session_idle;
 pub mod signin;
 pub mod signout;
 pub mod signup;
+pub mod update_me;
+pub mod verify_otp;
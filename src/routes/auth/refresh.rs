@@ -0,0 +1,51 @@
+use poem::{Request, web::Data};
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        types::session::AuthSession,
+    },
+    routes::auth::session_cookie::refresh_token_from_cookie,
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct RefreshRequest {
+    /// May be left out when the session lives in cookies.
+    #[oai(default)]
+    pub refresh_token: String,
+}
+
+#[derive(Debug)]
+pub struct RefreshResponse {
+    pub session: AuthSession,
+    /// The refresh token came from the session cookie, so the new session goes back there.
+    pub use_cookie: bool,
+}
+
+pub async fn refresh_handler(
+    state: Data<&AppState>,
+    req: &Request,
+    payload: Json<RefreshRequest>,
+) -> AppResult<RefreshResponse> {
+    let (refresh_token, use_cookie) = match payload.refresh_token.trim() {
+        "" => (
+            refresh_token_from_cookie(req)?.ok_or(AuthError::InvalidRefreshToken)?,
+            true,
+        ),
+        token => (token.to_string(), false),
+    };
+
+    let session = state
+        .auth_service
+        .read()
+        .await
+        .refresh_session(&refresh_token)
+        .await?;
+
+    Ok(RefreshResponse {
+        session,
+        use_cookie,
+    })
+}
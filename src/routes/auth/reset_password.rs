@@ -0,0 +1,36 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::password::Password},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub password: String,
+}
+
+#[derive(Object, Debug)]
+pub struct ResetPasswordResponse {
+    pub message: String,
+}
+
+pub async fn reset_password_handler(
+    state: Data<&AppState>,
+    payload: Json<ResetPasswordRequest>,
+) -> AppResult<ResetPasswordResponse> {
+    let password = Password::new(payload.password.clone(), &state.password_policy, None)?;
+
+    state
+        .auth_service
+        .read()
+        .await
+        .reset_password(&payload.token, &password)
+        .await?;
+
+    Ok(ResetPasswordResponse {
+        message: "Password reset successful, please sign in with your new password.".to_string(),
+    })
+}
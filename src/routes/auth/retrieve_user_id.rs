@@ -2,8 +2,13 @@ use poem::web::Data;
 use poem_openapi::{Object, payload::Json};
 
 use crate::{
-    domain::{error::app_error::AppResult, types::email::Email},
+    domain::{
+        error::app_error::{AppError, AppResult, AuthError},
+        types::{audit::AuthAuditAction, email::Email},
+    },
+    routes::auth::{audit::record_auth_event, guard::AuthenticatedUser},
     state::AppState,
+    utils::tracing::RequestContext,
 };
 
 #[derive(Object, Debug)]
@@ -16,18 +21,51 @@ pub struct RetrieveUserIdResponse {
     pub user_id: String,
 }
 
-pub async fn retrieve_user_id_handler(
-    state: Data<&AppState>,
-    payload: Json<RetrieveUserIdRequest>,
-) -> AppResult<RetrieveUserIdResponse> {
+/// Platform admins may look anyone up; everyone else only themselves, and gets the same
+/// `insufficient_role` whether or not another address exists. Practice roles don't count,
+/// since anyone can create a practice and own it.
+async fn retrieve_user_id(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    payload: &RetrieveUserIdRequest,
+) -> AppResult<String> {
     let email = Email::new(payload.email.clone())?;
+    let can_look_up_others = user.platform_admin;
 
-    let user_id = state
+    let result = state
         .auth_service
         .read()
         .await
         .retrieve_user_id(&email)
-        .await?;
+        .await;
+
+    match result {
+        Ok(user_id) if can_look_up_others || user_id == user.user_id => Ok(user_id),
+        Err(e) if can_look_up_others => Err(e),
+        Err(AppError::Auth(AuthError::UserNotFound)) | Ok(_) => {
+            Err(AuthError::InsufficientRole.into())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+pub async fn retrieve_user_id_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    auth: AuthenticatedUser,
+    payload: Json<RetrieveUserIdRequest>,
+) -> AppResult<RetrieveUserIdResponse> {
+    let result = retrieve_user_id(&state, &auth, &payload).await;
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::RetrieveUserId,
+        Some(auth.user_id.clone()),
+        Some(payload.email.clone()),
+        &result,
+    )
+    .await;
 
-    Ok(RetrieveUserIdResponse { user_id })
+    Ok(RetrieveUserIdResponse { user_id: result? })
 }
@@ -0,0 +1,56 @@
+use poem::web::{Data, cookie::CookieJar};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        types::audit::AuthAuditAction,
+    },
+    routes::auth::{
+        audit::record_auth_event, guard::AuthenticatedUser, session_activity::revoke_sessions,
+        session_cookie::clear_session_cookies,
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+async fn revoke_own_session(state: &AppState, user_id: &str, session_id: &str) -> AppResult<()> {
+    let activity = state
+        .session_activity_tracker
+        .read()
+        .await
+        .get(session_id)
+        .await?;
+
+    // Someone else's session looks the same as a missing one.
+    match activity {
+        Some(activity) if activity.user_id == user_id => {
+            revoke_sessions(state, user_id, Some(session_id)).await
+        }
+        _ => Err(AuthError::SessionNotFound.into()),
+    }
+}
+
+pub async fn revoke_session_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    cookies: &CookieJar,
+    auth: AuthenticatedUser,
+    session_id: &str,
+) -> AppResult<()> {
+    let result = revoke_own_session(&state, &auth.user_id, session_id).await;
+    if result.is_ok() && auth.from_cookie && auth.session_id.as_deref() == Some(session_id) {
+        clear_session_cookies(cookies);
+    }
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::RevokeSessions,
+        Some(auth.user_id.clone()),
+        Some(session_id.to_string()),
+        &result,
+    )
+    .await;
+
+    result
+}
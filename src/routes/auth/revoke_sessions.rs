@@ -0,0 +1,36 @@
+use poem::web::{Data, cookie::CookieJar};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::audit::AuthAuditAction},
+    routes::auth::{
+        audit::record_auth_event, guard::AuthenticatedUser, session_activity::revoke_sessions,
+        session_cookie::clear_session_cookies,
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+/// Signs the caller out everywhere, including the session making the request.
+pub async fn revoke_sessions_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    cookies: &CookieJar,
+    auth: AuthenticatedUser,
+) -> AppResult<()> {
+    let result = revoke_sessions(&state, &auth.user_id, None).await;
+    if result.is_ok() && auth.from_cookie {
+        clear_session_cookies(cookies);
+    }
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::RevokeSessions,
+        Some(auth.user_id.clone()),
+        Some(auth.user_id.clone()),
+        &result,
+    )
+    .await;
+
+    result
+}
@@ -0,0 +1,183 @@
+use poem::Request;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        types::{
+            mfa::MfaFactor,
+            session::{AuthSession, SessionActivity},
+        },
+    },
+    state::AppState,
+    utils::{clock::unix_now, tracing::client_ip},
+};
+
+/// Activity is only written back when the stored time is at least this stale, so a busy
+/// page doesn't cost a write per request.
+const WRITE_INTERVAL_SECS: i64 = 15;
+
+/// The session's idle timeout: the strictest among the caller's practices, or the app
+/// default. It is fixed for the session's lifetime, so a failed lookup fails the request
+/// rather than settle on a laxer default.
+async fn idle_timeout_secs(state: &AppState, token: &str) -> AppResult<i64> {
+    Ok(state
+        .practice_service
+        .read()
+        .await
+        .session_idle_timeout(token)
+        .await?
+        .unwrap_or(state.session_idle_timeout_secs))
+}
+
+/// Whether the user `token` was issued to has verified a second factor, so their sessions
+/// must reach AAL2 before the API accepts them.
+pub async fn has_verified_factor(state: &AppState, token: &str) -> AppResult<bool> {
+    Ok(state
+        .auth_service
+        .read()
+        .await
+        .list_factors(token)
+        .await?
+        .iter()
+        .any(MfaFactor::is_verified))
+}
+
+fn user_agent(req: &Request) -> Option<String> {
+    req.header(poem::http::header::USER_AGENT)
+        .map(|ua| ua.chars().take(512).collect())
+}
+
+/// Fails with `SessionRevoked` once the session has been ended remotely, and with
+/// `SessionIdleTimeout` once it has gone unused for longer than its timeout. With `touch`,
+/// the request also counts as activity.
+pub async fn check_session_activity(
+    state: &AppState,
+    req: &Request,
+    token: &str,
+    user_id: &str,
+    session_id: &str,
+    touch: bool,
+) -> AppResult<SessionActivity> {
+    let now = unix_now();
+    let existing = state
+        .session_activity_tracker
+        .read()
+        .await
+        .get(session_id)
+        .await?;
+
+    let mut activity = match existing {
+        Some(activity) => activity,
+        None => {
+            let activity = SessionActivity {
+                session_id: session_id.to_string(),
+                user_id: user_id.to_string(),
+                created_at: now,
+                last_seen_at: now,
+                idle_timeout_secs: idle_timeout_secs(state, token).await?,
+                expired: false,
+                ip_address: client_ip(req),
+                user_agent: user_agent(req),
+                revoked: false,
+                mfa_required: has_verified_factor(state, token).await?,
+            };
+            state
+                .session_activity_tracker
+                .read()
+                .await
+                .save(&activity)
+                .await?;
+            return Ok(activity);
+        }
+    };
+
+    let tracker = state.session_activity_tracker.read().await;
+    if activity.revoked {
+        return Err(AuthError::SessionRevoked.into());
+    }
+    if activity.is_idle(now) {
+        if !activity.expired {
+            activity.expired = true;
+            tracker.save(&activity).await?;
+        }
+        return Err(AuthError::SessionIdleTimeout.into());
+    }
+
+    let write_interval = WRITE_INTERVAL_SECS.min(activity.idle_timeout_secs / 10);
+    if touch && now - activity.last_seen_at >= write_interval {
+        activity.last_seen_at = now;
+        activity.ip_address = client_ip(req);
+        activity.user_agent = user_agent(req);
+        tracker.save(&activity).await?;
+    }
+
+    Ok(activity)
+}
+
+/// Ends the user's sessions, or just `session_id` when given: Supabase drops them so their
+/// refresh tokens stop working, and their activity records are marked revoked so access
+/// tokens already handed out are refused too.
+pub async fn revoke_sessions(
+    state: &AppState,
+    user_id: &str,
+    session_id: Option<&str>,
+) -> AppResult<()> {
+    let auth_service = state.auth_service.read().await;
+    match session_id {
+        Some(session_id) => auth_service.revoke_session(user_id, session_id).await?,
+        None => auth_service.revoke_user_sessions(user_id).await?,
+    }
+
+    let tracker = state.session_activity_tracker.read().await;
+    for mut activity in tracker.list_for_user(user_id).await? {
+        if activity.revoked || session_id.is_some_and(|id| id != activity.session_id) {
+            continue;
+        }
+        activity.revoked = true;
+        tracker.save(&activity).await?;
+    }
+    Ok(())
+}
+
+/// Records on every session the user already has whether it must reach AAL2, once they
+/// verify a factor or remove their last verified one. Sessions seen for the first time later
+/// look it up themselves.
+pub async fn set_mfa_required(state: &AppState, user_id: &str, required: bool) -> AppResult<()> {
+    let tracker = state.session_activity_tracker.read().await;
+    for mut activity in tracker.list_for_user(user_id).await? {
+        if activity.mfa_required != required {
+            activity.mfa_required = required;
+            tracker.save(&activity).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Ends a session we have just issued but won't hand out because a later check failed.
+/// Failures are only logged: the caller is already returning an error.
+pub async fn discard_session(state: &AppState, session: &AuthSession) {
+    let claims = state
+        .token_verifier
+        .read()
+        .await
+        .verify(&session.access_token)
+        .await;
+
+    let result = match claims {
+        Ok(claims) => match claims.session_id {
+            Some(session_id) => {
+                state
+                    .auth_service
+                    .read()
+                    .await
+                    .revoke_session(&session.user_id, &session_id)
+                    .await
+            }
+            None => Ok(()),
+        },
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        tracing::error!(error = %e, "Failed to revoke discarded session");
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use poem::{
+    Request,
+    http::{HeaderMap, Method, header},
+    web::cookie::{Cookie, CookieJar, SameSite},
+};
+use rand::RngCore;
+use serde_json::{Value, json};
+use subtle::ConstantTimeEq;
+
+use crate::domain::{
+    error::app_error::{AppResult, AuthError},
+    types::session::AuthSession,
+};
+
+pub const ACCESS_COOKIE: &str = "breeze_access_token";
+pub const REFRESH_COOKIE: &str = "breeze_refresh_token";
+/// Readable by page scripts, which echo it back in [`CSRF_HEADER`] (double-submit).
+pub const CSRF_COOKIE: &str = "breeze_csrf_token";
+pub const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// The refresh token is only ever sent to the refresh endpoint.
+const REFRESH_PATH: &str = "/api/auth/refresh";
+/// Refresh tokens outlive the access token; the browser drops them after this.
+const REFRESH_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+fn cookie(name: &str, value: String, path: &str, max_age: Duration, http_only: bool) -> Cookie {
+    let mut cookie = Cookie::new_with_str(name, value);
+    cookie.set_path(path);
+    cookie.set_max_age(max_age);
+    cookie.set_http_only(http_only);
+    cookie.set_secure(true);
+    cookie.set_same_site(SameSite::Strict);
+    cookie
+}
+
+fn csrf_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Response body for a newly issued session. In cookie mode the tokens go into HttpOnly
+/// cookies and the body carries only the CSRF token and expiry.
+pub fn session_body(jar: &CookieJar, session: &AuthSession, use_cookie: bool) -> Value {
+    if !use_cookie {
+        return json!({
+            "token": session.access_token,
+            "refresh_token": session.refresh_token,
+            "expires_in": session.expires_in,
+            "expires_at": session.expires_at,
+        });
+    }
+
+    let access_max_age = Duration::from_secs(session.expires_in.max(0) as u64);
+    let csrf_token = csrf_token();
+    jar.add(cookie(
+        ACCESS_COOKIE,
+        session.access_token.clone(),
+        "/api",
+        access_max_age,
+        true,
+    ));
+    jar.add(cookie(
+        REFRESH_COOKIE,
+        session.refresh_token.clone(),
+        REFRESH_PATH,
+        REFRESH_MAX_AGE,
+        true,
+    ));
+    jar.add(cookie(
+        CSRF_COOKIE,
+        csrf_token.clone(),
+        "/",
+        REFRESH_MAX_AGE,
+        false,
+    ));
+
+    json!({
+        "csrf_token": csrf_token,
+        "expires_in": session.expires_in,
+        "expires_at": session.expires_at,
+    })
+}
+
+/// Expires every session cookie. Each removal must repeat the path it was set with.
+pub fn clear_session_cookies(jar: &CookieJar) {
+    for (name, path) in [
+        (ACCESS_COOKIE, "/api"),
+        (REFRESH_COOKIE, REFRESH_PATH),
+        (CSRF_COOKIE, "/"),
+    ] {
+        let mut removal = cookie(
+            name,
+            String::new(),
+            path,
+            Duration::ZERO,
+            name != CSRF_COOKIE,
+        );
+        removal.make_removal();
+        jar.add(removal);
+    }
+}
+
+/// Reads a cookie straight from the headers, for code running outside the API's
+/// `CookieJarManager`.
+pub fn cookie_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// A cookie-authenticated request that changes state must echo the CSRF cookie in
+/// [`CSRF_HEADER`]. Cross-site pages can make the browser send the cookie but can't read it.
+pub fn verify_csrf(req: &Request) -> AppResult<()> {
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(());
+    }
+
+    let expected = cookie_value(req.headers(), CSRF_COOKIE);
+    let submitted = req.header(CSRF_HEADER);
+    match (expected, submitted) {
+        (Some(expected), Some(submitted))
+            if bool::from(expected.as_bytes().ct_eq(submitted.as_bytes())) =>
+        {
+            Ok(())
+        }
+        _ => Err(AuthError::CsrfTokenMismatch.into()),
+    }
+}
+
+/// The refresh token from the cookie, once the CSRF check has passed.
+pub fn refresh_token_from_cookie(req: &Request) -> AppResult<Option<String>> {
+    match cookie_value(req.headers(), REFRESH_COOKIE) {
+        Some(token) => {
+            verify_csrf(req)?;
+            Ok(Some(token))
+        }
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{
+    domain::{error::app_error::AppResult, types::session::SessionActivity},
+    routes::auth::guard::PassiveAuthenticatedUser,
+    utils::clock::unix_now,
+};
+
+#[derive(Debug)]
+pub struct SessionIdleStatus {
+    pub idle_timeout_secs: i64,
+    pub seconds_remaining: i64,
+    pub last_seen_at: i64,
+}
+
+impl From<&SessionActivity> for SessionIdleStatus {
+    fn from(activity: &SessionActivity) -> Self {
+        Self {
+            idle_timeout_secs: activity.idle_timeout_secs,
+            seconds_remaining: activity.seconds_remaining(unix_now()),
+            last_seen_at: activity.last_seen_at,
+        }
+    }
+}
+
+/// How long until the caller's session idles out, for the frontend's logoff warning.
+/// `None` when the session isn't tracked.
+pub async fn session_idle_handler(
+    caller: PassiveAuthenticatedUser,
+) -> AppResult<Option<SessionIdleStatus>> {
+    Ok(caller.activity.as_ref().map(SessionIdleStatus::from))
+}
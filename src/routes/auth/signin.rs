@@ -3,36 +3,115 @@ use poem_openapi::{Object, payload::Json};
 
 use crate::{
     domain::{
-        error::app_error::AppResult,
-        types::{email::Email, password::Password},
+        error::app_error::{AppError, AppResult, AuthError},
+        types::{audit::AuthAuditAction, email::Email, password::Password, session::AuthSession},
+    },
+    routes::auth::{
+        audit::record_auth_event,
+        session_activity::{discard_session, has_verified_factor},
     },
     state::AppState,
+    utils::tracing::RequestContext,
 };
 
 #[derive(Object, Debug)]
 pub struct SigninRequest {
     pub email: String,
     pub password: String,
+    /// Issue the session as HttpOnly cookies instead of returning the tokens.
+    #[oai(default)]
+    pub use_cookie: bool,
 }
 
-#[derive(Object, Debug)]
+/// A new session, and whether the user must verify a factor before the API accepts it.
+#[derive(Debug)]
 pub struct SigninResponse {
-    pub token: String,
+    pub session: AuthSession,
+    pub mfa_required: bool,
+}
+
+/// Tells the caller to step up right away when the user has a verified factor, rather than
+/// leave them to find out from the first `mfa_required` refusal.
+pub async fn signin_response(state: &AppState, session: AuthSession) -> AppResult<SigninResponse> {
+    match has_verified_factor(state, &session.access_token).await {
+        Ok(mfa_required) => Ok(SigninResponse {
+            session,
+            mfa_required,
+        }),
+        Err(e) => {
+            discard_session(state, &session).await;
+            Err(e)
+        }
+    }
+}
+
+/// Checks a password against the `LoginAttemptTracker` first and records the outcome, so
+/// every route that signs in with one is throttled and locked out alike.
+pub async fn tracked_signin(
+    state: &AppState,
+    ctx: &RequestContext,
+    email: &Email,
+    password: &Password,
+) -> AppResult<AuthSession> {
+    let client_ip = ctx.client_ip.as_deref();
+    let tracker = state.login_attempt_tracker.read().await;
+
+    // Without the attempt store we can't tell a locked account, so refuse to guess.
+    tracker.check(email, client_ip).await?;
+
+    let result = state
+        .auth_service
+        .read()
+        .await
+        .signin(email, password)
+        .await;
+
+    let tracked = match &result {
+        Ok(_) => tracker.record_success(email).await,
+        // Outages and unconfirmed addresses say nothing about the password.
+        Err(AppError::Auth(AuthError::InvalidCredentials)) => {
+            tracker.record_failure(email, client_ip).await
+        }
+        Err(_) => Ok(()),
+    };
+    if let Err(e) = tracked {
+        tracing::error!(error = %e, "Failed to record login attempt");
+    }
+
+    result
+}
+
+async fn signin(
+    state: &AppState,
+    ctx: &RequestContext,
+    payload: &SigninRequest,
+) -> AppResult<SigninResponse> {
+    let email = Email::new(payload.email.clone())?;
+    let password = Password::existing(payload.password.clone());
+    let session = tracked_signin(state, ctx, &email, &password).await?;
+
+    signin_response(state, session).await
 }
 
 pub async fn signin_handler(
     state: Data<&AppState>,
+    ctx: &RequestContext,
     payload: Json<SigninRequest>,
 ) -> AppResult<SigninResponse> {
-    let email = Email::new(payload.email.clone())?;
-    let password = Password::new(payload.password.clone())?;
+    let result = signin(&state, ctx, &payload).await;
 
-    let token = state
-        .auth_service
-        .read()
-        .await
-        .signin(&email, &password)
-        .await?;
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::Signin,
+        result
+            .as_ref()
+            .ok()
+            .map(|signed_in| signed_in.session.user_id.clone()),
+        Some(payload.email.clone()),
+        &result,
+    )
+    .await;
 
-    Ok(SigninResponse { token })
+    result
 }
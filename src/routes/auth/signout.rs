@@ -1,11 +1,45 @@
-use poem::web::Data;
+use poem::web::{Data, cookie::CookieJar};
 
 use crate::{
-    domain::error::app_error::AppResult, routes::auth::guard::AuthenticatedUser, state::AppState,
+    domain::{error::app_error::AppResult, types::audit::AuthAuditAction},
+    routes::auth::{
+        audit::record_auth_event, guard::AuthenticatedUser, session_cookie::clear_session_cookies,
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
 };
 
-pub async fn signout_handler(state: Data<&AppState>, auth: AuthenticatedUser) -> AppResult<()> {
-    state.auth_service.read().await.signout(&auth.token).await?;
+pub async fn signout_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    cookies: &CookieJar,
+    auth: AuthenticatedUser,
+) -> AppResult<()> {
+    let result = state.auth_service.read().await.signout(&auth.token).await;
+    if auth.from_cookie {
+        clear_session_cookies(cookies);
+    }
+    if result.is_ok()
+        && let Some(session_id) = &auth.session_id
+        && let Err(e) = state
+            .session_activity_tracker
+            .read()
+            .await
+            .remove(session_id)
+            .await
+    {
+        tracing::error!(error = %e, "Failed to remove session activity");
+    }
 
-    Ok(())
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::Signout,
+        Some(auth.user_id.clone()),
+        None,
+        &result,
+    )
+    .await;
+
+    result
 }
@@ -26,7 +26,11 @@ pub async fn signup_handler(
     payload: Json<SignupRequest>,
 ) -> AppResult<SignupResponse> {
     let email = Email::new(payload.email.clone())?;
-    let password = Password::new(payload.password.clone())?;
+    let password = Password::new(
+        payload.password.clone(),
+        &state.password_policy,
+        Some(&email),
+    )?;
 
     let redirect = payload.redirect_to.clone();
 
@@ -0,0 +1,245 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, AuthError, ValidationError},
+        types::{
+            audit::AuthAuditAction,
+            email::Email,
+            npi::Npi,
+            password::Password,
+            phone_number::PhoneNumber,
+            profile::{ProfileMetadata, UserProfile, UserUpdate},
+        },
+    },
+    routes::auth::{audit::record_auth_event, guard::AuthenticatedUser},
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+const MAX_DISPLAY_NAME_CHARS: usize = 100;
+const MAX_CREDENTIALS: usize = 10;
+const MAX_CREDENTIAL_CHARS: usize = 20;
+
+/// Omitted fields are left alone; an empty string clears a profile field.
+#[derive(Object, Debug)]
+pub struct UpdateMeRequest {
+    pub display_name: Option<String>,
+    /// Replaces the list, e.g. `["LCSW", "PhD"]`.
+    pub credentials: Option<Vec<String>>,
+    pub npi: Option<String>,
+    pub phone: Option<String>,
+    /// Takes effect once the confirmation link Supabase emails is followed.
+    pub email: Option<String>,
+    pub password: Option<String>,
+    /// Required to change `email` or `password`.
+    pub current_password: Option<String>,
+    /// Where the email change confirmation link lands.
+    pub redirect_to: Option<String>,
+}
+
+impl UpdateMeRequest {
+    fn touches_metadata(&self) -> bool {
+        self.display_name.is_some()
+            || self.credentials.is_some()
+            || self.npi.is_some()
+            || self.phone.is_some()
+    }
+}
+
+/// Trimmed, with an empty value meaning "clear the field".
+fn non_empty(value: &str) -> Option<&str> {
+    Some(value.trim()).filter(|v| !v.is_empty())
+}
+
+fn display_name(value: &str) -> AppResult<Option<String>> {
+    let Some(name) = non_empty(value) else {
+        return Ok(None);
+    };
+    if name.chars().count() > MAX_DISPLAY_NAME_CHARS || name.chars().any(char::is_control) {
+        return Err(ValidationError::InvalidInput(format!(
+            "display_name must be at most {MAX_DISPLAY_NAME_CHARS} characters"
+        ))
+        .into());
+    }
+    Ok(Some(name.to_string()))
+}
+
+fn credentials(values: &[String]) -> AppResult<Vec<String>> {
+    let mut credentials: Vec<String> = Vec::new();
+    for value in values {
+        let Some(credential) = non_empty(value) else {
+            continue;
+        };
+        if credential.chars().count() > MAX_CREDENTIAL_CHARS
+            || !credential
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '.' | '/' | ' '))
+        {
+            return Err(
+                ValidationError::InvalidInput(format!("Invalid credential: {credential}")).into(),
+            );
+        }
+        if !credentials
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(credential))
+        {
+            credentials.push(credential.to_string());
+        }
+    }
+    if credentials.len() > MAX_CREDENTIALS {
+        return Err(ValidationError::InvalidInput(format!(
+            "At most {MAX_CREDENTIALS} credentials are allowed"
+        ))
+        .into());
+    }
+    Ok(credentials)
+}
+
+/// The stored metadata with the request's changes applied.
+fn merged_metadata(
+    current: &ProfileMetadata,
+    payload: &UpdateMeRequest,
+) -> AppResult<ProfileMetadata> {
+    let mut metadata = current.clone();
+    if let Some(value) = &payload.display_name {
+        metadata.display_name = display_name(value)?;
+    }
+    if let Some(values) = &payload.credentials {
+        metadata.credentials = credentials(values)?;
+    }
+    if let Some(value) = &payload.npi {
+        metadata.npi = non_empty(value)
+            .map(|npi| Npi::new(npi.to_string()))
+            .transpose()?
+            .map(|npi| npi.as_ref().to_string());
+    }
+    if let Some(value) = &payload.phone {
+        metadata.phone = non_empty(value)
+            .map(|phone| PhoneNumber::new(phone.to_string()))
+            .transpose()?
+            .map(|phone| phone.as_ref().to_string());
+    }
+    Ok(metadata)
+}
+
+/// Checks `current_password` against the caller's account, throttled like a signin so it
+/// can't be used to guess passwords from a stolen session.
+async fn verify_current_password(
+    state: &AppState,
+    ctx: &RequestContext,
+    email: &str,
+    current_password: Option<&str>,
+) -> AppResult<()> {
+    let current_password = current_password.ok_or_else(|| {
+        ValidationError::InvalidInput(
+            "current_password is required to change email or password".to_string(),
+        )
+    })?;
+    let email = Email::new(email.to_string())?;
+    let password = Password::existing(current_password.to_string());
+    let client_ip = ctx.client_ip.as_deref();
+    let tracker = state.login_attempt_tracker.read().await;
+
+    tracker.check(&email, client_ip).await?;
+
+    let result = state
+        .auth_service
+        .read()
+        .await
+        .verify_password(&email, &password)
+        .await;
+
+    let tracked = match &result {
+        Ok(()) => tracker.record_success(&email).await,
+        Err(AppError::Auth(AuthError::InvalidCurrentPassword)) => {
+            tracker.record_failure(&email, client_ip).await
+        }
+        Err(_) => Ok(()),
+    };
+    if let Err(e) = tracked {
+        tracing::error!(error = %e, "Failed to record login attempt");
+    }
+
+    result
+}
+
+async fn update_me(
+    state: &AppState,
+    ctx: &RequestContext,
+    auth: &AuthenticatedUser,
+    payload: &UpdateMeRequest,
+) -> AppResult<UserProfile> {
+    let current = state
+        .auth_service
+        .read()
+        .await
+        .get_user(&auth.token)
+        .await?;
+
+    let email = payload.email.clone().map(Email::new).transpose()?;
+    // Checked against the address the account will have once the update lands.
+    let policy_email = match &email {
+        Some(email) => Some(email.clone()),
+        None => Email::new(current.email.clone()).ok(),
+    };
+    let update = UserUpdate {
+        password: payload
+            .password
+            .clone()
+            .map(|password| Password::new(password, &state.password_policy, policy_email.as_ref()))
+            .transpose()?,
+        email,
+        metadata: payload
+            .touches_metadata()
+            .then(|| merged_metadata(&current.metadata, payload))
+            .transpose()?,
+    };
+
+    if update.email.is_none() && update.password.is_none() && update.metadata.is_none() {
+        return Ok(current);
+    }
+    if update.email.is_some() || update.password.is_some() {
+        verify_current_password(
+            state,
+            ctx,
+            &current.email,
+            payload.current_password.as_deref(),
+        )
+        .await?;
+    }
+
+    state
+        .auth_service
+        .read()
+        .await
+        .update_user(
+            &auth.token,
+            &auth.user_id,
+            &update,
+            payload.redirect_to.as_deref(),
+        )
+        .await
+}
+
+pub async fn update_me_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    auth: AuthenticatedUser,
+    payload: Json<UpdateMeRequest>,
+) -> AppResult<UserProfile> {
+    let result = update_me(&state, ctx, &auth, &payload).await;
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::UpdateUser,
+        Some(auth.user_id.clone()),
+        Some(auth.user_id.clone()),
+        &result,
+    )
+    .await;
+
+    result
+}
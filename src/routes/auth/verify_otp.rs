@@ -0,0 +1,134 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, AuthError, ValidationError},
+        types::{audit::AuthAuditAction, email::Email, otp::OtpVerification, session::AuthSession},
+    },
+    routes::auth::{
+        audit::record_auth_event,
+        session_activity::discard_session,
+        signin::{SigninResponse, signin_response},
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Object, Debug)]
+pub struct VerifyOtpRequest {
+    /// Required with `token`, the six-digit code from the email.
+    pub email: Option<String>,
+    pub token: Option<String>,
+    /// The `token_hash` carried by the magic link, used instead of `email` and `token`.
+    pub token_hash: Option<String>,
+    /// Issue the session as HttpOnly cookies instead of returning the tokens.
+    #[oai(default)]
+    pub use_cookie: bool,
+}
+
+fn parse_verification(payload: &VerifyOtpRequest) -> AppResult<OtpVerification> {
+    match (&payload.token_hash, &payload.email, &payload.token) {
+        (Some(token_hash), None, None) => Ok(OtpVerification::TokenHash(token_hash.clone())),
+        (None, Some(email), Some(code)) => Ok(OtpVerification::Code {
+            email: Email::new(email.clone())?,
+            code: code.trim().to_string(),
+        }),
+        _ => Err(ValidationError::InvalidInput(
+            "Provide either token_hash, or email and token".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Refuses the new session if any of the user's practices has turned passwordless signin
+/// off, or if that can't be checked.
+async fn enforce_practice_policy(state: &AppState, session: &AuthSession) -> AppResult<()> {
+    let allowed = state
+        .practice_service
+        .read()
+        .await
+        .passwordless_signin_allowed(&session.access_token)
+        .await;
+
+    match allowed {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            discard_session(state, session).await;
+            Err(AuthError::PasswordlessSigninDisabled.into())
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Could not check passwordless signin policy");
+            discard_session(state, session).await;
+            Err(e)
+        }
+    }
+}
+
+async fn verify_otp(
+    state: &AppState,
+    ctx: &RequestContext,
+    verification: &OtpVerification,
+) -> AppResult<SigninResponse> {
+    // Guessing six-digit codes is throttled like guessing passwords.
+    let email = match verification {
+        OtpVerification::Code { email, .. } => Some(email),
+        OtpVerification::TokenHash(_) => None,
+    };
+    let client_ip = ctx.client_ip.as_deref();
+    let tracker = state.login_attempt_tracker.read().await;
+
+    if let Some(email) = email {
+        tracker.check(email, client_ip).await?;
+    }
+
+    let result = state
+        .auth_service
+        .read()
+        .await
+        .verify_otp(verification)
+        .await;
+
+    if let Some(email) = email {
+        let tracked = match &result {
+            Ok(_) => tracker.record_success(email).await,
+            Err(AppError::Auth(AuthError::InvalidOtp)) => {
+                tracker.record_failure(email, client_ip).await
+            }
+            Err(_) => Ok(()),
+        };
+        if let Err(e) = tracked {
+            tracing::error!(error = %e, "Failed to record login attempt");
+        }
+    }
+
+    let session = result?;
+    enforce_practice_policy(state, &session).await?;
+    signin_response(state, session).await
+}
+
+pub async fn verify_otp_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    payload: Json<VerifyOtpRequest>,
+) -> AppResult<SigninResponse> {
+    let result = match parse_verification(&payload) {
+        Ok(verification) => verify_otp(&state, ctx, &verification).await,
+        Err(e) => Err(e),
+    };
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::Signin,
+        result
+            .as_ref()
+            .ok()
+            .map(|signed_in| signed_in.session.user_id.clone()),
+        payload.email.clone(),
+        &result,
+    )
+    .await;
+
+    result
+}
@@ -0,0 +1,59 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::client::{Client, ClientAssignment},
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+/// Replaces the whole assignment; omitted fields are unassigned.
+#[derive(Object, Debug)]
+pub struct AssignClientRequest {
+    /// An active clinician or clinical supervisor of the practice.
+    pub primary_clinician_id: Option<String>,
+    /// An active clinical supervisor of the practice.
+    pub supervising_clinician_id: Option<String>,
+    /// A team of the practice; its members share the client.
+    pub team_id: Option<String>,
+}
+
+pub async fn assign_client_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    client_id: &str,
+    payload: Json<AssignClientRequest>,
+) -> AppResult<Client> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let client_id = uuid_param(client_id, "client_id")?;
+    let assignment = ClientAssignment {
+        primary_clinician_id: payload
+            .primary_clinician_id
+            .as_deref()
+            .map(|id| uuid_param(id, "primary_clinician_id"))
+            .transpose()?,
+        supervising_clinician_id: payload
+            .supervising_clinician_id
+            .as_deref()
+            .map(|id| uuid_param(id, "supervising_clinician_id"))
+            .transpose()?,
+        team_id: payload
+            .team_id
+            .as_deref()
+            .map(|id| uuid_param(id, "team_id"))
+            .transpose()?,
+    };
+
+    let client = state
+        .client_service
+        .read()
+        .await
+        .assign_client(&auth.token, &practice_id, &client_id, &assignment)
+        .await?;
+
+    Ok(client)
+}
@@ -0,0 +1,86 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::{
+            client::{Client, ClientName, Mrn, NewClient},
+            date_of_birth::DateOfBirth,
+        },
+    },
+    routes::{auth::guard::AuthenticatedUser, clients::demographics, params::uuid_param},
+    state::AppState,
+};
+
+/// Blank optional fields are treated as omitted. An address needs line 1, city, state and
+/// postal code; an emergency contact needs a name and phone.
+#[derive(Object, Debug)]
+pub struct CreateClientRequest {
+    /// Assigned by the practice when omitted.
+    pub mrn: Option<String>,
+    pub first_name: String,
+    pub last_name: String,
+    pub preferred_name: Option<String>,
+    /// `YYYY-MM-DD`.
+    pub date_of_birth: String,
+    pub phone: Option<String>,
+    pub address_line1: Option<String>,
+    pub address_line2: Option<String>,
+    pub city: Option<String>,
+    /// Two-letter USPS code.
+    pub state: Option<String>,
+    /// ZIP or ZIP+4.
+    pub postal_code: Option<String>,
+    pub emergency_contact_name: Option<String>,
+    pub emergency_contact_relationship: Option<String>,
+    pub emergency_contact_phone: Option<String>,
+}
+
+pub async fn create_client_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<CreateClientRequest>,
+) -> AppResult<Client> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let payload = payload.0;
+
+    let client = NewClient {
+        mrn: payload
+            .mrn
+            .filter(|mrn| !mrn.trim().is_empty())
+            .map(Mrn::new)
+            .transpose()?,
+        first_name: ClientName::new(payload.first_name)?,
+        last_name: ClientName::new(payload.last_name)?,
+        preferred_name: payload
+            .preferred_name
+            .filter(|name| !name.trim().is_empty())
+            .map(ClientName::new)
+            .transpose()?,
+        date_of_birth: DateOfBirth::new(payload.date_of_birth)?,
+        phone: demographics::phone(payload.phone.as_ref())?,
+        address: demographics::address(
+            payload.address_line1.as_ref(),
+            payload.address_line2.as_ref(),
+            payload.city.as_ref(),
+            payload.state.as_ref(),
+            payload.postal_code.as_ref(),
+        )?,
+        emergency_contact: demographics::emergency_contact(
+            payload.emergency_contact_name.as_ref(),
+            payload.emergency_contact_relationship.as_ref(),
+            payload.emergency_contact_phone.as_ref(),
+        )?,
+    };
+
+    let client = state
+        .client_service
+        .read()
+        .await
+        .create_client(&auth.token, &practice_id, &client)
+        .await?;
+
+    Ok(client)
+}
@@ -0,0 +1,24 @@
+use poem::web::Data;
+
+use crate::{
+    domain::error::app_error::AppResult,
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn delete_client_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    client_id: &str,
+) -> AppResult<()> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let client_id = uuid_param(client_id, "client_id")?;
+
+    state
+        .client_service
+        .read()
+        .await
+        .delete_client(&auth.token, &practice_id, &client_id)
+        .await
+}
@@ -0,0 +1,63 @@
+use crate::domain::{
+    error::app_error::AppResult,
+    types::{
+        emergency_contact::EmergencyContact, phone_number::PhoneNumber,
+        postal_address::PostalAddress,
+    },
+};
+
+/// Trimmed, with an empty value meaning "not given".
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+pub fn phone(value: Option<&String>) -> AppResult<Option<PhoneNumber>> {
+    non_empty(value).map(PhoneNumber::new).transpose()
+}
+
+/// `None` when every part is blank; otherwise the required parts must all be there.
+pub fn address(
+    line1: Option<&String>,
+    line2: Option<&String>,
+    city: Option<&String>,
+    state: Option<&String>,
+    postal_code: Option<&String>,
+) -> AppResult<Option<PostalAddress>> {
+    let parts = [line1, line2, city, state, postal_code].map(non_empty);
+    if parts.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+    let [line1, line2, city, state, postal_code] = parts;
+
+    PostalAddress::new(
+        line1.unwrap_or_default(),
+        line2,
+        city.unwrap_or_default(),
+        state.unwrap_or_default(),
+        postal_code.unwrap_or_default(),
+    )
+    .map(Some)
+}
+
+/// `None` when every part is blank; otherwise a name and phone are required.
+pub fn emergency_contact(
+    name: Option<&String>,
+    relationship: Option<&String>,
+    phone: Option<&String>,
+) -> AppResult<Option<EmergencyContact>> {
+    let parts = [name, relationship, phone].map(non_empty);
+    if parts.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+    let [name, relationship, phone] = parts;
+
+    EmergencyContact::new(
+        name.unwrap_or_default(),
+        relationship,
+        phone.unwrap_or_default(),
+    )
+    .map(Some)
+}
@@ -0,0 +1,26 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::client::Client},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn get_client_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    client_id: &str,
+) -> AppResult<Client> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let client_id = uuid_param(client_id, "client_id")?;
+
+    let client = state
+        .client_service
+        .read()
+        .await
+        .get_client(&auth.token, &practice_id, &client_id)
+        .await?;
+
+    Ok(client)
+}
@@ -0,0 +1,7 @@
+pub mod assign_client;
+pub mod create_client;
+pub mod delete_client;
+pub mod demographics;
+pub mod get_client;
+pub mod search_clients;
+pub mod update_client;
@@ -0,0 +1,72 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, ValidationError},
+        types::{
+            client::{Client, ClientSearch, ClientStatus, Mrn},
+            date_of_birth::DateOfBirth,
+        },
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+const MAX_NAME_QUERY_CHARS: usize = 100;
+
+/// Raw query string filters, validated into a [`ClientSearch`] by the handler.
+#[derive(Debug, Default)]
+pub struct ClientSearchQuery {
+    pub name: Option<String>,
+    pub date_of_birth: Option<String>,
+    pub mrn: Option<String>,
+    pub status: Option<String>,
+}
+
+impl ClientSearchQuery {
+    fn into_search(self) -> AppResult<ClientSearch> {
+        let name = self
+            .name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+        if name
+            .as_ref()
+            .is_some_and(|name| name.chars().count() > MAX_NAME_QUERY_CHARS)
+        {
+            return Err(ValidationError::InvalidInput(format!(
+                "name must be at most {MAX_NAME_QUERY_CHARS} characters"
+            ))
+            .into());
+        }
+
+        Ok(ClientSearch {
+            name,
+            date_of_birth: self.date_of_birth.map(DateOfBirth::new).transpose()?,
+            mrn: self.mrn.map(Mrn::new).transpose()?,
+            status: self
+                .status
+                .as_deref()
+                .map(ClientStatus::parse)
+                .transpose()?,
+        })
+    }
+}
+
+pub async fn search_clients_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    query: ClientSearchQuery,
+) -> AppResult<Vec<Client>> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let search = query.into_search()?;
+
+    let clients = state
+        .client_service
+        .read()
+        .await
+        .search_clients(&auth.token, &practice_id, &search)
+        .await?;
+
+    Ok(clients)
+}
@@ -0,0 +1,117 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::{
+            client::{Client, ClientName, ClientStatus, ClientUpdate, Mrn},
+            date_of_birth::DateOfBirth,
+        },
+    },
+    routes::{auth::guard::AuthenticatedUser, clients::demographics, params::uuid_param},
+    state::AppState,
+};
+
+/// Omitted fields are left alone; an empty string clears an optional one. The address and
+/// the emergency contact are each replaced as a whole when any of their fields is sent.
+#[derive(Object, Debug)]
+pub struct UpdateClientRequest {
+    pub mrn: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub preferred_name: Option<String>,
+    /// `YYYY-MM-DD`.
+    pub date_of_birth: Option<String>,
+    pub phone: Option<String>,
+    pub address_line1: Option<String>,
+    pub address_line2: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub postal_code: Option<String>,
+    pub emergency_contact_name: Option<String>,
+    pub emergency_contact_relationship: Option<String>,
+    pub emergency_contact_phone: Option<String>,
+    /// `active` or `discharged`.
+    pub status: Option<String>,
+}
+
+impl UpdateClientRequest {
+    fn into_update(self) -> AppResult<ClientUpdate> {
+        let touches_address = self.address_line1.is_some()
+            || self.address_line2.is_some()
+            || self.city.is_some()
+            || self.state.is_some()
+            || self.postal_code.is_some();
+        let touches_emergency_contact = self.emergency_contact_name.is_some()
+            || self.emergency_contact_relationship.is_some()
+            || self.emergency_contact_phone.is_some();
+
+        Ok(ClientUpdate {
+            mrn: self.mrn.map(Mrn::new).transpose()?,
+            first_name: self.first_name.map(ClientName::new).transpose()?,
+            last_name: self.last_name.map(ClientName::new).transpose()?,
+            preferred_name: self
+                .preferred_name
+                .map(|name| {
+                    Some(name)
+                        .filter(|name| !name.trim().is_empty())
+                        .map(ClientName::new)
+                        .transpose()
+                })
+                .transpose()?,
+            date_of_birth: self.date_of_birth.map(DateOfBirth::new).transpose()?,
+            phone: self
+                .phone
+                .as_ref()
+                .map(|phone| demographics::phone(Some(phone)))
+                .transpose()?,
+            address: touches_address
+                .then(|| {
+                    demographics::address(
+                        self.address_line1.as_ref(),
+                        self.address_line2.as_ref(),
+                        self.city.as_ref(),
+                        self.state.as_ref(),
+                        self.postal_code.as_ref(),
+                    )
+                })
+                .transpose()?,
+            emergency_contact: touches_emergency_contact
+                .then(|| {
+                    demographics::emergency_contact(
+                        self.emergency_contact_name.as_ref(),
+                        self.emergency_contact_relationship.as_ref(),
+                        self.emergency_contact_phone.as_ref(),
+                    )
+                })
+                .transpose()?,
+            status: self
+                .status
+                .as_deref()
+                .map(ClientStatus::parse)
+                .transpose()?,
+        })
+    }
+}
+
+pub async fn update_client_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    client_id: &str,
+    payload: Json<UpdateClientRequest>,
+) -> AppResult<Client> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let client_id = uuid_param(client_id, "client_id")?;
+    let update = payload.0.into_update()?;
+
+    let client = state
+        .client_service
+        .read()
+        .await
+        .update_client(&auth.token, &practice_id, &client_id, &update)
+        .await?;
+
+    Ok(client)
+}
@@ -0,0 +1,101 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use hmac::{Hmac, Mac};
+use poem::{http::HeaderMap, web::Data};
+use secrecy::ExposeSecret;
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, SmsError},
+        types::{mfa::sms_code_message, phone_number::PhoneNumber},
+    },
+    state::AppState,
+    utils::clock::unix_now,
+};
+
+/// How far a hook call's timestamp may drift from ours before it is treated as a replay.
+const TIMESTAMP_TOLERANCE_SECS: i64 = 300;
+
+#[derive(Deserialize)]
+struct SendSmsPayload {
+    user: HookUser,
+    sms: HookSms,
+}
+
+#[derive(Deserialize)]
+struct HookUser {
+    phone: String,
+}
+
+#[derive(Deserialize)]
+struct HookSms {
+    otp: String,
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> AppResult<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| SmsError::InvalidHookSignature.into())
+}
+
+/// Checks a Standard Webhooks signature: HMAC-SHA256 over `{id}.{timestamp}.{body}`, keyed
+/// with the base64 part of a `v1,whsec_...` secret.
+fn verify_signature(secret: &str, headers: &HeaderMap, body: &[u8]) -> AppResult<()> {
+    let id = header(headers, "webhook-id")?;
+    let timestamp = header(headers, "webhook-timestamp")?;
+    let signatures = header(headers, "webhook-signature")?;
+
+    let sent_at: i64 = timestamp
+        .parse()
+        .map_err(|_| SmsError::InvalidHookSignature)?;
+    if (unix_now() - sent_at).abs() > TIMESTAMP_TOLERANCE_SECS {
+        return Err(SmsError::InvalidHookSignature.into());
+    }
+
+    let encoded = secret.strip_prefix("v1,").unwrap_or(secret);
+    let encoded = encoded.strip_prefix("whsec_").unwrap_or(encoded);
+    let key = STANDARD
+        .decode(encoded)
+        .ok()
+        .filter(|key| !key.is_empty())
+        .ok_or(SmsError::InvalidHookSignature)?;
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(&key).map_err(|_| SmsError::InvalidHookSignature)?;
+    mac.update(format!("{id}.{timestamp}.").as_bytes());
+    mac.update(body);
+
+    // The header may carry several space-separated signatures during secret rotation.
+    let valid = signatures
+        .split(' ')
+        .filter_map(|sig| sig.strip_prefix("v1,"))
+        .filter_map(|sig| STANDARD.decode(sig).ok())
+        .any(|sig| mac.clone().verify_slice(&sig).is_ok());
+    if !valid {
+        return Err(SmsError::InvalidHookSignature.into());
+    }
+    Ok(())
+}
+
+/// Supabase Auth's Send SMS hook: texts the code it generated through our gateway.
+pub async fn send_sms_hook_handler(
+    state: Data<&AppState>,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> AppResult<()> {
+    verify_signature(state.send_sms_hook_secret.expose_secret(), headers, body)?;
+
+    let payload: SendSmsPayload = serde_json::from_slice(body)
+        .map_err(|e| SmsError::SendError(format!("Malformed hook payload: {e}")))?;
+    // Supabase stores numbers without the leading `+`.
+    let phone = PhoneNumber::new(format!("+{}", payload.user.phone.trim_start_matches('+')))?;
+
+    state
+        .sms_gateway
+        .read()
+        .await
+        .send(&phone, &sms_code_message(&payload.sms.otp))
+        .await
+}
@@ -0,0 +1,97 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, AuthError},
+        types::{email::Email, invitation::InviteToken, password::Password, session::AuthSession},
+    },
+    routes::auth::signin::tracked_signin,
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Object, Debug)]
+pub struct AcceptInvitationRequest {
+    pub token: String,
+    /// New password for first-time users, or the current one for existing accounts.
+    pub password: String,
+    /// Issue the session as HttpOnly cookies instead of returning the tokens.
+    #[oai(default)]
+    pub use_cookie: bool,
+}
+
+#[derive(Debug)]
+pub struct AcceptInvitationResponse {
+    pub practice_id: String,
+    pub session: AuthSession,
+}
+
+pub async fn accept_invitation_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    payload: Json<AcceptInvitationRequest>,
+) -> AppResult<AcceptInvitationResponse> {
+    let invite_token = InviteToken::new(payload.token.clone())?;
+    let token_hash = invite_token.hash();
+
+    let preview = state
+        .invitation_service
+        .read()
+        .await
+        .find_invitation(&token_hash)
+        .await?;
+    preview.ensure_pending()?;
+
+    let email = Email::new(preview.email.clone())?;
+    let account_exists = match state
+        .auth_service
+        .read()
+        .await
+        .retrieve_user_id(&email)
+        .await
+    {
+        Ok(_) => true,
+        Err(AppError::Auth(AuthError::UserNotFound)) => false,
+        Err(e) => return Err(e),
+    };
+
+    // The link arrived at this address, so a new account can start out confirmed. Existing
+    // accounts prove ownership by signing in with their current password instead, which
+    // the policy for new passwords doesn't apply to.
+    let password = if account_exists {
+        Password::existing(payload.password.clone())
+    } else {
+        let password = Password::new(
+            payload.password.clone(),
+            &state.password_policy,
+            Some(&email),
+        )?;
+        match state
+            .auth_service
+            .read()
+            .await
+            .create_confirmed_user(&email, &password)
+            .await
+        {
+            Ok(()) | Err(AppError::Auth(AuthError::EmailAlreadyInUse)) => {}
+            Err(e) => return Err(e),
+        }
+        password
+    };
+
+    // Guessing an existing account's password here is throttled like signin.
+    let session = tracked_signin(&state, ctx, &email, &password).await?;
+
+    let invitation = state
+        .invitation_service
+        .read()
+        .await
+        .accept_invitation(&session.access_token, &token_hash)
+        .await?;
+
+    Ok(AcceptInvitationResponse {
+        practice_id: invitation.practice_id,
+        session,
+    })
+}
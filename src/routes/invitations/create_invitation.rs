@@ -0,0 +1,67 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, ValidationError},
+        types::{
+            email::Email,
+            invitation::{Invitation, InviteToken},
+            practice_role::PracticeRole,
+        },
+    },
+    routes::{
+        auth::guard::AuthenticatedUser, invitations::email::send_invitation_email,
+        params::uuid_param,
+    },
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct CreateInvitationRequest {
+    pub email: String,
+    /// Role codes the invitee receives on acceptance, e.g. `["biller"]`.
+    pub roles: Vec<String>,
+}
+
+pub async fn create_invitation_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<CreateInvitationRequest>,
+) -> AppResult<Invitation> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let email = Email::new(payload.email.trim().to_ascii_lowercase())?;
+
+    let mut roles = Vec::with_capacity(payload.roles.len());
+    for code in &payload.roles {
+        let role = PracticeRole::parse(code)?;
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+    }
+    if roles.is_empty() {
+        return Err(
+            ValidationError::InvalidInput("At least one role is required".to_string()).into(),
+        );
+    }
+
+    let invite_token = InviteToken::generate();
+
+    let invitation = state
+        .invitation_service
+        .read()
+        .await
+        .create_invitation(
+            &auth.token,
+            &practice_id,
+            &email,
+            &roles,
+            &invite_token.hash(),
+        )
+        .await?;
+
+    send_invitation_email(&state, &auth.token, &invitation, &invite_token).await?;
+
+    Ok(invitation)
+}
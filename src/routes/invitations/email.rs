@@ -0,0 +1,61 @@
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::{
+            email::Email,
+            invitation::{Invitation, InviteToken},
+        },
+    },
+    state::AppState,
+};
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Emails the accept link for `invitation`; the plaintext token only ever lives in this link.
+pub async fn send_invitation_email(
+    state: &AppState,
+    token: &str,
+    invitation: &Invitation,
+    invite_token: &InviteToken,
+) -> AppResult<()> {
+    let practice = state
+        .practice_service
+        .read()
+        .await
+        .get_practice(token, &invitation.practice_id)
+        .await?;
+
+    let link = format!(
+        "{}/accept-invite?token={}",
+        state.public_url,
+        invite_token.as_ref()
+    );
+    let roles = invitation
+        .roles
+        .iter()
+        .map(|role| role.as_str().replace('_', " "))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let practice_name = escape_html(&practice.name);
+
+    let subject = format!("You're invited to join {} on Breeze EHR", practice.name);
+    let body = format!(
+        "<p>You've been invited to join <strong>{practice_name}</strong> on Breeze EHR as: \
+         {roles}.</p>\
+         <p><a href=\"{link}\">Accept your invitation</a></p>\
+         <p>This link can be used once and expires in 7 days.</p>"
+    );
+
+    state
+        .email_sender
+        .read()
+        .await
+        .send(&Email::new(invitation.email.clone())?, &subject, &body)
+        .await
+}
@@ -0,0 +1,24 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::invitation::Invitation},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn list_invitations_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+) -> AppResult<Vec<Invitation>> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+
+    let invitations = state
+        .invitation_service
+        .read()
+        .await
+        .list_invitations(&auth.token, &practice_id)
+        .await?;
+
+    Ok(invitations)
+}
@@ -0,0 +1,7 @@
+pub mod accept_invitation;
+pub mod create_invitation;
+pub mod email;
+pub mod list_invitations;
+pub mod preview_invitation;
+pub mod resend_invitation;
+pub mod revoke_invitation;
@@ -0,0 +1,31 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::invitation::{InvitationPreview, InviteToken},
+    },
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct PreviewInvitationRequest {
+    pub token: String,
+}
+
+pub async fn preview_invitation_handler(
+    state: Data<&AppState>,
+    payload: Json<PreviewInvitationRequest>,
+) -> AppResult<InvitationPreview> {
+    let invite_token = InviteToken::new(payload.token.clone())?;
+
+    let invitation = state
+        .invitation_service
+        .read()
+        .await
+        .find_invitation(&invite_token.hash())
+        .await?;
+
+    Ok(invitation)
+}
@@ -0,0 +1,42 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::invitation::{Invitation, InviteToken},
+    },
+    routes::{
+        auth::guard::AuthenticatedUser, invitations::email::send_invitation_email,
+        params::uuid_param,
+    },
+    state::AppState,
+};
+
+pub async fn resend_invitation_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    invitation_id: &str,
+) -> AppResult<Invitation> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let invitation_id = uuid_param(invitation_id, "invitation_id")?;
+
+    // A fresh token means links from earlier emails stop working.
+    let invite_token = InviteToken::generate();
+
+    let invitation = state
+        .invitation_service
+        .read()
+        .await
+        .resend_invitation(
+            &auth.token,
+            &practice_id,
+            &invitation_id,
+            &invite_token.hash(),
+        )
+        .await?;
+
+    send_invitation_email(&state, &auth.token, &invitation, &invite_token).await?;
+
+    Ok(invitation)
+}
@@ -0,0 +1,26 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::invitation::Invitation},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn revoke_invitation_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    invitation_id: &str,
+) -> AppResult<Invitation> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let invitation_id = uuid_param(invitation_id, "invitation_id")?;
+
+    let invitation = state
+        .invitation_service
+        .read()
+        .await
+        .revoke_invitation(&auth.token, &practice_id, &invitation_id)
+        .await?;
+
+    Ok(invitation)
+}
@@ -0,0 +1,37 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::{membership::PracticeMember, practice_role::PracticeRole},
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct AddMemberRoleRequest {
+    pub role: String,
+}
+
+pub async fn add_member_role_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    membership_id: &str,
+    payload: Json<AddMemberRoleRequest>,
+) -> AppResult<PracticeMember> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let membership_id = uuid_param(membership_id, "membership_id")?;
+    let role = PracticeRole::parse(&payload.role)?;
+
+    let member = state
+        .membership_service
+        .read()
+        .await
+        .add_role(&auth.token, &practice_id, &membership_id, role)
+        .await?;
+
+    Ok(member)
+}
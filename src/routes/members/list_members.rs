@@ -0,0 +1,24 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::membership::PracticeMember},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn list_members_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+) -> AppResult<Vec<PracticeMember>> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+
+    let members = state
+        .membership_service
+        .read()
+        .await
+        .list_members(&auth.token, &practice_id)
+        .await?;
+
+    Ok(members)
+}
@@ -0,0 +1,5 @@
+pub mod add_member_role;
+pub mod list_members;
+pub mod remove_member_role;
+pub mod revoke_member_sessions;
+pub mod update_membership;
@@ -0,0 +1,31 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::{membership::PracticeMember, practice_role::PracticeRole},
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn remove_member_role_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    membership_id: &str,
+    role: &str,
+) -> AppResult<PracticeMember> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let membership_id = uuid_param(membership_id, "membership_id")?;
+    let role = PracticeRole::parse(role)?;
+
+    let member = state
+        .membership_service
+        .read()
+        .await
+        .remove_role(&auth.token, &practice_id, &membership_id, role)
+        .await?;
+
+    Ok(member)
+}
@@ -0,0 +1,79 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, MembershipError},
+        types::{audit::AuthAuditAction, practice_role::PracticeRole},
+    },
+    routes::{
+        auth::{
+            audit::record_auth_event,
+            authorization::{OwnerOrAdmin, RequirePracticeRole},
+            session_activity::revoke_sessions,
+        },
+        params::uuid_param,
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+/// The member's user id, once the caller is allowed to sign them out: owners may sign out
+/// anyone in the practice, admins anyone but an owner.
+async fn authorize(
+    state: &AppState,
+    access: &RequirePracticeRole<OwnerOrAdmin>,
+    practice_id: &str,
+    membership_id: &str,
+) -> AppResult<String> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let membership_id = uuid_param(membership_id, "membership_id")?;
+
+    let member = state
+        .membership_service
+        .read()
+        .await
+        .list_members(&access.roles.user.token, &practice_id)
+        .await?
+        .into_iter()
+        .find(|m| m.membership_id == membership_id)
+        .ok_or(MembershipError::MembershipNotFound)?;
+
+    if member.has_role(PracticeRole::Owner)
+        && !access
+            .roles
+            .has_any_in(&practice_id, &[PracticeRole::Owner])
+    {
+        return Err(MembershipError::CannotManageMembership.into());
+    }
+
+    Ok(member.user_id)
+}
+
+/// Ends every session of a practice member, e.g. when their account may be compromised.
+pub async fn revoke_member_sessions_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    access: RequirePracticeRole<OwnerOrAdmin>,
+    practice_id: &str,
+    membership_id: &str,
+) -> AppResult<()> {
+    let (target, result) = match authorize(&state, &access, practice_id, membership_id).await {
+        Ok(user_id) => {
+            let result = revoke_sessions(&state, &user_id, None).await;
+            (user_id, result)
+        }
+        Err(e) => (membership_id.to_string(), Err(e)),
+    };
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::RevokeSessions,
+        Some(access.roles.user.user_id.clone()),
+        Some(target),
+        &result,
+    )
+    .await;
+
+    result
+}
@@ -0,0 +1,33 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::membership::PracticeMember},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct UpdateMembershipRequest {
+    pub is_active: bool,
+}
+
+pub async fn update_membership_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    membership_id: &str,
+    payload: Json<UpdateMembershipRequest>,
+) -> AppResult<PracticeMember> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let membership_id = uuid_param(membership_id, "membership_id")?;
+
+    let member = state
+        .membership_service
+        .read()
+        .await
+        .set_membership_active(&auth.token, &practice_id, &membership_id, payload.is_active)
+        .await?;
+
+    Ok(member)
+}
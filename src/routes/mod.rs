@@ -1 +1,12 @@
+pub mod appointments;
+pub mod audit;
 pub mod auth;
+pub mod clients;
+pub mod hooks;
+pub mod invitations;
+pub mod members;
+pub mod params;
+pub mod practices;
+pub mod rate_limit;
+pub mod sso;
+pub mod teams;
@@ -0,0 +1,8 @@
+use crate::domain::error::app_error::{AppResult, ValidationError};
+
+/// Path ids are Postgres uuids; reject anything else before it reaches PostgREST.
+pub fn uuid_param(value: &str, name: &str) -> AppResult<String> {
+    uuid::Uuid::parse_str(value)
+        .map(|id| id.to_string())
+        .map_err(|_| ValidationError::InvalidInput(format!("{name} must be a UUID")).into())
+}
@@ -0,0 +1,33 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::practice::{Practice, PracticeName},
+    },
+    routes::auth::guard::AuthenticatedUser,
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct CreatePracticeRequest {
+    pub name: String,
+}
+
+pub async fn create_practice_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    payload: Json<CreatePracticeRequest>,
+) -> AppResult<Practice> {
+    let name = PracticeName::new(payload.name.clone())?;
+
+    let practice = state
+        .practice_service
+        .read()
+        .await
+        .create_practice(&auth.token, &name)
+        .await?;
+
+    Ok(practice)
+}
@@ -0,0 +1,24 @@
+use poem::web::Data;
+
+use crate::{
+    domain::error::app_error::AppResult,
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn delete_practice_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+) -> AppResult<()> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+
+    state
+        .practice_service
+        .read()
+        .await
+        .delete_practice(&auth.token, &practice_id)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::practice::Practice},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn get_practice_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+) -> AppResult<Practice> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+
+    let practice = state
+        .practice_service
+        .read()
+        .await
+        .get_practice(&auth.token, &practice_id)
+        .await?;
+
+    Ok(practice)
+}
@@ -0,0 +1,21 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::practice::Practice},
+    routes::auth::guard::AuthenticatedUser,
+    state::AppState,
+};
+
+pub async fn list_practices_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+) -> AppResult<Vec<Practice>> {
+    let practices = state
+        .practice_service
+        .read()
+        .await
+        .list_practices(&auth.token)
+        .await?;
+
+    Ok(practices)
+}
@@ -0,0 +1,7 @@
+pub mod create_practice;
+pub mod delete_practice;
+pub mod get_practice;
+pub mod list_practices;
+pub mod rename_practice;
+pub mod set_idle_timeout;
+pub mod set_passwordless_signin;
@@ -0,0 +1,35 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::practice::{Practice, PracticeName},
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct RenamePracticeRequest {
+    pub name: String,
+}
+
+pub async fn rename_practice_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<RenamePracticeRequest>,
+) -> AppResult<Practice> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let name = PracticeName::new(payload.name.clone())?;
+
+    let practice = state
+        .practice_service
+        .read()
+        .await
+        .rename_practice(&auth.token, &practice_id, &name)
+        .await?;
+
+    Ok(practice)
+}
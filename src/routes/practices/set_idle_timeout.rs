@@ -0,0 +1,46 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, ValidationError},
+        types::practice::Practice,
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+const MIN_IDLE_TIMEOUT_SECS: i64 = 60;
+const MAX_IDLE_TIMEOUT_SECS: i64 = 86_400;
+
+#[derive(Object, Debug)]
+pub struct SetIdleTimeoutRequest {
+    /// Seconds of inactivity before members are signed out; null restores the default.
+    pub idle_timeout_secs: Option<i64>,
+}
+
+pub async fn set_idle_timeout_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<SetIdleTimeoutRequest>,
+) -> AppResult<Practice> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    if let Some(secs) = payload.idle_timeout_secs
+        && !(MIN_IDLE_TIMEOUT_SECS..=MAX_IDLE_TIMEOUT_SECS).contains(&secs)
+    {
+        return Err(ValidationError::InvalidInput(format!(
+            "idle_timeout_secs must be between {MIN_IDLE_TIMEOUT_SECS} and {MAX_IDLE_TIMEOUT_SECS}"
+        ))
+        .into());
+    }
+
+    let practice = state
+        .practice_service
+        .read()
+        .await
+        .set_idle_timeout(&auth.token, &practice_id, payload.idle_timeout_secs)
+        .await?;
+
+    Ok(practice)
+}
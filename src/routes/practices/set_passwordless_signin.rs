@@ -0,0 +1,32 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::practice::Practice},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct SetPasswordlessSigninRequest {
+    /// Whether members may sign in with a magic link or emailed code.
+    pub allowed: bool,
+}
+
+pub async fn set_passwordless_signin_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<SetPasswordlessSigninRequest>,
+) -> AppResult<Practice> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+
+    let practice = state
+        .practice_service
+        .read()
+        .await
+        .set_passwordless_signin(&auth.token, &practice_id, payload.allowed)
+        .await?;
+
+    Ok(practice)
+}
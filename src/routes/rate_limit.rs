@@ -0,0 +1,199 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use poem::{Endpoint, IntoResponse, Middleware, Request, Response, http::HeaderValue};
+
+use crate::{
+    domain::{
+        error::app_error::RateLimitError,
+        types::rate_limit::{RateLimitConfig, RateLimitPolicy},
+    },
+    routes::auth::{
+        guard::reject,
+        session_cookie::{ACCESS_COOKIE, cookie_value},
+    },
+    state::AppState,
+    utils::tracing::client_ip,
+};
+
+/// Past this many buckets, full ones are dropped; a full bucket is indistinguishable from a
+/// missing one.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, policy: &RateLimitPolicy, now: Instant) {
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * policy.refill_rate()).min(policy.limit as f64);
+        self.updated_at = now;
+    }
+
+    fn is_full(&self, policy: &RateLimitPolicy, now: Instant) -> bool {
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens + elapsed * policy.refill_rate() >= policy.limit as f64
+    }
+}
+
+struct Decision {
+    policy: RateLimitPolicy,
+    remaining: u64,
+    /// Seconds until the bucket is full again.
+    reset_secs: u64,
+    /// Set when the request was refused.
+    retry_after_secs: Option<u64>,
+}
+
+/// Token-bucket rate limiting for the API. Signed-in callers are counted by user id, everyone
+/// else by client IP, and each route policy keeps its own buckets. Counts live in this
+/// process only.
+#[derive(Clone)]
+pub struct RateLimit {
+    config: Arc<RateLimitConfig>,
+    /// Each bucket remembers its policy so pruning knows when it is full.
+    buckets: Arc<Mutex<HashMap<String, (Bucket, RateLimitPolicy)>>>,
+}
+
+impl RateLimit {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            buckets: Arc::default(),
+        }
+    }
+
+    fn take(&self, key: String, policy: RateLimitPolicy) -> Decision {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|_, (bucket, policy)| !bucket.is_full(policy, now));
+        }
+
+        let (bucket, _) = buckets.entry(key).or_insert_with(|| {
+            (
+                Bucket {
+                    tokens: policy.limit as f64,
+                    updated_at: now,
+                },
+                policy,
+            )
+        });
+        bucket.refill(&policy, now);
+
+        let rate = policy.refill_rate();
+        let retry_after_secs = if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(((1.0 - bucket.tokens) / rate).ceil().max(1.0) as u64)
+        };
+
+        Decision {
+            policy,
+            remaining: bucket.tokens.floor() as u64,
+            reset_secs: ((policy.limit as f64 - bucket.tokens) / rate).ceil() as u64,
+            retry_after_secs,
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for RateLimit {
+    type Output = RateLimitEndpoint<E>;
+
+    fn transform(&self, inner: E) -> Self::Output {
+        RateLimitEndpoint {
+            inner,
+            limiter: self.clone(),
+        }
+    }
+}
+
+pub struct RateLimitEndpoint<E> {
+    inner: E,
+    limiter: RateLimit,
+}
+
+/// `user:<id>` for a caller with a valid access token, otherwise `ip:<addr>`. Invalid tokens
+/// fall back to the IP so forged ones can't mint fresh buckets.
+async fn principal(req: &Request) -> (String, bool) {
+    let token = req
+        .headers()
+        .get(poem::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.strip_prefix("Bearer "))
+        .map(str::to_string)
+        .or_else(|| cookie_value(req.headers(), ACCESS_COOKIE));
+
+    if let (Some(token), Some(state)) = (token, req.data::<AppState>())
+        && let Ok(claims) = state.token_verifier.read().await.verify(&token).await
+    {
+        return (format!("user:{}", claims.sub), true);
+    }
+
+    let ip = client_ip(req).unwrap_or_else(|| "unknown".to_string());
+    (format!("ip:{ip}"), false)
+}
+
+fn set_headers(response: &mut Response, decision: &Decision) {
+    let headers = response.headers_mut();
+    let policy = &decision.policy;
+    for (name, value) in [
+        ("RateLimit-Limit", policy.limit.to_string()),
+        ("RateLimit-Remaining", decision.remaining.to_string()),
+        ("RateLimit-Reset", decision.reset_secs.to_string()),
+        (
+            "RateLimit-Policy",
+            format!("{};w={}", policy.limit, policy.window_secs),
+        ),
+    ] {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    }
+}
+
+impl<E: Endpoint> Endpoint for RateLimitEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> poem::Result<Self::Output> {
+        let config = &self.limiter.config;
+        if !config.enabled {
+            return self.inner.call(req).await.map(IntoResponse::into_response);
+        }
+
+        let (principal, authenticated) = principal(&req).await;
+        let path = req.original_uri().path();
+        let (scope, policy) = match config
+            .routes
+            .iter()
+            .enumerate()
+            .find(|(_, route)| route.matches(req.method(), path))
+        {
+            Some((index, route)) => (format!("route{index}"), route.policy),
+            None if authenticated => ("default".to_string(), config.authenticated),
+            None => ("default".to_string(), config.anonymous),
+        };
+
+        let decision = self.limiter.take(format!("{scope}:{principal}"), policy);
+
+        let mut response = match decision.retry_after_secs {
+            Some(retry_after_secs) => {
+                tracing::warn!(%principal, path, retry_after_secs, "Rate limit exceeded");
+                reject(&req, RateLimitError::TooManyRequests { retry_after_secs }).into_response()
+            }
+            None => match self.inner.call(req).await {
+                Ok(response) => response.into_response(),
+                Err(e) => e.into_response(),
+            },
+        };
+        set_headers(&mut response, &decision);
+        Ok(response)
+    }
+}
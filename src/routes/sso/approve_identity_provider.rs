@@ -0,0 +1,32 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        types::sso::IdentityProvider,
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+/// A provider asserts emails for its domains, so a platform admin has to check that the
+/// practice controls the issuer and domains before it signs anyone in.
+pub async fn approve_identity_provider_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    provider_id: &str,
+) -> AppResult<IdentityProvider> {
+    if !auth.platform_admin {
+        return Err(AuthError::InsufficientRole.into());
+    }
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let provider_id = uuid_param(provider_id, "provider_id")?;
+
+    state
+        .identity_provider_service
+        .read()
+        .await
+        .approve_provider(&practice_id, &provider_id)
+        .await
+}
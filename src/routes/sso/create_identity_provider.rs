@@ -0,0 +1,58 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::{
+            practice_role::PracticeRole,
+            sso::{IdentityProvider, NewIdentityProvider},
+        },
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct CreateIdentityProviderRequest {
+    /// Shown on the login button, e.g. "Google Workspace".
+    pub name: String,
+    /// The provider's OIDC issuer, e.g. `https://accounts.google.com`.
+    pub issuer: String,
+    pub client_id: String,
+    /// Omit for public clients that rely on PKCE alone.
+    pub client_secret: Option<String>,
+    /// Role for staff signing in for the first time; defaults to `clinician`.
+    pub default_role: Option<String>,
+    /// Email domains the provider may sign in, e.g. `["examplepractice.com"]`.
+    pub email_domains: Vec<String>,
+}
+
+pub async fn create_identity_provider_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<CreateIdentityProviderRequest>,
+) -> AppResult<IdentityProvider> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let payload = payload.0;
+    let default_role = match payload.default_role.as_deref() {
+        Some(code) => PracticeRole::parse(code)?,
+        None => PracticeRole::Clinician,
+    };
+    let provider = NewIdentityProvider::new(
+        payload.name,
+        payload.issuer,
+        payload.client_id,
+        payload.client_secret,
+        default_role,
+        payload.email_domains,
+    )?;
+
+    state
+        .identity_provider_service
+        .read()
+        .await
+        .create_provider(&auth.token, &practice_id, &provider)
+        .await
+}
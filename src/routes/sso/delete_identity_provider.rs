@@ -0,0 +1,24 @@
+use poem::web::Data;
+
+use crate::{
+    domain::error::app_error::AppResult,
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn delete_identity_provider_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    provider_id: &str,
+) -> AppResult<()> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let provider_id = uuid_param(provider_id, "provider_id")?;
+
+    state
+        .identity_provider_service
+        .read()
+        .await
+        .delete_provider(&auth.token, &practice_id, &provider_id)
+        .await
+}
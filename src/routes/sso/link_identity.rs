@@ -0,0 +1,88 @@
+use poem::web::{Data, cookie::CookieJar};
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, SsoError},
+        types::audit::AuthAuditAction,
+    },
+    routes::{
+        auth::{audit::record_auth_event, guard::AuthenticatedUser},
+        sso::{
+            login_cookie::take_login_cookie,
+            sso_callback::{matching_login, verified_identity},
+        },
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+#[derive(Object, Debug)]
+pub struct LinkIdentityRequest {
+    /// The `sso_code` the callback added to the page's query.
+    pub code: String,
+    pub state: String,
+}
+
+async fn link_identity(
+    state: &AppState,
+    cookies: &CookieJar,
+    auth: &AuthenticatedUser,
+    payload: &LinkIdentityRequest,
+    email: &mut Option<String>,
+) -> AppResult<()> {
+    let login = matching_login(
+        take_login_cookie(cookies, &state.sso_cookie_secret),
+        Some(&payload.state),
+    )?;
+    if !login.link {
+        return Err(SsoError::InvalidState.into());
+    }
+
+    let (provider, identity) = verified_identity(state, &login, &payload.code, email).await?;
+
+    let linked_user = state
+        .identity_provider_service
+        .read()
+        .await
+        .linked_user(&provider, &identity)
+        .await?;
+    if linked_user.is_some_and(|user_id| user_id != auth.user_id) {
+        return Err(SsoError::ProviderError(
+            "This identity is linked to a different account".to_string(),
+        )
+        .into());
+    }
+
+    state
+        .identity_provider_service
+        .read()
+        .await
+        .link_identity(&provider, &identity, &auth.user_id, true)
+        .await
+}
+
+/// Finishes a link the caller started with `POST /api/auth/sso/{provider_id}/link`, so the
+/// identity signs in to their account from then on.
+pub async fn link_identity_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    cookies: &CookieJar,
+    auth: AuthenticatedUser,
+    payload: Json<LinkIdentityRequest>,
+) -> AppResult<()> {
+    let mut email = None;
+    let result = link_identity(&state, cookies, &auth, &payload, &mut email).await;
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::LinkIdentity,
+        Some(auth.user_id.clone()),
+        email,
+        &result,
+    )
+    .await;
+
+    result
+}
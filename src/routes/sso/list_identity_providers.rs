@@ -0,0 +1,22 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::sso::IdentityProvider},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn list_identity_providers_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+) -> AppResult<Vec<IdentityProvider>> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+
+    state
+        .identity_provider_service
+        .read()
+        .await
+        .list_providers(&auth.token, &practice_id)
+        .await
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use poem::web::cookie::{Cookie, CookieJar, SameSite};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::Sha256;
+
+use crate::{domain::types::sso::SsoLogin, utils::clock::unix_now};
+
+/// Holds the pending [`SsoLogin`] between the start and callback requests.
+pub const SSO_LOGIN_COOKIE: &str = "breeze_sso_login";
+
+/// Only the SSO endpoints need it.
+const SSO_LOGIN_PATH: &str = "/api/auth/sso";
+/// The user has this long to finish signing in at the provider.
+const SSO_LOGIN_MAX_AGE: Duration = Duration::from_secs(600);
+
+fn cookie(value: String, max_age: Duration) -> Cookie {
+    let mut cookie = Cookie::new_with_str(SSO_LOGIN_COOKIE, value);
+    cookie.set_path(SSO_LOGIN_PATH);
+    cookie.set_max_age(max_age);
+    cookie.set_http_only(true);
+    cookie.set_secure(true);
+    // The callback is a cross-site top-level navigation from the provider, which
+    // `Strict` would strip the cookie from.
+    cookie.set_same_site(SameSite::Lax);
+    cookie
+}
+
+fn mac(secret: &SecretString, payload: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.expose_secret().as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+/// Stores the login as `{json}.{expires_at}.{mac}`, so the browser can hold it but not
+/// change it or keep it past its lifetime.
+pub fn set_login_cookie(jar: &CookieJar, secret: &SecretString, login: &SsoLogin) {
+    let json = serde_json::to_vec(login).unwrap_or_default();
+    let expires_at = unix_now() + SSO_LOGIN_MAX_AGE.as_secs() as i64;
+    let payload = format!("{}.{expires_at}", URL_SAFE_NO_PAD.encode(json));
+    let signature = URL_SAFE_NO_PAD.encode(mac(secret, &payload).finalize().into_bytes());
+    jar.add(cookie(format!("{payload}.{signature}"), SSO_LOGIN_MAX_AGE));
+}
+
+fn verified_login(secret: &SecretString, value: &str) -> Option<SsoLogin> {
+    let (payload, signature) = value.rsplit_once('.')?;
+    let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+    mac(secret, payload).verify_slice(&signature).ok()?;
+
+    let (json, expires_at) = payload.split_once('.')?;
+    if expires_at.parse::<i64>().ok()? <= unix_now() {
+        return None;
+    }
+    serde_json::from_slice(&URL_SAFE_NO_PAD.decode(json).ok()?).ok()
+}
+
+/// Reads and expires the pending login; each one can only be completed once. A cookie that
+/// wasn't signed with `secret`, or has outlived its lifetime, reads as no login.
+pub fn take_login_cookie(jar: &CookieJar, secret: &SecretString) -> Option<SsoLogin> {
+    let login = jar
+        .get(SSO_LOGIN_COOKIE)
+        .and_then(|c| verified_login(secret, c.value_str()));
+
+    let mut removal = cookie(String::new(), Duration::ZERO);
+    removal.make_removal();
+    jar.add(removal);
+
+    login
+}
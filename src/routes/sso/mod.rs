@@ -0,0 +1,8 @@
+pub mod approve_identity_provider;
+pub mod create_identity_provider;
+pub mod delete_identity_provider;
+pub mod link_identity;
+pub mod list_identity_providers;
+pub mod login_cookie;
+pub mod sso_callback;
+pub mod start_sso;
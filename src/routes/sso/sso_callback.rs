@@ -0,0 +1,216 @@
+use poem::web::{Data, cookie::CookieJar};
+use subtle::ConstantTimeEq;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, SsoError},
+        types::{
+            audit::AuthAuditAction,
+            session::AuthSession,
+            sso::{FederatedIdentity, IdentityProvider, SsoLogin},
+        },
+    },
+    routes::{
+        auth::{audit::record_auth_event, session_activity::discard_session},
+        sso::login_cookie::{set_login_cookie, take_login_cookie},
+    },
+    state::AppState,
+    utils::tracing::RequestContext,
+};
+
+/// Where providers send the browser back to; it must be registered with each of them.
+pub fn callback_url(state: &AppState) -> String {
+    format!("{}/api/auth/sso/callback", state.public_url)
+}
+
+/// Query parameters the provider appends to the callback URL.
+#[derive(Debug, Default)]
+pub struct SsoCallbackParams {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+/// Where the callback sends the browser next.
+pub enum SsoCallbackOutcome {
+    /// Signed in; the session goes into cookies.
+    SignedIn {
+        session: AuthSession,
+        redirect_to: String,
+    },
+    /// A link the signed-in user started. The page at `redirect_to` gets the code and
+    /// state, and finishes with `POST /api/auth/sso/link` under the user's own session.
+    Link { redirect_to: String },
+}
+
+/// The pending login, as long as it belongs to this callback.
+pub(crate) fn matching_login(login: Option<SsoLogin>, state: Option<&str>) -> AppResult<SsoLogin> {
+    match (login, state) {
+        (Some(login), Some(state))
+            if bool::from(login.state.as_bytes().ct_eq(state.as_bytes())) =>
+        {
+            Ok(login)
+        }
+        _ => Err(SsoError::InvalidState.into()),
+    }
+}
+
+fn authorization_code(params: &SsoCallbackParams) -> AppResult<&str> {
+    if let Some(error) = &params.error {
+        let detail = params.error_description.as_deref().unwrap_or(error);
+        return Err(SsoError::ProviderError(detail.to_string()).into());
+    }
+    params
+        .code
+        .as_deref()
+        .ok_or_else(|| SsoError::ProviderError("No authorization code".to_string()).into())
+}
+
+/// Redeems the code with an approved provider and checks the identity's email domain.
+/// `email` is set as soon as the provider has named one, for the audit log.
+pub(crate) async fn verified_identity(
+    state: &AppState,
+    login: &SsoLogin,
+    code: &str,
+    email: &mut Option<String>,
+) -> AppResult<(IdentityProvider, FederatedIdentity)> {
+    let provider = state
+        .identity_provider_service
+        .read()
+        .await
+        .get_provider(&login.provider_id)
+        .await?;
+    provider.ensure_approved()?;
+
+    let identity = state
+        .oidc_client
+        .read()
+        .await
+        .exchange_code(&provider, login, code, &callback_url(state))
+        .await?;
+    *email = Some(identity.email.normalized());
+
+    if !provider.allows_email(&identity.email) {
+        return Err(SsoError::EmailNotAllowed.into());
+    }
+    Ok((provider, identity))
+}
+
+/// Signs in to the account the identity is linked to, or to a new one on first login.
+/// An existing account is never signed in to through an identity it hasn't linked.
+async fn complete_sso(
+    state: &AppState,
+    login: &SsoLogin,
+    code: &str,
+    email: &mut Option<String>,
+) -> AppResult<AuthSession> {
+    let (provider, identity) = verified_identity(state, login, code, email).await?;
+
+    let linked_user = state
+        .identity_provider_service
+        .read()
+        .await
+        .linked_user(&provider, &identity)
+        .await?;
+
+    let session = state
+        .auth_service
+        .read()
+        .await
+        .signin_with_identity(&identity, linked_user.as_deref())
+        .await?;
+
+    if let Err(e) = state
+        .identity_provider_service
+        .read()
+        .await
+        .link_identity(
+            &provider,
+            &identity,
+            &session.user_id,
+            linked_user.is_none(),
+        )
+        .await
+    {
+        discard_session(state, &session).await;
+        // A new account that can't be linked could never be signed in to again.
+        if linked_user.is_none()
+            && let Err(e) = state
+                .auth_service
+                .read()
+                .await
+                .delete_user(&session.user_id)
+                .await
+        {
+            tracing::error!(error = %e, "Failed to delete unlinked SSO account");
+        }
+        return Err(e);
+    }
+
+    Ok(session)
+}
+
+/// `redirect_to` with the code and state added to its query.
+fn link_redirect(redirect_to: &str, code: &str, state: &str) -> AppResult<String> {
+    let mut url = reqwest::Url::parse("http://localhost")
+        .and_then(|base| base.join(redirect_to))
+        .map_err(|_| SsoError::InvalidState)?;
+    url.query_pairs_mut()
+        .append_pair("sso_code", code)
+        .append_pair("state", state);
+    Ok(format!(
+        "{}?{}",
+        url.path(),
+        url.query().unwrap_or_default()
+    ))
+}
+
+/// Finishes the login the provider redirected back from. Sign-ins return the new session
+/// and the local path the browser asked to land on; links are handed to the app.
+pub async fn sso_callback_handler(
+    state: Data<&AppState>,
+    ctx: &RequestContext,
+    cookies: &CookieJar,
+    params: SsoCallbackParams,
+) -> AppResult<SsoCallbackOutcome> {
+    let login = matching_login(
+        take_login_cookie(cookies, &state.sso_cookie_secret),
+        params.state.as_deref(),
+    );
+
+    if let Ok(login) = &login
+        && login.link
+    {
+        let code = authorization_code(&params)?;
+        let redirect_to = link_redirect(&login.redirect_to, code, &login.state)?;
+        // The app still needs it to finish the link.
+        set_login_cookie(cookies, &state.sso_cookie_secret, login);
+        return Ok(SsoCallbackOutcome::Link { redirect_to });
+    }
+
+    let mut email = None;
+    let result = match &login {
+        Ok(login) => match authorization_code(&params) {
+            Ok(code) => complete_sso(&state, login, code, &mut email).await,
+            Err(e) => Err(e),
+        },
+        Err(_) => Err(SsoError::InvalidState.into()),
+    };
+
+    record_auth_event(
+        &state,
+        ctx,
+        AuthAuditAction::Signin,
+        result.as_ref().ok().map(|session| session.user_id.clone()),
+        email,
+        &result,
+    )
+    .await;
+
+    let session = result?;
+    Ok(SsoCallbackOutcome::SignedIn {
+        session,
+        redirect_to: login?.redirect_to,
+    })
+}
@@ -0,0 +1,59 @@
+use poem::web::{Data, cookie::CookieJar};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, ValidationError},
+        types::sso::SsoLogin,
+    },
+    routes::{
+        params::uuid_param, sso::login_cookie::set_login_cookie, sso::sso_callback::callback_url,
+    },
+    state::AppState,
+};
+
+/// Only paths on this site, so the login can't be used as an open redirect.
+fn local_path(redirect_to: Option<String>) -> AppResult<String> {
+    match redirect_to {
+        None => Ok("/".to_string()),
+        Some(path) if path.starts_with('/') && !path.starts_with("//") && !path.contains('\\') => {
+            Ok(path)
+        }
+        Some(_) => Err(ValidationError::InvalidInput(
+            "redirect_to must be a path on this site".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Begins an authorization-code login and returns the provider URL to send the browser to.
+/// With `link`, the signed-in caller is linking the identity to their account instead.
+pub async fn start_sso_handler(
+    state: Data<&AppState>,
+    cookies: &CookieJar,
+    provider_id: &str,
+    redirect_to: Option<String>,
+    link: bool,
+) -> AppResult<String> {
+    let provider_id = uuid_param(provider_id, "provider_id")?;
+    let redirect_to = local_path(redirect_to)?;
+
+    let provider = state
+        .identity_provider_service
+        .read()
+        .await
+        .get_provider(&provider_id)
+        .await?;
+    provider.ensure_approved()?;
+
+    let mut login = SsoLogin::new(provider.id.clone(), redirect_to);
+    login.link = link;
+    let url = state
+        .oidc_client
+        .read()
+        .await
+        .authorization_url(&provider, &login, &callback_url(&state))
+        .await?;
+
+    set_login_cookie(cookies, &state.sso_cookie_secret, &login);
+    Ok(url)
+}
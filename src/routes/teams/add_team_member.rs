@@ -0,0 +1,34 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{error::app_error::AppResult, types::team::Team},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct AddTeamMemberRequest {
+    pub user_id: String,
+}
+
+pub async fn add_team_member_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    team_id: &str,
+    payload: Json<AddTeamMemberRequest>,
+) -> AppResult<Team> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let team_id = uuid_param(team_id, "team_id")?;
+    let user_id = uuid_param(&payload.user_id, "user_id")?;
+
+    let team = state
+        .team_service
+        .read()
+        .await
+        .add_team_member(&auth.token, &practice_id, &team_id, &user_id)
+        .await?;
+
+    Ok(team)
+}
@@ -0,0 +1,35 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::team::{Team, TeamName},
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct CreateTeamRequest {
+    pub name: String,
+}
+
+pub async fn create_team_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    payload: Json<CreateTeamRequest>,
+) -> AppResult<Team> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let name = TeamName::new(payload.name.clone())?;
+
+    let team = state
+        .team_service
+        .read()
+        .await
+        .create_team(&auth.token, &practice_id, &name)
+        .await?;
+
+    Ok(team)
+}
@@ -0,0 +1,24 @@
+use poem::web::Data;
+
+use crate::{
+    domain::error::app_error::AppResult,
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn delete_team_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    team_id: &str,
+) -> AppResult<()> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let team_id = uuid_param(team_id, "team_id")?;
+
+    state
+        .team_service
+        .read()
+        .await
+        .delete_team(&auth.token, &practice_id, &team_id)
+        .await
+}
@@ -0,0 +1,26 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::team::Team},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn get_team_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    team_id: &str,
+) -> AppResult<Team> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let team_id = uuid_param(team_id, "team_id")?;
+
+    let team = state
+        .team_service
+        .read()
+        .await
+        .get_team(&auth.token, &practice_id, &team_id)
+        .await?;
+
+    Ok(team)
+}
@@ -0,0 +1,24 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::team::Team},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn list_teams_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+) -> AppResult<Vec<Team>> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+
+    let teams = state
+        .team_service
+        .read()
+        .await
+        .list_teams(&auth.token, &practice_id)
+        .await?;
+
+    Ok(teams)
+}
@@ -0,0 +1,28 @@
+use poem::web::Data;
+
+use crate::{
+    domain::{error::app_error::AppResult, types::team::Team},
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+pub async fn remove_team_member_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    team_id: &str,
+    user_id: &str,
+) -> AppResult<Team> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let team_id = uuid_param(team_id, "team_id")?;
+    let user_id = uuid_param(user_id, "user_id")?;
+
+    let team = state
+        .team_service
+        .read()
+        .await
+        .remove_team_member(&auth.token, &practice_id, &team_id, &user_id)
+        .await?;
+
+    Ok(team)
+}
@@ -0,0 +1,37 @@
+use poem::web::Data;
+use poem_openapi::{Object, payload::Json};
+
+use crate::{
+    domain::{
+        error::app_error::AppResult,
+        types::team::{Team, TeamName},
+    },
+    routes::{auth::guard::AuthenticatedUser, params::uuid_param},
+    state::AppState,
+};
+
+#[derive(Object, Debug)]
+pub struct RenameTeamRequest {
+    pub name: String,
+}
+
+pub async fn rename_team_handler(
+    state: Data<&AppState>,
+    auth: AuthenticatedUser,
+    practice_id: &str,
+    team_id: &str,
+    payload: Json<RenameTeamRequest>,
+) -> AppResult<Team> {
+    let practice_id = uuid_param(practice_id, "practice_id")?;
+    let team_id = uuid_param(team_id, "team_id")?;
+    let name = TeamName::new(payload.name.clone())?;
+
+    let team = state
+        .team_service
+        .read()
+        .await
+        .rename_team(&auth.token, &practice_id, &team_id, &name)
+        .await?;
+
+    Ok(team)
+}
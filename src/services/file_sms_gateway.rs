@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tokio::io::AsyncWriteExt;
+
+use crate::domain::{
+    error::app_error::{AppResult, SmsError},
+    interfaces::sms_gateway::SmsGateway,
+    types::phone_number::PhoneNumber,
+};
+
+/// Appends each message to a local file instead of sending it, for development without an
+/// SMS provider. `tail -f` the file to read codes.
+pub struct FileSmsGateway {
+    path: PathBuf,
+}
+
+impl FileSmsGateway {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl SmsGateway for FileSmsGateway {
+    async fn send(&self, to: &PhoneNumber, body: &str) -> AppResult<()> {
+        let sent_at = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_default();
+        let line = format!("{sent_at} {} {body}\n", to.as_ref());
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| SmsError::SendError(format!("Failed to open SMS outbox: {e}")))?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| SmsError::SendError(format!("Failed to write SMS outbox: {e}")))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header, jwk::JwkSet};
+use secrecy::ExposeSecret;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use crate::domain::{
+    error::app_error::{AppResult, SsoError},
+    interfaces::oidc_client::OidcClient,
+    types::{
+        email::Email,
+        sso::{FederatedIdentity, IdentityProvider, SsoLogin},
+    },
+};
+
+/// How long a provider's discovery document and keys are trusted before refetching.
+const METADATA_TTL: Duration = Duration::from_secs(600);
+
+const SCOPES: &str = "openid email profile";
+
+const ID_TOKEN_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::ES256,
+    Algorithm::ES384,
+];
+
+#[derive(Clone, Deserialize)]
+struct Discovery {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Clone)]
+struct ProviderMetadata {
+    discovery: Discovery,
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    email: Option<String>,
+    email_verified: Option<bool>,
+    name: Option<String>,
+    nonce: Option<String>,
+}
+
+/// Talks to providers over HTTP, caching each issuer's discovery document and JWKS.
+pub struct HttpOidcClient {
+    client: reqwest::Client,
+    metadata: RwLock<HashMap<String, ProviderMetadata>>,
+}
+
+impl Default for HttpOidcClient {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            metadata: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+fn request_error(e: impl std::fmt::Display) -> SsoError {
+    SsoError::SsoRequestError(e.to_string())
+}
+
+impl HttpOidcClient {
+    async fn fetch_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> AppResult<T> {
+        let value = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(request_error)?
+            .json::<T>()
+            .await
+            .map_err(request_error)?;
+        Ok(value)
+    }
+
+    async fn fetch_metadata(&self, issuer: &str) -> AppResult<ProviderMetadata> {
+        let discovery: Discovery = self
+            .fetch_json(&format!("{issuer}/.well-known/openid-configuration"))
+            .await?;
+        // OIDC Discovery §4.3: the document must be for the issuer we asked about.
+        if discovery.issuer.trim_end_matches('/') != issuer {
+            return Err(request_error(format!(
+                "discovery document is for {}, not {issuer}",
+                discovery.issuer
+            ))
+            .into());
+        }
+        let keys: JwkSet = self.fetch_json(&discovery.jwks_uri).await?;
+
+        Ok(ProviderMetadata {
+            discovery,
+            keys,
+            fetched_at: Instant::now(),
+        })
+    }
+
+    /// Cached metadata for `issuer`; `refresh` forces a refetch, e.g. for a rotated key.
+    async fn metadata(&self, issuer: &str, refresh: bool) -> AppResult<ProviderMetadata> {
+        if !refresh
+            && let Some(cached) = self.metadata.read().await.get(issuer)
+            && cached.fetched_at.elapsed() < METADATA_TTL
+        {
+            return Ok(cached.clone());
+        }
+
+        let fetched = self.fetch_metadata(issuer).await?;
+        self.metadata
+            .write()
+            .await
+            .insert(issuer.to_string(), fetched.clone());
+        Ok(fetched)
+    }
+
+    async fn decoding_key(&self, issuer: &str, kid: &str) -> AppResult<DecodingKey> {
+        for refresh in [false, true] {
+            let metadata = self.metadata(issuer, refresh).await?;
+            if let Some(jwk) = metadata.keys.find(kid) {
+                return DecodingKey::from_jwk(jwk).map_err(|_| {
+                    SsoError::ProviderError("Unusable ID token key".to_string()).into()
+                });
+            }
+        }
+        Err(SsoError::ProviderError("ID token signed with an unknown key".to_string()).into())
+    }
+
+    async fn validate_id_token(
+        &self,
+        provider: &IdentityProvider,
+        login: &SsoLogin,
+        id_token: &str,
+    ) -> AppResult<FederatedIdentity> {
+        let invalid = || SsoError::ProviderError("Invalid ID token".to_string());
+
+        let header = decode_header(id_token).map_err(|_| invalid())?;
+        if !ID_TOKEN_ALGORITHMS.contains(&header.alg) {
+            return Err(invalid().into());
+        }
+        let kid = header.kid.as_deref().ok_or_else(invalid)?;
+        let key = self.decoding_key(&provider.issuer, kid).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&provider.issuer]);
+        validation.set_audience(&[&provider.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|_| invalid())?
+            .claims;
+
+        // The nonce ties the token to the login this browser started.
+        if claims.nonce.as_deref() != Some(login.nonce.as_str()) {
+            return Err(invalid().into());
+        }
+        if claims.email_verified == Some(false) {
+            return Err(
+                SsoError::ProviderError("Email address is not verified".to_string()).into(),
+            );
+        }
+        let email = claims
+            .email
+            .ok_or_else(|| SsoError::ProviderError("ID token has no email".to_string()))
+            .and_then(|email| {
+                Email::new(email)
+                    .map_err(|_| SsoError::ProviderError("ID token email is invalid".to_string()))
+            })?;
+
+        Ok(FederatedIdentity {
+            issuer: provider.issuer.clone(),
+            subject: claims.sub,
+            email,
+            name: claims.name.filter(|n| !n.trim().is_empty()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl OidcClient for HttpOidcClient {
+    async fn authorization_url(
+        &self,
+        provider: &IdentityProvider,
+        login: &SsoLogin,
+        redirect_uri: &str,
+    ) -> AppResult<String> {
+        let metadata = self.metadata(&provider.issuer, false).await?;
+        let url = reqwest::Url::parse_with_params(
+            &metadata.discovery.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", provider.client_id.as_str()),
+                ("redirect_uri", redirect_uri),
+                ("scope", SCOPES),
+                ("state", login.state.as_str()),
+                ("nonce", login.nonce.as_str()),
+                ("code_challenge", login.code_challenge().as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(request_error)?;
+
+        Ok(url.into())
+    }
+
+    async fn exchange_code(
+        &self,
+        provider: &IdentityProvider,
+        login: &SsoLogin,
+        code: &str,
+        redirect_uri: &str,
+    ) -> AppResult<FederatedIdentity> {
+        let metadata = self.metadata(&provider.issuer, false).await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("client_id", provider.client_id.as_str()),
+            ("code_verifier", login.code_verifier.as_str()),
+        ];
+        if let Some(secret) = &provider.client_secret {
+            form.push(("client_secret", secret.expose_secret()));
+        }
+
+        let resp = self
+            .client
+            .post(&metadata.discovery.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+            let error = body
+                .get("error_description")
+                .or_else(|| body.get("error"))
+                .and_then(Value::as_str)
+                .unwrap_or("token request failed");
+            return Err(SsoError::ProviderError(format!(
+                "Code exchange rejected with status {status}: {error}"
+            ))
+            .into());
+        }
+
+        let tokens = resp
+            .json::<TokenResponse>()
+            .await
+            .map_err(|_| SsoError::ProviderError("Token response has no ID token".to_string()))?;
+
+        self.validate_id_token(provider, login, &tokens.id_token)
+            .await
+    }
+}
@@ -0,0 +1,25 @@
+use tokio::sync::Mutex;
+
+use crate::domain::{
+    error::app_error::AppResult, interfaces::audit_sink::AuditSink, types::audit::AuthAuditEvent,
+};
+
+/// Keeps events in memory so tests can assert on what was recorded.
+#[derive(Default)]
+pub struct InMemoryAuditSink {
+    events: Mutex<Vec<AuthAuditEvent>>,
+}
+
+impl InMemoryAuditSink {
+    pub async fn events(&self) -> Vec<AuthAuditEvent> {
+        self.events.lock().await.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditSink for InMemoryAuditSink {
+    async fn record(&self, event: AuthAuditEvent) -> AppResult<()> {
+        self.events.lock().await.push(event);
+        Ok(())
+    }
+}
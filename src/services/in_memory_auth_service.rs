@@ -0,0 +1,925 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use rand::RngCore;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+use tokio::sync::{Mutex, RwLock};
+use totp_rs::{Secret, TOTP};
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, AuthError, SsoError, ValidationError},
+        interfaces::{auth_service::AuthService, sms_gateway::SmsGateway},
+        types::{
+            email::Email,
+            mfa::{
+                MfaChallenge, MfaFactor, PhoneEnrollment, SMS_RESEND_INTERVAL_SECS, TotpEnrollment,
+                sms_code_message,
+            },
+            otp::OtpVerification,
+            password::Password,
+            phone_number::PhoneNumber,
+            profile::{ProfileMetadata, UserProfile, UserUpdate},
+            session::{AppMetadata, AuthSession},
+            sso::FederatedIdentity,
+        },
+    },
+    state::SmsGatewayType,
+    utils::clock::unix_now,
+};
+
+const DEFAULT_ACCESS_TOKEN_TTL: Duration = Duration::from_secs(3600);
+const RECOVERY_TOKEN_TTL: Duration = Duration::from_secs(3600);
+const CHALLENGE_TTL: Duration = Duration::from_secs(300);
+const OTP_TTL: Duration = Duration::from_secs(3600);
+const TOTP_ISSUER: &str = "BreezeEHR";
+
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    email: String,
+    aal: String,
+    session_id: String,
+    role: String,
+    app_metadata: AppMetadata,
+    iss: String,
+    aud: String,
+    iat: i64,
+    exp: i64,
+}
+
+struct User {
+    id: String,
+    email: String,
+    /// An address change waiting for [`InMemoryAuthService::confirm_email_change`].
+    pending_email: Option<String>,
+    password: Password,
+    confirmed: bool,
+    factors: Vec<Factor>,
+    metadata: ProfileMetadata,
+    platform_admin: bool,
+}
+
+impl User {
+    fn profile(&self) -> UserProfile {
+        UserProfile {
+            user_id: self.id.clone(),
+            email: self.email.clone(),
+            pending_email: self.pending_email.clone(),
+            metadata: self.metadata.clone(),
+        }
+    }
+}
+
+struct Factor {
+    id: String,
+    friendly_name: Option<String>,
+    kind: FactorKind,
+    verified: bool,
+}
+
+enum FactorKind {
+    Totp {
+        secret: Vec<u8>,
+    },
+    Phone {
+        phone: PhoneNumber,
+        /// When the last code was texted, for resend throttling.
+        last_sent_at: Option<i64>,
+    },
+}
+
+impl FactorKind {
+    fn factor_type(&self) -> &'static str {
+        match self {
+            FactorKind::Totp { .. } => "totp",
+            FactorKind::Phone { .. } => "phone",
+        }
+    }
+}
+
+struct Session {
+    user_id: String,
+    aal: String,
+}
+
+struct Challenge {
+    factor_id: String,
+    expires_at: i64,
+    /// The texted code, for phone factors; TOTP challenges check the authenticator instead.
+    code: Option<String>,
+}
+
+struct RecoveryToken {
+    user_id: String,
+    expires_at: i64,
+}
+
+struct Otp {
+    user_id: String,
+    code: String,
+    expires_at: i64,
+}
+
+#[derive(Default)]
+struct Store {
+    users: HashMap<String, User>,
+    sessions: HashMap<String, Session>,
+    /// Refresh token -> session id. Tokens are single-use and rotate on every refresh.
+    refresh_tokens: HashMap<String, String>,
+    challenges: HashMap<String, Challenge>,
+    recovery_tokens: HashMap<String, RecoveryToken>,
+    /// Magic-link token hash -> the code and user it signs in. One outstanding per user.
+    otps: HashMap<String, Otp>,
+}
+
+impl Store {
+    fn user_by_email(&self, email: &str) -> Option<&User> {
+        self.users.values().find(|u| u.email == email)
+    }
+
+    fn revoke_sessions(&mut self, user_id: &str) {
+        self.revoke_matching(|_, s| s.user_id == user_id);
+    }
+
+    fn revoke_matching(&mut self, revoke: impl Fn(&str, &Session) -> bool) {
+        self.sessions.retain(|id, s| !revoke(id, s));
+        let sessions = &self.sessions;
+        self.refresh_tokens
+            .retain(|_, session_id| sessions.contains_key(session_id));
+    }
+}
+
+/// GoTrue stand-in for the memory backend and hermetic tests. Users, sessions and factors
+/// live in a map, and access tokens are HS256 JWTs that `JwksTokenVerifier` accepts when
+/// configured with the same secret, issuer and audience.
+pub struct InMemoryAuthService {
+    issuer: String,
+    audience: String,
+    jwt_secret: SecretString,
+    access_token_ttl: Duration,
+    auto_confirm: bool,
+    sms_gateway: Option<SmsGatewayType>,
+    store: Mutex<Store>,
+}
+
+impl InMemoryAuthService {
+    pub fn new(issuer: String, audience: String, jwt_secret: SecretString) -> Self {
+        Self {
+            issuer,
+            audience,
+            jwt_secret,
+            access_token_ttl: DEFAULT_ACCESS_TOKEN_TTL,
+            auto_confirm: false,
+            sms_gateway: None,
+            store: Mutex::new(Store::default()),
+        }
+    }
+
+    /// Confirm new signups immediately, since there is no mailbox to follow a link from.
+    pub fn with_auto_confirm(mut self, auto_confirm: bool) -> Self {
+        self.auto_confirm = auto_confirm;
+        self
+    }
+
+    /// Where phone-factor challenges text their codes; without one they fail.
+    pub fn with_sms_gateway(mut self, gateway: Arc<RwLock<dyn SmsGateway + Send + Sync>>) -> Self {
+        self.sms_gateway = Some(gateway);
+        self
+    }
+
+    pub fn with_access_token_ttl(mut self, ttl: Duration) -> Self {
+        self.access_token_ttl = ttl;
+        self
+    }
+
+    /// What following the signup confirmation link does.
+    pub async fn confirm_email(&self, email: &Email) -> AppResult<()> {
+        let email = email.normalized();
+        let mut store = self.store.lock().await;
+        let user = store
+            .users
+            .values_mut()
+            .find(|u| u.email == email)
+            .ok_or(AuthError::UserNotFound)?;
+        user.confirmed = true;
+        Ok(())
+    }
+
+    /// Follows the link an email-change confirmation would have carried.
+    pub async fn confirm_email_change(&self, new_email: &Email) -> AppResult<()> {
+        let new_email = new_email.normalized();
+        let mut store = self.store.lock().await;
+        let user = store
+            .users
+            .values_mut()
+            .find(|u| u.pending_email.as_deref() == Some(new_email.as_str()))
+            .ok_or(AuthError::UserNotFound)?;
+        user.email = new_email;
+        user.pending_email = None;
+        Ok(())
+    }
+
+    /// What setting `app_metadata.platform_admin` with the service role does; takes effect
+    /// from the user's next token.
+    pub async fn grant_platform_admin(&self, email: &Email) -> AppResult<()> {
+        let email = email.normalized();
+        let mut store = self.store.lock().await;
+        let user = store
+            .users
+            .values_mut()
+            .find(|u| u.email == email)
+            .ok_or(AuthError::UserNotFound)?;
+        user.platform_admin = true;
+        Ok(())
+    }
+
+    /// The code and link token a magic-link email would have carried, if one is outstanding.
+    pub async fn magic_link_otp(&self, email: &Email) -> Option<(String, String)> {
+        let email = email.normalized();
+        let store = self.store.lock().await;
+        let user_id = &store.user_by_email(&email)?.id;
+        store
+            .otps
+            .iter()
+            .find(|(_, otp)| &otp.user_id == user_id)
+            .map(|(token_hash, otp)| (otp.code.clone(), token_hash.clone()))
+    }
+
+    /// The token a recovery email would have carried, if one is outstanding.
+    pub async fn recovery_token(&self, email: &Email) -> Option<String> {
+        let email = email.normalized();
+        let store = self.store.lock().await;
+        let user_id = &store.user_by_email(&email)?.id;
+        store
+            .recovery_tokens
+            .iter()
+            .find(|(_, t)| &t.user_id == user_id)
+            .map(|(token, _)| token.clone())
+    }
+
+    // Same key derivation as `JwksTokenVerifier`, so its HS256 fallback accepts our tokens.
+    fn encoding_key(&self) -> EncodingKey {
+        let secret = self.jwt_secret.expose_secret();
+        EncodingKey::from_base64_secret(secret)
+            .unwrap_or_else(|_| EncodingKey::from_secret(secret.as_bytes()))
+    }
+
+    fn decoding_key(&self) -> DecodingKey {
+        let secret = self.jwt_secret.expose_secret();
+        DecodingKey::from_base64_secret(secret)
+            .unwrap_or_else(|_| DecodingKey::from_secret(secret.as_bytes()))
+    }
+
+    fn issue_session(
+        &self,
+        store: &mut Store,
+        session_id: &str,
+        user_id: &str,
+    ) -> AppResult<AuthSession> {
+        let session = store
+            .sessions
+            .get(session_id)
+            .ok_or(AuthError::InvalidToken)?;
+        let user = store.users.get(user_id).ok_or(AuthError::UserNotFound)?;
+
+        let now = unix_now();
+        let expires_in = self.access_token_ttl.as_secs() as i64;
+        let claims = Claims {
+            sub: user.id.clone(),
+            email: user.email.clone(),
+            aal: session.aal.clone(),
+            session_id: session_id.to_string(),
+            role: "authenticated".to_string(),
+            app_metadata: AppMetadata {
+                platform_admin: user.platform_admin,
+            },
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            iat: now,
+            exp: now + expires_in,
+        };
+        let access_token = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &self.encoding_key(),
+        )
+        .map_err(AppError::internal)?;
+
+        let refresh_token = random_token();
+        store
+            .refresh_tokens
+            .insert(refresh_token.clone(), session_id.to_string());
+
+        Ok(AuthSession {
+            user_id: claims.sub,
+            access_token,
+            refresh_token,
+            expires_in,
+            expires_at: now + expires_in,
+        })
+    }
+
+    /// Opens a fresh AAL1 session, as a password or magic-link signin does.
+    fn start_session(&self, store: &mut Store, user_id: &str) -> AppResult<AuthSession> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        store.sessions.insert(
+            session_id.clone(),
+            Session {
+                user_id: user_id.to_string(),
+                aal: "aal1".to_string(),
+            },
+        );
+
+        self.issue_session(store, &session_id, user_id)
+    }
+
+    /// Decodes one of our access tokens and checks its session hasn't been signed out.
+    fn session_claims(&self, store: &Store, token: &str) -> Option<Claims> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+
+        let claims = decode::<Claims>(token, &self.decoding_key(), &validation)
+            .ok()?
+            .claims;
+        store
+            .sessions
+            .get(&claims.session_id)
+            .filter(|s| s.user_id == claims.sub)?;
+        Some(claims)
+    }
+
+    fn mfa_claims(&self, store: &Store, token: &str) -> AppResult<Claims> {
+        self.session_claims(store, token)
+            .ok_or_else(|| AuthError::InvalidToken.into())
+    }
+
+    fn check_friendly_name(user: &User, friendly_name: Option<&str>) -> AppResult<()> {
+        if let Some(name) = friendly_name
+            && user
+                .factors
+                .iter()
+                .any(|f| f.friendly_name.as_deref() == Some(name))
+        {
+            return Err(AuthError::MfaError(format!(
+                "A factor with the friendly name \"{name}\" for this user already exists"
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    fn create_user(
+        &self,
+        store: &mut Store,
+        email: &Email,
+        password: &Password,
+        confirmed: bool,
+    ) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        store.users.insert(
+            id.clone(),
+            User {
+                id: id.clone(),
+                email: email.normalized(),
+                pending_email: None,
+                password: password.clone(),
+                confirmed,
+                factors: Vec::new(),
+                metadata: ProfileMetadata::default(),
+                platform_admin: false,
+            },
+        );
+        id
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthService for InMemoryAuthService {
+    async fn challenge_factor(&self, token: &str, factor_id: &str) -> AppResult<MfaChallenge> {
+        let mut store = self.store.lock().await;
+        let claims = self.mfa_claims(&store, token)?;
+
+        let now = unix_now();
+        let factor = store
+            .users
+            .get_mut(&claims.sub)
+            .and_then(|u| u.factors.iter_mut().find(|f| f.id == factor_id))
+            .ok_or(AuthError::FactorNotFound)?;
+
+        let sms = match &mut factor.kind {
+            FactorKind::Totp { .. } => None,
+            FactorKind::Phone {
+                phone,
+                last_sent_at,
+            } => {
+                if let Some(sent_at) = *last_sent_at
+                    && now - sent_at < SMS_RESEND_INTERVAL_SECS
+                {
+                    return Err(AuthError::SmsResendThrottled {
+                        retry_after_secs: (sent_at + SMS_RESEND_INTERVAL_SECS - now) as u64,
+                    }
+                    .into());
+                }
+                let gateway = self
+                    .sms_gateway
+                    .clone()
+                    .ok_or_else(|| AuthError::MfaError("SMS is not configured".to_string()))?;
+                *last_sent_at = Some(now);
+                Some((gateway, phone.clone(), random_code()))
+            }
+        };
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let expires_at = now + CHALLENGE_TTL.as_secs() as i64;
+        store.challenges.insert(
+            id.clone(),
+            Challenge {
+                factor_id: factor_id.to_string(),
+                expires_at,
+                code: sms.as_ref().map(|(_, _, code)| code.clone()),
+            },
+        );
+        drop(store);
+
+        if let Some((gateway, phone, code)) = sms {
+            gateway
+                .read()
+                .await
+                .send(&phone, &sms_code_message(&code))
+                .await?;
+        }
+
+        Ok(MfaChallenge { id, expires_at })
+    }
+
+    async fn create_confirmed_user(&self, email: &Email, password: &Password) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        if store.user_by_email(&email.normalized()).is_some() {
+            return Err(AuthError::EmailAlreadyInUse.into());
+        }
+        self.create_user(&mut store, email, password, true);
+        Ok(())
+    }
+
+    async fn delete_user(&self, user_id: &str) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        if store.users.remove(user_id).is_none() {
+            return Err(AuthError::DeleteUserError(
+                "Failed to delete user with status 404 Not Found: User not found".to_string(),
+            )
+            .into());
+        }
+        store.revoke_sessions(user_id);
+        store.recovery_tokens.retain(|_, t| t.user_id != user_id);
+        Ok(())
+    }
+
+    async fn enroll_phone(
+        &self,
+        token: &str,
+        phone: &PhoneNumber,
+        friendly_name: Option<&str>,
+    ) -> AppResult<PhoneEnrollment> {
+        let mut store = self.store.lock().await;
+        let claims = self.mfa_claims(&store, token)?;
+        let user = store
+            .users
+            .get_mut(&claims.sub)
+            .ok_or(AuthError::UserNotFound)?;
+
+        Self::check_friendly_name(user, friendly_name)?;
+        if user.factors.iter().any(
+            |f| matches!(&f.kind, FactorKind::Phone { phone: p, .. } if p == phone && f.verified),
+        ) {
+            return Err(AuthError::MfaError(
+                "A verified phone factor with this number already exists".to_string(),
+            )
+            .into());
+        }
+
+        let factor_id = uuid::Uuid::new_v4().to_string();
+        user.factors.push(Factor {
+            id: factor_id.clone(),
+            friendly_name: friendly_name.map(str::to_string),
+            kind: FactorKind::Phone {
+                phone: phone.clone(),
+                last_sent_at: None,
+            },
+            verified: false,
+        });
+
+        Ok(PhoneEnrollment {
+            factor_id,
+            friendly_name: friendly_name.map(str::to_string),
+            phone: phone.as_ref().to_string(),
+        })
+    }
+
+    async fn enroll_totp(
+        &self,
+        token: &str,
+        friendly_name: Option<&str>,
+    ) -> AppResult<TotpEnrollment> {
+        let mut store = self.store.lock().await;
+        let claims = self.mfa_claims(&store, token)?;
+        let user = store
+            .users
+            .get_mut(&claims.sub)
+            .ok_or(AuthError::UserNotFound)?;
+
+        Self::check_friendly_name(user, friendly_name)?;
+
+        let mut secret = vec![0u8; 20];
+        rand::rng().fill_bytes(&mut secret);
+        let encoded = Secret::Raw(secret.clone()).to_encoded().to_string();
+        let factor_id = uuid::Uuid::new_v4().to_string();
+
+        user.factors.push(Factor {
+            id: factor_id.clone(),
+            friendly_name: friendly_name.map(str::to_string),
+            kind: FactorKind::Totp { secret },
+            verified: false,
+        });
+
+        Ok(TotpEnrollment {
+            factor_id,
+            friendly_name: friendly_name.map(str::to_string),
+            uri: format!(
+                "otpauth://totp/{TOTP_ISSUER}:{}?secret={encoded}&issuer={TOTP_ISSUER}",
+                user.email
+            ),
+            // No QR renderer here; authenticator apps can take the URI or secret directly.
+            qr_code: String::new(),
+            secret: encoded,
+        })
+    }
+
+    async fn forgot_password(&self, email: &Email, _redirect_to: Option<&str>) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        // Unknown emails succeed silently, as with GoTrue, so accounts can't be enumerated.
+        let Some(user_id) = store
+            .user_by_email(&email.normalized())
+            .map(|u| u.id.clone())
+        else {
+            return Ok(());
+        };
+
+        store.recovery_tokens.retain(|_, t| t.user_id != user_id);
+        store.recovery_tokens.insert(
+            random_token(),
+            RecoveryToken {
+                user_id,
+                expires_at: unix_now() + RECOVERY_TOKEN_TTL.as_secs() as i64,
+            },
+        );
+        Ok(())
+    }
+
+    async fn get_user(&self, token: &str) -> AppResult<UserProfile> {
+        let store = self.store.lock().await;
+        let claims = self
+            .session_claims(&store, token)
+            .ok_or(AuthError::InvalidToken)?;
+        store
+            .users
+            .get(&claims.sub)
+            .map(User::profile)
+            .ok_or_else(|| AuthError::UserNotFound.into())
+    }
+
+    async fn list_factors(&self, token: &str) -> AppResult<Vec<MfaFactor>> {
+        let store = self.store.lock().await;
+        let claims = self.mfa_claims(&store, token)?;
+        let user = store
+            .users
+            .get(&claims.sub)
+            .ok_or(AuthError::UserNotFound)?;
+
+        Ok(user
+            .factors
+            .iter()
+            .map(|f| MfaFactor {
+                id: f.id.clone(),
+                factor_type: f.kind.factor_type().to_string(),
+                friendly_name: f.friendly_name.clone(),
+                status: if f.verified { "verified" } else { "unverified" }.to_string(),
+                phone: match &f.kind {
+                    FactorKind::Phone { phone, .. } => Some(phone.as_ref().to_string()),
+                    FactorKind::Totp { .. } => None,
+                },
+            })
+            .collect())
+    }
+
+    async fn refresh_session(&self, refresh_token: &str) -> AppResult<AuthSession> {
+        let mut store = self.store.lock().await;
+        let session_id = store
+            .refresh_tokens
+            .remove(refresh_token)
+            .ok_or(AuthError::InvalidRefreshToken)?;
+        let user_id = store
+            .sessions
+            .get(&session_id)
+            .map(|s| s.user_id.clone())
+            .ok_or(AuthError::InvalidRefreshToken)?;
+
+        self.issue_session(&mut store, &session_id, &user_id)
+    }
+
+    async fn reset_password(&self, recovery_token: &str, new_password: &Password) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        let recovery = store
+            .recovery_tokens
+            .remove(recovery_token)
+            .ok_or(AuthError::InvalidRecoveryToken)?;
+        if recovery.expires_at <= unix_now() {
+            return Err(AuthError::ExpiredRecoveryToken.into());
+        }
+
+        let user = store
+            .users
+            .get_mut(&recovery.user_id)
+            .ok_or(AuthError::InvalidRecoveryToken)?;
+        user.password = new_password.clone();
+        Ok(())
+    }
+
+    async fn retrieve_user_id(&self, email: &Email) -> AppResult<String> {
+        let store = self.store.lock().await;
+        store
+            .user_by_email(&email.normalized())
+            .map(|u| u.id.clone())
+            .ok_or_else(|| AuthError::UserNotFound.into())
+    }
+
+    async fn revoke_session(&self, user_id: &str, session_id: &str) -> AppResult<()> {
+        self.store
+            .lock()
+            .await
+            .revoke_matching(|id, s| id == session_id && s.user_id == user_id);
+        Ok(())
+    }
+
+    async fn revoke_user_sessions(&self, user_id: &str) -> AppResult<()> {
+        self.store.lock().await.revoke_sessions(user_id);
+        Ok(())
+    }
+
+    async fn send_magic_link(&self, email: &Email, _redirect_to: Option<&str>) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        let Some(user_id) = store
+            .user_by_email(&email.normalized())
+            .filter(|u| u.confirmed)
+            .map(|u| u.id.clone())
+        else {
+            return Ok(());
+        };
+
+        let code = random_code();
+        store.otps.retain(|_, otp| otp.user_id != user_id);
+        store.otps.insert(
+            random_token(),
+            Otp {
+                user_id,
+                code,
+                expires_at: unix_now() + OTP_TTL.as_secs() as i64,
+            },
+        );
+        Ok(())
+    }
+
+    async fn signin(&self, email: &Email, password: &Password) -> AppResult<AuthSession> {
+        let mut store = self.store.lock().await;
+        let user = store
+            .user_by_email(&email.normalized())
+            .filter(|u| &u.password == password)
+            .ok_or(AuthError::InvalidCredentials)?;
+
+        if !user.confirmed {
+            return Err(AuthError::SignInError("Email not confirmed".to_string()).into());
+        }
+
+        let user_id = user.id.clone();
+        self.start_session(&mut store, &user_id)
+    }
+
+    async fn signin_with_identity(
+        &self,
+        identity: &FederatedIdentity,
+        user_id: Option<&str>,
+    ) -> AppResult<AuthSession> {
+        let mut store = self.store.lock().await;
+        let user_id = match user_id {
+            Some(id) if store.users.contains_key(id) => id.to_string(),
+            Some(_) => return Err(AuthError::UserNotFound.into()),
+            None => {
+                // An existing account is never taken over by an unlinked identity.
+                if store.user_by_email(&identity.email.normalized()).is_some() {
+                    return Err(SsoError::AccountExists.into());
+                }
+                // Nobody knows this password; the user signs in through their provider.
+                let password = Password::existing(format!("Sso-{}-1!", random_token()));
+                let id = self.create_user(&mut store, &identity.email, &password, true);
+                if let Some(user) = store.users.get_mut(&id) {
+                    user.metadata.display_name = identity.name.clone();
+                }
+                id
+            }
+        };
+
+        self.start_session(&mut store, &user_id)
+    }
+
+    async fn signout(&self, token: &str) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        let claims = self.session_claims(&store, token).ok_or_else(|| {
+            AuthError::SignOutError(
+                "Failed to logout with status 403 Forbidden: Session not found".to_string(),
+            )
+        })?;
+
+        // GoTrue's default `global` scope ends every session the user has.
+        store.revoke_sessions(&claims.sub);
+        Ok(())
+    }
+
+    async fn signup(
+        &self,
+        email: &Email,
+        password: &Password,
+        _redirect_to: Option<&str>,
+    ) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        if store.user_by_email(&email.normalized()).is_some() {
+            return Err(AuthError::EmailAlreadyInUse.into());
+        }
+        self.create_user(&mut store, email, password, self.auto_confirm);
+        Ok(())
+    }
+
+    async fn unenroll_factor(&self, token: &str, factor_id: &str) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        let claims = self.mfa_claims(&store, token)?;
+        let user = store
+            .users
+            .get_mut(&claims.sub)
+            .ok_or(AuthError::UserNotFound)?;
+
+        let index = user
+            .factors
+            .iter()
+            .position(|f| f.id == factor_id)
+            .ok_or(AuthError::FactorNotFound)?;
+        if user.factors[index].verified && claims.aal != "aal2" {
+            return Err(AuthError::MfaRequired.into());
+        }
+
+        user.factors.remove(index);
+        Ok(())
+    }
+
+    async fn update_user(
+        &self,
+        token: &str,
+        _user_id: &str,
+        update: &UserUpdate,
+        _redirect_to: Option<&str>,
+    ) -> AppResult<UserProfile> {
+        let mut store = self.store.lock().await;
+        let claims = self
+            .session_claims(&store, token)
+            .ok_or(AuthError::InvalidToken)?;
+
+        let new_email = update.email.as_ref().map(Email::normalized);
+        if let Some(new_email) = &new_email
+            && store
+                .users
+                .values()
+                .any(|u| u.id != claims.sub && &u.email == new_email)
+        {
+            return Err(AuthError::EmailAlreadyInUse.into());
+        }
+
+        let user = store
+            .users
+            .get_mut(&claims.sub)
+            .ok_or(AuthError::UserNotFound)?;
+        if let Some(password) = &update.password {
+            if &user.password == password {
+                return Err(ValidationError::InvalidInput(
+                    "New password should be different from the old password".to_string(),
+                )
+                .into());
+            }
+            user.password = password.clone();
+        }
+        if let Some(new_email) = new_email {
+            user.pending_email = (new_email != user.email).then_some(new_email);
+        }
+        if let Some(metadata) = &update.metadata {
+            user.metadata = metadata.clone();
+        }
+
+        Ok(user.profile())
+    }
+
+    async fn verify_factor(
+        &self,
+        token: &str,
+        factor_id: &str,
+        challenge_id: &str,
+        code: &str,
+    ) -> AppResult<AuthSession> {
+        let mut store = self.store.lock().await;
+        let claims = self.mfa_claims(&store, token)?;
+
+        let totp_secret = match store
+            .users
+            .get(&claims.sub)
+            .and_then(|u| u.factors.iter().find(|f| f.id == factor_id))
+            .map(|f| &f.kind)
+            .ok_or(AuthError::FactorNotFound)?
+        {
+            FactorKind::Totp { secret } => Some(secret.clone()),
+            FactorKind::Phone { .. } => None,
+        };
+
+        // Challenges are single-use whether or not the code turns out to be right.
+        let challenge = store
+            .challenges
+            .remove(challenge_id)
+            .filter(|c| c.factor_id == factor_id && c.expires_at > unix_now())
+            .ok_or(AuthError::InvalidMfaCode)?;
+
+        let code_valid = match (totp_secret, challenge.code) {
+            (Some(secret), _) => TOTP::new(totp_rs::Algorithm::SHA1, 6, 1, 30, secret)
+                .map_err(AppError::internal)?
+                .check_current(code)
+                .map_err(AppError::internal)?,
+            (None, Some(sent)) => bool::from(sent.as_bytes().ct_eq(code.as_bytes())),
+            (None, None) => false,
+        };
+        if !code_valid {
+            return Err(AuthError::InvalidMfaCode.into());
+        }
+
+        if let Some(factor) = store
+            .users
+            .get_mut(&claims.sub)
+            .and_then(|u| u.factors.iter_mut().find(|f| f.id == factor_id))
+        {
+            factor.verified = true;
+        }
+        if let Some(session) = store.sessions.get_mut(&claims.session_id) {
+            session.aal = "aal2".to_string();
+        }
+
+        self.issue_session(&mut store, &claims.session_id, &claims.sub)
+    }
+
+    async fn verify_otp(&self, verification: &OtpVerification) -> AppResult<AuthSession> {
+        let mut store = self.store.lock().await;
+        let token_hash = match verification {
+            OtpVerification::TokenHash(token_hash) => Some(token_hash.clone()),
+            OtpVerification::Code { email, code } => {
+                let user_id = store.user_by_email(&email.normalized()).map(|u| &u.id);
+                store
+                    .otps
+                    .iter()
+                    .find(|(_, otp)| Some(&otp.user_id) == user_id && &otp.code == code)
+                    .map(|(token_hash, _)| token_hash.clone())
+            }
+        };
+
+        let otp = token_hash
+            .and_then(|token_hash| store.otps.remove(&token_hash))
+            .filter(|otp| otp.expires_at > unix_now())
+            .ok_or(AuthError::InvalidOtp)?;
+
+        self.start_session(&mut store, &otp.user_id)
+    }
+
+    async fn verify_password(&self, email: &Email, password: &Password) -> AppResult<()> {
+        let store = self.store.lock().await;
+        store
+            .user_by_email(&email.normalized())
+            .filter(|u| &u.password == password)
+            .map(|_| ())
+            .ok_or_else(|| AuthError::InvalidCurrentPassword.into())
+    }
+}
+
+/// Hex, like the token hashes in GoTrue's recovery links.
+fn random_code() -> String {
+    format!("{:06}", rand::rng().next_u32() % 1_000_000)
+}
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,37 @@
+use secrecy::ExposeSecret;
+use tokio::sync::Mutex;
+
+use crate::domain::{
+    error::app_error::AppResult, interfaces::email_sender::EmailSender, types::email::Email,
+};
+
+#[derive(Debug, Clone)]
+pub struct SentEmail {
+    pub to: String,
+    pub subject: String,
+    pub html_body: String,
+}
+
+/// Outbox for the memory backend; tests read it instead of polling Mailpit.
+#[derive(Default)]
+pub struct InMemoryEmailSender {
+    sent: Mutex<Vec<SentEmail>>,
+}
+
+impl InMemoryEmailSender {
+    pub async fn sent(&self) -> Vec<SentEmail> {
+        self.sent.lock().await.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for InMemoryEmailSender {
+    async fn send(&self, to: &Email, subject: &str, html_body: &str) -> AppResult<()> {
+        self.sent.lock().await.push(SentEmail {
+            to: to.as_ref().expose_secret().to_string(),
+            subject: subject.to_string(),
+            html_body: html_body.to_string(),
+        });
+        Ok(())
+    }
+}
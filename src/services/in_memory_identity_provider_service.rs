@@ -0,0 +1,190 @@
+use std::{collections::HashMap, sync::Arc};
+
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, SsoError, ValidationError},
+        interfaces::identity_provider_service::IdentityProviderService,
+        types::sso::{FederatedIdentity, IdentityProvider, NewIdentityProvider},
+    },
+    services::in_memory_membership_service::InMemoryMembershipService,
+};
+
+#[derive(Default)]
+struct Store {
+    providers: HashMap<String, IdentityProvider>,
+    /// (issuer, subject) -> user id.
+    identities: HashMap<(String, String), String>,
+}
+
+/// Identity providers for the memory backend. First logins become members through the
+/// shared [`InMemoryMembershipService`], standing in for `link_federated_identity`.
+pub struct InMemoryIdentityProviderService {
+    store: Mutex<Store>,
+    memberships: Arc<RwLock<InMemoryMembershipService>>,
+}
+
+impl InMemoryIdentityProviderService {
+    pub fn new(memberships: Arc<RwLock<InMemoryMembershipService>>) -> Self {
+        Self {
+            store: Mutex::new(Store::default()),
+            memberships,
+        }
+    }
+}
+
+fn now() -> AppResult<String> {
+    OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .map_err(AppError::internal)
+}
+
+#[async_trait::async_trait]
+impl IdentityProviderService for InMemoryIdentityProviderService {
+    async fn create_provider(
+        &self,
+        _token: &str,
+        practice_id: &str,
+        provider: &NewIdentityProvider,
+    ) -> AppResult<IdentityProvider> {
+        let mut store = self.store.lock().await;
+        if store.providers.values().any(|p| {
+            p.practice_id == practice_id
+                && p.issuer == provider.issuer
+                && p.client_id == provider.client_id
+        }) {
+            return Err(ValidationError::InvalidInput(
+                "This provider is already configured for the practice".to_string(),
+            )
+            .into());
+        }
+
+        let created = IdentityProvider {
+            id: uuid::Uuid::new_v4().to_string(),
+            practice_id: practice_id.to_string(),
+            name: provider.name.clone(),
+            issuer: provider.issuer.clone(),
+            client_id: provider.client_id.clone(),
+            client_secret: provider.client_secret.clone(),
+            default_role: provider.default_role,
+            email_domains: provider.email_domains.clone(),
+            created_at: now()?,
+            approved_at: None,
+        };
+        store.providers.insert(created.id.clone(), created.clone());
+        Ok(created)
+    }
+
+    async fn delete_provider(
+        &self,
+        _token: &str,
+        practice_id: &str,
+        provider_id: &str,
+    ) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        match store.providers.get(provider_id) {
+            Some(p) if p.practice_id == practice_id => {
+                store.providers.remove(provider_id);
+                Ok(())
+            }
+            _ => Err(SsoError::ProviderNotFound.into()),
+        }
+    }
+
+    async fn approve_provider(
+        &self,
+        practice_id: &str,
+        provider_id: &str,
+    ) -> AppResult<IdentityProvider> {
+        let mut store = self.store.lock().await;
+        match store.providers.get_mut(provider_id) {
+            Some(p) if p.practice_id == practice_id => {
+                if p.approved_at.is_none() {
+                    p.approved_at = Some(now()?);
+                }
+                Ok(p.clone())
+            }
+            _ => Err(SsoError::ProviderNotFound.into()),
+        }
+    }
+
+    async fn get_provider(&self, provider_id: &str) -> AppResult<IdentityProvider> {
+        self.store
+            .lock()
+            .await
+            .providers
+            .get(provider_id)
+            .cloned()
+            .ok_or_else(|| SsoError::ProviderNotFound.into())
+    }
+
+    async fn linked_user(
+        &self,
+        provider: &IdentityProvider,
+        identity: &FederatedIdentity,
+    ) -> AppResult<Option<String>> {
+        let key = (provider.issuer.clone(), identity.subject.clone());
+        Ok(self.store.lock().await.identities.get(&key).cloned())
+    }
+
+    async fn link_identity(
+        &self,
+        provider: &IdentityProvider,
+        identity: &FederatedIdentity,
+        user_id: &str,
+        new_link: bool,
+    ) -> AppResult<()> {
+        let mut store = self.store.lock().await;
+        if store
+            .providers
+            .get(&provider.id)
+            .is_none_or(|p| p.approved_at.is_none())
+        {
+            return Err(SsoError::ProviderNotFound.into());
+        }
+
+        let key = (provider.issuer.clone(), identity.subject.clone());
+        match store.identities.get(&key) {
+            Some(linked) if linked == user_id => {}
+            None if new_link => {
+                store.identities.insert(key, user_id.to_string());
+            }
+            _ => {
+                return Err(SsoError::ProviderError(
+                    "This identity is linked to a different account".to_string(),
+                )
+                .into());
+            }
+        }
+
+        let memberships = self.memberships.read().await;
+        match memberships.membership(&provider.practice_id, user_id).await {
+            Some(member) if !member.is_active => Err(SsoError::MembershipInactive.into()),
+            Some(_) => Ok(()),
+            None => {
+                memberships
+                    .add_member(&provider.practice_id, user_id, &[provider.default_role])
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn list_providers(
+        &self,
+        _token: &str,
+        practice_id: &str,
+    ) -> AppResult<Vec<IdentityProvider>> {
+        let store = self.store.lock().await;
+        let mut providers: Vec<IdentityProvider> = store
+            .providers
+            .values()
+            .filter(|p| p.practice_id == practice_id)
+            .cloned()
+            .collect();
+        providers.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(providers)
+    }
+}
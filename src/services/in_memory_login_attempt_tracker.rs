@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        interfaces::login_attempt_tracker::LoginAttemptTracker,
+        types::{
+            email::Email,
+            login_attempt::{AttemptState, LockoutPolicy, attempt_keys},
+        },
+    },
+    utils::clock::unix_now,
+};
+
+/// Per-process attempt counts. Fine for a single API instance; several instances each keep
+/// their own counts, so use [`SupabaseLoginAttemptTracker`] behind a load balancer.
+///
+/// [`SupabaseLoginAttemptTracker`]: crate::services::supabase_login_attempt_tracker::SupabaseLoginAttemptTracker
+pub struct InMemoryLoginAttemptTracker {
+    account_policy: LockoutPolicy,
+    client_policy: LockoutPolicy,
+    attempts: Mutex<HashMap<String, AttemptState>>,
+}
+
+impl Default for InMemoryLoginAttemptTracker {
+    fn default() -> Self {
+        Self::new(LockoutPolicy::per_account(), LockoutPolicy::per_client_ip())
+    }
+}
+
+impl InMemoryLoginAttemptTracker {
+    pub fn new(account_policy: LockoutPolicy, client_policy: LockoutPolicy) -> Self {
+        Self {
+            account_policy,
+            client_policy,
+            attempts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn keys(&self, email: &Email, client_ip: Option<&str>) -> Vec<(String, LockoutPolicy)> {
+        attempt_keys(
+            &email.normalized(),
+            client_ip,
+            self.account_policy,
+            self.client_policy,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl LoginAttemptTracker for InMemoryLoginAttemptTracker {
+    async fn check(&self, email: &Email, client_ip: Option<&str>) -> AppResult<()> {
+        let now = unix_now();
+        let attempts = self.attempts.lock().await;
+        let retry_after = self
+            .keys(email, client_ip)
+            .iter()
+            .filter_map(|(key, _)| attempts.get(key)?.retry_after(now))
+            .max();
+
+        match retry_after {
+            Some(retry_after_secs) => Err(AuthError::AccountLocked { retry_after_secs }.into()),
+            None => Ok(()),
+        }
+    }
+
+    async fn record_failure(&self, email: &Email, client_ip: Option<&str>) -> AppResult<()> {
+        let now = unix_now();
+        let mut attempts = self.attempts.lock().await;
+        for (key, policy) in self.keys(email, client_ip) {
+            let state = attempts.entry(key).or_default();
+            *state = state.after_failure(&policy, now);
+        }
+        // Drop keys whose failures have aged out so the map doesn't grow without bound.
+        let window = self
+            .account_policy
+            .window_secs
+            .max(self.client_policy.window_secs);
+        attempts.retain(|_, s| s.locked_until > now || now - s.last_failure_at <= window);
+        Ok(())
+    }
+
+    async fn record_success(&self, email: &Email) -> AppResult<()> {
+        let mut attempts = self.attempts.lock().await;
+        for (key, _) in self.keys(email, None) {
+            attempts.remove(&key);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tokio::sync::Mutex;
+
+use crate::domain::{
+    error::app_error::{AppError, AppResult, MembershipError},
+    interfaces::membership_service::MembershipService,
+    types::{
+        membership::{PracticeMember, PracticeRoleSet},
+        practice_role::PracticeRole,
+    },
+};
+
+struct Membership {
+    practice_id: String,
+    member: PracticeMember,
+}
+
+/// Memberships for the memory backend. There is no RLS to consult, so every caller may see
+/// and change every membership; only the last-owner invariant is enforced.
+#[derive(Default)]
+pub struct InMemoryMembershipService {
+    memberships: Mutex<Vec<Membership>>,
+}
+
+impl InMemoryMembershipService {
+    /// Seeds a membership, standing in for the `create_practice` RPC and accepted invitations.
+    pub async fn add_member(
+        &self,
+        practice_id: &str,
+        user_id: &str,
+        roles: &[PracticeRole],
+    ) -> AppResult<PracticeMember> {
+        let member = PracticeMember {
+            membership_id: uuid::Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            is_active: true,
+            created_at: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .map_err(AppError::internal)?,
+            roles: roles.to_vec(),
+        };
+
+        self.memberships.lock().await.push(Membership {
+            practice_id: practice_id.to_string(),
+            member: member.clone(),
+        });
+        Ok(member)
+    }
+
+    /// `user_id`'s membership in the practice, active or not.
+    pub async fn membership(&self, practice_id: &str, user_id: &str) -> Option<PracticeMember> {
+        self.memberships
+            .lock()
+            .await
+            .iter()
+            .find(|m| m.practice_id == practice_id && m.member.user_id == user_id)
+            .map(|m| m.member.clone())
+    }
+}
+
+fn find<'a>(
+    memberships: &'a mut [Membership],
+    practice_id: &str,
+    membership_id: &str,
+) -> AppResult<&'a mut PracticeMember> {
+    memberships
+        .iter_mut()
+        .find(|m| m.practice_id == practice_id && m.member.membership_id == membership_id)
+        .map(|m| &mut m.member)
+        .ok_or_else(|| MembershipError::MembershipNotFound.into())
+}
+
+/// Whether `membership_id` is the only active owner left in the practice.
+fn is_last_owner(memberships: &[Membership], practice_id: &str, membership_id: &str) -> bool {
+    let owners: Vec<&str> = memberships
+        .iter()
+        .filter(|m| {
+            m.practice_id == practice_id
+                && m.member.is_active
+                && m.member.has_role(PracticeRole::Owner)
+        })
+        .map(|m| m.member.membership_id.as_str())
+        .collect();
+    owners == [membership_id]
+}
+
+#[async_trait::async_trait]
+impl MembershipService for InMemoryMembershipService {
+    async fn add_role(
+        &self,
+        _token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        role: PracticeRole,
+    ) -> AppResult<PracticeMember> {
+        let mut memberships = self.memberships.lock().await;
+        let member = find(&mut memberships, practice_id, membership_id)?;
+        if member.has_role(role) {
+            return Err(MembershipError::RoleAlreadyAssigned.into());
+        }
+        member.roles.push(role);
+        Ok(member.clone())
+    }
+
+    async fn list_members(
+        &self,
+        _token: &str,
+        practice_id: &str,
+    ) -> AppResult<Vec<PracticeMember>> {
+        let memberships = self.memberships.lock().await;
+        Ok(memberships
+            .iter()
+            .filter(|m| m.practice_id == practice_id)
+            .map(|m| m.member.clone())
+            .collect())
+    }
+
+    async fn list_user_roles(
+        &self,
+        _token: &str,
+        user_id: &str,
+    ) -> AppResult<Vec<PracticeRoleSet>> {
+        let memberships = self.memberships.lock().await;
+        Ok(memberships
+            .iter()
+            .filter(|m| m.member.user_id == user_id && m.member.is_active)
+            .map(|m| PracticeRoleSet {
+                practice_id: m.practice_id.clone(),
+                roles: m.member.roles.clone(),
+            })
+            .collect())
+    }
+
+    async fn remove_role(
+        &self,
+        _token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        role: PracticeRole,
+    ) -> AppResult<PracticeMember> {
+        let mut memberships = self.memberships.lock().await;
+        if role == PracticeRole::Owner && is_last_owner(&memberships, practice_id, membership_id) {
+            return Err(MembershipError::LastOwner.into());
+        }
+
+        let member = find(&mut memberships, practice_id, membership_id)?;
+        if !member.has_role(role) {
+            return Err(MembershipError::RoleNotAssigned.into());
+        }
+        member.roles.retain(|r| *r != role);
+        Ok(member.clone())
+    }
+
+    async fn set_membership_active(
+        &self,
+        _token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        is_active: bool,
+    ) -> AppResult<PracticeMember> {
+        let mut memberships = self.memberships.lock().await;
+        if !is_active && is_last_owner(&memberships, practice_id, membership_id) {
+            return Err(MembershipError::LastOwner.into());
+        }
+
+        let member = find(&mut memberships, practice_id, membership_id)?;
+        member.is_active = is_active;
+        Ok(member.clone())
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use tokio::sync::Mutex;
+
+use crate::{
+    domain::{
+        error::app_error::AppResult, interfaces::session_activity_tracker::SessionActivityTracker,
+        types::session::SessionActivity,
+    },
+    utils::clock::unix_now,
+};
+
+/// Past this many records, ones idle for longer than [`RETENTION_SECS`] are dropped.
+const PRUNE_THRESHOLD: usize = 10_000;
+/// Expired records are kept this long so a session can't be revived by a refreshed token.
+const RETENTION_SECS: i64 = 30 * 24 * 3600;
+
+/// Per-process activity records. Behind a load balancer each instance only sees the
+/// requests it served, so use [`SupabaseSessionActivityTracker`] there.
+///
+/// [`SupabaseSessionActivityTracker`]: crate::services::supabase_session_activity_tracker::SupabaseSessionActivityTracker
+#[derive(Default)]
+pub struct InMemorySessionActivityTracker {
+    sessions: Mutex<HashMap<String, SessionActivity>>,
+}
+
+#[async_trait::async_trait]
+impl SessionActivityTracker for InMemorySessionActivityTracker {
+    async fn get(&self, session_id: &str) -> AppResult<Option<SessionActivity>> {
+        Ok(self.sessions.lock().await.get(session_id).cloned())
+    }
+
+    async fn list_for_user(&self, user_id: &str) -> AppResult<Vec<SessionActivity>> {
+        let mut sessions: Vec<SessionActivity> = self
+            .sessions
+            .lock()
+            .await
+            .values()
+            .filter(|s| s.user_id == user_id)
+            .cloned()
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_seen_at));
+        Ok(sessions)
+    }
+
+    async fn save(&self, activity: &SessionActivity) -> AppResult<()> {
+        let mut sessions = self.sessions.lock().await;
+        if sessions.len() > PRUNE_THRESHOLD {
+            let now = unix_now();
+            sessions.retain(|_, s| now - s.last_seen_at <= RETENTION_SECS);
+        }
+        sessions.insert(activity.session_id.clone(), activity.clone());
+        Ok(())
+    }
+
+    async fn remove(&self, session_id: &str) -> AppResult<()> {
+        self.sessions.lock().await.remove(session_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use tokio::sync::Mutex;
+
+use crate::domain::{
+    error::app_error::AppResult, interfaces::sms_gateway::SmsGateway,
+    types::phone_number::PhoneNumber,
+};
+
+#[derive(Debug, Clone)]
+pub struct SentSms {
+    pub to: String,
+    pub body: String,
+}
+
+/// Outbox for hermetic tests, which read codes from it instead of a handset.
+#[derive(Default)]
+pub struct InMemorySmsGateway {
+    sent: Mutex<Vec<SentSms>>,
+}
+
+impl InMemorySmsGateway {
+    pub async fn sent(&self) -> Vec<SentSms> {
+        self.sent.lock().await.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl SmsGateway for InMemorySmsGateway {
+    async fn send(&self, to: &PhoneNumber, body: &str) -> AppResult<()> {
+        self.sent.lock().await.push(SentSms {
+            to: to.as_ref().to_string(),
+            body: body.to_string(),
+        });
+        Ok(())
+    }
+}
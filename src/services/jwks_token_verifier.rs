@@ -0,0 +1,237 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use jsonwebtoken::{
+    Algorithm, DecodingKey, Header, Validation, decode, decode_header, errors::ErrorKind, jwk::Jwk,
+};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::domain::{
+    error::app_error::{AppError, AppResult, AuthError},
+    interfaces::token_verifier::TokenVerifier,
+    types::session::TokenClaims,
+};
+
+/// How long a fetched key set is trusted before it is refetched.
+const JWKS_TTL: Duration = Duration::from_secs(600);
+/// Unknown `kid`s trigger a refetch (key rotation), but no more often than this so forged
+/// tokens can't make us hammer the JWKS endpoint.
+const DEFAULT_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+const ASYMMETRIC_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::EdDSA,
+];
+
+pub struct JwtSettings {
+    /// `None` (the memory backend) rejects asymmetric tokens without fetching anything.
+    pub jwks_url: Option<String>,
+    pub issuer: String,
+    pub audience: String,
+    /// Shared secret for HS256 tokens; `None` rejects them outright.
+    pub hs256_secret: Option<SecretString>,
+}
+
+#[derive(Deserialize)]
+struct JwksResponse {
+    keys: Vec<Value>,
+}
+
+struct CachedKey {
+    key: DecodingKey,
+    /// `alg` advertised by the JWK, if any; tokens must then use exactly that.
+    algorithm: Option<String>,
+}
+
+#[derive(Default)]
+struct JwksCache {
+    keys: HashMap<String, CachedKey>,
+    fetched_at: Option<Instant>,
+}
+
+/// Verifies Supabase access tokens against the project's JWKS, caching keys by `kid`.
+pub struct JwksTokenVerifier {
+    client: reqwest::Client,
+    settings: JwtSettings,
+    min_refresh_interval: Duration,
+    cache: RwLock<JwksCache>,
+    /// When the key set was last requested, whether or not that worked. Held while
+    /// fetching, so concurrent misses wait for one fetch instead of each starting their own.
+    last_attempt: Mutex<Option<Instant>>,
+}
+
+impl JwksTokenVerifier {
+    pub fn new(settings: JwtSettings) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            settings,
+            min_refresh_interval: DEFAULT_MIN_REFRESH_INTERVAL,
+            cache: RwLock::new(JwksCache::default()),
+            last_attempt: Mutex::new(None),
+        }
+    }
+
+    pub fn with_min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    fn validation(&self, algorithm: Algorithm) -> Validation {
+        let mut validation = Validation::new(algorithm);
+        validation.set_issuer(&[&self.settings.issuer]);
+        validation.set_audience(&[&self.settings.audience]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        validation
+    }
+
+    async fn fetch_keys(&self, jwks_url: &str) -> AppResult<HashMap<String, CachedKey>> {
+        let response = self
+            .client
+            .get(jwks_url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(AppError::internal)?;
+
+        let jwks = response
+            .json::<JwksResponse>()
+            .await
+            .map_err(AppError::internal)?;
+
+        // Skip keys we can't use (unknown key types, symmetric keys) rather than failing
+        // the whole set.
+        let keys = jwks
+            .keys
+            .into_iter()
+            .filter_map(|value| serde_json::from_value::<Jwk>(value).ok())
+            .filter_map(|jwk| {
+                let kid = jwk.common.key_id.clone()?;
+                let key = DecodingKey::from_jwk(&jwk).ok()?;
+                let algorithm = jwk.common.key_algorithm.map(|alg| alg.to_string());
+                Some((kid, CachedKey { key, algorithm }))
+            })
+            .collect();
+
+        Ok(keys)
+    }
+
+    async fn key_for(
+        &self,
+        jwks_url: &str,
+        kid: &str,
+        algorithm: Algorithm,
+    ) -> AppResult<DecodingKey> {
+        let algorithm = format!("{algorithm:?}");
+        let matches = |cached: &CachedKey| {
+            cached
+                .algorithm
+                .as_deref()
+                .is_none_or(|advertised| advertised == algorithm)
+        };
+
+        {
+            let cache = self.cache.read().await;
+            let fresh = cache.fetched_at.is_some_and(|t| t.elapsed() < JWKS_TTL);
+            if fresh && let Some(cached) = cache.keys.get(kid) {
+                return if matches(cached) {
+                    Ok(cached.key.clone())
+                } else {
+                    Err(AuthError::InvalidToken.into())
+                };
+            }
+        }
+
+        // Fetch without holding the cache lock, so tokens with cached keys keep verifying
+        // while the JWKS endpoint is slow.
+        let mut last_attempt = self.last_attempt.lock().await;
+        let due = {
+            let cache = self.cache.read().await;
+            let stale = cache.fetched_at.is_none_or(|t| t.elapsed() >= JWKS_TTL);
+            (stale || !cache.keys.contains_key(kid))
+                && last_attempt.is_none_or(|t| t.elapsed() >= self.min_refresh_interval)
+        };
+
+        if due {
+            *last_attempt = Some(Instant::now());
+            let fetched = self.fetch_keys(jwks_url).await;
+            let mut cache = self.cache.write().await;
+            match fetched {
+                Ok(keys) => {
+                    cache.keys = keys;
+                    cache.fetched_at = Some(Instant::now());
+                }
+                // Keep serving the previous keys if the endpoint hiccups.
+                Err(e) if !cache.keys.is_empty() => {
+                    tracing::warn!(error = %e, "Failed to refresh JWKS; using cached keys");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        drop(last_attempt);
+
+        let cache = self.cache.read().await;
+        if cache.fetched_at.is_none() {
+            return Err(AppError::internal(anyhow::anyhow!(
+                "JWKS unavailable; retrying after the refresh interval"
+            )));
+        }
+        match cache.keys.get(kid) {
+            Some(cached) if matches(cached) => Ok(cached.key.clone()),
+            _ => Err(AuthError::InvalidToken.into()),
+        }
+    }
+
+    async fn decoding_key(&self, header: &Header) -> AppResult<DecodingKey> {
+        if header.alg == Algorithm::HS256 {
+            let secret = self
+                .settings
+                .hs256_secret
+                .as_ref()
+                .ok_or(AuthError::InvalidToken)?
+                .expose_secret();
+            return Ok(DecodingKey::from_base64_secret(secret)
+                .unwrap_or_else(|_| DecodingKey::from_secret(secret.as_bytes())));
+        }
+
+        if !ASYMMETRIC_ALGORITHMS.contains(&header.alg) {
+            return Err(AuthError::InvalidToken.into());
+        }
+
+        let jwks_url = self
+            .settings
+            .jwks_url
+            .as_deref()
+            .ok_or(AuthError::InvalidToken)?;
+        let kid = header.kid.as_deref().ok_or(AuthError::InvalidToken)?;
+        self.key_for(jwks_url, kid, header.alg).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenVerifier for JwksTokenVerifier {
+    async fn verify(&self, token: &str) -> AppResult<TokenClaims> {
+        let header = decode_header(token).map_err(|_| AuthError::InvalidToken)?;
+        let key = self.decoding_key(&header).await?;
+
+        let data = decode::<TokenClaims>(token, &key, &self.validation(header.alg)).map_err(
+            |e| match e.kind() {
+                ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
+                _ => AuthError::InvalidToken,
+            },
+        )?;
+
+        Ok(data.claims)
+    }
+}
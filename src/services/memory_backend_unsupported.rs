@@ -0,0 +1,329 @@
+use crate::domain::{
+    error::app_error::{AppError, AppResult},
+    interfaces::{
+        appointment_service::AppointmentService, audit_log_service::AuditLogService,
+        client_service::ClientService, invitation_service::InvitationService,
+        practice_service::PracticeService, team_service::TeamService,
+    },
+    types::{
+        appointment::{
+            Appointment, AppointmentFilter, AppointmentSeries, AppointmentUpdate, NewAppointment,
+            NewAppointmentSeries,
+        },
+        audit::{AuditFilter, AuditPage},
+        client::{Client, ClientAssignment, ClientSearch, ClientUpdate, NewClient},
+        email::Email,
+        invitation::{Invitation, InvitationPreview},
+        practice::{Practice, PracticeName},
+        practice_role::PracticeRole,
+        team::{Team, TeamName},
+    },
+};
+
+/// Stands in for the services that only exist in Postgres when `BACKEND=memory`, so their
+/// routes answer `503 memory_backend_unsupported` straight away instead of reaching for a
+/// Supabase that isn't there.
+pub struct MemoryBackendUnsupported;
+
+fn unsupported<T>(feature: &'static str) -> AppResult<T> {
+    Err(AppError::MemoryBackendUnsupported(feature))
+}
+
+#[async_trait::async_trait]
+impl AppointmentService for MemoryBackendUnsupported {
+    async fn cancel_appointment_series(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _series_id: &str,
+        _from: &str,
+        _reason: Option<&str>,
+    ) -> AppResult<AppointmentSeries> {
+        unsupported("Appointments")
+    }
+
+    async fn create_appointment(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _appointment: &NewAppointment,
+    ) -> AppResult<Appointment> {
+        unsupported("Appointments")
+    }
+
+    async fn create_appointment_series(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _series: &NewAppointmentSeries,
+    ) -> AppResult<AppointmentSeries> {
+        unsupported("Appointments")
+    }
+
+    async fn get_appointment(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _appointment_id: &str,
+    ) -> AppResult<Appointment> {
+        unsupported("Appointments")
+    }
+
+    async fn get_appointment_series(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _series_id: &str,
+    ) -> AppResult<AppointmentSeries> {
+        unsupported("Appointments")
+    }
+
+    async fn list_appointments(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _filter: &AppointmentFilter,
+    ) -> AppResult<Vec<Appointment>> {
+        unsupported("Appointments")
+    }
+
+    async fn update_appointment(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _appointment_id: &str,
+        _update: &AppointmentUpdate,
+    ) -> AppResult<Appointment> {
+        unsupported("Appointments")
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogService for MemoryBackendUnsupported {
+    async fn list_audit_log(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _filter: &AuditFilter,
+    ) -> AppResult<AuditPage> {
+        unsupported("Audit logs")
+    }
+}
+
+#[async_trait::async_trait]
+impl ClientService for MemoryBackendUnsupported {
+    async fn assign_client(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _client_id: &str,
+        _assignment: &ClientAssignment,
+    ) -> AppResult<Client> {
+        unsupported("Clients")
+    }
+
+    async fn create_client(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _client: &NewClient,
+    ) -> AppResult<Client> {
+        unsupported("Clients")
+    }
+
+    async fn delete_client(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _client_id: &str,
+    ) -> AppResult<()> {
+        unsupported("Clients")
+    }
+
+    async fn get_client(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _client_id: &str,
+    ) -> AppResult<Client> {
+        unsupported("Clients")
+    }
+
+    async fn search_clients(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _search: &ClientSearch,
+    ) -> AppResult<Vec<Client>> {
+        unsupported("Clients")
+    }
+
+    async fn update_client(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _client_id: &str,
+        _update: &ClientUpdate,
+    ) -> AppResult<Client> {
+        unsupported("Clients")
+    }
+}
+
+#[async_trait::async_trait]
+impl InvitationService for MemoryBackendUnsupported {
+    async fn accept_invitation(&self, _token: &str, _token_hash: &str) -> AppResult<Invitation> {
+        unsupported("Invitations")
+    }
+
+    async fn create_invitation(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _email: &Email,
+        _roles: &[PracticeRole],
+        _token_hash: &str,
+    ) -> AppResult<Invitation> {
+        unsupported("Invitations")
+    }
+
+    async fn find_invitation(&self, _token_hash: &str) -> AppResult<InvitationPreview> {
+        unsupported("Invitations")
+    }
+
+    async fn list_invitations(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+    ) -> AppResult<Vec<Invitation>> {
+        unsupported("Invitations")
+    }
+
+    async fn resend_invitation(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _invitation_id: &str,
+        _token_hash: &str,
+    ) -> AppResult<Invitation> {
+        unsupported("Invitations")
+    }
+
+    async fn revoke_invitation(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _invitation_id: &str,
+    ) -> AppResult<Invitation> {
+        unsupported("Invitations")
+    }
+}
+
+/// Practices themselves are unsupported, but the auth routes also ask for practice
+/// settings. No practice can set any here, so those get the defaults.
+#[async_trait::async_trait]
+impl PracticeService for MemoryBackendUnsupported {
+    async fn create_practice(&self, _token: &str, _name: &PracticeName) -> AppResult<Practice> {
+        unsupported("Practices")
+    }
+
+    async fn delete_practice(&self, _token: &str, _practice_id: &str) -> AppResult<()> {
+        unsupported("Practices")
+    }
+
+    async fn get_practice(&self, _token: &str, _practice_id: &str) -> AppResult<Practice> {
+        unsupported("Practices")
+    }
+
+    async fn list_practices(&self, _token: &str) -> AppResult<Vec<Practice>> {
+        unsupported("Practices")
+    }
+
+    async fn rename_practice(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _name: &PracticeName,
+    ) -> AppResult<Practice> {
+        unsupported("Practices")
+    }
+
+    async fn set_idle_timeout(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _idle_timeout_secs: Option<i64>,
+    ) -> AppResult<Practice> {
+        unsupported("Practices")
+    }
+
+    async fn set_passwordless_signin(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _allowed: bool,
+    ) -> AppResult<Practice> {
+        unsupported("Practices")
+    }
+
+    async fn session_idle_timeout(&self, _token: &str) -> AppResult<Option<i64>> {
+        Ok(None)
+    }
+
+    async fn passwordless_signin_allowed(&self, _token: &str) -> AppResult<bool> {
+        Ok(true)
+    }
+}
+
+#[async_trait::async_trait]
+impl TeamService for MemoryBackendUnsupported {
+    async fn add_team_member(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _team_id: &str,
+        _user_id: &str,
+    ) -> AppResult<Team> {
+        unsupported("Teams")
+    }
+
+    async fn create_team(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _name: &TeamName,
+    ) -> AppResult<Team> {
+        unsupported("Teams")
+    }
+
+    async fn delete_team(&self, _token: &str, _practice_id: &str, _team_id: &str) -> AppResult<()> {
+        unsupported("Teams")
+    }
+
+    async fn get_team(&self, _token: &str, _practice_id: &str, _team_id: &str) -> AppResult<Team> {
+        unsupported("Teams")
+    }
+
+    async fn list_teams(&self, _token: &str, _practice_id: &str) -> AppResult<Vec<Team>> {
+        unsupported("Teams")
+    }
+
+    async fn remove_team_member(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _team_id: &str,
+        _user_id: &str,
+    ) -> AppResult<Team> {
+        unsupported("Teams")
+    }
+
+    async fn rename_team(
+        &self,
+        _token: &str,
+        _practice_id: &str,
+        _team_id: &str,
+        _name: &TeamName,
+    ) -> AppResult<Team> {
+        unsupported("Teams")
+    }
+}
@@ -1 +1,27 @@
+pub mod file_sms_gateway;
+pub mod http_oidc_client;
+pub mod in_memory_audit_sink;
+pub mod in_memory_auth_service;
+pub mod in_memory_email_sender;
+pub mod in_memory_identity_provider_service;
+pub mod in_memory_login_attempt_tracker;
+pub mod in_memory_membership_service;
+pub mod in_memory_session_activity_tracker;
+pub mod in_memory_sms_gateway;
+pub mod jwks_token_verifier;
+pub mod memory_backend_unsupported;
+pub mod postgrest;
+pub mod smtp_email_sender;
+pub mod supabase_appointment_service;
+pub mod supabase_audit_log_service;
+pub mod supabase_audit_sink;
 pub mod supabase_auth_service;
+pub mod supabase_client_service;
+pub mod supabase_identity_provider_service;
+pub mod supabase_invitation_service;
+pub mod supabase_login_attempt_tracker;
+pub mod supabase_membership_service;
+pub mod supabase_practice_service;
+pub mod supabase_session_activity_tracker;
+pub mod supabase_team_service;
+pub mod twilio_sms_gateway;
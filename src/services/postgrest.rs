@@ -0,0 +1,125 @@
+use reqwest::{Method, RequestBuilder, StatusCode};
+use secrecy::{ExposeSecret, SecretString};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Error returned by PostgREST, with the Postgres SQLSTATE when one was reported.
+#[derive(Debug)]
+pub struct PostgrestError {
+    pub status: Option<StatusCode>,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl PostgrestError {
+    fn transport(message: String) -> Self {
+        Self {
+            status: None,
+            code: None,
+            message,
+        }
+    }
+
+    /// RLS `with check` failures and explicit `42501` raises both land here.
+    pub fn is_permission_denied(&self) -> bool {
+        self.code.as_deref() == Some("42501")
+            || matches!(
+                self.status,
+                Some(StatusCode::UNAUTHORIZED) | Some(StatusCode::FORBIDDEN)
+            )
+    }
+
+    pub fn is_unique_violation(&self) -> bool {
+        self.code.as_deref() == Some("23505")
+    }
+
+    pub fn is_foreign_key_violation(&self) -> bool {
+        self.code.as_deref() == Some("23503")
+    }
+
+    /// `23514`, a table `check` constraint.
+    pub fn is_check_violation(&self) -> bool {
+        self.code.as_deref() == Some("23514")
+    }
+
+    /// `23P01`; RPCs also raise it for "conflicts with an open request" checks.
+    pub fn is_exclusion_violation(&self) -> bool {
+        self.code.as_deref() == Some("23P01")
+    }
+
+    /// `P0002` (`no_data_found`) is what our RPCs raise for missing rows.
+    pub fn is_not_found(&self) -> bool {
+        self.code.as_deref() == Some("P0002")
+    }
+
+    pub fn is_invalid_input(&self) -> bool {
+        matches!(
+            self.code.as_deref(),
+            Some("22P02") | Some("22023") | Some("22007")
+        )
+    }
+}
+
+impl std::fmt::Display for PostgrestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.status, &self.code) {
+            (Some(status), Some(code)) => write!(f, "{status} ({code}): {}", self.message),
+            (Some(status), None) => write!(f, "{status}: {}", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Thin PostgREST client that forwards the caller's JWT so row level security is
+/// evaluated as that user.
+pub struct PostgrestClient {
+    pub client: reqwest::Client,
+    pub rest_url: String,
+    pub supabase_anon_key: SecretString,
+}
+
+impl PostgrestClient {
+    pub fn new(supabase_url: &str, supabase_anon_key: SecretString) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            rest_url: format!("{supabase_url}/rest/v1"),
+            supabase_anon_key,
+        }
+    }
+
+    pub fn request(&self, method: Method, path: &str, token: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}/{}", self.rest_url, path))
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+    }
+
+    pub async fn send<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, PostgrestError> {
+        let resp = request
+            .send()
+            .await
+            .map_err(|e| PostgrestError::transport(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            return Err(PostgrestError {
+                status: Some(status),
+                code: body.get("code").and_then(Value::as_str).map(str::to_string),
+                message: body
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("PostgREST request failed")
+                    .to_string(),
+            });
+        }
+
+        serde_json::from_value(body)
+            .map_err(|e| PostgrestError::transport(format!("Failed to parse response: {e}")))
+    }
+}
@@ -0,0 +1,55 @@
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, header::ContentType},
+};
+use secrecy::ExposeSecret;
+
+use crate::domain::{
+    error::app_error::{AppError, AppResult},
+    interfaces::email_sender::EmailSender,
+    types::email::Email,
+};
+
+pub struct SmtpEmailSender {
+    pub transport: AsyncSmtpTransport<Tokio1Executor>,
+    pub from: String,
+}
+
+impl SmtpEmailSender {
+    /// Plain SMTP without auth or TLS, which is what Mailpit and local relays expect.
+    pub fn new(smtp_host: &str, smtp_port: u16, from: String) -> Self {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(smtp_host)
+            .port(smtp_port)
+            .build();
+
+        Self { transport, from }
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, to: &Email, subject: &str, html_body: &str) -> AppResult<()> {
+        let to = to
+            .as_ref()
+            .expose_secret()
+            .parse::<Mailbox>()
+            .map_err(AppError::internal)?;
+
+        let from = self.from.parse::<Mailbox>().map_err(AppError::internal)?;
+
+        let message = Message::builder()
+            .from(from)
+            .to(to)
+            .subject(subject)
+            .header(ContentType::TEXT_HTML)
+            .body(html_body.to_string())
+            .map_err(AppError::internal)?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(AppError::internal)?;
+
+        Ok(())
+    }
+}
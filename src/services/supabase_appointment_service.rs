@@ -0,0 +1,332 @@
+use reqwest::Method;
+use secrecy::SecretString;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, AppointmentError, ValidationError},
+        interfaces::appointment_service::AppointmentService,
+        types::appointment::{
+            Appointment, AppointmentFilter, AppointmentLocation, AppointmentSeries,
+            AppointmentStatus, AppointmentUpdate, NewAppointment, NewAppointmentSeries,
+        },
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+    utils::clock::format_iso_date,
+};
+
+/// Appointment columns plus the client, which is `null` when RLS hides it.
+const APPOINTMENT_SELECT: &str = "*,client:clients(id,mrn,first_name,last_name,preferred_name)";
+const SERIES_SELECT: &str =
+    "*,appointments(*,client:clients(id,mrn,first_name,last_name,preferred_name))";
+
+#[derive(Deserialize)]
+struct SeriesId {
+    id: String,
+}
+
+pub struct SupabaseAppointmentService {
+    pub postgrest: PostgrestClient,
+}
+
+impl SupabaseAppointmentService {
+    pub fn new(supabase_url: String, supabase_anon_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_anon_key),
+        }
+    }
+
+    fn map_error(error: PostgrestError) -> AppError {
+        if error.is_not_found() {
+            AppointmentError::AppointmentNotFound.into()
+        } else if error.is_permission_denied() {
+            AppointmentError::AppointmentForbidden.into()
+        } else if error.is_exclusion_violation() {
+            AppointmentError::ClinicianDoubleBooked.into()
+        } else if error.is_foreign_key_violation() {
+            // `trg_appointments_check_parties` explains whether the clinician or client failed.
+            AppointmentError::InvalidParticipant(error.message).into()
+        } else if error.is_invalid_input() || error.is_check_violation() {
+            ValidationError::InvalidInput(error.message).into()
+        } else {
+            AppointmentError::AppointmentRequestError(error.to_string()).into()
+        }
+    }
+
+    fn map_series_error(error: PostgrestError) -> AppError {
+        if error.is_not_found() {
+            AppointmentError::SeriesNotFound.into()
+        } else {
+            Self::map_error(error)
+        }
+    }
+}
+
+fn location_columns(row: &mut Map<String, Value>, location: &AppointmentLocation) {
+    row.insert("location_kind".into(), json!(location.kind()));
+    row.insert("location".into(), json!(location.location()));
+    row.insert("telehealth_url".into(), json!(location.telehealth_url()));
+}
+
+fn update_row(update: &AppointmentUpdate, current: &Appointment) -> Value {
+    let mut row = Map::new();
+    if let Some(clinician_id) = &update.clinician_id {
+        row.insert("clinician_id".into(), json!(clinician_id));
+    }
+    if let Some(slot) = &update.slot {
+        row.insert("starts_at".into(), json!(slot.starts_at()));
+        row.insert("ends_at".into(), json!(slot.ends_at()));
+    }
+    if let Some(location) = &update.location {
+        location_columns(&mut row, location);
+    }
+    if let Some(status) = update.status {
+        // `trg_appointments_touch` stamps status_changed_at to match.
+        row.insert("status".into(), json!(status.as_str()));
+        if status == AppointmentStatus::Cancelled {
+            row.insert(
+                "cancellation_reason".into(),
+                json!(update.cancellation_reason),
+            );
+        }
+    }
+    if update.departs_from_series(current) {
+        row.insert("is_exception".into(), json!(true));
+    }
+    Value::Object(row)
+}
+
+#[async_trait::async_trait]
+impl AppointmentService for SupabaseAppointmentService {
+    async fn cancel_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        series_id: &str,
+        from: &str,
+        reason: Option<&str>,
+    ) -> AppResult<AppointmentSeries> {
+        // The function takes only the series id, so make sure it is in this practice first.
+        self.get_appointment_series(token, practice_id, series_id)
+            .await?;
+
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/cancel_appointment_series", token)
+            .json(&json!({
+                "p_series_id": series_id,
+                "p_from": from,
+                "p_reason": reason,
+            }));
+
+        self.postgrest
+            .send::<Value>(request)
+            .await
+            .map_err(Self::map_series_error)?;
+
+        self.get_appointment_series(token, practice_id, series_id)
+            .await
+    }
+
+    async fn create_appointment(
+        &self,
+        token: &str,
+        practice_id: &str,
+        appointment: &NewAppointment,
+    ) -> AppResult<Appointment> {
+        let mut row = Map::new();
+        row.insert("practice_id".into(), json!(practice_id));
+        row.insert("client_id".into(), json!(appointment.client_id));
+        row.insert("clinician_id".into(), json!(appointment.clinician_id));
+        row.insert("starts_at".into(), json!(appointment.slot.starts_at()));
+        row.insert("ends_at".into(), json!(appointment.slot.ends_at()));
+        location_columns(&mut row, &appointment.location);
+
+        let request = self
+            .postgrest
+            .request(Method::POST, "appointments", token)
+            .header("Prefer", "return=representation")
+            .query(&[("select", APPOINTMENT_SELECT)])
+            .json(&Value::Object(row));
+
+        self.postgrest
+            .send::<Vec<Appointment>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AppointmentError::AppointmentRequestError("Insert returned no row".to_string())
+                    .into()
+            })
+    }
+
+    async fn create_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        series: &NewAppointmentSeries,
+    ) -> AppResult<AppointmentSeries> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/create_appointment_series", token)
+            .json(&json!({
+                "p_practice_id": practice_id,
+                "p_client_id": series.client_id,
+                "p_clinician_id": series.clinician_id,
+                "p_rrule": series.rule.to_string(),
+                "p_timezone": series.timezone.as_ref(),
+                "p_start_date": format_iso_date(series.start_date),
+                "p_start_time": format!(
+                    "{:02}:{:02}",
+                    series.start_time.hour(),
+                    series.start_time.minute()
+                ),
+                "p_duration_minutes": series.duration_minutes,
+                "p_exdates": series.exdates.iter().copied().map(format_iso_date).collect::<Vec<_>>(),
+                "p_location_kind": series.location.kind(),
+                "p_location": series.location.location(),
+                "p_telehealth_url": series.location.telehealth_url(),
+            }));
+
+        let created = self
+            .postgrest
+            .send::<SeriesId>(request)
+            .await
+            .map_err(Self::map_series_error)?;
+
+        self.get_appointment_series(token, practice_id, &created.id)
+            .await
+    }
+
+    async fn get_appointment(
+        &self,
+        token: &str,
+        practice_id: &str,
+        appointment_id: &str,
+    ) -> AppResult<Appointment> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "appointments", token)
+            .query(&[
+                ("id", format!("eq.{appointment_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", APPOINTMENT_SELECT.to_string()),
+            ]);
+
+        self.postgrest
+            .send::<Vec<Appointment>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppointmentError::AppointmentNotFound.into())
+    }
+
+    async fn get_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        series_id: &str,
+    ) -> AppResult<AppointmentSeries> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "appointment_series", token)
+            .query(&[
+                ("id", format!("eq.{series_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", SERIES_SELECT.to_string()),
+                ("appointments.order", "starts_at.asc".to_string()),
+            ]);
+
+        self.postgrest
+            .send::<Vec<AppointmentSeries>>(request)
+            .await
+            .map_err(Self::map_series_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppointmentError::SeriesNotFound.into())
+    }
+
+    async fn list_appointments(
+        &self,
+        token: &str,
+        practice_id: &str,
+        filter: &AppointmentFilter,
+    ) -> AppResult<Vec<Appointment>> {
+        let mut query = vec![
+            ("practice_id", format!("eq.{practice_id}")),
+            ("starts_at", format!("lt.{}", filter.range.to())),
+            ("ends_at", format!("gt.{}", filter.range.from())),
+            ("select", APPOINTMENT_SELECT.to_string()),
+            ("order", "starts_at.asc,id.asc".to_string()),
+        ];
+        if let Some(clinician_id) = &filter.clinician_id {
+            query.push(("clinician_id", format!("eq.{clinician_id}")));
+        }
+        if let Some(client_id) = &filter.client_id {
+            query.push(("client_id", format!("eq.{client_id}")));
+        }
+        if let Some(status) = filter.status {
+            query.push(("status", format!("eq.{}", status.as_str())));
+        }
+
+        let request = self
+            .postgrest
+            .request(Method::GET, "appointments", token)
+            .query(&query);
+
+        self.postgrest
+            .send::<Vec<Appointment>>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn update_appointment(
+        &self,
+        token: &str,
+        practice_id: &str,
+        appointment_id: &str,
+        update: &AppointmentUpdate,
+    ) -> AppResult<Appointment> {
+        let current = self
+            .get_appointment(token, practice_id, appointment_id)
+            .await?;
+        update.check_against(&current)?;
+        if update.is_empty() {
+            return Ok(current);
+        }
+
+        // Matching on the status read above keeps a concurrent change from slipping past
+        // the transition check.
+        let request = self
+            .postgrest
+            .request(Method::PATCH, "appointments", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{appointment_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("status", format!("eq.{}", current.status.as_str())),
+                ("select", APPOINTMENT_SELECT.to_string()),
+            ])
+            .json(&update_row(update, &current));
+
+        let updated = self
+            .postgrest
+            .send::<Vec<Appointment>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        match updated.into_iter().next() {
+            Some(appointment) => Ok(appointment),
+            None => {
+                let current = self
+                    .get_appointment(token, practice_id, appointment_id)
+                    .await?;
+                update.check_against(&current)?;
+                Err(AppointmentError::AppointmentNotFound.into())
+            }
+        }
+    }
+}
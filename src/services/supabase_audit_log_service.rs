@@ -0,0 +1,96 @@
+use reqwest::Method;
+use secrecy::SecretString;
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, AuditError, ValidationError},
+        interfaces::audit_log_service::AuditLogService,
+        types::audit::{AuditEntry, AuditFilter, AuditPage},
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+};
+
+const AUDIT_SELECT: &str =
+    "id,occurred_at,table_name,operation,actor_user_id,practice_id,row_id,before_data,after_data";
+
+pub struct SupabaseAuditLogService {
+    pub postgrest: PostgrestClient,
+}
+
+impl SupabaseAuditLogService {
+    pub fn new(supabase_url: String, supabase_anon_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_anon_key),
+        }
+    }
+
+    fn map_error(error: PostgrestError) -> AppError {
+        if error.is_invalid_input() {
+            ValidationError::InvalidInput(error.message).into()
+        } else {
+            AuditError::AuditRequestError(error.to_string()).into()
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogService for SupabaseAuditLogService {
+    async fn list_audit_log(
+        &self,
+        token: &str,
+        practice_id: &str,
+        filter: &AuditFilter,
+    ) -> AppResult<AuditPage> {
+        let mut query = vec![
+            ("practice_id", format!("eq.{practice_id}")),
+            ("select", AUDIT_SELECT.to_string()),
+            ("order", "id.desc".to_string()),
+            // One extra row tells us whether another page exists.
+            ("limit", (filter.limit + 1).to_string()),
+        ];
+        if let Some(table_name) = &filter.table_name {
+            query.push(("table_name", format!("eq.{table_name}")));
+        }
+        if let Some(operation) = &filter.operation {
+            query.push(("operation", format!("eq.{}", operation.as_ref())));
+        }
+        if let Some(actor_user_id) = &filter.actor_user_id {
+            query.push(("actor_user_id", format!("eq.{actor_user_id}")));
+        }
+        if let Some(row_id) = &filter.row_id {
+            query.push(("row_id", format!("eq.{row_id}")));
+        }
+        if let Some(from) = &filter.from {
+            query.push(("occurred_at", format!("gte.{from}")));
+        }
+        if let Some(to) = &filter.to {
+            query.push(("occurred_at", format!("lt.{to}")));
+        }
+        if let Some(cursor) = filter.cursor {
+            query.push(("id", format!("lt.{cursor}")));
+        }
+
+        let request = self
+            .postgrest
+            .request(Method::GET, "audit_log", token)
+            .query(&query);
+
+        let mut entries = self
+            .postgrest
+            .send::<Vec<AuditEntry>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        let next_cursor = if entries.len() > filter.limit {
+            entries.truncate(filter.limit);
+            entries.last().map(|e| e.id)
+        } else {
+            None
+        };
+
+        Ok(AuditPage {
+            entries: entries.into_iter().map(AuditEntry::with_changes).collect(),
+            next_cursor,
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use reqwest::Method;
+use secrecy::{ExposeSecret, SecretString};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuditError},
+        interfaces::audit_sink::AuditSink,
+        types::audit::AuthAuditEvent,
+    },
+    services::postgrest::PostgrestClient,
+};
+
+/// Writes to `auth_audit_events` with the service role, since failed signins have no user
+/// token and the table is closed to regular users.
+pub struct SupabaseAuditSink {
+    pub postgrest: PostgrestClient,
+    pub supabase_service_role_key: SecretString,
+}
+
+impl SupabaseAuditSink {
+    pub fn new(supabase_url: String, supabase_service_role_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_service_role_key.clone()),
+            supabase_service_role_key,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditSink for SupabaseAuditSink {
+    async fn record(&self, event: AuthAuditEvent) -> AppResult<()> {
+        let request = self
+            .postgrest
+            .request(
+                Method::POST,
+                "auth_audit_events",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .header("Prefer", "return=minimal")
+            .json(&event);
+
+        // `return=minimal` leaves an empty body, which `send` reads back as null.
+        self.postgrest
+            .send::<serde_json::Value>(request)
+            .await
+            .map_err(|e| AuditError::AuditRequestError(e.to_string()))?;
+
+        Ok(())
+    }
+}
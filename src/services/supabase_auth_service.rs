@@ -3,9 +3,19 @@ use secrecy::{ExposeSecret, SecretString};
 use serde_json::{Value, json};
 
 use crate::domain::{
-    error::app_error::{AppResult, AuthError},
+    error::app_error::{AppError, AppResult, AuthError, SsoError, ValidationError},
     interfaces::auth_service::AuthService,
-    types::{email::Email, password::Password},
+    types::{
+        email::Email,
+        mfa::{MfaChallenge, MfaFactor, PhoneEnrollment, SMS_RESEND_INTERVAL_SECS, TotpEnrollment},
+        otp::OtpVerification,
+        password::Password,
+        password_policy::PasswordViolation,
+        phone_number::PhoneNumber,
+        profile::{ProfileMetadata, UserProfile, UserUpdate},
+        session::AuthSession,
+        sso::FederatedIdentity,
+    },
 };
 
 pub struct SupabaseAuthService {
@@ -38,10 +48,320 @@ impl SupabaseAuthService {
             .or_else(|| value.get("error"))
             .and_then(|v| v.as_str())
     }
+
+    /// `/auth/v1/factors/{factor_id}[/{action}]`, with the id percent-encoded as a single
+    /// path segment.
+    fn factor_url(&self, factor_id: &str, action: Option<&str>) -> Result<Url, AuthError> {
+        let mut url = Url::parse(&format!("{}/auth/v1/factors", self.supabase_url))
+            .map_err(|e| AuthError::MfaError(format!("Failed to build request URL: {e}")))?;
+        url.path_segments_mut()
+            .map_err(|_| AuthError::MfaError("Supabase URL cannot have a path".to_string()))?
+            .push(factor_id)
+            .extend(action);
+        Ok(url)
+    }
+
+    fn mfa_error(status: StatusCode, body: &Value) -> AuthError {
+        match body.get("error_code").and_then(|v| v.as_str()) {
+            Some("mfa_factor_not_found") => AuthError::FactorNotFound,
+            Some("mfa_verification_failed") | Some("mfa_challenge_expired") => {
+                AuthError::InvalidMfaCode
+            }
+            Some("insufficient_aal") => AuthError::MfaRequired,
+            // The guard reads factors for tokens whose session may since have been signed out.
+            Some("session_not_found") => AuthError::InvalidToken,
+            // GoTrue's `[auth.mfa.phone] max_frequency`; the wait is only in the message.
+            Some("over_sms_send_rate_limit") => AuthError::SmsResendThrottled {
+                retry_after_secs: Self::error_message(body)
+                    .and_then(|msg| {
+                        msg.split(|c: char| !c.is_ascii_digit())
+                            .find_map(|n| n.parse().ok())
+                    })
+                    .unwrap_or(SMS_RESEND_INTERVAL_SECS as u64),
+            },
+            _ => {
+                let message = Self::error_message(body).unwrap_or("MFA request failed");
+                AuthError::MfaError(format!(
+                    "MFA request failed with status {status}: {message}"
+                ))
+            }
+        }
+    }
+
+    /// Parses the session GoTrue returns from its token and factor-verify endpoints.
+    fn session_from_value(value: &Value) -> Option<AuthSession> {
+        let expires_in = value.get("expires_in").and_then(Value::as_i64)?;
+        Some(AuthSession {
+            user_id: value.pointer("/user/id")?.as_str()?.to_string(),
+            access_token: value.get("access_token")?.as_str()?.to_string(),
+            refresh_token: value.get("refresh_token")?.as_str()?.to_string(),
+            expires_in,
+            expires_at: value
+                .get("expires_at")
+                .and_then(Value::as_i64)
+                .unwrap_or_else(|| {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs() as i64)
+                        .unwrap_or_default();
+                    now + expires_in
+                }),
+        })
+    }
+
+    /// Builds the profile from a GoTrue user object; unknown or malformed metadata reads as
+    /// empty.
+    /// First SSO login: creates a confirmed account with no password; the provider is the
+    /// way in. An existing account for the email is never taken over.
+    async fn create_identity_user(&self, identity: &FederatedIdentity) -> AppResult<()> {
+        let create_request = json!({
+            "email": identity.email.as_ref().expose_secret(),
+            "email_confirm": true,
+            "user_metadata": { "origin": "sso" },
+            "app_metadata": { "profile": { "display_name": identity.name } },
+        });
+        let resp = self
+            .client
+            .post(format!("{}/auth/v1/admin/users", self.supabase_url))
+            .header("apikey", self.supabase_service_role_key.expose_secret())
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.supabase_service_role_key.expose_secret()),
+            )
+            .json(&create_request)
+            .send()
+            .await
+            .map_err(|e| SsoError::SsoRequestError(format!("Failed to send request: {e}")))?;
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+        let exists = status == StatusCode::UNPROCESSABLE_ENTITY
+            || matches!(
+                body.get("error_code").and_then(Value::as_str),
+                Some("email_exists") | Some("user_already_exists")
+            );
+        if exists {
+            return Err(SsoError::AccountExists.into());
+        }
+        let message = Self::error_message(&body).unwrap_or("Create user failed");
+        Err(SsoError::SsoRequestError(format!(
+            "Failed to create user with status {status}: {message}"
+        ))
+        .into())
+    }
+
+    async fn admin_user_email(&self, user_id: &str) -> AppResult<String> {
+        let resp = self
+            .client
+            .get(format!(
+                "{}/auth/v1/admin/users/{user_id}",
+                self.supabase_url
+            ))
+            .header("apikey", self.supabase_service_role_key.expose_secret())
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.supabase_service_role_key.expose_secret()),
+            )
+            .send()
+            .await
+            .map_err(|e| SsoError::SsoRequestError(format!("Failed to send request: {e}")))?;
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        body.get("email")
+            .and_then(Value::as_str)
+            .filter(|_| status.is_success())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                let message = Self::error_message(&body).unwrap_or("Get user failed");
+                SsoError::SsoRequestError(format!(
+                    "Failed to get user with status {status}: {message}"
+                ))
+                .into()
+            })
+    }
+
+    fn profile_from_value(value: &Value) -> Option<UserProfile> {
+        Some(UserProfile {
+            user_id: value.get("id")?.as_str()?.to_string(),
+            email: value.get("email")?.as_str()?.to_string(),
+            pending_email: value
+                .get("new_email")
+                .and_then(Value::as_str)
+                .filter(|email| !email.is_empty())
+                .map(str::to_string),
+            metadata: value
+                .pointer("/app_metadata/profile")
+                .cloned()
+                .and_then(|metadata| serde_json::from_value::<ProfileMetadata>(metadata).ok())
+                .unwrap_or_default(),
+        })
+    }
+
+    fn profile_error(status: StatusCode, body: &Value) -> AppError {
+        let message = Self::error_message(body).unwrap_or("Profile request failed");
+        match body.get("error_code").and_then(|v| v.as_str()) {
+            Some("email_exists") => AuthError::EmailAlreadyInUse.into(),
+            // Our policy has already passed it, so only GoTrue's own breach check is news.
+            Some("weak_password") => ValidationError::WeakPassword(
+                body.pointer("/weak_password/reasons")
+                    .and_then(Value::as_array)
+                    .filter(|reasons| reasons.iter().any(|r| r == "pwned"))
+                    .map(|_| vec![PasswordViolation::Breached])
+                    .unwrap_or_default(),
+            )
+            .into(),
+            Some("same_password") => ValidationError::InvalidInput(message.to_string()).into(),
+            Some("reauthentication_needed") => AuthError::ReauthenticationRequired.into(),
+            _ if status == StatusCode::UNAUTHORIZED => AuthError::InvalidToken.into(),
+            _ => AuthError::ProfileError(format!(
+                "Profile request failed with status {status}: {message}"
+            ))
+            .into(),
+        }
+    }
+
+    fn factor_from_value(value: &Value) -> Option<MfaFactor> {
+        Some(MfaFactor {
+            id: value.get("id")?.as_str()?.to_string(),
+            factor_type: value.get("factor_type")?.as_str()?.to_string(),
+            friendly_name: value
+                .get("friendly_name")
+                .and_then(|v| v.as_str())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string),
+            status: value.get("status")?.as_str()?.to_string(),
+            phone: value
+                .get("phone")
+                .and_then(Value::as_str)
+                .filter(|phone| !phone.is_empty())
+                .map(|phone| format!("+{}", phone.trim_start_matches('+'))),
+        })
+    }
+
+    /// GoTrue can only sign out the caller's own sessions, so remote revocation goes through
+    /// the service-role-only `revoke_auth_sessions` RPC instead.
+    async fn revoke_auth_sessions(&self, user_id: &str, session_id: Option<&str>) -> AppResult<()> {
+        let url = format!("{}/rest/v1/rpc/revoke_auth_sessions", self.supabase_url);
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("apikey", self.supabase_service_role_key.expose_secret())
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.supabase_service_role_key.expose_secret()),
+            )
+            .header("Content-Type", "application/json")
+            .json(&json!({ "p_user_id": user_id, "p_session_id": session_id }))
+            .send()
+            .await
+            .map_err(|e| AuthError::RevokeSessionError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+
+        if status.is_success() {
+            Ok(())
+        } else {
+            let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+            let message = Self::error_message(&body).unwrap_or("Revoke sessions failed");
+
+            Err(AuthError::RevokeSessionError(format!(
+                "Failed to revoke sessions with status {status}: {message}"
+            ))
+            .into())
+        }
+    }
 }
 
 #[async_trait::async_trait]
 impl AuthService for SupabaseAuthService {
+    async fn challenge_factor(&self, token: &str, factor_id: &str) -> AppResult<MfaChallenge> {
+        let url = self.factor_url(factor_id, Some("challenge"))?;
+
+        let resp = self
+            .client
+            .post(url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+            .map_err(|e| AuthError::MfaError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            return Err(Self::mfa_error(status, &body).into());
+        }
+
+        let id = body
+            .get("id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AuthError::MfaError("No challenge id in response".to_string()))?;
+        let expires_at = body
+            .get("expires_at")
+            .and_then(|v| v.as_i64())
+            .unwrap_or_default();
+
+        Ok(MfaChallenge {
+            id: id.to_string(),
+            expires_at,
+        })
+    }
+
+    async fn create_confirmed_user(&self, email: &Email, password: &Password) -> AppResult<()> {
+        let url = format!("{}/auth/v1/admin/users", self.supabase_url);
+        let create_request = json!({
+            "email": email.as_ref().expose_secret(),
+            "password": password.as_ref().expose_secret(),
+            "email_confirm": true,
+            "user_metadata": { "origin": "invitation" },
+        });
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("apikey", self.supabase_service_role_key.expose_secret())
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.supabase_service_role_key.expose_secret()),
+            )
+            .header("Content-Type", "application/json")
+            .json(&create_request)
+            .send()
+            .await
+            .map_err(|e| AuthError::SignUpError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+        let error_code = body.get("error_code").and_then(|v| v.as_str());
+
+        if status == StatusCode::UNPROCESSABLE_ENTITY
+            || matches!(
+                error_code,
+                Some("email_exists") | Some("user_already_exists")
+            )
+        {
+            return Err(AuthError::EmailAlreadyInUse.into());
+        }
+
+        let message = Self::error_message(&body).unwrap_or("Create user failed");
+        Err(AuthError::SignUpError(format!(
+            "Failed to create user with status {status}: {message}"
+        ))
+        .into())
+    }
+
     async fn delete_user(&self, user_id: &str) -> AppResult<()> {
         let url = format!("{}/auth/v1/admin/users/{}", self.supabase_url, user_id);
 
@@ -76,6 +396,313 @@ impl AuthService for SupabaseAuthService {
         }
     }
 
+    async fn enroll_phone(
+        &self,
+        token: &str,
+        phone: &PhoneNumber,
+        friendly_name: Option<&str>,
+    ) -> AppResult<PhoneEnrollment> {
+        let url = format!("{}/auth/v1/factors", self.supabase_url);
+
+        let mut enroll_request = json!({
+            "factor_type": "phone",
+            "phone": phone.as_ref(),
+        });
+
+        if let Some(name) = friendly_name {
+            enroll_request["friendly_name"] = json!(name);
+        }
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .json(&enroll_request)
+            .send()
+            .await
+            .map_err(|e| AuthError::MfaError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            return Err(Self::mfa_error(status, &body).into());
+        }
+
+        let factor_id = body
+            .get("id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AuthError::MfaError("No factor id in response".to_string()))?;
+
+        Ok(PhoneEnrollment {
+            factor_id: factor_id.to_string(),
+            friendly_name: friendly_name.map(str::to_string),
+            phone: phone.as_ref().to_string(),
+        })
+    }
+
+    async fn enroll_totp(
+        &self,
+        token: &str,
+        friendly_name: Option<&str>,
+    ) -> AppResult<TotpEnrollment> {
+        let url = format!("{}/auth/v1/factors", self.supabase_url);
+
+        let mut enroll_request = json!({
+            "factor_type": "totp",
+            "issuer": "BreezeEHR",
+        });
+
+        if let Some(name) = friendly_name {
+            enroll_request["friendly_name"] = json!(name);
+        }
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .json(&enroll_request)
+            .send()
+            .await
+            .map_err(|e| AuthError::MfaError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            return Err(Self::mfa_error(status, &body).into());
+        }
+
+        let field = |value: Option<&Value>, name: &str| -> AppResult<String> {
+            value
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| AuthError::MfaError(format!("No {name} in response")).into())
+        };
+
+        let totp = body.get("totp");
+
+        Ok(TotpEnrollment {
+            factor_id: field(body.get("id"), "factor id")?,
+            friendly_name: friendly_name.map(str::to_string),
+            uri: field(totp.and_then(|t| t.get("uri")), "TOTP URI")?,
+            qr_code: field(totp.and_then(|t| t.get("qr_code")), "QR code")?,
+            secret: field(totp.and_then(|t| t.get("secret")), "TOTP secret")?,
+        })
+    }
+
+    async fn forgot_password(&self, email: &Email, redirect_to: Option<&str>) -> AppResult<()> {
+        let mut url =
+            Url::parse(&format!("{}/auth/v1/recover", self.supabase_url)).map_err(|e| {
+                AuthError::PasswordRecoveryError(format!("Failed to build request URL: {e}"))
+            })?;
+        if let Some(redirect) = redirect_to {
+            url.query_pairs_mut().append_pair("redirect_to", redirect);
+        }
+
+        let recover_request = json!({ "email": email.as_ref().expose_secret() });
+
+        let resp = self
+            .client
+            .post(url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Content-Type", "application/json")
+            .json(&recover_request)
+            .send()
+            .await
+            .map_err(|e| {
+                AuthError::PasswordRecoveryError(format!("Failed to send request: {e}"))
+            })?;
+
+        let status = resp.status();
+
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+        let message = Self::error_message(&body).unwrap_or("Password recovery failed");
+
+        Err(AuthError::PasswordRecoveryError(format!(
+            "Failed to send recovery email with status {status}: {message}"
+        ))
+        .into())
+    }
+
+    async fn get_user(&self, token: &str) -> AppResult<UserProfile> {
+        let url = format!("{}/auth/v1/user", self.supabase_url);
+
+        let resp = self
+            .client
+            .get(&url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {token}"))
+            .send()
+            .await
+            .map_err(|e| AuthError::ProfileError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            return Err(Self::profile_error(status, &body));
+        }
+
+        Self::profile_from_value(&body)
+            .ok_or_else(|| AuthError::ProfileError("No user in response".to_string()).into())
+    }
+
+    async fn list_factors(&self, token: &str) -> AppResult<Vec<MfaFactor>> {
+        let url = format!("{}/auth/v1/user", self.supabase_url);
+
+        let resp = self
+            .client
+            .get(&url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {token}"))
+            .send()
+            .await
+            .map_err(|e| AuthError::MfaError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            return Err(Self::mfa_error(status, &body).into());
+        }
+
+        // Supabase omits `factors` entirely for users that never enrolled one.
+        let factors = body
+            .get("factors")
+            .and_then(Value::as_array)
+            .map(|factors| factors.iter().filter_map(Self::factor_from_value).collect())
+            .unwrap_or_default();
+
+        Ok(factors)
+    }
+
+    async fn refresh_session(&self, refresh_token: &str) -> AppResult<AuthSession> {
+        let url = format!(
+            "{}/auth/v1/token?grant_type=refresh_token",
+            self.supabase_url
+        );
+        let refresh_request = json!({ "refresh_token": refresh_token });
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Content-Type", "application/json")
+            .json(&refresh_request)
+            .send()
+            .await
+            .map_err(|e| AuthError::RefreshError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if status.is_client_error() {
+            return Err(AuthError::InvalidRefreshToken.into());
+        }
+
+        if !status.is_success() {
+            let message = Self::error_message(&body).unwrap_or("Refresh failed");
+            return Err(AuthError::RefreshError(format!(
+                "Failed to refresh session with status {status}: {message}"
+            ))
+            .into());
+        }
+
+        Self::session_from_value(&body)
+            .ok_or_else(|| AuthError::RefreshError("No session in response".to_string()).into())
+    }
+
+    async fn reset_password(&self, recovery_token: &str, new_password: &Password) -> AppResult<()> {
+        // Recovery links carry GoTrue's token hash, hex or `pkce_`-prefixed; it goes in the
+        // JSON body, so GoTrue alone decides whether it is valid.
+        if recovery_token.trim().is_empty() {
+            return Err(AuthError::InvalidRecoveryToken.into());
+        }
+
+        let verify_url = format!("{}/auth/v1/verify", self.supabase_url);
+        let verify_request = json!({
+            "type": "recovery",
+            "token_hash": recovery_token,
+        });
+
+        let resp = self
+            .client
+            .post(&verify_url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Content-Type", "application/json")
+            .json(&verify_request)
+            .send()
+            .await
+            .map_err(|e| {
+                AuthError::PasswordRecoveryError(format!("Failed to send request: {e}"))
+            })?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            let error_code = body.get("error_code").and_then(|v| v.as_str());
+            return match error_code {
+                Some("otp_expired") => Err(AuthError::ExpiredRecoveryToken.into()),
+                _ if status.is_client_error() => Err(AuthError::InvalidRecoveryToken.into()),
+                _ => {
+                    let message = Self::error_message(&body).unwrap_or("Token verification failed");
+                    Err(AuthError::PasswordRecoveryError(format!(
+                        "Failed to verify recovery token with status {status}: {message}"
+                    ))
+                    .into())
+                }
+            };
+        }
+
+        let access_token = body
+            .get("access_token")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| {
+                AuthError::PasswordRecoveryError("No access token in response".to_string())
+            })?;
+
+        let update_url = format!("{}/auth/v1/user", self.supabase_url);
+        let update_request = json!({ "password": new_password.as_ref().expose_secret() });
+
+        let resp = self
+            .client
+            .put(&update_url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {access_token}"))
+            .header("Content-Type", "application/json")
+            .json(&update_request)
+            .send()
+            .await
+            .map_err(|e| {
+                AuthError::PasswordRecoveryError(format!("Failed to send request: {e}"))
+            })?;
+
+        let status = resp.status();
+
+        if !status.is_success() {
+            let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+            let message = Self::error_message(&body).unwrap_or("Password update failed");
+            return Err(AuthError::PasswordRecoveryError(message.to_string()).into());
+        }
+
+        // The recovery session was only needed to set the new password.
+        if let Err(e) = self.signout(access_token).await {
+            tracing::warn!(error = %e, "Failed to revoke recovery session after password reset");
+        }
+
+        Ok(())
+    }
+
     async fn retrieve_user_id(&self, email: &Email) -> AppResult<String> {
         let mut url =
             Url::parse(&format!("{}/auth/v1/admin/users", self.supabase_url)).map_err(|e| {
@@ -156,7 +783,63 @@ impl AuthService for SupabaseAuthService {
         Ok(user_id.to_string())
     }
 
-    async fn signin(&self, email: &Email, password: &Password) -> AppResult<String> {
+    async fn revoke_session(&self, user_id: &str, session_id: &str) -> AppResult<()> {
+        self.revoke_auth_sessions(user_id, Some(session_id)).await
+    }
+
+    async fn revoke_user_sessions(&self, user_id: &str) -> AppResult<()> {
+        self.revoke_auth_sessions(user_id, None).await
+    }
+
+    async fn send_magic_link(&self, email: &Email, redirect_to: Option<&str>) -> AppResult<()> {
+        let mut url = Url::parse(&format!("{}/auth/v1/otp", self.supabase_url))
+            .map_err(|e| AuthError::OtpError(format!("Failed to build request URL: {e}")))?;
+        if let Some(redirect) = redirect_to {
+            url.query_pairs_mut().append_pair("redirect_to", redirect);
+        }
+
+        // `create_user: false` keeps this from doubling as a signup endpoint.
+        let otp_request = json!({
+            "email": email.as_ref().expose_secret(),
+            "create_user": false,
+        });
+
+        let resp = self
+            .client
+            .post(url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Content-Type", "application/json")
+            .json(&otp_request)
+            .send()
+            .await
+            .map_err(|e| AuthError::OtpError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        // GoTrue refuses unknown addresses when it may not create users; report success so
+        // callers can't tell which addresses have accounts.
+        if matches!(
+            body.get("error_code").and_then(|v| v.as_str()),
+            Some("otp_disabled") | Some("signup_disabled") | Some("user_not_found")
+        ) {
+            tracing::debug!("Magic link requested for an unknown address");
+            return Ok(());
+        }
+
+        let message = Self::error_message(&body).unwrap_or("Magic link request failed");
+        Err(AuthError::OtpError(format!(
+            "Magic link request failed with status {status}: {message}"
+        ))
+        .into())
+    }
+
+    async fn signin(&self, email: &Email, password: &Password) -> AppResult<AuthSession> {
         let url = format!("{}/auth/v1/token?grant_type=password", self.supabase_url);
         let signin_request = json!({
             "email": email.as_ref().expose_secret(),
@@ -180,18 +863,73 @@ impl AuthService for SupabaseAuthService {
             .map_err(|e| AuthError::SignInError(format!("Failed to parse response: {e}")))?;
 
         if !status.is_success() {
-            let message = Self::error_message(&resp_json).unwrap_or("Sign-in failed");
+            // Older GoTrue releases only say `invalid_grant` with this description.
+            let invalid_credentials = resp_json.get("error_code").and_then(Value::as_str)
+                == Some("invalid_credentials")
+                || (status == StatusCode::BAD_REQUEST
+                    && resp_json.get("error_description").and_then(Value::as_str)
+                        == Some("Invalid login credentials"));
+            if invalid_credentials {
+                return Err(AuthError::InvalidCredentials.into());
+            }
 
+            let message = Self::error_message(&resp_json).unwrap_or("Sign-in failed");
             return Err(AuthError::SignInError(message.to_string()).into());
         }
 
-        if let Some(token) = resp_json.get("access_token").and_then(|t| t.as_str()) {
-            Ok(token.to_string())
+        if let Some(session) = Self::session_from_value(&resp_json) {
+            Ok(session)
         } else {
-            Err(AuthError::SignInError("No access token in response".to_string()).into())
+            Err(AuthError::SignInError("No session in response".to_string()).into())
         }
     }
 
+    async fn signin_with_identity(
+        &self,
+        identity: &FederatedIdentity,
+        user_id: Option<&str>,
+    ) -> AppResult<AuthSession> {
+        let email = match user_id {
+            // The linked account, under whatever address it has now.
+            Some(user_id) => self.admin_user_email(user_id).await?,
+            None => {
+                self.create_identity_user(identity).await?;
+                identity.email.as_ref().expose_secret().to_string()
+            }
+        };
+
+        // GoTrue can't mint a session directly, so generate a magic link without sending
+        // it and redeem its token straight away.
+        let resp = self
+            .client
+            .post(format!("{}/auth/v1/admin/generate_link", self.supabase_url))
+            .header("apikey", self.supabase_service_role_key.expose_secret())
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.supabase_service_role_key.expose_secret()),
+            )
+            .json(&json!({ "type": "magiclink", "email": email }))
+            .send()
+            .await
+            .map_err(|e| SsoError::SsoRequestError(format!("Failed to send request: {e}")))?;
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+        let hashed_token = body
+            .get("hashed_token")
+            .or_else(|| body.pointer("/properties/hashed_token"))
+            .and_then(Value::as_str)
+            .filter(|_| status.is_success())
+            .ok_or_else(|| {
+                let message = Self::error_message(&body).unwrap_or("Generate link failed");
+                SsoError::SsoRequestError(format!(
+                    "Failed to generate link with status {status}: {message}"
+                ))
+            })?;
+
+        self.verify_otp(&OtpVerification::TokenHash(hashed_token.to_string()))
+            .await
+    }
+
     async fn signout(&self, token: &str) -> AppResult<()> {
         let url = format!("{}/auth/v1/logout", self.supabase_url);
 
@@ -292,4 +1030,208 @@ impl AuthService for SupabaseAuthService {
                 .into(),
         )
     }
+
+    async fn unenroll_factor(&self, token: &str, factor_id: &str) -> AppResult<()> {
+        let url = self.factor_url(factor_id, None)?;
+
+        let resp = self
+            .client
+            .delete(url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+            .map_err(|e| AuthError::MfaError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+        Err(Self::mfa_error(status, &body).into())
+    }
+
+    async fn update_user(
+        &self,
+        token: &str,
+        user_id: &str,
+        update: &UserUpdate,
+        redirect_to: Option<&str>,
+    ) -> AppResult<UserProfile> {
+        let mut body = Value::Null;
+
+        if update.email.is_some() || update.password.is_some() {
+            let mut url =
+                Url::parse(&format!("{}/auth/v1/user", self.supabase_url)).map_err(|e| {
+                    AuthError::ProfileError(format!("Failed to build request URL: {e}"))
+                })?;
+            if let Some(redirect) = redirect_to {
+                url.query_pairs_mut().append_pair("redirect_to", redirect);
+            }
+
+            let mut update_request = json!({});
+            if let Some(email) = &update.email {
+                update_request["email"] = json!(email.as_ref().expose_secret());
+            }
+            if let Some(password) = &update.password {
+                update_request["password"] = json!(password.as_ref().expose_secret());
+            }
+
+            let resp = self
+                .client
+                .put(url)
+                .header("apikey", self.supabase_anon_key.expose_secret())
+                .header("Authorization", format!("Bearer {token}"))
+                .header("Content-Type", "application/json")
+                .json(&update_request)
+                .send()
+                .await
+                .map_err(|e| AuthError::ProfileError(format!("Failed to send request: {e}")))?;
+
+            let status = resp.status();
+            body = resp.json::<Value>().await.unwrap_or(Value::Null);
+            if !status.is_success() {
+                return Err(Self::profile_error(status, &body));
+            }
+        }
+
+        // The profile lives in `app_metadata`, which only the service role can write, so
+        // users can't bypass the API's validation by updating their own `user_metadata`.
+        // GoTrue merges top-level keys, so this replaces just `profile`.
+        if let Some(metadata) = &update.metadata {
+            let resp = self
+                .client
+                .put(format!(
+                    "{}/auth/v1/admin/users/{user_id}",
+                    self.supabase_url
+                ))
+                .header("apikey", self.supabase_service_role_key.expose_secret())
+                .header(
+                    "Authorization",
+                    format!("Bearer {}", self.supabase_service_role_key.expose_secret()),
+                )
+                .header("Content-Type", "application/json")
+                .json(&json!({ "app_metadata": { "profile": metadata } }))
+                .send()
+                .await
+                .map_err(|e| AuthError::ProfileError(format!("Failed to send request: {e}")))?;
+
+            let status = resp.status();
+            body = resp.json::<Value>().await.unwrap_or(Value::Null);
+            if !status.is_success() {
+                return Err(Self::profile_error(status, &body));
+            }
+        }
+
+        Self::profile_from_value(&body)
+            .ok_or_else(|| AuthError::ProfileError("No user in response".to_string()).into())
+    }
+
+    async fn verify_factor(
+        &self,
+        token: &str,
+        factor_id: &str,
+        challenge_id: &str,
+        code: &str,
+    ) -> AppResult<AuthSession> {
+        let url = self.factor_url(factor_id, Some("verify"))?;
+        let verify_request = json!({
+            "challenge_id": challenge_id,
+            "code": code,
+        });
+
+        let resp = self
+            .client
+            .post(url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .json(&verify_request)
+            .send()
+            .await
+            .map_err(|e| AuthError::MfaError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            return Err(Self::mfa_error(status, &body).into());
+        }
+
+        Self::session_from_value(&body)
+            .ok_or_else(|| AuthError::MfaError("No session in response".to_string()).into())
+    }
+
+    async fn verify_otp(&self, verification: &OtpVerification) -> AppResult<AuthSession> {
+        let url = format!("{}/auth/v1/verify", self.supabase_url);
+        let verify_request = match verification {
+            OtpVerification::Code { email, code } => json!({
+                "type": "email",
+                "email": email.as_ref().expose_secret(),
+                "token": code,
+            }),
+            OtpVerification::TokenHash(token_hash) => json!({
+                "type": "email",
+                "token_hash": token_hash,
+            }),
+        };
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Content-Type", "application/json")
+            .json(&verify_request)
+            .send()
+            .await
+            .map_err(|e| AuthError::OtpError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+
+        if status.is_success() {
+            return Self::session_from_value(&body)
+                .ok_or_else(|| AuthError::OtpError("No session in response".to_string()).into());
+        }
+
+        if matches!(
+            status,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::NOT_FOUND
+        ) || body.get("error_code").and_then(|v| v.as_str()) == Some("otp_expired")
+        {
+            return Err(AuthError::InvalidOtp.into());
+        }
+
+        let message = Self::error_message(&body).unwrap_or("Verification failed");
+        Err(AuthError::OtpError(format!(
+            "Verification failed with status {status}: {message}"
+        ))
+        .into())
+    }
+
+    async fn verify_password(&self, email: &Email, password: &Password) -> AppResult<()> {
+        let session = self.signin(email, password).await.map_err(|e| match e {
+            AppError::Auth(AuthError::InvalidCredentials) => {
+                AuthError::InvalidCurrentPassword.into()
+            }
+            e => e,
+        })?;
+
+        // The password grant had to open a session; end just that one again.
+        let url = format!("{}/auth/v1/logout?scope=local", self.supabase_url);
+        let logout = self
+            .client
+            .post(&url)
+            .header("apikey", self.supabase_anon_key.expose_secret())
+            .header("Authorization", format!("Bearer {}", session.access_token))
+            .send()
+            .await;
+        if let Err(e) = logout.and_then(|resp| resp.error_for_status()) {
+            tracing::warn!(error = %e, "Failed to end password verification session");
+        }
+        Ok(())
+    }
 }
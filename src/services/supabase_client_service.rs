@@ -0,0 +1,306 @@
+use reqwest::Method;
+use secrecy::SecretString;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, ClientError, ValidationError},
+        interfaces::client_service::ClientService,
+        types::{
+            client::{Client, ClientAssignment, ClientSearch, ClientUpdate, NewClient},
+            emergency_contact::EmergencyContact,
+            postal_address::PostalAddress,
+        },
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+};
+
+pub struct SupabaseClientService {
+    pub postgrest: PostgrestClient,
+}
+
+impl SupabaseClientService {
+    pub fn new(supabase_url: String, supabase_anon_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_anon_key),
+        }
+    }
+
+    fn map_error(error: PostgrestError) -> AppError {
+        if error.is_not_found() {
+            ClientError::ClientNotFound.into()
+        } else if error.is_permission_denied() {
+            ClientError::ClientForbidden.into()
+        } else if error.is_unique_violation() {
+            // The only unique key besides the id is (practice_id, mrn).
+            ClientError::MrnTaken.into()
+        } else if error.is_invalid_input() || error.is_check_violation() {
+            ValidationError::InvalidInput(error.message).into()
+        } else {
+            ClientError::ClientRequestError(error.to_string()).into()
+        }
+    }
+}
+
+fn address_columns(row: &mut Map<String, Value>, address: Option<&PostalAddress>) {
+    row.insert("address_line1".into(), json!(address.map(|a| a.line1())));
+    row.insert(
+        "address_line2".into(),
+        json!(address.and_then(|a| a.line2())),
+    );
+    row.insert("city".into(), json!(address.map(|a| a.city())));
+    row.insert("state".into(), json!(address.map(|a| a.state())));
+    row.insert(
+        "postal_code".into(),
+        json!(address.map(|a| a.postal_code())),
+    );
+}
+
+fn emergency_contact_columns(row: &mut Map<String, Value>, contact: Option<&EmergencyContact>) {
+    row.insert(
+        "emergency_contact_name".into(),
+        json!(contact.map(|c| c.name())),
+    );
+    row.insert(
+        "emergency_contact_relationship".into(),
+        json!(contact.and_then(|c| c.relationship())),
+    );
+    row.insert(
+        "emergency_contact_phone".into(),
+        json!(contact.map(|c| c.phone().as_ref())),
+    );
+}
+
+fn insert_row(practice_id: &str, client: &NewClient) -> Value {
+    let mut row = Map::new();
+    row.insert("practice_id".into(), json!(practice_id));
+    if let Some(mrn) = &client.mrn {
+        row.insert("mrn".into(), json!(mrn.as_ref()));
+    }
+    row.insert("first_name".into(), json!(client.first_name.as_ref()));
+    row.insert("last_name".into(), json!(client.last_name.as_ref()));
+    row.insert(
+        "preferred_name".into(),
+        json!(client.preferred_name.as_ref().map(AsRef::as_ref)),
+    );
+    row.insert(
+        "date_of_birth".into(),
+        json!(client.date_of_birth.to_string()),
+    );
+    row.insert(
+        "phone".into(),
+        json!(client.phone.as_ref().map(AsRef::as_ref)),
+    );
+    address_columns(&mut row, client.address.as_ref());
+    emergency_contact_columns(&mut row, client.emergency_contact.as_ref());
+    Value::Object(row)
+}
+
+fn update_row(update: &ClientUpdate) -> Value {
+    let mut row = Map::new();
+    if let Some(mrn) = &update.mrn {
+        row.insert("mrn".into(), json!(mrn.as_ref()));
+    }
+    if let Some(first_name) = &update.first_name {
+        row.insert("first_name".into(), json!(first_name.as_ref()));
+    }
+    if let Some(last_name) = &update.last_name {
+        row.insert("last_name".into(), json!(last_name.as_ref()));
+    }
+    if let Some(preferred_name) = &update.preferred_name {
+        row.insert(
+            "preferred_name".into(),
+            json!(preferred_name.as_ref().map(AsRef::as_ref)),
+        );
+    }
+    if let Some(date_of_birth) = &update.date_of_birth {
+        row.insert("date_of_birth".into(), json!(date_of_birth.to_string()));
+    }
+    if let Some(phone) = &update.phone {
+        row.insert("phone".into(), json!(phone.as_ref().map(AsRef::as_ref)));
+    }
+    if let Some(address) = &update.address {
+        address_columns(&mut row, address.as_ref());
+    }
+    if let Some(contact) = &update.emergency_contact {
+        emergency_contact_columns(&mut row, contact.as_ref());
+    }
+    if let Some(status) = update.status {
+        // `trg_clients_touch` stamps or clears discharged_at to match.
+        row.insert("status".into(), json!(status.as_str()));
+    }
+    Value::Object(row)
+}
+
+#[async_trait::async_trait]
+impl ClientService for SupabaseClientService {
+    async fn assign_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+        assignment: &ClientAssignment,
+    ) -> AppResult<Client> {
+        let request = self
+            .postgrest
+            .request(Method::PATCH, "clients", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{client_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+            ])
+            .json(&json!({
+                "primary_clinician_id": assignment.primary_clinician_id,
+                "supervising_clinician_id": assignment.supervising_clinician_id,
+                "team_id": assignment.team_id,
+            }));
+
+        self.postgrest
+            .send::<Vec<Client>>(request)
+            .await
+            .map_err(|e| -> AppError {
+                // `trg_clients_check_assignment` explains which assignee it rejected.
+                if e.is_foreign_key_violation() {
+                    ClientError::InvalidAssignment(e.message).into()
+                } else {
+                    Self::map_error(e)
+                }
+            })?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ClientError::ClientNotFound.into())
+    }
+
+    async fn create_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client: &NewClient,
+    ) -> AppResult<Client> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "clients", token)
+            .header("Prefer", "return=representation")
+            .json(&insert_row(practice_id, client));
+
+        self.postgrest
+            .send::<Vec<Client>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                ClientError::ClientRequestError("Insert returned no row".to_string()).into()
+            })
+    }
+
+    async fn delete_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+    ) -> AppResult<()> {
+        let request = self
+            .postgrest
+            .request(Method::DELETE, "clients", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{client_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", "id".to_string()),
+            ]);
+
+        let deleted = self
+            .postgrest
+            .send::<Vec<Value>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        if deleted.is_empty() {
+            // Visible but not deleted means the delete policy filtered it out.
+            self.get_client(token, practice_id, client_id).await?;
+            return Err(ClientError::ClientForbidden.into());
+        }
+
+        Ok(())
+    }
+
+    async fn get_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+    ) -> AppResult<Client> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "clients", token)
+            .query(&[
+                ("id", format!("eq.{client_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+            ]);
+
+        self.postgrest
+            .send::<Vec<Client>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ClientError::ClientNotFound.into())
+    }
+
+    async fn search_clients(
+        &self,
+        token: &str,
+        practice_id: &str,
+        search: &ClientSearch,
+    ) -> AppResult<Vec<Client>> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/search_clients", token)
+            .json(&json!({
+                "p_practice_id": practice_id,
+                "p_name": search.name.as_deref().map(str::trim).filter(|n| !n.is_empty()),
+                "p_date_of_birth": search.date_of_birth.map(|d| d.to_string()),
+                "p_mrn": search.mrn.as_ref().map(AsRef::as_ref),
+                "p_status": search.status.map(|s| s.as_str()),
+            }));
+
+        self.postgrest
+            .send::<Vec<Client>>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn update_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+        update: &ClientUpdate,
+    ) -> AppResult<Client> {
+        if update.is_empty() {
+            return self.get_client(token, practice_id, client_id).await;
+        }
+
+        let request = self
+            .postgrest
+            .request(Method::PATCH, "clients", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{client_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+            ])
+            .json(&update_row(update));
+
+        let updated = self
+            .postgrest
+            .send::<Vec<Client>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        updated
+            .into_iter()
+            .next()
+            .ok_or_else(|| ClientError::ClientNotFound.into())
+    }
+}
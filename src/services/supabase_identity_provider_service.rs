@@ -0,0 +1,252 @@
+use reqwest::Method;
+use secrecy::{ExposeSecret, SecretString};
+use serde_json::json;
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, PracticeError, SsoError, ValidationError},
+        interfaces::identity_provider_service::IdentityProviderService,
+        types::sso::{FederatedIdentity, IdentityProvider, NewIdentityProvider},
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+};
+
+const PROVIDER_COLUMNS: &str =
+    "id,practice_id,name,issuer,client_id,default_role,email_domains,created_at,approved_at";
+
+pub struct SupabaseIdentityProviderService {
+    pub postgrest: PostgrestClient,
+    pub supabase_service_role_key: SecretString,
+}
+
+impl SupabaseIdentityProviderService {
+    pub fn new(
+        supabase_url: String,
+        supabase_anon_key: SecretString,
+        supabase_service_role_key: SecretString,
+    ) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_anon_key),
+            supabase_service_role_key,
+        }
+    }
+
+    fn map_error(error: PostgrestError) -> AppError {
+        if error.is_not_found() {
+            SsoError::ProviderNotFound.into()
+        } else if error.is_permission_denied() {
+            PracticeError::PracticeForbidden.into()
+        } else if error.is_unique_violation() {
+            ValidationError::InvalidInput(
+                "This provider is already configured for the practice".to_string(),
+            )
+            .into()
+        } else if error.is_invalid_input() {
+            ValidationError::InvalidInput(error.message).into()
+        } else {
+            SsoError::SsoRequestError(error.to_string()).into()
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IdentityProviderService for SupabaseIdentityProviderService {
+    async fn create_provider(
+        &self,
+        token: &str,
+        practice_id: &str,
+        provider: &NewIdentityProvider,
+    ) -> AppResult<IdentityProvider> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "practice_identity_providers", token)
+            .header("Prefer", "return=representation")
+            .query(&[("select", PROVIDER_COLUMNS)])
+            .json(&json!({
+                "practice_id": practice_id,
+                "name": provider.name,
+                "issuer": provider.issuer,
+                "client_id": provider.client_id,
+                "client_secret": provider.client_secret.as_ref().map(|s| s.expose_secret()),
+                "default_role": provider.default_role,
+                "email_domains": provider.email_domains,
+            }));
+
+        self.postgrest
+            .send::<Vec<IdentityProvider>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| PracticeError::PracticeForbidden.into())
+    }
+
+    async fn delete_provider(
+        &self,
+        token: &str,
+        practice_id: &str,
+        provider_id: &str,
+    ) -> AppResult<()> {
+        let request = self
+            .postgrest
+            .request(Method::DELETE, "practice_identity_providers", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{provider_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", "id".to_string()),
+            ]);
+
+        let deleted = self
+            .postgrest
+            .send::<Vec<serde_json::Value>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        if deleted.is_empty() {
+            return Err(SsoError::ProviderNotFound.into());
+        }
+        Ok(())
+    }
+
+    async fn approve_provider(
+        &self,
+        practice_id: &str,
+        provider_id: &str,
+    ) -> AppResult<IdentityProvider> {
+        let request = self
+            .postgrest
+            .request(
+                Method::POST,
+                "rpc/approve_identity_provider",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .query(&[("select", PROVIDER_COLUMNS)])
+            .json(&json!({
+                "p_practice_id": practice_id,
+                "p_provider_id": provider_id,
+            }));
+
+        self.postgrest
+            .send::<Vec<IdentityProvider>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| SsoError::ProviderNotFound.into())
+    }
+
+    async fn get_provider(&self, provider_id: &str) -> AppResult<IdentityProvider> {
+        let request = self
+            .postgrest
+            .request(
+                Method::GET,
+                "practice_identity_providers",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .query(&[
+                ("id", format!("eq.{provider_id}")),
+                ("practices.deleted_at", "is.null".to_string()),
+                (
+                    "select",
+                    format!("{PROVIDER_COLUMNS},client_secret,practices!inner(id)"),
+                ),
+            ]);
+
+        self.postgrest
+            .send::<Vec<IdentityProvider>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| SsoError::ProviderNotFound.into())
+    }
+
+    async fn linked_user(
+        &self,
+        provider: &IdentityProvider,
+        identity: &FederatedIdentity,
+    ) -> AppResult<Option<String>> {
+        let request = self
+            .postgrest
+            .request(
+                Method::GET,
+                "federated_identities",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .query(&[
+                ("issuer", format!("eq.{}", provider.issuer)),
+                ("subject", format!("eq.{}", identity.subject)),
+                ("select", "user_id".to_string()),
+            ]);
+
+        Ok(self
+            .postgrest
+            .send::<Vec<serde_json::Value>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .and_then(|row| row.get("user_id")?.as_str().map(str::to_string)))
+    }
+
+    async fn link_identity(
+        &self,
+        provider: &IdentityProvider,
+        identity: &FederatedIdentity,
+        user_id: &str,
+        new_link: bool,
+    ) -> AppResult<()> {
+        let request = self
+            .postgrest
+            .request(
+                Method::POST,
+                "rpc/link_federated_identity",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .json(&json!({
+                "p_provider_id": provider.id,
+                "p_subject": identity.subject,
+                "p_user_id": user_id,
+                "p_new_link": new_link,
+            }));
+
+        self.postgrest
+            .send::<serde_json::Value>(request)
+            .await
+            .map_err(|e| -> AppError {
+                if e.code.as_deref() == Some("42501") {
+                    SsoError::MembershipInactive.into()
+                } else if e.is_unique_violation() {
+                    SsoError::ProviderError(
+                        "This identity is linked to a different account".to_string(),
+                    )
+                    .into()
+                } else {
+                    Self::map_error(e)
+                }
+            })?;
+
+        Ok(())
+    }
+
+    async fn list_providers(
+        &self,
+        token: &str,
+        practice_id: &str,
+    ) -> AppResult<Vec<IdentityProvider>> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practice_identity_providers", token)
+            .query(&[
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", PROVIDER_COLUMNS.to_string()),
+                ("order", "name.asc".to_string()),
+            ]);
+
+        self.postgrest
+            .send::<Vec<IdentityProvider>>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+}
@@ -0,0 +1,207 @@
+use reqwest::Method;
+use secrecy::{ExposeSecret, SecretString};
+use serde_json::json;
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, InvitationError, ValidationError},
+        interfaces::invitation_service::InvitationService,
+        types::{
+            email::Email,
+            invitation::{Invitation, InvitationPreview, InvitationStatus},
+            practice_role::PracticeRole,
+        },
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+};
+
+const INVITATION_SELECT: &str =
+    "id,practice_id,email,role_codes,status,expires_at,accepted_at,revoked_at,created_at";
+
+pub struct SupabaseInvitationService {
+    pub postgrest: PostgrestClient,
+    pub supabase_anon_key: SecretString,
+}
+
+impl SupabaseInvitationService {
+    pub fn new(supabase_url: String, supabase_anon_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_anon_key.clone()),
+            supabase_anon_key,
+        }
+    }
+
+    fn map_error(error: PostgrestError) -> AppError {
+        if error.is_not_found() {
+            InvitationError::InvitationNotFound.into()
+        } else if error.is_permission_denied() {
+            InvitationError::CannotInvite.into()
+        } else if error.is_unique_violation() {
+            InvitationError::AlreadyMember.into()
+        } else if error.is_exclusion_violation() {
+            InvitationError::InvitationPending.into()
+        } else if error.is_invalid_input() {
+            ValidationError::InvalidInput(error.message).into()
+        } else {
+            InvitationError::InvitationRequestError(error.to_string()).into()
+        }
+    }
+
+    /// Reads through RLS first so closed invitations get a specific error rather than the
+    /// generic one the RPCs raise.
+    async fn get_open_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        invitation_id: &str,
+    ) -> AppResult<Invitation> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practice_invitations", token)
+            .query(&[
+                ("id", format!("eq.{invitation_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", INVITATION_SELECT.to_string()),
+            ]);
+
+        let invitation = self
+            .postgrest
+            .send::<Vec<Invitation>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or(InvitationError::InvitationNotFound)?;
+
+        // Lapsed invitations can still be resent or revoked.
+        match invitation.status {
+            InvitationStatus::Accepted => Err(InvitationError::InvitationAlreadyAccepted.into()),
+            InvitationStatus::Revoked => Err(InvitationError::InvitationRevoked.into()),
+            InvitationStatus::Pending | InvitationStatus::Expired => Ok(invitation),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl InvitationService for SupabaseInvitationService {
+    async fn accept_invitation(&self, token: &str, token_hash: &str) -> AppResult<Invitation> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/accept_practice_invitation", token)
+            .query(&[("select", INVITATION_SELECT)])
+            .json(&json!({ "p_token_hash": token_hash }));
+
+        self.postgrest
+            .send::<Invitation>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn create_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        email: &Email,
+        roles: &[PracticeRole],
+        token_hash: &str,
+    ) -> AppResult<Invitation> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/create_practice_invitation", token)
+            .query(&[("select", INVITATION_SELECT)])
+            .json(&json!({
+                "p_practice_id": practice_id,
+                "p_email": email.as_ref().expose_secret(),
+                "p_role_codes": roles,
+                "p_token_hash": token_hash,
+            }));
+
+        self.postgrest
+            .send::<Invitation>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn find_invitation(&self, token_hash: &str) -> AppResult<InvitationPreview> {
+        // The invitee may not have an account yet, so this runs as `anon`.
+        let request = self
+            .postgrest
+            .request(
+                Method::POST,
+                "rpc/find_practice_invitation",
+                self.supabase_anon_key.expose_secret(),
+            )
+            .json(&json!({ "p_token_hash": token_hash }));
+
+        self.postgrest
+            .send::<Vec<InvitationPreview>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| InvitationError::InvitationNotFound.into())
+    }
+
+    async fn list_invitations(&self, token: &str, practice_id: &str) -> AppResult<Vec<Invitation>> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practice_invitations", token)
+            .query(&[
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", INVITATION_SELECT.to_string()),
+                ("order", "created_at.desc".to_string()),
+            ]);
+
+        // Non-managers get an empty list from RLS rather than an error.
+        self.postgrest
+            .send::<Vec<Invitation>>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn resend_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        invitation_id: &str,
+        token_hash: &str,
+    ) -> AppResult<Invitation> {
+        self.get_open_invitation(token, practice_id, invitation_id)
+            .await?;
+
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/resend_practice_invitation", token)
+            .query(&[("select", INVITATION_SELECT)])
+            .json(&json!({
+                "p_invitation_id": invitation_id,
+                "p_token_hash": token_hash,
+            }));
+
+        self.postgrest
+            .send::<Invitation>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn revoke_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        invitation_id: &str,
+    ) -> AppResult<Invitation> {
+        self.get_open_invitation(token, practice_id, invitation_id)
+            .await?;
+
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/revoke_practice_invitation", token)
+            .query(&[("select", INVITATION_SELECT)])
+            .json(&json!({ "p_invitation_id": invitation_id }));
+
+        self.postgrest
+            .send::<Invitation>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+}
@@ -0,0 +1,133 @@
+use reqwest::Method;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        interfaces::login_attempt_tracker::LoginAttemptTracker,
+        types::{
+            email::Email,
+            login_attempt::{AttemptState, LockoutPolicy, attempt_keys},
+        },
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+    utils::clock::unix_now,
+};
+
+#[derive(Serialize, Deserialize)]
+struct AttemptRow {
+    key: String,
+    #[serde(flatten)]
+    state: AttemptState,
+}
+
+/// Keeps counts in `login_attempts` so every API instance sees the same failures. Updates
+/// are read-modify-write, so concurrent failures for one key can undercount by a few.
+pub struct SupabaseLoginAttemptTracker {
+    pub postgrest: PostgrestClient,
+    pub supabase_service_role_key: SecretString,
+    account_policy: LockoutPolicy,
+    client_policy: LockoutPolicy,
+}
+
+impl SupabaseLoginAttemptTracker {
+    pub fn new(supabase_url: String, supabase_service_role_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_service_role_key.clone()),
+            supabase_service_role_key,
+            account_policy: LockoutPolicy::per_account(),
+            client_policy: LockoutPolicy::per_client_ip(),
+        }
+    }
+
+    fn keys(&self, email: &Email, client_ip: Option<&str>) -> Vec<(String, LockoutPolicy)> {
+        attempt_keys(
+            &email.normalized(),
+            client_ip,
+            self.account_policy,
+            self.client_policy,
+        )
+    }
+
+    fn map_error(error: PostgrestError) -> AuthError {
+        AuthError::LoginAttemptError(error.to_string())
+    }
+
+    async fn load(&self, key: &str) -> AppResult<AttemptState> {
+        let request = self
+            .postgrest
+            .request(
+                Method::GET,
+                "login_attempts",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .query(&[("key", format!("eq.{key}")), ("select", "*".to_string())]);
+
+        let rows = self
+            .postgrest
+            .send::<Vec<AttemptRow>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        Ok(rows.into_iter().next().map(|r| r.state).unwrap_or_default())
+    }
+}
+
+#[async_trait::async_trait]
+impl LoginAttemptTracker for SupabaseLoginAttemptTracker {
+    async fn check(&self, email: &Email, client_ip: Option<&str>) -> AppResult<()> {
+        let now = unix_now();
+        let mut retry_after = None;
+        for (key, _) in self.keys(email, client_ip) {
+            retry_after = retry_after.max(self.load(&key).await?.retry_after(now));
+        }
+
+        match retry_after {
+            Some(retry_after_secs) => Err(AuthError::AccountLocked { retry_after_secs }.into()),
+            None => Ok(()),
+        }
+    }
+
+    async fn record_failure(&self, email: &Email, client_ip: Option<&str>) -> AppResult<()> {
+        let now = unix_now();
+        for (key, policy) in self.keys(email, client_ip) {
+            let state = self.load(&key).await?.after_failure(&policy, now);
+            let request = self
+                .postgrest
+                .request(
+                    Method::POST,
+                    "login_attempts",
+                    self.supabase_service_role_key.expose_secret(),
+                )
+                .header("Prefer", "resolution=merge-duplicates,return=minimal")
+                .json(&AttemptRow { key, state });
+
+            // `return=minimal` leaves an empty body, which `send` reads back as null.
+            self.postgrest
+                .send::<serde_json::Value>(request)
+                .await
+                .map_err(Self::map_error)?;
+        }
+        Ok(())
+    }
+
+    async fn record_success(&self, email: &Email) -> AppResult<()> {
+        for (key, _) in self.keys(email, None) {
+            let request = self
+                .postgrest
+                .request(
+                    Method::DELETE,
+                    "login_attempts",
+                    self.supabase_service_role_key.expose_secret(),
+                )
+                .query(&[("key", format!("eq.{key}"))]);
+
+            self.postgrest
+                .send::<serde_json::Value>(request)
+                .await
+                .map_err(Self::map_error)?;
+        }
+        Ok(())
+    }
+}
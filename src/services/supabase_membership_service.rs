@@ -0,0 +1,345 @@
+use reqwest::Method;
+use secrecy::SecretString;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, MembershipError, ValidationError},
+        interfaces::membership_service::MembershipService,
+        types::{
+            membership::{PracticeMember, PracticeRoleSet},
+            practice_role::PracticeRole,
+        },
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+};
+
+const MEMBER_SELECT: &str =
+    "id,user_id,is_active,created_at,practice_membership_roles(practice_roles(code))";
+
+#[derive(Deserialize)]
+struct MembershipRow {
+    id: String,
+    user_id: String,
+    is_active: bool,
+    created_at: String,
+    #[serde(default)]
+    practice_membership_roles: Vec<MembershipRoleRow>,
+}
+
+#[derive(Deserialize)]
+struct UserRolesRow {
+    practice_id: String,
+    #[serde(default)]
+    practice_membership_roles: Vec<MembershipRoleRow>,
+}
+
+#[derive(Deserialize)]
+struct MembershipRoleRow {
+    practice_roles: Option<RoleCodeRow>,
+}
+
+#[derive(Deserialize)]
+struct RoleCodeRow {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct IdRow {
+    id: String,
+}
+
+fn parse_roles(rows: Vec<MembershipRoleRow>) -> Vec<PracticeRole> {
+    rows.into_iter()
+        .filter_map(|r| r.practice_roles)
+        .filter_map(|r| PracticeRole::parse(&r.code).ok())
+        .collect()
+}
+
+impl From<MembershipRow> for PracticeMember {
+    fn from(row: MembershipRow) -> Self {
+        PracticeMember {
+            membership_id: row.id,
+            user_id: row.user_id,
+            is_active: row.is_active,
+            created_at: row.created_at,
+            roles: parse_roles(row.practice_membership_roles),
+        }
+    }
+}
+
+impl From<UserRolesRow> for PracticeRoleSet {
+    fn from(row: UserRolesRow) -> Self {
+        PracticeRoleSet {
+            practice_id: row.practice_id,
+            roles: parse_roles(row.practice_membership_roles),
+        }
+    }
+}
+
+pub struct SupabaseMembershipService {
+    pub postgrest: PostgrestClient,
+}
+
+impl SupabaseMembershipService {
+    pub fn new(supabase_url: String, supabase_anon_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_anon_key),
+        }
+    }
+
+    fn request_error(error: PostgrestError) -> AppError {
+        if error.is_invalid_input() {
+            ValidationError::InvalidInput(error.message).into()
+        } else {
+            MembershipError::MembershipRequestError(error.to_string()).into()
+        }
+    }
+
+    async fn get_member(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+    ) -> AppResult<PracticeMember> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practice_memberships", token)
+            .query(&[
+                ("id", format!("eq.{membership_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", MEMBER_SELECT.to_string()),
+            ]);
+
+        self.postgrest
+            .send::<Vec<MembershipRow>>(request)
+            .await
+            .map_err(Self::request_error)?
+            .into_iter()
+            .next()
+            .map(PracticeMember::from)
+            .ok_or_else(|| MembershipError::MembershipNotFound.into())
+    }
+
+    async fn role_id(&self, token: &str, role: PracticeRole) -> AppResult<String> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practice_roles", token)
+            .query(&[("code", format!("eq.{role}")), ("select", "id".to_string())]);
+
+        self.postgrest
+            .send::<Vec<IdRow>>(request)
+            .await
+            .map_err(Self::request_error)?
+            .into_iter()
+            .next()
+            .map(|row| row.id)
+            .ok_or_else(|| ValidationError::InvalidRole(role.to_string()).into())
+    }
+
+    /// Refuses changes that would leave the practice without an active owner.
+    async fn ensure_other_owner(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+    ) -> AppResult<()> {
+        let other_owners = self
+            .list_members(token, practice_id)
+            .await?
+            .into_iter()
+            .filter(|m| {
+                m.membership_id != membership_id && m.is_active && m.has_role(PracticeRole::Owner)
+            })
+            .count();
+
+        if other_owners == 0 {
+            return Err(MembershipError::LastOwner.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl MembershipService for SupabaseMembershipService {
+    async fn add_role(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        role: PracticeRole,
+    ) -> AppResult<PracticeMember> {
+        let member = self.get_member(token, practice_id, membership_id).await?;
+        if member.has_role(role) {
+            return Err(MembershipError::RoleAlreadyAssigned.into());
+        }
+
+        let role_id = self.role_id(token, role).await?;
+
+        let request = self
+            .postgrest
+            .request(Method::POST, "practice_membership_roles", token)
+            .header("Prefer", "return=minimal")
+            .json(&json!({ "membership_id": membership_id, "role_id": role_id }));
+
+        self.postgrest
+            .send::<()>(request)
+            .await
+            .map_err(|e| -> AppError {
+                if e.is_unique_violation() {
+                    MembershipError::RoleAlreadyAssigned.into()
+                } else if e.is_permission_denied() {
+                    MembershipError::CannotManageRole.into()
+                } else {
+                    Self::request_error(e)
+                }
+            })?;
+
+        self.get_member(token, practice_id, membership_id).await
+    }
+
+    async fn list_members(&self, token: &str, practice_id: &str) -> AppResult<Vec<PracticeMember>> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practice_memberships", token)
+            .query(&[
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", MEMBER_SELECT.to_string()),
+                ("order", "created_at.asc".to_string()),
+            ]);
+
+        // Owners and admins see every member; everyone else only sees their own row.
+        let members = self
+            .postgrest
+            .send::<Vec<MembershipRow>>(request)
+            .await
+            .map_err(Self::request_error)?
+            .into_iter()
+            .map(PracticeMember::from)
+            .collect();
+
+        Ok(members)
+    }
+
+    async fn list_user_roles(&self, token: &str, user_id: &str) -> AppResult<Vec<PracticeRoleSet>> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practice_memberships", token)
+            .query(&[
+                ("user_id", format!("eq.{user_id}")),
+                ("is_active", "eq.true".to_string()),
+                ("practices.deleted_at", "is.null".to_string()),
+                (
+                    "select",
+                    "practice_id,practices!inner(id),practice_membership_roles(practice_roles(code))"
+                        .to_string(),
+                ),
+            ]);
+
+        let roles = self
+            .postgrest
+            .send::<Vec<UserRolesRow>>(request)
+            .await
+            .map_err(Self::request_error)?
+            .into_iter()
+            .map(PracticeRoleSet::from)
+            .collect();
+
+        Ok(roles)
+    }
+
+    async fn remove_role(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        role: PracticeRole,
+    ) -> AppResult<PracticeMember> {
+        let member = self.get_member(token, practice_id, membership_id).await?;
+        if !member.has_role(role) {
+            return Err(MembershipError::RoleNotAssigned.into());
+        }
+
+        if role == PracticeRole::Owner {
+            self.ensure_other_owner(token, practice_id, membership_id)
+                .await?;
+        }
+
+        let role_id = self.role_id(token, role).await?;
+
+        let request = self
+            .postgrest
+            .request(Method::DELETE, "practice_membership_roles", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("membership_id", format!("eq.{membership_id}")),
+                ("role_id", format!("eq.{role_id}")),
+                ("select", "id".to_string()),
+            ]);
+
+        let deleted =
+            self.postgrest
+                .send::<Vec<IdRow>>(request)
+                .await
+                .map_err(|e| -> AppError {
+                    if e.is_permission_denied() {
+                        MembershipError::CannotManageRole.into()
+                    } else {
+                        Self::request_error(e)
+                    }
+                })?;
+
+        // The role is visible to us but the delete policy filtered it out.
+        if deleted.is_empty() {
+            return Err(MembershipError::CannotManageRole.into());
+        }
+
+        self.get_member(token, practice_id, membership_id).await
+    }
+
+    async fn set_membership_active(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        is_active: bool,
+    ) -> AppResult<PracticeMember> {
+        let member = self.get_member(token, practice_id, membership_id).await?;
+
+        if !is_active && member.is_active && member.has_role(PracticeRole::Owner) {
+            self.ensure_other_owner(token, practice_id, membership_id)
+                .await?;
+        }
+
+        let request = self
+            .postgrest
+            .request(Method::PATCH, "practice_memberships", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{membership_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", "id".to_string()),
+            ])
+            .json(&json!({ "is_active": is_active }));
+
+        let updated =
+            self.postgrest
+                .send::<Vec<IdRow>>(request)
+                .await
+                .map_err(|e| -> AppError {
+                    if e.is_permission_denied() {
+                        MembershipError::CannotManageMembership.into()
+                    } else {
+                        Self::request_error(e)
+                    }
+                })?;
+
+        if updated.is_empty() {
+            return Err(MembershipError::CannotManageMembership.into());
+        }
+
+        self.get_member(token, practice_id, membership_id).await
+    }
+}
@@ -0,0 +1,201 @@
+use reqwest::Method;
+use secrecy::SecretString;
+use serde_json::json;
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, PracticeError, ValidationError},
+        interfaces::practice_service::PracticeService,
+        types::practice::{Practice, PracticeName},
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+};
+
+const PRACTICE_COLUMNS: &str =
+    "id,name,idle_timeout_secs,passwordless_signin,created_at,updated_at";
+
+pub struct SupabasePracticeService {
+    pub postgrest: PostgrestClient,
+}
+
+impl SupabasePracticeService {
+    pub fn new(supabase_url: String, supabase_anon_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_anon_key),
+        }
+    }
+
+    fn map_error(error: PostgrestError) -> AppError {
+        if error.is_not_found() {
+            PracticeError::PracticeNotFound.into()
+        } else if error.is_permission_denied() {
+            PracticeError::PracticeForbidden.into()
+        } else if error.is_invalid_input() {
+            ValidationError::InvalidInput(error.message).into()
+        } else {
+            PracticeError::PracticeRequestError(error.to_string()).into()
+        }
+    }
+
+    /// PATCHes the practice, telling a row the update policy filtered out apart from a
+    /// missing one.
+    async fn update_practice(
+        &self,
+        token: &str,
+        practice_id: &str,
+        changes: serde_json::Value,
+    ) -> AppResult<Practice> {
+        let request = self
+            .postgrest
+            .request(Method::PATCH, "practices", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{practice_id}")),
+                ("deleted_at", "is.null".to_string()),
+                ("select", PRACTICE_COLUMNS.to_string()),
+            ])
+            .json(&changes);
+
+        let updated = self
+            .postgrest
+            .send::<Vec<Practice>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        if let Some(practice) = updated.into_iter().next() {
+            return Ok(practice);
+        }
+
+        // The update policy filtered the row out: members can still read it, so tell
+        // "not allowed" apart from "doesn't exist".
+        self.get_practice(token, practice_id).await?;
+        Err(PracticeError::PracticeForbidden.into())
+    }
+}
+
+#[async_trait::async_trait]
+impl PracticeService for SupabasePracticeService {
+    async fn create_practice(&self, token: &str, name: &PracticeName) -> AppResult<Practice> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/create_practice", token)
+            .json(&json!({ "p_name": name.as_ref() }));
+
+        self.postgrest
+            .send::<Practice>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn delete_practice(&self, token: &str, practice_id: &str) -> AppResult<()> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/soft_delete_practice", token)
+            .json(&json!({ "p_practice_id": practice_id }));
+
+        self.postgrest
+            .send::<Practice>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        Ok(())
+    }
+
+    async fn get_practice(&self, token: &str, practice_id: &str) -> AppResult<Practice> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practices", token)
+            .query(&[
+                ("id", format!("eq.{practice_id}")),
+                ("deleted_at", "is.null".to_string()),
+                ("select", PRACTICE_COLUMNS.to_string()),
+            ]);
+
+        // RLS hides practices the caller doesn't belong to, so those read as missing too.
+        self.postgrest
+            .send::<Vec<Practice>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| PracticeError::PracticeNotFound.into())
+    }
+
+    async fn list_practices(&self, token: &str) -> AppResult<Vec<Practice>> {
+        let request = self
+            .postgrest
+            .request(Method::GET, "practices", token)
+            .query(&[
+                ("deleted_at", "is.null"),
+                ("select", PRACTICE_COLUMNS),
+                ("order", "name.asc"),
+            ]);
+
+        self.postgrest
+            .send::<Vec<Practice>>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn rename_practice(
+        &self,
+        token: &str,
+        practice_id: &str,
+        name: &PracticeName,
+    ) -> AppResult<Practice> {
+        self.update_practice(token, practice_id, json!({ "name": name.as_ref() }))
+            .await
+    }
+
+    async fn set_idle_timeout(
+        &self,
+        token: &str,
+        practice_id: &str,
+        idle_timeout_secs: Option<i64>,
+    ) -> AppResult<Practice> {
+        self.update_practice(
+            token,
+            practice_id,
+            json!({ "idle_timeout_secs": idle_timeout_secs }),
+        )
+        .await
+    }
+
+    async fn set_passwordless_signin(
+        &self,
+        token: &str,
+        practice_id: &str,
+        allowed: bool,
+    ) -> AppResult<Practice> {
+        self.update_practice(
+            token,
+            practice_id,
+            json!({ "passwordless_signin": allowed }),
+        )
+        .await
+    }
+
+    async fn session_idle_timeout(&self, token: &str) -> AppResult<Option<i64>> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/session_idle_timeout", token)
+            .json(&json!({}));
+
+        self.postgrest
+            .send::<Option<i64>>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn passwordless_signin_allowed(&self, token: &str) -> AppResult<bool> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/passwordless_signin_allowed", token)
+            .json(&json!({}));
+
+        self.postgrest
+            .send::<bool>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+}
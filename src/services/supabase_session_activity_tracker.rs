@@ -0,0 +1,111 @@
+use reqwest::Method;
+use secrecy::{ExposeSecret, SecretString};
+
+use crate::{
+    domain::{
+        error::app_error::{AppResult, AuthError},
+        interfaces::session_activity_tracker::SessionActivityTracker,
+        types::session::SessionActivity,
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+};
+
+/// Keeps activity in `session_activity` so every API instance sees the same last-seen times.
+pub struct SupabaseSessionActivityTracker {
+    pub postgrest: PostgrestClient,
+    pub supabase_service_role_key: SecretString,
+}
+
+impl SupabaseSessionActivityTracker {
+    pub fn new(supabase_url: String, supabase_service_role_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_service_role_key.clone()),
+            supabase_service_role_key,
+        }
+    }
+
+    fn map_error(error: PostgrestError) -> AuthError {
+        AuthError::SessionActivityError(error.to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionActivityTracker for SupabaseSessionActivityTracker {
+    async fn get(&self, session_id: &str) -> AppResult<Option<SessionActivity>> {
+        let request = self
+            .postgrest
+            .request(
+                Method::GET,
+                "session_activity",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .query(&[
+                ("session_id", format!("eq.{session_id}")),
+                ("select", "*".to_string()),
+            ]);
+
+        let rows = self
+            .postgrest
+            .send::<Vec<SessionActivity>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        Ok(rows.into_iter().next())
+    }
+
+    async fn list_for_user(&self, user_id: &str) -> AppResult<Vec<SessionActivity>> {
+        let request = self
+            .postgrest
+            .request(
+                Method::GET,
+                "session_activity",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .query(&[
+                ("user_id", format!("eq.{user_id}")),
+                ("order", "last_seen_at.desc".to_string()),
+                ("select", "*".to_string()),
+            ]);
+
+        self.postgrest
+            .send::<Vec<SessionActivity>>(request)
+            .await
+            .map_err(|e| Self::map_error(e).into())
+    }
+
+    async fn save(&self, activity: &SessionActivity) -> AppResult<()> {
+        let request = self
+            .postgrest
+            .request(
+                Method::POST,
+                "session_activity",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .header("Prefer", "resolution=merge-duplicates,return=minimal")
+            .json(activity);
+
+        // `return=minimal` leaves an empty body, which `send` reads back as null.
+        self.postgrest
+            .send::<serde_json::Value>(request)
+            .await
+            .map_err(Self::map_error)?;
+        Ok(())
+    }
+
+    async fn remove(&self, session_id: &str) -> AppResult<()> {
+        let request = self
+            .postgrest
+            .request(
+                Method::DELETE,
+                "session_activity",
+                self.supabase_service_role_key.expose_secret(),
+            )
+            .query(&[("session_id", format!("eq.{session_id}"))]);
+
+        self.postgrest
+            .send::<serde_json::Value>(request)
+            .await
+            .map_err(Self::map_error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+use reqwest::Method;
+use secrecy::SecretString;
+use serde_json::{Value, json};
+
+use crate::{
+    domain::{
+        error::app_error::{AppError, AppResult, TeamError, ValidationError},
+        interfaces::team_service::TeamService,
+        types::team::{Team, TeamName},
+    },
+    services::postgrest::{PostgrestClient, PostgrestError},
+};
+
+const TEAM_SELECT: &str = "id,practice_id,name,created_at,team_members(user_id,created_at)";
+
+pub struct SupabaseTeamService {
+    pub postgrest: PostgrestClient,
+}
+
+impl SupabaseTeamService {
+    pub fn new(supabase_url: String, supabase_anon_key: SecretString) -> Self {
+        Self {
+            postgrest: PostgrestClient::new(&supabase_url, supabase_anon_key),
+        }
+    }
+
+    fn map_error(error: PostgrestError) -> AppError {
+        if error.is_not_found() {
+            TeamError::TeamNotFound.into()
+        } else if error.is_permission_denied() {
+            TeamError::TeamForbidden.into()
+        } else if error.is_unique_violation() {
+            // `idx_teams_name_unique` is on (practice_id, lower(name)).
+            TeamError::TeamNameTaken.into()
+        } else if error.is_invalid_input() {
+            ValidationError::InvalidInput(error.message).into()
+        } else {
+            TeamError::TeamRequestError(error.to_string()).into()
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TeamService for SupabaseTeamService {
+    async fn add_team_member(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        user_id: &str,
+    ) -> AppResult<Team> {
+        let team = self.get_team(token, practice_id, team_id).await?;
+        if team.has_member(user_id) {
+            return Err(TeamError::AlreadyOnTeam.into());
+        }
+
+        let request = self
+            .postgrest
+            .request(Method::POST, "rpc/add_team_member", token)
+            .json(&json!({ "p_team_id": team_id, "p_user_id": user_id }));
+
+        self.postgrest
+            .send::<Value>(request)
+            .await
+            .map_err(|e| -> AppError {
+                if e.is_foreign_key_violation() {
+                    TeamError::NotPracticeMember.into()
+                } else if e.is_unique_violation() {
+                    TeamError::AlreadyOnTeam.into()
+                } else {
+                    Self::map_error(e)
+                }
+            })?;
+
+        self.get_team(token, practice_id, team_id).await
+    }
+
+    async fn create_team(
+        &self,
+        token: &str,
+        practice_id: &str,
+        name: &TeamName,
+    ) -> AppResult<Team> {
+        let request = self
+            .postgrest
+            .request(Method::POST, "teams", token)
+            .header("Prefer", "return=representation")
+            .query(&[("select", TEAM_SELECT)])
+            .json(&json!({ "practice_id": practice_id, "name": name.as_ref() }));
+
+        self.postgrest
+            .send::<Vec<Team>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| TeamError::TeamRequestError("Insert returned no row".to_string()).into())
+    }
+
+    async fn delete_team(&self, token: &str, practice_id: &str, team_id: &str) -> AppResult<()> {
+        let request = self
+            .postgrest
+            .request(Method::DELETE, "teams", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{team_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", "id".to_string()),
+            ]);
+
+        let deleted = self
+            .postgrest
+            .send::<Vec<Value>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        if deleted.is_empty() {
+            // Visible but not deleted means the delete policy filtered it out.
+            self.get_team(token, practice_id, team_id).await?;
+            return Err(TeamError::TeamForbidden.into());
+        }
+
+        Ok(())
+    }
+
+    async fn get_team(&self, token: &str, practice_id: &str, team_id: &str) -> AppResult<Team> {
+        let request = self.postgrest.request(Method::GET, "teams", token).query(&[
+            ("id", format!("eq.{team_id}")),
+            ("practice_id", format!("eq.{practice_id}")),
+            ("select", TEAM_SELECT.to_string()),
+        ]);
+
+        self.postgrest
+            .send::<Vec<Team>>(request)
+            .await
+            .map_err(Self::map_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| TeamError::TeamNotFound.into())
+    }
+
+    async fn list_teams(&self, token: &str, practice_id: &str) -> AppResult<Vec<Team>> {
+        let request = self.postgrest.request(Method::GET, "teams", token).query(&[
+            ("practice_id", format!("eq.{practice_id}")),
+            ("select", TEAM_SELECT.to_string()),
+            ("order", "name.asc".to_string()),
+        ]);
+
+        self.postgrest
+            .send::<Vec<Team>>(request)
+            .await
+            .map_err(Self::map_error)
+    }
+
+    async fn remove_team_member(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        user_id: &str,
+    ) -> AppResult<Team> {
+        let team = self.get_team(token, practice_id, team_id).await?;
+        if !team.has_member(user_id) {
+            return Err(TeamError::NotOnTeam.into());
+        }
+
+        let request = self
+            .postgrest
+            .request(Method::DELETE, "team_members", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("team_id", format!("eq.{team_id}")),
+                ("user_id", format!("eq.{user_id}")),
+                ("select", "id".to_string()),
+            ]);
+
+        let deleted = self
+            .postgrest
+            .send::<Vec<Value>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        if deleted.is_empty() {
+            return Err(TeamError::TeamForbidden.into());
+        }
+
+        self.get_team(token, practice_id, team_id).await
+    }
+
+    async fn rename_team(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        name: &TeamName,
+    ) -> AppResult<Team> {
+        let request = self
+            .postgrest
+            .request(Method::PATCH, "teams", token)
+            .header("Prefer", "return=representation")
+            .query(&[
+                ("id", format!("eq.{team_id}")),
+                ("practice_id", format!("eq.{practice_id}")),
+                ("select", TEAM_SELECT.to_string()),
+            ])
+            .json(&json!({ "name": name.as_ref() }));
+
+        let updated = self
+            .postgrest
+            .send::<Vec<Team>>(request)
+            .await
+            .map_err(Self::map_error)?;
+
+        if let Some(team) = updated.into_iter().next() {
+            return Ok(team);
+        }
+
+        self.get_team(token, practice_id, team_id).await?;
+        Err(TeamError::TeamForbidden.into())
+    }
+}
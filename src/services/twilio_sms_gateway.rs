@@ -0,0 +1,76 @@
+use secrecy::{ExposeSecret, SecretString};
+use serde_json::Value;
+
+use crate::domain::{
+    error::app_error::{AppResult, SmsError},
+    interfaces::sms_gateway::SmsGateway,
+    types::phone_number::PhoneNumber,
+};
+
+/// Sends through Twilio's Messages API, or any provider that mimics it.
+pub struct TwilioSmsGateway {
+    client: reqwest::Client,
+    api_url: String,
+    account_sid: String,
+    auth_token: SecretString,
+    /// A sending number in E.164 form, or a Messaging Service SID (`MG…`).
+    from: String,
+}
+
+impl TwilioSmsGateway {
+    pub fn new(
+        api_url: String,
+        account_sid: String,
+        auth_token: SecretString,
+        from: String,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            account_sid,
+            auth_token,
+            from,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SmsGateway for TwilioSmsGateway {
+    async fn send(&self, to: &PhoneNumber, body: &str) -> AppResult<()> {
+        let url = format!(
+            "{}/2010-04-01/Accounts/{}/Messages.json",
+            self.api_url, self.account_sid
+        );
+        let sender = if self.from.starts_with("MG") {
+            "MessagingServiceSid"
+        } else {
+            "From"
+        };
+        let form = [
+            ("To", to.as_ref()),
+            (sender, self.from.as_str()),
+            ("Body", body),
+        ];
+
+        let resp = self
+            .client
+            .post(&url)
+            .basic_auth(&self.account_sid, Some(self.auth_token.expose_secret()))
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| SmsError::SendError(format!("Failed to send request: {e}")))?;
+
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = resp.json::<Value>().await.unwrap_or(Value::Null);
+        let message = body
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("message rejected");
+        Err(SmsError::SendError(format!("Twilio responded with status {status}: {message}")).into())
+    }
+}
@@ -3,21 +3,63 @@ use std::sync::Arc;
 use secrecy::SecretString;
 use tokio::sync::RwLock;
 
-use crate::domain::interfaces::auth_service::AuthService;
+use crate::domain::{
+    interfaces::{
+        appointment_service::AppointmentService, audit_log_service::AuditLogService,
+        audit_sink::AuditSink, auth_service::AuthService, client_service::ClientService,
+        email_sender::EmailSender, identity_provider_service::IdentityProviderService,
+        invitation_service::InvitationService, login_attempt_tracker::LoginAttemptTracker,
+        membership_service::MembershipService, oidc_client::OidcClient,
+        practice_service::PracticeService, session_activity_tracker::SessionActivityTracker,
+        sms_gateway::SmsGateway, team_service::TeamService, token_verifier::TokenVerifier,
+    },
+    types::password_policy::PasswordPolicy,
+};
 
-type AuthServiceType = Arc<RwLock<dyn AuthService + Send + Sync>>;
+pub(crate) type AppointmentServiceType = Arc<RwLock<dyn AppointmentService + Send + Sync>>;
+pub(crate) type AuditLogServiceType = Arc<RwLock<dyn AuditLogService + Send + Sync>>;
+pub(crate) type AuditSinkType = Arc<RwLock<dyn AuditSink + Send + Sync>>;
+pub(crate) type AuthServiceType = Arc<RwLock<dyn AuthService + Send + Sync>>;
+pub(crate) type ClientServiceType = Arc<RwLock<dyn ClientService + Send + Sync>>;
+pub(crate) type EmailSenderType = Arc<RwLock<dyn EmailSender + Send + Sync>>;
+pub(crate) type IdentityProviderServiceType =
+    Arc<RwLock<dyn IdentityProviderService + Send + Sync>>;
+pub(crate) type InvitationServiceType = Arc<RwLock<dyn InvitationService + Send + Sync>>;
+pub(crate) type LoginAttemptTrackerType = Arc<RwLock<dyn LoginAttemptTracker + Send + Sync>>;
+pub(crate) type MembershipServiceType = Arc<RwLock<dyn MembershipService + Send + Sync>>;
+type OidcClientType = Arc<RwLock<dyn OidcClient + Send + Sync>>;
+pub(crate) type PracticeServiceType = Arc<RwLock<dyn PracticeService + Send + Sync>>;
+pub(crate) type SessionActivityTrackerType = Arc<RwLock<dyn SessionActivityTracker + Send + Sync>>;
+pub(crate) type SmsGatewayType = Arc<RwLock<dyn SmsGateway + Send + Sync>>;
+pub(crate) type TeamServiceType = Arc<RwLock<dyn TeamService + Send + Sync>>;
+type TokenVerifierType = Arc<RwLock<dyn TokenVerifier + Send + Sync>>;
 
 #[derive(Clone)]
 pub struct AppState {
+    pub appointment_service: AppointmentServiceType,
+    pub audit_log_service: AuditLogServiceType,
+    pub audit_sink: AuditSinkType,
     pub auth_service: AuthServiceType,
-    pub supabase_jwt_secret: SecretString,
-}
-
-impl AppState {
-    pub fn new(auth_service: AuthServiceType, supabase_jwt_secret: SecretString) -> Self {
-        AppState {
-            auth_service,
-            supabase_jwt_secret,
-        }
-    }
+    pub client_service: ClientServiceType,
+    pub email_sender: EmailSenderType,
+    pub identity_provider_service: IdentityProviderServiceType,
+    pub invitation_service: InvitationServiceType,
+    pub login_attempt_tracker: LoginAttemptTrackerType,
+    pub membership_service: MembershipServiceType,
+    pub oidc_client: OidcClientType,
+    pub practice_service: PracticeServiceType,
+    pub session_activity_tracker: SessionActivityTrackerType,
+    pub sms_gateway: SmsGatewayType,
+    pub team_service: TeamServiceType,
+    pub token_verifier: TokenVerifierType,
+    /// Base URL for links sent by email.
+    pub public_url: String,
+    /// Idle logoff for sessions not governed by a practice setting.
+    pub session_idle_timeout_secs: i64,
+    /// Rules for passwords users choose at signup, reset, invitation and profile update.
+    pub password_policy: PasswordPolicy,
+    /// Verifies Supabase's Send SMS hook calls.
+    pub send_sms_hook_secret: SecretString,
+    /// Signs the SSO login-state cookie.
+    pub sso_cookie_secret: SecretString,
 }
@@ -1,14 +1,217 @@
+use std::sync::Arc;
+
+use rand::RngCore;
 use secrecy::SecretString;
 
+use crate::domain::types::{
+    password_policy::PasswordPolicy,
+    rate_limit::{RateLimitConfig, RateLimitPolicy, RouteRateLimit},
+};
+
+/// Where auth state lives. `Memory` runs the server with no Supabase, Mailpit or SMTP
+/// relay, for demos and hermetic tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    #[default]
+    Supabase,
+    Memory,
+}
+
+impl Backend {
+    fn from_env() -> Self {
+        match std::env::var("BACKEND").as_deref() {
+            Err(_) | Ok("supabase") => Backend::Supabase,
+            Ok("memory") => Backend::Memory,
+            Ok(other) => panic!("BACKEND must be 'supabase' or 'memory', got '{other}'"),
+        }
+    }
+}
+
+/// Where failed signin counts are kept. `Postgres` shares them between API instances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoginAttemptStore {
+    #[default]
+    Memory,
+    Postgres,
+}
+
+impl LoginAttemptStore {
+    fn from_env() -> Self {
+        match std::env::var("LOGIN_ATTEMPT_STORE").as_deref() {
+            Err(_) | Ok("memory") => LoginAttemptStore::Memory,
+            Ok("postgres") => LoginAttemptStore::Postgres,
+            Ok(other) => {
+                panic!("LOGIN_ATTEMPT_STORE must be 'memory' or 'postgres', got '{other}'")
+            }
+        }
+    }
+}
+
+/// Where session activity records are kept. `Postgres` shares them between API instances
+/// and survives restarts, which would otherwise revive idle and revoked sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionActivityStore {
+    Memory,
+    #[default]
+    Postgres,
+}
+
+impl SessionActivityStore {
+    fn from_env() -> Self {
+        match std::env::var("SESSION_ACTIVITY_STORE").as_deref() {
+            Err(_) | Ok("postgres") => SessionActivityStore::Postgres,
+            Ok("memory") => SessionActivityStore::Memory,
+            Ok(other) => {
+                panic!("SESSION_ACTIVITY_STORE must be 'memory' or 'postgres', got '{other}'")
+            }
+        }
+    }
+}
+
+/// How SMS codes leave the app. `File` appends them to `SMS_OUTBOX_PATH` for development.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmsDelivery {
+    File,
+    Twilio,
+}
+
+impl SmsDelivery {
+    /// Real users' codes must never land in a file, so only the memory backend may use (and
+    /// defaults to) `file`; with Supabase the gateway has to be chosen explicitly.
+    fn from_env(backend: Backend) -> Self {
+        match (std::env::var("SMS_DELIVERY").as_deref(), backend) {
+            (Ok("twilio"), _) => SmsDelivery::Twilio,
+            (Err(_) | Ok("file"), Backend::Memory) => SmsDelivery::File,
+            (Ok("file"), Backend::Supabase) => {
+                panic!("SMS_DELIVERY=file is only allowed with BACKEND=memory")
+            }
+            (Err(_), Backend::Supabase) => panic!("SMS_DELIVERY must be set"),
+            (Ok(other), _) => panic!("SMS_DELIVERY must be 'file' or 'twilio', got '{other}'"),
+        }
+    }
+}
+
+/// Idle logoff after 15 minutes unless a practice sets its own timeout.
+const DEFAULT_SESSION_IDLE_TIMEOUT_SECS: i64 = 900;
+
+/// Reads `RATE_LIMIT_*` over the defaults. `RATE_LIMIT_ROUTES` replaces the default route
+/// list with comma-separated `[METHOD ]/path=N/<window>` entries.
+fn rate_limits_from_env() -> RateLimitConfig {
+    let defaults = RateLimitConfig::default();
+    let policy = |name: &str, default: RateLimitPolicy| match std::env::var(name) {
+        Ok(spec) => RateLimitPolicy::parse(&spec).unwrap_or_else(|e| panic!("{name}: {e}")),
+        Err(_) => default,
+    };
+
+    RateLimitConfig {
+        enabled: std::env::var("RATE_LIMIT_ENABLED")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(defaults.enabled),
+        anonymous: policy("RATE_LIMIT_ANONYMOUS", defaults.anonymous),
+        authenticated: policy("RATE_LIMIT_AUTHENTICATED", defaults.authenticated),
+        routes: match std::env::var("RATE_LIMIT_ROUTES") {
+            Ok(specs) => specs
+                .split(',')
+                .filter(|spec| !spec.trim().is_empty())
+                .map(|spec| {
+                    RouteRateLimit::parse(spec).unwrap_or_else(|e| panic!("RATE_LIMIT_ROUTES: {e}"))
+                })
+                .collect(),
+            Err(_) => defaults.routes,
+        },
+    }
+}
+
+/// Reads `PASSWORD_*` over the defaults, and loads the breached-password list named by
+/// `BREACHED_PASSWORDS_PATH`, if any.
+fn password_policy_from_env() -> PasswordPolicy {
+    let defaults = PasswordPolicy::default();
+    let var = |name: &str| std::env::var(name).ok();
+    let flag = |name: &str, default: bool| match var(name) {
+        Some(v) => v
+            .parse()
+            .unwrap_or_else(|_| panic!("{name} must be true or false, got '{v}'")),
+        None => default,
+    };
+    let length = |name: &str, default: usize| match var(name) {
+        Some(v) => v
+            .parse()
+            .ok()
+            .filter(|n| *n > 0)
+            .unwrap_or_else(|| panic!("{name} must be a positive number, got '{v}'")),
+        None => default,
+    };
+
+    let breached = match var("BREACHED_PASSWORDS_PATH") {
+        Some(path) => {
+            let list = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("BREACHED_PASSWORDS_PATH: cannot read {path}: {e}"));
+            Arc::new(
+                PasswordPolicy::parse_breached(&list)
+                    .unwrap_or_else(|e| panic!("BREACHED_PASSWORDS_PATH: {path}: {e}")),
+            )
+        }
+        None => defaults.breached,
+    };
+
+    let policy = PasswordPolicy {
+        min_length: length("PASSWORD_MIN_LENGTH", defaults.min_length),
+        max_length: length("PASSWORD_MAX_LENGTH", defaults.max_length),
+        require_lowercase: flag("PASSWORD_REQUIRE_LOWERCASE", defaults.require_lowercase),
+        require_uppercase: flag("PASSWORD_REQUIRE_UPPERCASE", defaults.require_uppercase),
+        require_digit: flag("PASSWORD_REQUIRE_DIGIT", defaults.require_digit),
+        require_symbol: flag("PASSWORD_REQUIRE_SYMBOL", defaults.require_symbol),
+        forbid_email: flag("PASSWORD_FORBID_EMAIL", defaults.forbid_email),
+        breached,
+    };
+    assert!(
+        policy.min_length <= policy.max_length,
+        "PASSWORD_MIN_LENGTH must not exceed PASSWORD_MAX_LENGTH"
+    );
+    policy
+}
+
 #[derive(Debug, Clone)]
 pub struct AppConfig {
+    pub backend: Backend,
+    pub login_attempt_store: LoginAttemptStore,
+    pub session_activity_store: SessionActivityStore,
+    /// Used for sessions whose user belongs to no practice with its own timeout.
+    pub session_idle_timeout_secs: i64,
     pub app_address: String,
     pub log_level: String,
     pub supabase_url: String,
     pub supabase_anon_key: SecretString,
     pub supabase_service_role_key: SecretString,
     pub supabase_jwt_secret: SecretString,
+    /// Where the auth guard fetches the project's signing keys.
+    pub jwks_url: String,
+    pub jwt_issuer: String,
+    pub jwt_audience: String,
+    /// Accept HS256 tokens signed with `supabase_jwt_secret` (legacy projects and the local
+    /// CLI stack).
+    pub jwt_hs256_fallback: bool,
     pub mailpit_url: String,
+    /// Base URL the browser reaches the app on; used to build links in outgoing email.
+    pub public_url: String,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub email_from: String,
+    pub sms_delivery: SmsDelivery,
+    pub sms_outbox_path: String,
+    pub twilio_api_url: String,
+    pub twilio_account_sid: String,
+    pub twilio_auth_token: SecretString,
+    /// Sending number or Messaging Service SID.
+    pub twilio_from: String,
+    /// Standard Webhooks secret (`v1,whsec_…`) Supabase signs Send SMS hook calls with.
+    /// Empty rejects every call.
+    pub send_sms_hook_secret: SecretString,
+    /// Signs the SSO login-state cookie; every instance must share it.
+    pub sso_cookie_secret: SecretString,
+    pub password_policy: PasswordPolicy,
+    pub rate_limits: RateLimitConfig,
 }
 
 impl AppConfig {
@@ -21,39 +224,167 @@ impl AppConfig {
 
         let log_level = std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
 
-        let supabase_url = std::env::var("SUPABASE_URL").expect("SUPABASE_URL must be set");
-        let supabase_anon_key =
-            std::env::var("SUPABASE_ANON_KEY").expect("SUPABASE_ANON_KEY must be set");
-        let supabase_service_role_key = std::env::var("SUPABASE_SERVICE_ROLE_KEY")
-            .expect("SUPABASE_SERVICE_ROLE_KEY must be set");
-        let supabase_jwt_secret =
-            std::env::var("SUPABASE_JWT_SECRET").expect("SUPABASE_JWT_SECRET must be set");
+        let backend = Backend::from_env();
+        // The memory backend has no Supabase to talk to: Postgres-only features answer 503
+        // and tokens are checked against the shared secret, so its settings become optional.
+        let supabase_var = |name: &str, memory_default: &str| match backend {
+            Backend::Supabase => {
+                std::env::var(name).unwrap_or_else(|_| panic!("{name} must be set"))
+            }
+            Backend::Memory => std::env::var(name).unwrap_or_else(|_| memory_default.to_string()),
+        };
+
+        let supabase_url = supabase_var("SUPABASE_URL", "http://127.0.0.1:54321");
+        let supabase_anon_key = supabase_var("SUPABASE_ANON_KEY", "");
+        let supabase_service_role_key = supabase_var("SUPABASE_SERVICE_ROLE_KEY", "");
+        let supabase_jwt_secret = supabase_var("SUPABASE_JWT_SECRET", &random_secret());
+        let sso_cookie_secret = supabase_var("SSO_COOKIE_SECRET", &random_secret());
+        let login_attempt_store = match backend {
+            Backend::Supabase => LoginAttemptStore::from_env(),
+            Backend::Memory => LoginAttemptStore::Memory,
+        };
+        let session_activity_store = match backend {
+            Backend::Supabase => SessionActivityStore::from_env(),
+            Backend::Memory => SessionActivityStore::Memory,
+        };
+        let session_idle_timeout_secs = match std::env::var("SESSION_IDLE_TIMEOUT_SECS") {
+            Ok(v) => v.parse().ok().filter(|secs| *secs > 0).unwrap_or_else(|| {
+                panic!("SESSION_IDLE_TIMEOUT_SECS must be a positive number, got '{v}'")
+            }),
+            Err(_) => DEFAULT_SESSION_IDLE_TIMEOUT_SECS,
+        };
+        let jwks_url = std::env::var("SUPABASE_JWKS_URL")
+            .unwrap_or_else(|_| format!("{supabase_url}/auth/v1/.well-known/jwks.json"));
+        let jwt_issuer = std::env::var("SUPABASE_JWT_ISSUER")
+            .unwrap_or_else(|_| format!("{supabase_url}/auth/v1"));
+        let jwt_audience =
+            std::env::var("SUPABASE_JWT_AUDIENCE").unwrap_or_else(|_| "authenticated".to_string());
+        let jwt_hs256_fallback = std::env::var("SUPABASE_JWT_HS256_FALLBACK")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(false)
+            // In-memory sessions are signed with the shared secret.
+            || backend == Backend::Memory;
 
         let mailpit_url =
             std::env::var("MAILPIT_URL").unwrap_or_else(|_| "http://127.0.0.1:54324".to_string());
 
+        let public_url = std::env::var("PUBLIC_URL")
+            .unwrap_or_else(|_| format!("http://{app_address}"))
+            .trim_end_matches('/')
+            .to_string();
+        let smtp_host = std::env::var("SMTP_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+        let smtp_port = std::env::var("SMTP_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(54325);
+        let email_from = std::env::var("EMAIL_FROM")
+            .unwrap_or_else(|_| "Breeze EHR <no-reply@breezeehr.local>".to_string());
+
+        let sms_delivery = SmsDelivery::from_env(backend);
+        let sms_outbox_path =
+            std::env::var("SMS_OUTBOX_PATH").unwrap_or_else(|_| "sms_outbox.log".to_string());
+        let twilio_var = |name: &str| match sms_delivery {
+            SmsDelivery::Twilio => {
+                std::env::var(name).unwrap_or_else(|_| panic!("{name} must be set"))
+            }
+            SmsDelivery::File => std::env::var(name).unwrap_or_default(),
+        };
+        let twilio_api_url = std::env::var("TWILIO_API_URL")
+            .unwrap_or_else(|_| "https://api.twilio.com".to_string());
+        let twilio_account_sid = twilio_var("TWILIO_ACCOUNT_SID");
+        let twilio_auth_token = twilio_var("TWILIO_AUTH_TOKEN");
+        let twilio_from = twilio_var("TWILIO_FROM");
+        let send_sms_hook_secret = std::env::var("SEND_SMS_HOOK_SECRET").unwrap_or_default();
+
+        let password_policy = password_policy_from_env();
+        let rate_limits = rate_limits_from_env();
+
         AppConfig {
+            backend,
+            login_attempt_store,
+            session_activity_store,
+            session_idle_timeout_secs,
             app_address,
             log_level,
             supabase_url,
             supabase_anon_key: SecretString::from(supabase_anon_key),
             supabase_service_role_key: SecretString::from(supabase_service_role_key),
             supabase_jwt_secret: SecretString::from(supabase_jwt_secret),
+            jwks_url,
+            jwt_issuer,
+            jwt_audience,
+            jwt_hs256_fallback,
             mailpit_url,
+            public_url,
+            smtp_host,
+            smtp_port,
+            email_from,
+            sms_delivery,
+            sms_outbox_path,
+            twilio_api_url,
+            twilio_account_sid,
+            twilio_auth_token: SecretString::from(twilio_auth_token),
+            twilio_from,
+            send_sms_hook_secret: SecretString::from(send_sms_hook_secret),
+            sso_cookie_secret: SecretString::from(sso_cookie_secret),
+            password_policy,
+            rate_limits,
         }
     }
 
-    pub fn for_tests() -> Self {
-        dotenvy::dotenv().ok();
-
+    /// Settings shared by the test configs: a free local port, in-memory stores, texts to a
+    /// temp file and the local Supabase CLI stack's URLs. Reads no environment.
+    fn test_base() -> Self {
         let port = std::net::TcpListener::bind("127.0.0.1:0")
             .unwrap()
             .local_addr()
             .unwrap()
             .port();
         let app_address = format!("127.0.0.1:{port}");
+        let supabase_url = "http://127.0.0.1:54321".to_string();
+
+        AppConfig {
+            backend: Backend::Supabase,
+            login_attempt_store: LoginAttemptStore::Memory,
+            session_activity_store: SessionActivityStore::Memory,
+            session_idle_timeout_secs: DEFAULT_SESSION_IDLE_TIMEOUT_SECS,
+            public_url: format!("http://{app_address}"),
+            app_address,
+            log_level: "info".to_string(),
+            supabase_anon_key: SecretString::from(""),
+            supabase_service_role_key: SecretString::from(""),
+            supabase_jwt_secret: SecretString::from(random_secret()),
+            jwks_url: format!("{supabase_url}/auth/v1/.well-known/jwks.json"),
+            jwt_issuer: format!("{supabase_url}/auth/v1"),
+            jwt_audience: "authenticated".to_string(),
+            jwt_hs256_fallback: true,
+            supabase_url,
+            mailpit_url: "http://127.0.0.1:54324".to_string(),
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: 54325,
+            email_from: "Breeze EHR <no-reply@breezeehr.local>".to_string(),
+            sms_delivery: SmsDelivery::File,
+            sms_outbox_path: std::env::temp_dir()
+                .join("breeze_ehr_sms_outbox.log")
+                .to_string_lossy()
+                .into_owned(),
+            twilio_api_url: "https://api.twilio.com".to_string(),
+            twilio_account_sid: String::new(),
+            twilio_auth_token: SecretString::from(""),
+            twilio_from: String::new(),
+            send_sms_hook_secret: SecretString::from(""),
+            sso_cookie_secret: SecretString::from(random_secret()),
+            password_policy: PasswordPolicy::default(),
+            rate_limits: RateLimitConfig::default(),
+        }
+    }
 
-        let log_level = "info".to_string();
+    /// Test config for the Supabase backend, pointed at the project in `.env`.
+    pub fn for_tests() -> Self {
+        dotenvy::dotenv().ok();
+        let base = Self::test_base();
+        let env_or = |name: &str, default: String| std::env::var(name).unwrap_or(default);
 
         let supabase_url = std::env::var("SUPABASE_URL_TEST").expect("SUPABASE_URL must be set");
         let supabase_anon_key =
@@ -63,17 +394,51 @@ impl AppConfig {
         let supabase_jwt_secret =
             std::env::var("SUPABASE_JWT_SECRET").expect("SUPABASE_JWT_SECRET must be set");
 
-        let mailpit_url =
-            std::env::var("MAILPIT_URL").unwrap_or_else(|_| "http://127.0.0.1:54324".to_string());
-
         AppConfig {
-            app_address,
-            log_level,
+            jwks_url: env_or(
+                "SUPABASE_JWKS_URL",
+                format!("{supabase_url}/auth/v1/.well-known/jwks.json"),
+            ),
+            jwt_issuer: env_or("SUPABASE_JWT_ISSUER", format!("{supabase_url}/auth/v1")),
+            jwt_audience: env_or("SUPABASE_JWT_AUDIENCE", base.jwt_audience.clone()),
+            jwt_hs256_fallback: std::env::var("SUPABASE_JWT_HS256_FALLBACK")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(base.jwt_hs256_fallback),
             supabase_url,
             supabase_anon_key: SecretString::from(supabase_anon_key),
             supabase_service_role_key: SecretString::from(supabase_service_role_key),
             supabase_jwt_secret: SecretString::from(supabase_jwt_secret),
-            mailpit_url,
+            mailpit_url: env_or("MAILPIT_URL", base.mailpit_url.clone()),
+            public_url: env_or("PUBLIC_URL", base.public_url.clone())
+                .trim_end_matches('/')
+                .to_string(),
+            smtp_host: env_or("SMTP_HOST", base.smtp_host.clone()),
+            smtp_port: std::env::var("SMTP_PORT")
+                .ok()
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(base.smtp_port),
+            email_from: env_or("EMAIL_FROM", base.email_from.clone()),
+            send_sms_hook_secret: SecretString::from(
+                std::env::var("SEND_SMS_HOOK_SECRET").unwrap_or_default(),
+            ),
+            ..base
+        }
+    }
+
+    /// Test config for the memory backend; reads no environment so it works anywhere.
+    pub fn for_memory_tests() -> Self {
+        AppConfig {
+            backend: Backend::Memory,
+            ..Self::test_base()
         }
     }
 }
+
+/// Signing secret for memory-backend sessions and SSO logins when none is configured;
+/// neither survives a restart anyway, since the sessions and providers don't.
+fn random_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -37,6 +37,9 @@ pub fn init_tracing(log_level: &str) {
 #[derive(Clone)]
 pub struct RequestContext {
     pub request_id: String,
+    /// Originating client address. Caddy terminates connections in front of the app, so the
+    /// first `X-Forwarded-For` hop is preferred over the socket peer.
+    pub client_ip: Option<String>,
 }
 
 impl<'a> FromRequest<'a> for RequestContext {
@@ -46,6 +49,22 @@ impl<'a> FromRequest<'a> for RequestContext {
             .map(|s| s.to_string())
             .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-        Ok(RequestContext { request_id })
+        Ok(RequestContext {
+            request_id,
+            client_ip: client_ip(req),
+        })
     }
 }
+
+/// The first `X-Forwarded-For` hop, falling back to the socket peer.
+pub fn client_ip(req: &Request) -> Option<String> {
+    req.header("x-forwarded-for")
+        .and_then(|v| v.split(',').next())
+        .map(|ip| ip.trim().to_string())
+        .filter(|ip| !ip.is_empty())
+        .or_else(|| {
+            req.remote_addr()
+                .as_socket_addr()
+                .map(|addr| addr.ip().to_string())
+        })
+}
@@ -0,0 +1,93 @@
+-- Practice API support: creation with owner bootstrap, soft delete, updated_at maintenance.
+
+-- 1) Keep updated_at current on every write
+create or replace function private.touch_updated_at()
+returns trigger
+language plpgsql
+set search_path = ''
+as $$
+begin
+  NEW.updated_at := now();
+  return NEW;
+end
+$$;
+
+drop trigger if exists trg_touch_practices on public.practices;
+create trigger trg_touch_practices
+before update on public.practices
+for each row execute function private.touch_updated_at();
+
+-- 2) Create a practice and make the caller its owner.
+-- The insert policy requires owner/admin of the new row, which nobody can be yet,
+-- so the bootstrap runs as SECURITY DEFINER and seeds the membership + owner role.
+create or replace function public.create_practice(p_name text)
+returns public.practices
+language plpgsql
+security definer
+set search_path = ''
+as $$
+declare
+  v_user uuid := (select auth.uid());
+  v_practice public.practices;
+  v_membership_id uuid;
+begin
+  if v_user is null then
+    raise exception 'not authenticated' using errcode = '42501';
+  end if;
+
+  if p_name is null or length(btrim(p_name)) = 0 then
+    raise exception 'practice name is required' using errcode = '22023';
+  end if;
+
+  insert into public.practices (name)
+  values (btrim(p_name))
+  returning * into v_practice;
+
+  insert into public.practice_memberships (user_id, practice_id)
+  values (v_user, v_practice.id)
+  returning id into v_membership_id;
+
+  insert into public.practice_membership_roles (membership_id, role_id)
+  select v_membership_id, r.id
+  from public.practice_roles r
+  where r.code = 'owner';
+
+  return v_practice;
+end
+$$;
+
+revoke execute on function public.create_practice(text) from public, anon;
+grant execute on function public.create_practice(text) to authenticated;
+
+-- 3) Soft delete: owners only, runs with the caller's rights so RLS still applies.
+create or replace function public.soft_delete_practice(p_practice_id uuid)
+returns public.practices
+language plpgsql
+security invoker
+set search_path = ''
+as $$
+declare
+  v_practice public.practices;
+begin
+  if not exists (
+    select 1 from public.practices p
+    where p.id = p_practice_id and p.deleted_at is null
+  ) then
+    raise exception 'practice not found' using errcode = 'P0002';
+  end if;
+
+  if not private.is_owner(p_practice_id) then
+    raise exception 'only owners can delete a practice' using errcode = '42501';
+  end if;
+
+  update public.practices
+  set deleted_at = now()
+  where id = p_practice_id
+  returning * into v_practice;
+
+  return v_practice;
+end
+$$;
+
+revoke execute on function public.soft_delete_practice(uuid) from public, anon;
+grant execute on function public.soft_delete_practice(uuid) to authenticated;
@@ -0,0 +1,313 @@
+-- Staff invitations: owners/admins invite by email with roles pre-selected; the invitee
+-- accepts through a single-use link that expires after 7 days.
+-- Only a SHA-256 hash of the link token is stored.
+
+-- 1) Table
+create table if not exists public.practice_invitations (
+  id           uuid primary key default gen_random_uuid(),
+  practice_id  uuid not null references public.practices(id) on delete cascade,
+  email        text not null,
+  role_codes   text[] not null check (cardinality(role_codes) > 0),
+  token_hash   text not null unique,
+  invited_by   uuid references auth.users(id) on delete set null,
+  expires_at   timestamptz not null,
+  accepted_at  timestamptz,
+  accepted_by  uuid references auth.users(id) on delete set null,
+  revoked_at   timestamptz,
+  created_at   timestamptz not null default now(),
+  updated_at   timestamptz not null default now()
+);
+
+create index if not exists idx_invitations_practice on public.practice_invitations (practice_id);
+
+-- At most one open invitation per address and practice
+create unique index if not exists uq_invitations_pending_email
+  on public.practice_invitations (practice_id, lower(email))
+  where accepted_at is null and revoked_at is null;
+
+drop trigger if exists trg_touch_practice_invitations on public.practice_invitations;
+create trigger trg_touch_practice_invitations
+before update on public.practice_invitations
+for each row execute function private.touch_updated_at();
+
+-- 2) Computed `status` column, selectable through PostgREST as `status`
+create or replace function public.status(p_invitation public.practice_invitations)
+returns text
+language sql
+stable
+set search_path = ''
+as $$
+  select case
+    when p_invitation.accepted_at is not null then 'accepted'
+    when p_invitation.revoked_at is not null then 'revoked'
+    when p_invitation.expires_at <= now() then 'expired'
+    else 'pending'
+  end;
+$$;
+
+-- 3) RLS: owners/admins can read their practice's invitations; all writes go through RPCs
+alter table public.practice_invitations enable row level security;
+
+create policy "invitations_select_owner_admin"
+  on public.practice_invitations
+  for select
+  to authenticated
+  using (private.is_owner_or_admin(practice_id));
+
+revoke insert, update, delete on public.practice_invitations from authenticated, anon;
+
+-- Shared permission check: owner/admin of the practice, and only owners may hand out `owner`.
+create or replace function private.can_invite(p_practice_id uuid, p_role_codes text[])
+returns boolean
+language sql
+stable
+security definer
+set search_path = ''
+as $$
+  select private.is_owner_or_admin(p_practice_id)
+    and (not ('owner' = any(p_role_codes)) or private.is_owner(p_practice_id));
+$$;
+
+-- 4) Create an invitation
+create or replace function public.create_practice_invitation(
+  p_practice_id uuid,
+  p_email text,
+  p_role_codes text[],
+  p_token_hash text
+)
+returns public.practice_invitations
+language plpgsql
+security definer
+set search_path = ''
+as $$
+declare
+  v_user uuid := (select auth.uid());
+  v_invitation public.practice_invitations;
+begin
+  if v_user is null then
+    raise exception 'not authenticated' using errcode = '42501';
+  end if;
+
+  if not exists (
+    select 1 from public.practices p
+    where p.id = p_practice_id and p.deleted_at is null
+  ) then
+    raise exception 'practice not found' using errcode = 'P0002';
+  end if;
+
+  if not private.can_invite(p_practice_id, p_role_codes) then
+    raise exception 'not allowed to invite with these roles' using errcode = '42501';
+  end if;
+
+  if coalesce(cardinality(p_role_codes), 0) = 0 or exists (
+    select 1 from unnest(p_role_codes) c
+    where not exists (select 1 from public.practice_roles r where r.code = c)
+  ) then
+    raise exception 'unknown role code' using errcode = '22023';
+  end if;
+
+  if exists (
+    select 1
+    from public.practice_memberships m
+    join auth.users u on u.id = m.user_id
+    where m.practice_id = p_practice_id
+      and m.is_active
+      and lower(u.email) = lower(btrim(p_email))
+  ) then
+    raise exception 'already a member of this practice' using errcode = '23505';
+  end if;
+
+  -- Lapsed invitations no longer count as open
+  update public.practice_invitations
+  set revoked_at = now()
+  where practice_id = p_practice_id
+    and lower(email) = lower(btrim(p_email))
+    and accepted_at is null
+    and revoked_at is null
+    and expires_at <= now();
+
+  if exists (
+    select 1 from public.practice_invitations i
+    where i.practice_id = p_practice_id
+      and lower(i.email) = lower(btrim(p_email))
+      and i.accepted_at is null
+      and i.revoked_at is null
+  ) then
+    raise exception 'an invitation is already pending for this email' using errcode = '23P01';
+  end if;
+
+  insert into public.practice_invitations
+    (practice_id, email, role_codes, token_hash, invited_by, expires_at)
+  values
+    (p_practice_id, lower(btrim(p_email)), p_role_codes, p_token_hash, v_user,
+     now() + interval '7 days')
+  returning * into v_invitation;
+
+  return v_invitation;
+end
+$$;
+
+revoke execute on function public.create_practice_invitation(uuid, text, text[], text) from public, anon;
+grant execute on function public.create_practice_invitation(uuid, text, text[], text) to authenticated;
+
+-- 5) Resend: rotate the token (old links stop working) and restart the expiry window
+create or replace function public.resend_practice_invitation(
+  p_invitation_id uuid,
+  p_token_hash text
+)
+returns public.practice_invitations
+language plpgsql
+security definer
+set search_path = ''
+as $$
+declare
+  v_invitation public.practice_invitations;
+begin
+  select * into v_invitation
+  from public.practice_invitations
+  where id = p_invitation_id
+  for update;
+
+  if not found or not private.is_owner_or_admin(v_invitation.practice_id) then
+    raise exception 'invitation not found' using errcode = 'P0002';
+  end if;
+
+  if not private.can_invite(v_invitation.practice_id, v_invitation.role_codes) then
+    raise exception 'not allowed to invite with these roles' using errcode = '42501';
+  end if;
+
+  if v_invitation.accepted_at is not null or v_invitation.revoked_at is not null then
+    raise exception 'invitation is closed' using errcode = '22023';
+  end if;
+
+  update public.practice_invitations
+  set token_hash = p_token_hash,
+      expires_at = now() + interval '7 days'
+  where id = p_invitation_id
+  returning * into v_invitation;
+
+  return v_invitation;
+end
+$$;
+
+revoke execute on function public.resend_practice_invitation(uuid, text) from public, anon;
+grant execute on function public.resend_practice_invitation(uuid, text) to authenticated;
+
+-- 6) Revoke
+create or replace function public.revoke_practice_invitation(p_invitation_id uuid)
+returns public.practice_invitations
+language plpgsql
+security definer
+set search_path = ''
+as $$
+declare
+  v_invitation public.practice_invitations;
+begin
+  select * into v_invitation
+  from public.practice_invitations
+  where id = p_invitation_id
+  for update;
+
+  if not found or not private.is_owner_or_admin(v_invitation.practice_id) then
+    raise exception 'invitation not found' using errcode = 'P0002';
+  end if;
+
+  if not private.can_invite(v_invitation.practice_id, v_invitation.role_codes) then
+    raise exception 'not allowed to manage this invitation' using errcode = '42501';
+  end if;
+
+  if v_invitation.accepted_at is not null or v_invitation.revoked_at is not null then
+    raise exception 'invitation is closed' using errcode = '22023';
+  end if;
+
+  update public.practice_invitations
+  set revoked_at = now()
+  where id = p_invitation_id
+  returning * into v_invitation;
+
+  return v_invitation;
+end
+$$;
+
+revoke execute on function public.revoke_practice_invitation(uuid) from public, anon;
+grant execute on function public.revoke_practice_invitation(uuid) to authenticated;
+
+-- 7) Look up an invitation by token so the accept page can show who it is for.
+-- Holding the token is the credential, so this is open to anon.
+create or replace function public.find_practice_invitation(p_token_hash text)
+returns table (
+  id uuid,
+  practice_id uuid,
+  practice_name text,
+  email text,
+  role_codes text[],
+  expires_at timestamptz,
+  status text
+)
+language sql
+stable
+security definer
+set search_path = ''
+as $$
+  select i.id, i.practice_id, p.name, i.email, i.role_codes, i.expires_at, public.status(i)
+  from public.practice_invitations i
+  join public.practices p on p.id = i.practice_id and p.deleted_at is null
+  where i.token_hash = p_token_hash;
+$$;
+
+revoke execute on function public.find_practice_invitation(text) from public;
+grant execute on function public.find_practice_invitation(text) to anon, authenticated;
+
+-- 8) Accept as the signed-in invitee: create (or reactivate) the membership and attach roles.
+create or replace function public.accept_practice_invitation(p_token_hash text)
+returns public.practice_invitations
+language plpgsql
+security definer
+set search_path = ''
+as $$
+declare
+  v_user uuid := (select auth.uid());
+  v_email text := lower((select auth.jwt()) ->> 'email');
+  v_invitation public.practice_invitations;
+  v_membership_id uuid;
+begin
+  if v_user is null then
+    raise exception 'not authenticated' using errcode = '42501';
+  end if;
+
+  select * into v_invitation
+  from public.practice_invitations
+  where token_hash = p_token_hash
+  for update;
+
+  if not found or public.status(v_invitation) <> 'pending' then
+    raise exception 'invitation not found' using errcode = 'P0002';
+  end if;
+
+  if v_invitation.email <> v_email then
+    raise exception 'invitation was sent to a different email' using errcode = '42501';
+  end if;
+
+  insert into public.practice_memberships (user_id, practice_id)
+  values (v_user, v_invitation.practice_id)
+  on conflict (user_id, practice_id) do update set is_active = true
+  returning id into v_membership_id;
+
+  insert into public.practice_membership_roles (membership_id, role_id)
+  select v_membership_id, r.id
+  from public.practice_roles r
+  where r.code = any(v_invitation.role_codes)
+  on conflict (membership_id, role_id) do nothing;
+
+  update public.practice_invitations
+  set accepted_at = now(),
+      accepted_by = v_user
+  where id = v_invitation.id
+  returning * into v_invitation;
+
+  return v_invitation;
+end
+$$;
+
+revoke execute on function public.accept_practice_invitation(text) from public, anon;
+grant execute on function public.accept_practice_invitation(text) to authenticated;
@@ -0,0 +1,79 @@
+-- Team API support: clinical supervisors manage team rosters, and only active members of
+-- the team's practice can be added.
+
+-- 1) Is the given user an active member of the practice?
+create or replace function private.is_active_member(p_practice_id uuid, p_user_id uuid)
+returns boolean
+language sql
+security definer
+set search_path = ''
+as $$
+  select exists (
+    select 1
+    from public.practice_memberships m
+    where m.practice_id = p_practice_id
+      and m.user_id = p_user_id
+      and m.is_active
+  );
+$$;
+
+comment on function private.is_active_member is 'Checks a given user is an active member of practice';
+
+-- 2) Supervisors organize clinicians into teams alongside owners/admins
+drop policy if exists "team_members_insert_owner_admin" on public.team_members;
+create policy "team_members_insert_owner_admin_supervisor"
+  on public.team_members
+  for insert
+  to authenticated
+  with check (
+    (private.is_owner_or_admin(practice_id) or private.has_role(practice_id, 'clinical_supervisor'))
+    and private.is_active_member(practice_id, user_id)
+    and exists (
+      select 1 from public.teams t
+      where t.id = team_id and t.practice_id = public.team_members.practice_id
+    )
+  );
+
+drop policy if exists "team_members_delete_owner_admin" on public.team_members;
+create policy "team_members_delete_owner_admin_supervisor"
+  on public.team_members
+  for delete
+  to authenticated
+  using (
+    private.is_owner_or_admin(practice_id) or private.has_role(practice_id, 'clinical_supervisor')
+  );
+
+-- 3) Add a member, deriving practice_id from the team. Runs as the caller so RLS decides
+-- who may write; the explicit checks only exist to give precise errors.
+create or replace function public.add_team_member(p_team_id uuid, p_user_id uuid)
+returns public.team_members
+language plpgsql
+security invoker
+set search_path = ''
+as $$
+declare
+  v_practice_id uuid;
+  v_member public.team_members;
+begin
+  select t.practice_id into v_practice_id
+  from public.teams t
+  where t.id = p_team_id;
+
+  if v_practice_id is null then
+    raise exception 'team not found' using errcode = 'P0002';
+  end if;
+
+  if not private.is_active_member(v_practice_id, p_user_id) then
+    raise exception 'user is not an active member of this practice' using errcode = '23503';
+  end if;
+
+  insert into public.team_members (team_id, user_id, practice_id)
+  values (p_team_id, p_user_id, v_practice_id)
+  returning * into v_member;
+
+  return v_member;
+end
+$$;
+
+revoke execute on function public.add_team_member(uuid, uuid) from public, anon;
+grant execute on function public.add_team_member(uuid, uuid) to authenticated;
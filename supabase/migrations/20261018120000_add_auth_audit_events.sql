@@ -0,0 +1,24 @@
+-- Application-level audit trail for authentication actions the table triggers can't see
+-- (signins, signouts, user deletions, user id lookups). Written by the API with the
+-- service role; not readable through PostgREST by regular users.
+
+create table if not exists public.auth_audit_events (
+  id            bigserial primary key,
+  occurred_at   timestamptz not null default now(),
+  action        text not null check (action in ('signin','signout','delete_user','retrieve_user_id')),
+  outcome       text not null check (outcome in ('success','failure')),
+  actor_user_id uuid,                             -- null for failed signins
+  target        text,                             -- email or user id the action was about
+  request_id    text not null,
+  client_ip     text,
+  reason        text                              -- error message on failure
+);
+
+create index if not exists idx_auth_audit_events_actor
+  on public.auth_audit_events (actor_user_id, occurred_at desc);
+create index if not exists idx_auth_audit_events_occurred
+  on public.auth_audit_events (occurred_at desc);
+
+alter table public.auth_audit_events enable row level security;
+
+revoke all on public.auth_audit_events from anon, authenticated;
@@ -0,0 +1,17 @@
+-- Failed signin counters for brute-force protection, keyed by `email:<address>` or
+-- `ip:<address>`. Written by the API with the service role when several instances need to
+-- share counts; not readable through PostgREST by regular users.
+
+create table if not exists public.login_attempts (
+  key             text primary key,
+  failures        integer not null default 0,
+  last_failure_at bigint not null,                -- unix seconds
+  locked_until    bigint not null default 0       -- unix seconds; 0 when not locked
+);
+
+create index if not exists idx_login_attempts_last_failure
+  on public.login_attempts (last_failure_at);
+
+alter table public.login_attempts enable row level security;
+
+revoke all on public.login_attempts from anon, authenticated;
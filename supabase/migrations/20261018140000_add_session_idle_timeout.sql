@@ -0,0 +1,45 @@
+-- Automatic idle logoff: a per-practice idle timeout, a helper resolving the strictest one
+-- for the caller, and server-side session activity records.
+
+-- 1) Per-practice idle timeout; null falls back to the API's SESSION_IDLE_TIMEOUT_SECS.
+alter table public.practices
+  add column if not exists idle_timeout_secs integer
+  check (idle_timeout_secs between 60 and 86400);
+
+-- 2) The shortest idle timeout among the caller's active practices, or null if none set one.
+create or replace function public.session_idle_timeout()
+returns integer
+language sql
+stable
+security invoker
+set search_path = ''
+as $$
+  select min(p.idle_timeout_secs)
+  from public.practices p
+  join public.practice_memberships m on m.practice_id = p.id
+  where m.user_id = (select auth.uid())
+    and m.is_active
+    and p.deleted_at is null
+$$;
+
+revoke execute on function public.session_idle_timeout() from public, anon;
+grant execute on function public.session_idle_timeout() to authenticated;
+
+-- 3) Last activity per Supabase session, written by the API with the service role when
+-- several instances need to agree; not readable through PostgREST by regular users.
+create table if not exists public.session_activity (
+  session_id        text primary key,
+  user_id           uuid not null references auth.users(id) on delete cascade,
+  created_at        bigint not null,                  -- unix seconds
+  last_seen_at      bigint not null,                  -- unix seconds
+  idle_timeout_secs integer not null,
+  expired           boolean not null default false
+);
+
+create index if not exists idx_session_activity_user on public.session_activity (user_id);
+create index if not exists idx_session_activity_last_seen
+  on public.session_activity (last_seen_at);
+
+alter table public.session_activity enable row level security;
+
+revoke all on public.session_activity from anon, authenticated;
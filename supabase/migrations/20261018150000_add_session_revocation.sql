@@ -0,0 +1,44 @@
+-- Session listing and remote revocation: where each session was last used from, a
+-- revoked flag the API enforces on still-valid access tokens, and a service-role helper
+-- that ends Supabase sessions (and with them their refresh tokens).
+
+-- 1) Device details and revocation on the API's activity records.
+alter table public.session_activity
+  add column if not exists ip_address text,
+  add column if not exists user_agent text,
+  add column if not exists revoked    boolean not null default false;
+
+-- 2) Revocations are audited alongside signins and signouts.
+alter table public.auth_audit_events
+  drop constraint if exists auth_audit_events_action_check;
+alter table public.auth_audit_events
+  add constraint auth_audit_events_action_check
+  check (action in ('signin','signout','delete_user','retrieve_user_id','revoke_sessions'));
+
+-- 3) Ends one of a user's sessions, or all of them when p_session_id is null. Refresh
+-- tokens cascade with their session. GoTrue has no admin endpoint for this, so the API
+-- calls it with the service role; nobody else may.
+create or replace function public.revoke_auth_sessions(
+  p_user_id    uuid,
+  p_session_id uuid default null
+)
+returns integer
+language plpgsql
+volatile
+security definer
+set search_path = ''
+as $$
+declare
+  v_count integer;
+begin
+  delete from auth.sessions s
+  where s.user_id = p_user_id
+    and (p_session_id is null or s.id = p_session_id);
+  get diagnostics v_count = row_count;
+  return v_count;
+end;
+$$;
+
+revoke execute on function public.revoke_auth_sessions(uuid, uuid)
+  from public, anon, authenticated;
+grant execute on function public.revoke_auth_sessions(uuid, uuid) to service_role;
@@ -0,0 +1,8 @@
+-- Profile and account updates (`PATCH /api/auth/me`) are audited alongside signins.
+
+alter table public.auth_audit_events
+  drop constraint if exists auth_audit_events_action_check;
+alter table public.auth_audit_events
+  add constraint auth_audit_events_action_check
+  check (action in ('signin','signout','delete_user','retrieve_user_id','revoke_sessions',
+                    'update_user'));
@@ -0,0 +1,25 @@
+-- Passwordless signin (magic links and email OTP codes): a per-practice switch and a
+-- helper telling whether every practice the caller belongs to allows it.
+
+-- 1) On by default; owners and admins can turn it off for their practice.
+alter table public.practices
+  add column if not exists passwordless_signin boolean not null default true;
+
+-- 2) False if any of the caller's active practices has turned passwordless signin off.
+create or replace function public.passwordless_signin_allowed()
+returns boolean
+language sql
+stable
+security invoker
+set search_path = ''
+as $$
+  select coalesce(bool_and(p.passwordless_signin), true)
+  from public.practices p
+  join public.practice_memberships m on m.practice_id = p.id
+  where m.user_id = (select auth.uid())
+    and m.is_active
+    and p.deleted_at is null
+$$;
+
+revoke execute on function public.passwordless_signin_allowed() from public, anon;
+grant execute on function public.passwordless_signin_allowed() to authenticated;
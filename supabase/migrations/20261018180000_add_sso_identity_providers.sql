@@ -0,0 +1,129 @@
+-- OIDC single sign-on: the identity providers each practice signs its staff in with, and
+-- the federated identities (issuer + subject) that have signed in through them.
+
+-- 1) Per-practice provider configuration
+create table if not exists public.practice_identity_providers (
+  id             uuid primary key default gen_random_uuid(),
+  practice_id    uuid not null references public.practices(id) on delete cascade,
+  name           text not null check (char_length(name) between 1 and 120),
+  issuer         text not null,
+  client_id      text not null,
+  client_secret  text,
+  -- Role for first logins; SSO never hands out ownership.
+  default_role   text not null default 'clinician'
+                 references public.practice_roles(code)
+                 check (default_role <> 'owner'),
+  email_domains  text[] not null check (cardinality(email_domains) > 0),
+  created_at     timestamptz not null default now(),
+  unique (practice_id, issuer, client_id)
+);
+
+create index if not exists idx_identity_providers_practice
+  on public.practice_identity_providers (practice_id);
+
+-- 2) RLS: owners/admins manage their practice's providers. The client secret is
+-- write-only for them; the login flow reads it with the service role.
+alter table public.practice_identity_providers enable row level security;
+
+create policy "identity_providers_select_owner_admin"
+  on public.practice_identity_providers
+  for select
+  to authenticated
+  using (private.is_owner_or_admin(practice_id));
+
+create policy "identity_providers_insert_owner_admin"
+  on public.practice_identity_providers
+  for insert
+  to authenticated
+  with check (private.is_owner_or_admin(practice_id));
+
+create policy "identity_providers_delete_owner_admin"
+  on public.practice_identity_providers
+  for delete
+  to authenticated
+  using (private.is_owner_or_admin(practice_id));
+
+revoke all on public.practice_identity_providers from anon, authenticated;
+grant select (id, practice_id, name, issuer, client_id, default_role, email_domains, created_at)
+  on public.practice_identity_providers to authenticated;
+grant insert, delete on public.practice_identity_providers to authenticated;
+
+-- 3) Identities that have signed in, and the account each maps to. Service role only.
+create table if not exists public.federated_identities (
+  issuer          text not null,
+  subject         text not null,
+  provider_id     uuid not null references public.practice_identity_providers(id) on delete cascade,
+  user_id         uuid not null references auth.users(id) on delete cascade,
+  created_at      timestamptz not null default now(),
+  last_signin_at  timestamptz not null default now(),
+  primary key (issuer, subject)
+);
+
+create index if not exists idx_federated_identities_user on public.federated_identities (user_id);
+
+alter table public.federated_identities enable row level security;
+
+revoke all on public.federated_identities from anon, authenticated;
+
+-- 4) Called by the API after a successful login. Records the identity and, on first login,
+-- makes the user a member of the provider's practice with its default role. A membership
+-- an admin has deactivated stays deactivated and the login is refused (42501); an identity
+-- already mapped to another account is refused too (23505).
+create or replace function public.link_federated_identity(
+  p_provider_id uuid,
+  p_subject     text,
+  p_user_id     uuid
+)
+returns void
+language plpgsql
+volatile
+security definer
+set search_path = ''
+as $$
+declare
+  v_provider public.practice_identity_providers;
+  v_linked_user uuid;
+  v_membership public.practice_memberships;
+begin
+  select * into v_provider
+  from public.practice_identity_providers
+  where id = p_provider_id;
+
+  if not found then
+    raise exception 'identity provider not found' using errcode = 'P0002';
+  end if;
+
+  insert into public.federated_identities (issuer, subject, provider_id, user_id)
+  values (v_provider.issuer, p_subject, v_provider.id, p_user_id)
+  on conflict (issuer, subject) do update set last_signin_at = now()
+  returning user_id into v_linked_user;
+
+  if v_linked_user <> p_user_id then
+    raise exception 'identity is linked to another account' using errcode = '23505';
+  end if;
+
+  select * into v_membership
+  from public.practice_memberships
+  where user_id = p_user_id and practice_id = v_provider.practice_id;
+
+  if found then
+    if not v_membership.is_active then
+      raise exception 'membership is deactivated' using errcode = '42501';
+    end if;
+    return;
+  end if;
+
+  insert into public.practice_memberships (user_id, practice_id)
+  values (p_user_id, v_provider.practice_id)
+  returning * into v_membership;
+
+  insert into public.practice_membership_roles (membership_id, role_id)
+  select v_membership.id, r.id
+  from public.practice_roles r
+  where r.code = v_provider.default_role;
+end;
+$$;
+
+revoke execute on function public.link_federated_identity(uuid, text, uuid)
+  from public, anon, authenticated;
+grant execute on function public.link_federated_identity(uuid, text, uuid) to service_role;
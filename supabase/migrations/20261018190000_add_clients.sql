@@ -0,0 +1,149 @@
+-- Clients (patients) of a practice and their demographics. Every active member of the
+-- practice can see and edit its clients; only owners and admins can delete one, and
+-- discharging is the usual way out.
+
+-- 1) Medical record numbers default to C0000001, C0000002, ... unless the practice brings
+-- its own.
+create sequence if not exists public.client_mrn_seq;
+
+create table if not exists public.clients (
+  id                              uuid primary key default gen_random_uuid(),
+  practice_id                     uuid not null references public.practices(id) on delete cascade,
+  mrn                             text not null
+                                  default ('C' || lpad(nextval('public.client_mrn_seq')::text, 7, '0'))
+                                  check (mrn ~ '^[A-Za-z0-9-]{1,20}$'),
+  first_name                      text not null check (char_length(first_name) between 1 and 100),
+  last_name                       text not null check (char_length(last_name) between 1 and 100),
+  preferred_name                  text check (char_length(preferred_name) between 1 and 100),
+  date_of_birth                   date not null check (date_of_birth >= date '1900-01-01'),
+  phone                           text check (phone ~ '^\+[1-9][0-9]{7,14}$'),
+  address_line1                   text check (char_length(address_line1) between 1 and 200),
+  address_line2                   text check (char_length(address_line2) between 1 and 200),
+  city                            text check (char_length(city) between 1 and 100),
+  state                           text check (state ~ '^[A-Z]{2}$'),
+  postal_code                     text check (postal_code ~ '^[0-9]{5}(-[0-9]{4})?$'),
+  emergency_contact_name          text check (char_length(emergency_contact_name) between 1 and 200),
+  emergency_contact_relationship  text check (char_length(emergency_contact_relationship) between 1 and 100),
+  emergency_contact_phone         text check (emergency_contact_phone ~ '^\+[1-9][0-9]{7,14}$'),
+  status                          text not null default 'active'
+                                  check (status in ('active', 'discharged')),
+  discharged_at                   timestamptz,
+  created_by                      uuid default auth.uid() references auth.users(id) on delete set null,
+  created_at                      timestamptz not null default now(),
+  updated_at                      timestamptz not null default now(),
+  unique (practice_id, mrn),
+  check ((status = 'discharged') = (discharged_at is not null)),
+  -- An address is all or nothing, apart from the second line.
+  check (
+    (address_line1 is null and address_line2 is null and city is null and state is null and postal_code is null)
+    or (address_line1 is not null and city is not null and state is not null and postal_code is not null)
+  ),
+  check (
+    (emergency_contact_name is null) = (emergency_contact_phone is null)
+    and (emergency_contact_relationship is null or emergency_contact_name is not null)
+  )
+);
+
+create index if not exists idx_clients_practice_name
+  on public.clients (practice_id, lower(last_name), lower(first_name));
+create index if not exists idx_clients_practice_dob on public.clients (practice_id, date_of_birth);
+
+-- 2) Keep updated_at and discharged_at in step with the row.
+create or replace function private.fn_clients_touch()
+returns trigger
+language plpgsql
+set search_path = ''
+as $$
+begin
+  new.updated_at := now();
+  if new.status = 'discharged' and (tg_op = 'INSERT' or old.status <> 'discharged') then
+    new.discharged_at := now();
+  elsif new.status = 'active' then
+    new.discharged_at := null;
+  end if;
+  return new;
+end
+$$;
+
+drop trigger if exists trg_clients_touch on public.clients;
+create trigger trg_clients_touch
+before insert or update on public.clients
+for each row execute function private.fn_clients_touch();
+
+drop trigger if exists trg_audit_clients on public.clients;
+create trigger trg_audit_clients
+after insert or update or delete on public.clients
+for each row execute function public.fn_audit_trigger();
+
+-- 3) RLS
+alter table public.clients enable row level security;
+
+create policy "clients_select_for_members"
+  on public.clients
+  for select
+  to authenticated
+  using (private.is_member_of_practice(practice_id));
+
+create policy "clients_insert_for_members"
+  on public.clients
+  for insert
+  to authenticated
+  with check (private.is_member_of_practice(practice_id));
+
+create policy "clients_update_for_members"
+  on public.clients
+  for update
+  to authenticated
+  using (private.is_member_of_practice(practice_id))
+  with check (private.is_member_of_practice(practice_id));
+
+create policy "clients_delete_owner_admin"
+  on public.clients
+  for delete
+  to authenticated
+  using (private.is_owner_or_admin(practice_id));
+
+revoke all on public.clients from anon;
+grant select, insert, update, delete on public.clients to authenticated;
+grant usage on sequence public.client_mrn_seq to authenticated;
+
+-- 4) Search within a practice. Every word of p_name must start a first, last or preferred
+-- name; the other filters match exactly. Runs as the caller, so RLS still decides.
+create or replace function public.search_clients(
+  p_practice_id    uuid,
+  p_name           text default null,
+  p_date_of_birth  date default null,
+  p_mrn            text default null,
+  p_status         text default null
+)
+returns setof public.clients
+language sql
+stable
+security invoker
+set search_path = ''
+as $$
+  select c.*
+  from public.clients c
+  where c.practice_id = p_practice_id
+    and (p_date_of_birth is null or c.date_of_birth = p_date_of_birth)
+    and (p_mrn is null or upper(c.mrn) = upper(p_mrn))
+    and (p_status is null or c.status = p_status)
+    and (
+      p_name is null
+      or not exists (
+        select 1
+        from unnest(regexp_split_to_array(trim(p_name), '\s+')) as w(word)
+        where not (
+          c.first_name ilike (replace(replace(replace(w.word, '\', '\\'), '%', '\%'), '_', '\_') || '%')
+          or c.last_name ilike (replace(replace(replace(w.word, '\', '\\'), '%', '\%'), '_', '\_') || '%')
+          or coalesce(c.preferred_name, '')
+             ilike (replace(replace(replace(w.word, '\', '\\'), '%', '\%'), '_', '\_') || '%')
+        )
+      )
+    )
+  order by lower(c.last_name), lower(c.first_name), c.date_of_birth
+  limit 200;
+$$;
+
+revoke execute on function public.search_clients(uuid, text, date, text, text) from public, anon;
+grant execute on function public.search_clients(uuid, text, date, text, text) to authenticated;
@@ -0,0 +1,177 @@
+-- Caseloads: each client can have a primary clinician, a supervising clinician and a team.
+-- Clinicians see only the clients they are assigned to, directly or through one of their
+-- teams. Owners, admins and clinical supervisors see every client in the practice, as do
+-- schedulers and billers, whose work spans the whole client list.
+
+-- 1) Assignment columns
+alter table public.clients
+  add column if not exists primary_clinician_id uuid references auth.users(id) on delete set null,
+  add column if not exists supervising_clinician_id uuid references auth.users(id) on delete set null,
+  add column if not exists team_id uuid references public.teams(id) on delete set null;
+
+create index if not exists idx_clients_primary_clinician on public.clients (primary_clinician_id);
+create index if not exists idx_clients_supervising_clinician on public.clients (supervising_clinician_id);
+create index if not exists idx_clients_team on public.clients (team_id);
+
+-- 2) Helpers
+-- Does the given user hold a role in the practice? `has_role` only asks about the caller.
+create or replace function private.user_has_role(p_practice_id uuid, p_user_id uuid, p_role_code text)
+returns boolean
+language sql
+security definer
+set search_path = ''
+as $$
+  select exists (
+    select 1
+    from public.practice_memberships m
+    join public.practice_membership_roles mr on mr.membership_id = m.id
+    join public.practice_roles r on r.id = mr.role_id
+    where m.practice_id = p_practice_id
+      and m.user_id = p_user_id
+      and m.is_active
+      and r.code = p_role_code
+  );
+$$;
+
+-- Owners, admins and clinical supervisors decide who works with which client.
+create or replace function private.can_assign_clients(p_practice_id uuid)
+returns boolean
+language sql
+security definer
+set search_path = ''
+as $$
+  select
+    private.is_owner_or_admin(p_practice_id)
+    or private.has_role(p_practice_id, 'clinical_supervisor');
+$$;
+
+create or replace function private.has_practice_wide_client_access(p_practice_id uuid)
+returns boolean
+language sql
+security definer
+set search_path = ''
+as $$
+  select
+    private.can_assign_clients(p_practice_id)
+    or private.has_role(p_practice_id, 'scheduler')
+    or private.has_role(p_practice_id, 'biller');
+$$;
+
+create or replace function private.can_access_client(
+  p_practice_id uuid,
+  p_primary_clinician_id uuid,
+  p_supervising_clinician_id uuid,
+  p_team_id uuid
+)
+returns boolean
+language sql
+security definer
+set search_path = ''
+as $$
+  select
+    private.is_member_of_practice(p_practice_id)
+    and (
+      private.has_practice_wide_client_access(p_practice_id)
+      or p_primary_clinician_id = (select auth.uid())
+      or p_supervising_clinician_id = (select auth.uid())
+      or exists (
+        select 1
+        from public.team_members tm
+        where tm.team_id = p_team_id
+          and tm.user_id = (select auth.uid())
+      )
+    );
+$$;
+
+comment on function private.user_has_role is 'Checks a given user has a specific role in practice';
+comment on function private.can_assign_clients is 'Checks current auth.uid() may change client assignments (owner, admin, clinical supervisor)';
+comment on function private.has_practice_wide_client_access is 'Checks current auth.uid() sees every client in practice';
+comment on function private.can_access_client is 'Checks current auth.uid() sees a client, practice-wide or through their caseload';
+
+-- 3) Guard assignments. Only assigners may set or change them; a clinician who adds a
+-- client becomes its primary clinician so it stays in their caseload (schedulers and
+-- billers see every client, so theirs are left unassigned). Assignees must be active
+-- clinicians or supervisors of the practice, and the team must belong to it. Calls
+-- without a user (service role, seeds) skip the permission check but not validation.
+create or replace function private.fn_clients_check_assignment()
+returns trigger
+language plpgsql
+set search_path = ''
+as $$
+declare
+  v_user uuid := (select auth.uid());
+  v_changed boolean;
+begin
+  if tg_op = 'INSERT' then
+    v_changed := new.primary_clinician_id is not null
+      or new.supervising_clinician_id is not null
+      or new.team_id is not null;
+  else
+    v_changed := new.primary_clinician_id is distinct from old.primary_clinician_id
+      or new.supervising_clinician_id is distinct from old.supervising_clinician_id
+      or new.team_id is distinct from old.team_id;
+  end if;
+
+  if v_user is not null and not private.can_assign_clients(new.practice_id) then
+    if v_changed then
+      raise exception 'only owners, admins and clinical supervisors can assign clients'
+        using errcode = '42501';
+    end if;
+    if tg_op = 'INSERT' and not private.has_practice_wide_client_access(new.practice_id) then
+      new.primary_clinician_id := v_user;
+    end if;
+  end if;
+
+  if new.primary_clinician_id is not null
+     and not private.user_has_role(new.practice_id, new.primary_clinician_id, 'clinician')
+     and not private.user_has_role(new.practice_id, new.primary_clinician_id, 'clinical_supervisor') then
+    raise exception 'primary clinician must be an active clinician in this practice'
+      using errcode = '23503';
+  end if;
+
+  if new.supervising_clinician_id is not null
+     and not private.user_has_role(new.practice_id, new.supervising_clinician_id, 'clinical_supervisor') then
+    raise exception 'supervising clinician must be an active clinical supervisor in this practice'
+      using errcode = '23503';
+  end if;
+
+  if new.team_id is not null
+     and not exists (
+       select 1 from public.teams t
+       where t.id = new.team_id and t.practice_id = new.practice_id
+     ) then
+    raise exception 'team must belong to this practice' using errcode = '23503';
+  end if;
+
+  return new;
+end
+$$;
+
+drop trigger if exists trg_clients_check_assignment on public.clients;
+create trigger trg_clients_check_assignment
+before insert or update on public.clients
+for each row execute function private.fn_clients_check_assignment();
+
+-- 4) RLS: reads and edits follow the caseload; deletes stay with owners and admins.
+drop policy if exists "clients_select_for_members" on public.clients;
+create policy "clients_select_caseload"
+  on public.clients
+  for select
+  to authenticated
+  using (private.can_access_client(practice_id, primary_clinician_id, supervising_clinician_id, team_id));
+
+-- The trigger fills in primary_clinician_id before this check runs.
+drop policy if exists "clients_insert_for_members" on public.clients;
+create policy "clients_insert_caseload"
+  on public.clients
+  for insert
+  to authenticated
+  with check (private.can_access_client(practice_id, primary_clinician_id, supervising_clinician_id, team_id));
+
+drop policy if exists "clients_update_for_members" on public.clients;
+create policy "clients_update_caseload"
+  on public.clients
+  for update
+  to authenticated
+  using (private.can_access_client(practice_id, primary_clinician_id, supervising_clinician_id, team_id))
+  with check (private.can_access_client(practice_id, primary_clinician_id, supervising_clinician_id, team_id));
@@ -0,0 +1,350 @@
+-- Appointments: a client, a clinician and a place (a room or a telehealth link) at a time.
+-- Recurring appointments belong to a series whose occurrences the API expands from an
+-- RRULE and stores as ordinary appointments; an occurrence changed or cancelled on its own
+-- is marked as an exception. A clinician can never be booked twice at once.
+
+create extension if not exists btree_gist with schema extensions;
+
+-- 1) Series
+create table if not exists public.appointment_series (
+  id                uuid primary key default gen_random_uuid(),
+  practice_id       uuid not null references public.practices(id) on delete cascade,
+  client_id         uuid not null references public.clients(id) on delete cascade,
+  clinician_id      uuid not null references auth.users(id) on delete cascade,
+  -- Canonical form, e.g. FREQ=WEEKLY;INTERVAL=1;BYDAY=MO;COUNT=12
+  rrule             text not null check (char_length(rrule) between 1 and 200),
+  -- IANA name; occurrences keep their wall-clock time across DST changes.
+  timezone          text not null check (char_length(timezone) between 1 and 64),
+  start_date        date not null,
+  start_time        time not null,
+  duration_minutes  integer not null check (duration_minutes between 5 and 720),
+  exdates           date[] not null default '{}',
+  location_kind     text not null check (location_kind in ('in_person', 'telehealth')),
+  location          text check (char_length(location) between 1 and 200),
+  telehealth_url    text check (telehealth_url ~ '^https://' and char_length(telehealth_url) <= 500),
+  -- Set once the remaining occurrences were cancelled.
+  cancelled_from    timestamptz,
+  created_by        uuid default auth.uid() references auth.users(id) on delete set null,
+  created_at        timestamptz not null default now()
+);
+
+create index if not exists idx_appointment_series_practice on public.appointment_series (practice_id);
+create index if not exists idx_appointment_series_client on public.appointment_series (client_id);
+
+-- 2) Appointments
+create table if not exists public.appointments (
+  id                   uuid primary key default gen_random_uuid(),
+  practice_id          uuid not null references public.practices(id) on delete cascade,
+  client_id            uuid not null references public.clients(id) on delete cascade,
+  clinician_id         uuid not null references auth.users(id) on delete cascade,
+  series_id            uuid references public.appointment_series(id) on delete cascade,
+  is_exception         boolean not null default false,
+  starts_at            timestamptz not null,
+  ends_at              timestamptz not null,
+  location_kind        text not null check (location_kind in ('in_person', 'telehealth')),
+  location             text check (char_length(location) between 1 and 200),
+  telehealth_url       text check (telehealth_url ~ '^https://' and char_length(telehealth_url) <= 500),
+  status               text not null default 'scheduled'
+                       check (status in ('scheduled', 'checked_in', 'completed', 'no_show', 'cancelled')),
+  status_changed_at    timestamptz,
+  cancellation_reason  text check (char_length(cancellation_reason) between 1 and 500),
+  created_by           uuid default auth.uid() references auth.users(id) on delete set null,
+  created_at           timestamptz not null default now(),
+  updated_at           timestamptz not null default now(),
+  check (ends_at > starts_at and ends_at <= starts_at + interval '12 hours'),
+  check (is_exception = false or series_id is not null),
+  constraint appointments_no_double_booking
+    exclude using gist (clinician_id with =, tstzrange(starts_at, ends_at) with &&)
+    where (status <> 'cancelled')
+);
+
+create index if not exists idx_appointments_practice_starts on public.appointments (practice_id, starts_at);
+create index if not exists idx_appointments_client_starts on public.appointments (client_id, starts_at);
+create index if not exists idx_appointments_series on public.appointments (series_id);
+
+-- 3) Keep updated_at and status_changed_at current, and check the people involved: the
+-- clinician must be an active clinician or supervisor of the practice, and new bookings
+-- need an active client of the same practice.
+create or replace function private.fn_appointments_touch()
+returns trigger
+language plpgsql
+set search_path = ''
+as $$
+begin
+  new.updated_at := now();
+  if tg_op = 'INSERT' or new.status is distinct from old.status then
+    new.status_changed_at := case when new.status = 'scheduled' then null else now() end;
+  end if;
+  return new;
+end
+$$;
+
+create or replace function private.fn_appointments_check_parties()
+returns trigger
+language plpgsql
+security definer
+set search_path = ''
+as $$
+begin
+  if tg_op = 'INSERT' or new.clinician_id is distinct from old.clinician_id then
+    if not private.user_has_role(new.practice_id, new.clinician_id, 'clinician')
+       and not private.user_has_role(new.practice_id, new.clinician_id, 'clinical_supervisor') then
+      raise exception 'clinician must be an active clinician in this practice'
+        using errcode = '23503';
+    end if;
+  end if;
+
+  if tg_op = 'INSERT' or new.client_id is distinct from old.client_id then
+    if not exists (
+      select 1 from public.clients c
+      where c.id = new.client_id and c.practice_id = new.practice_id
+    ) then
+      raise exception 'client not found in this practice' using errcode = '23503';
+    end if;
+    if exists (
+      select 1 from public.clients c
+      where c.id = new.client_id and c.status = 'discharged'
+    ) then
+      raise exception 'client is discharged' using errcode = '22023';
+    end if;
+  end if;
+
+  return new;
+end
+$$;
+
+drop trigger if exists trg_appointments_check_parties on public.appointments;
+create trigger trg_appointments_check_parties
+before insert or update on public.appointments
+for each row execute function private.fn_appointments_check_parties();
+
+drop trigger if exists trg_appointments_touch on public.appointments;
+create trigger trg_appointments_touch
+before insert or update on public.appointments
+for each row execute function private.fn_appointments_touch();
+
+drop trigger if exists trg_audit_appointments on public.appointments;
+create trigger trg_audit_appointments
+after insert or update or delete on public.appointments
+for each row execute function public.fn_audit_trigger();
+
+drop trigger if exists trg_audit_appointment_series on public.appointment_series;
+create trigger trg_audit_appointment_series
+after insert or update or delete on public.appointment_series
+for each row execute function public.fn_audit_trigger();
+
+-- 4) Helpers. Owners, admins, schedulers and clinical supervisors book for any clinician;
+-- clinicians book for themselves. A clinician sees their own appointments and those of
+-- the clients they can see.
+create or replace function private.can_book_for(p_practice_id uuid, p_clinician_id uuid)
+returns boolean
+language sql
+security definer
+set search_path = ''
+as $$
+  select
+    private.is_member_of_practice(p_practice_id)
+    and (
+      private.is_owner_or_admin(p_practice_id)
+      or private.has_role(p_practice_id, 'scheduler')
+      or private.has_role(p_practice_id, 'clinical_supervisor')
+      or p_clinician_id = (select auth.uid())
+    );
+$$;
+
+create or replace function private.can_access_client_id(p_client_id uuid)
+returns boolean
+language sql
+security definer
+set search_path = ''
+as $$
+  select exists (
+    select 1
+    from public.clients c
+    where c.id = p_client_id
+      and private.can_access_client(c.practice_id, c.primary_clinician_id, c.supervising_clinician_id, c.team_id)
+  );
+$$;
+
+create or replace function private.can_access_appointment(p_practice_id uuid, p_client_id uuid, p_clinician_id uuid)
+returns boolean
+language sql
+security definer
+set search_path = ''
+as $$
+  select
+    private.is_member_of_practice(p_practice_id)
+    and (p_clinician_id = (select auth.uid()) or private.can_access_client_id(p_client_id));
+$$;
+
+comment on function private.can_book_for is 'Checks current auth.uid() may book appointments for a clinician in practice';
+comment on function private.can_access_client_id is 'Checks current auth.uid() can see a client';
+comment on function private.can_access_appointment is 'Checks current auth.uid() can see an appointment: their own, or one for a client they can see';
+
+-- 5) RLS: same rules for single appointments and series; deletes stay with owners and
+-- admins, since cancelling keeps the history.
+alter table public.appointments enable row level security;
+alter table public.appointment_series enable row level security;
+
+create policy "appointments_select_visible"
+  on public.appointments
+  for select
+  to authenticated
+  using (private.can_access_appointment(practice_id, client_id, clinician_id));
+
+create policy "appointments_insert_bookers"
+  on public.appointments
+  for insert
+  to authenticated
+  with check (private.can_book_for(practice_id, clinician_id) and private.can_access_client_id(client_id));
+
+-- A clinician may still update their own appointment with a client outside their caseload
+-- (booked for them by a scheduler), e.g. to check the client in.
+create policy "appointments_update_bookers"
+  on public.appointments
+  for update
+  to authenticated
+  using (private.can_access_appointment(practice_id, client_id, clinician_id))
+  with check (
+    private.can_book_for(practice_id, clinician_id)
+    and (clinician_id = (select auth.uid()) or private.can_access_client_id(client_id))
+  );
+
+create policy "appointments_delete_owner_admin"
+  on public.appointments
+  for delete
+  to authenticated
+  using (private.is_owner_or_admin(practice_id));
+
+create policy "appointment_series_select_visible"
+  on public.appointment_series
+  for select
+  to authenticated
+  using (private.can_access_appointment(practice_id, client_id, clinician_id));
+
+create policy "appointment_series_insert_bookers"
+  on public.appointment_series
+  for insert
+  to authenticated
+  with check (private.can_book_for(practice_id, clinician_id) and private.can_access_client_id(client_id));
+
+create policy "appointment_series_update_bookers"
+  on public.appointment_series
+  for update
+  to authenticated
+  using (private.can_access_appointment(practice_id, client_id, clinician_id))
+  with check (private.can_book_for(practice_id, clinician_id));
+
+create policy "appointment_series_delete_owner_admin"
+  on public.appointment_series
+  for delete
+  to authenticated
+  using (private.is_owner_or_admin(practice_id));
+
+revoke all on public.appointments, public.appointment_series from anon;
+grant select, insert, update, delete on public.appointments, public.appointment_series to authenticated;
+
+-- 6) Create a series and its occurrences in one transaction, so a clash on any date
+-- books nothing. p_dates are the local dates the API expanded from the rule; each starts
+-- at p_start_time in p_timezone. Runs as the caller, so RLS still decides.
+create or replace function public.create_appointment_series(
+  p_practice_id       uuid,
+  p_client_id         uuid,
+  p_clinician_id      uuid,
+  p_rrule             text,
+  p_timezone          text,
+  p_start_date        date,
+  p_start_time        time,
+  p_duration_minutes  integer,
+  p_exdates           date[],
+  p_location_kind     text,
+  p_location          text,
+  p_telehealth_url    text,
+  p_dates             date[]
+)
+returns public.appointment_series
+language plpgsql
+security invoker
+set search_path = ''
+as $$
+declare
+  v_series public.appointment_series;
+begin
+  if not exists (select 1 from pg_catalog.pg_timezone_names where name = p_timezone) then
+    raise exception 'unknown time zone: %', p_timezone using errcode = '22023';
+  end if;
+
+  if coalesce(cardinality(p_dates), 0) = 0 then
+    raise exception 'series has no occurrences' using errcode = '22023';
+  end if;
+
+  insert into public.appointment_series (
+    practice_id, client_id, clinician_id, rrule, timezone, start_date, start_time,
+    duration_minutes, exdates, location_kind, location, telehealth_url
+  )
+  values (
+    p_practice_id, p_client_id, p_clinician_id, p_rrule, p_timezone, p_start_date, p_start_time,
+    p_duration_minutes, coalesce(p_exdates, '{}'), p_location_kind, p_location, p_telehealth_url
+  )
+  returning * into v_series;
+
+  insert into public.appointments (
+    practice_id, client_id, clinician_id, series_id, starts_at, ends_at,
+    location_kind, location, telehealth_url
+  )
+  select
+    p_practice_id, p_client_id, p_clinician_id, v_series.id,
+    (d + p_start_time) at time zone p_timezone,
+    ((d + p_start_time) at time zone p_timezone) + make_interval(mins => p_duration_minutes),
+    p_location_kind, p_location, p_telehealth_url
+  from unnest(p_dates) as d;
+
+  return v_series;
+end
+$$;
+
+revoke execute on function public.create_appointment_series(uuid, uuid, uuid, text, text, date, time, integer, date[], text, text, text, date[]) from public, anon;
+grant execute on function public.create_appointment_series(uuid, uuid, uuid, text, text, date, time, integer, date[], text, text, text, date[]) to authenticated;
+
+-- 7) Cancel every still-scheduled occurrence starting at or after p_from. Earlier ones,
+-- and those already checked in or closed, are left as they are.
+create or replace function public.cancel_appointment_series(
+  p_series_id  uuid,
+  p_from       timestamptz,
+  p_reason     text default null
+)
+returns public.appointment_series
+language plpgsql
+security invoker
+set search_path = ''
+as $$
+declare
+  v_series public.appointment_series;
+begin
+  if not exists (select 1 from public.appointment_series s where s.id = p_series_id) then
+    raise exception 'series not found' using errcode = 'P0002';
+  end if;
+
+  update public.appointment_series s
+  set cancelled_from = p_from
+  where s.id = p_series_id
+  returning * into v_series;
+
+  -- Visible but filtered out by the update policy.
+  if v_series.id is null then
+    raise exception 'not allowed to cancel this series' using errcode = '42501';
+  end if;
+
+  update public.appointments a
+  set status = 'cancelled',
+      cancellation_reason = p_reason
+  where a.series_id = p_series_id
+    and a.starts_at >= p_from
+    and a.status = 'scheduled';
+
+  return v_series;
+end
+$$;
+
+revoke execute on function public.cancel_appointment_series(uuid, timestamptz, text) from public, anon;
+grant execute on function public.cancel_appointment_series(uuid, timestamptz, text) to authenticated;
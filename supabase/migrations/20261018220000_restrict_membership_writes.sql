@@ -0,0 +1,13 @@
+-- Memberships only come from create_practice, accept_practice_invitation and SSO
+-- linking. Letting an owner insert rows (or repoint an existing one) would let anyone
+-- who creates a practice enrol arbitrary users into it and then act on them as their
+-- "owner".
+
+-- 1) No direct inserts; the security definer functions above bypass RLS.
+drop policy if exists "memberships_insert_owner_admin" on public.practice_memberships;
+revoke insert on public.practice_memberships from anon, authenticated;
+
+-- 2) Owners and admins still (de)activate members, but a row's user and practice are
+-- fixed once created.
+revoke update on public.practice_memberships from anon, authenticated;
+grant update (is_active) on public.practice_memberships to authenticated;
@@ -0,0 +1,113 @@
+-- A provider asserts whatever email its users have, so it mustn't sign anyone in until a
+-- platform admin has checked that the practice controls its issuer and domains, and it
+-- must never reach an existing account that hasn't linked the identity itself.
+
+-- 1) Approval. Owners and admins can't set it: their insert grant leaves it out.
+alter table public.practice_identity_providers
+  add column if not exists approved_at timestamptz;
+
+revoke insert on public.practice_identity_providers from authenticated;
+grant insert (practice_id, name, issuer, client_id, client_secret, default_role, email_domains)
+  on public.practice_identity_providers to authenticated;
+grant select (approved_at) on public.practice_identity_providers to authenticated;
+
+create or replace function public.approve_identity_provider(
+  p_practice_id uuid,
+  p_provider_id uuid
+)
+returns setof public.practice_identity_providers
+language sql
+volatile
+security definer
+set search_path = ''
+as $$
+  update public.practice_identity_providers
+  set approved_at = coalesce(approved_at, now())
+  where id = p_provider_id and practice_id = p_practice_id
+  returning *;
+$$;
+
+revoke execute on function public.approve_identity_provider(uuid, uuid)
+  from public, anon, authenticated;
+grant execute on function public.approve_identity_provider(uuid, uuid) to service_role;
+
+-- 2) Linking. An identity is only linked to an account when the API says the link is new
+-- (a fresh SSO account, or a signed-in user linking it); otherwise it must already point
+-- at `p_user_id`. Unapproved providers link nothing.
+drop function if exists public.link_federated_identity(uuid, text, uuid);
+
+create or replace function public.link_federated_identity(
+  p_provider_id uuid,
+  p_subject     text,
+  p_user_id     uuid,
+  p_new_link    boolean
+)
+returns void
+language plpgsql
+volatile
+security definer
+set search_path = ''
+as $$
+declare
+  v_provider public.practice_identity_providers;
+  v_linked_user uuid;
+  v_membership public.practice_memberships;
+begin
+  select * into v_provider
+  from public.practice_identity_providers
+  where id = p_provider_id and approved_at is not null;
+
+  if not found then
+    raise exception 'identity provider not found' using errcode = 'P0002';
+  end if;
+
+  if p_new_link then
+    insert into public.federated_identities (issuer, subject, provider_id, user_id)
+    values (v_provider.issuer, p_subject, v_provider.id, p_user_id)
+    on conflict (issuer, subject) do update set last_signin_at = now()
+    returning user_id into v_linked_user;
+  else
+    update public.federated_identities
+    set last_signin_at = now()
+    where issuer = v_provider.issuer and subject = p_subject
+    returning user_id into v_linked_user;
+  end if;
+
+  if v_linked_user is distinct from p_user_id then
+    raise exception 'identity is linked to another account' using errcode = '23505';
+  end if;
+
+  select * into v_membership
+  from public.practice_memberships
+  where user_id = p_user_id and practice_id = v_provider.practice_id;
+
+  if found then
+    if not v_membership.is_active then
+      raise exception 'membership is deactivated' using errcode = '42501';
+    end if;
+    return;
+  end if;
+
+  insert into public.practice_memberships (user_id, practice_id)
+  values (p_user_id, v_provider.practice_id)
+  returning * into v_membership;
+
+  insert into public.practice_membership_roles (membership_id, role_id)
+  select v_membership.id, r.id
+  from public.practice_roles r
+  where r.code = v_provider.default_role;
+end;
+$$;
+
+revoke execute on function public.link_federated_identity(uuid, text, uuid, boolean)
+  from public, anon, authenticated;
+grant execute on function public.link_federated_identity(uuid, text, uuid, boolean)
+  to service_role;
+
+-- 3) Links are audited.
+alter table public.auth_audit_events
+  drop constraint if exists auth_audit_events_action_check;
+alter table public.auth_audit_events
+  add constraint auth_audit_events_action_check
+  check (action in ('signin','signout','delete_user','retrieve_user_id','revoke_sessions',
+                    'update_user','link_identity'));
@@ -0,0 +1,56 @@
+-- Series get the same party checks as single appointments, and moving either to another
+-- practice re-checks the clinician and client against that practice.
+
+-- 1) Appointments: a new practice_id counts as a change of both parties.
+create or replace function private.fn_appointments_check_parties()
+returns trigger
+language plpgsql
+security definer
+set search_path = ''
+as $$
+begin
+  if tg_op = 'INSERT'
+     or new.clinician_id is distinct from old.clinician_id
+     or new.practice_id is distinct from old.practice_id then
+    if not private.user_has_role(new.practice_id, new.clinician_id, 'clinician')
+       and not private.user_has_role(new.practice_id, new.clinician_id, 'clinical_supervisor') then
+      raise exception 'clinician must be an active clinician in this practice'
+        using errcode = '23503';
+    end if;
+  end if;
+
+  if tg_op = 'INSERT'
+     or new.client_id is distinct from old.client_id
+     or new.practice_id is distinct from old.practice_id then
+    if not exists (
+      select 1 from public.clients c
+      where c.id = new.client_id and c.practice_id = new.practice_id
+    ) then
+      raise exception 'client not found in this practice' using errcode = '23503';
+    end if;
+    if exists (
+      select 1 from public.clients c
+      where c.id = new.client_id and c.status = 'discharged'
+    ) then
+      raise exception 'client is discharged' using errcode = '22023';
+    end if;
+  end if;
+
+  return new;
+end
+$$;
+
+-- 2) Series: the function is shared, since both tables name their parties alike.
+drop trigger if exists trg_appointment_series_check_parties on public.appointment_series;
+create trigger trg_appointment_series_check_parties
+before insert or update on public.appointment_series
+for each row execute function private.fn_appointments_check_parties();
+
+-- 3) A series can't be repointed at a client outside the caller's caseload.
+drop policy if exists "appointment_series_update_bookers" on public.appointment_series;
+create policy "appointment_series_update_bookers"
+  on public.appointment_series
+  for update
+  to authenticated
+  using (private.can_access_appointment(practice_id, client_id, clinician_id))
+  with check (private.can_book_for(practice_id, clinician_id) and private.can_access_client_id(client_id));
@@ -0,0 +1,12 @@
+-- Users with a verified MFA factor must step every session up to AAL2 before the API
+-- accepts it. The API records that on each session's activity record; sessions already
+-- tracked take it from the factors their user has now.
+alter table public.session_activity
+  add column if not exists mfa_required boolean not null default false;
+
+update public.session_activity a
+set mfa_required = true
+where exists (
+  select 1 from auth.mfa_factors f
+  where f.user_id = a.user_id and f.status = 'verified'
+);
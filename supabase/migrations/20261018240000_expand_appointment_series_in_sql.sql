@@ -0,0 +1,195 @@
+-- create_appointment_series expands the rule itself instead of storing whatever dates the
+-- caller passed, so a series' occurrences always match its rrule and exdates.
+
+-- 1) The same subset of RFC 5545 as the API's RecurrenceRule, in the canonical form it
+-- writes (FREQ=…;INTERVAL=…[;BYDAY=…];COUNT=… or UNTIL=YYYYMMDD), with the same limits:
+-- at most 200 occurrences within 732 days of the first date. COUNT counts occurrences
+-- before exdates are removed, and BYDAY days before p_start in the first week are skipped.
+create or replace function private.expand_rrule(p_rrule text, p_start date, p_exdates date[])
+returns date[]
+language plpgsql
+immutable
+set search_path = ''
+as $$
+declare
+  v_parts     text[] := regexp_match(
+    p_rrule,
+    '^FREQ=(DAILY|WEEKLY|MONTHLY);INTERVAL=([0-9]{1,2})'
+    '(?:;BYDAY=((?:MO|TU|WE|TH|FR|SA|SU)(?:,(?:MO|TU|WE|TH|FR|SA|SU))*))?'
+    ';(?:COUNT=([0-9]{1,3})|UNTIL=([0-9]{8}))$'
+  );
+  v_freq      text;
+  v_interval  integer;
+  -- ISO day numbers, Monday = 1.
+  v_byday     integer[];
+  v_count     integer;
+  v_until     date;
+  v_horizon   date := p_start + 732;
+  v_day       integer := extract(day from p_start)::integer;
+  v_period    integer := 0;
+  v_step      integer;
+  v_months    integer;
+  v_first     date;
+  v_candidates date[];
+  v_date      date;
+  v_dates     date[] := '{}';
+begin
+  if v_parts is null then
+    raise exception 'unsupported rrule: %', p_rrule using errcode = '22023';
+  end if;
+
+  v_freq := v_parts[1];
+  v_interval := v_parts[2]::integer;
+  v_byday := array(
+    select distinct array_position(array['MO', 'TU', 'WE', 'TH', 'FR', 'SA', 'SU'], d)
+    from unnest(string_to_array(v_parts[3], ',')) as d
+    order by 1
+  );
+  v_count := v_parts[4]::integer;
+  if v_parts[5] is not null then
+    v_until := make_date(
+      substr(v_parts[5], 1, 4)::integer,
+      substr(v_parts[5], 5, 2)::integer,
+      substr(v_parts[5], 7, 2)::integer
+    );
+  end if;
+
+  if v_interval not between 1 and 99 then
+    raise exception 'INTERVAL must be between 1 and 99' using errcode = '22023';
+  end if;
+  if v_count not between 1 and 200 then
+    raise exception 'COUNT must be between 1 and 200' using errcode = '22023';
+  end if;
+  if cardinality(v_byday) > 0 and v_freq <> 'WEEKLY' then
+    raise exception 'BYDAY is only supported with FREQ=WEEKLY' using errcode = '22023';
+  end if;
+  if v_until < p_start then
+    raise exception 'UNTIL is before the first date' using errcode = '22023';
+  end if;
+  if v_until > v_horizon then
+    raise exception 'a series may run at most 732 days' using errcode = '22023';
+  end if;
+
+  <<periods>>
+  loop
+    v_step := v_period * v_interval;
+    v_period := v_period + 1;
+
+    if v_freq = 'DAILY' then
+      v_first := p_start + v_step;
+      v_candidates := array[v_first];
+    elsif v_freq = 'WEEKLY' then
+      v_first := p_start - (extract(isodow from p_start)::integer - 1) + 7 * v_step;
+      if cardinality(v_byday) = 0 then
+        v_candidates := array[p_start + 7 * v_step];
+      else
+        v_candidates := array(select v_first + d - 1 from unnest(v_byday) as d order by d);
+      end if;
+    else
+      v_months := extract(month from p_start)::integer - 1 + v_step;
+      v_first := make_date(extract(year from p_start)::integer + v_months / 12, v_months % 12 + 1, 1);
+      -- A month that lacks the first date's day of month has no occurrence.
+      if extract(day from (v_first + interval '1 month')::date - 1) >= v_day then
+        v_candidates := array[v_first + v_day - 1];
+      else
+        v_candidates := '{}';
+      end if;
+    end if;
+
+    if v_first > v_horizon then
+      -- UNTIL is within the horizon, so only COUNT can still be short here.
+      if v_count is not null then
+        raise exception 'a series may run at most 732 days' using errcode = '22023';
+      end if;
+      exit periods;
+    end if;
+
+    foreach v_date in array v_candidates loop
+      continue when v_date < p_start;
+      exit periods when v_date > v_until;
+      if v_date > v_horizon then
+        raise exception 'a series may run at most 732 days' using errcode = '22023';
+      end if;
+      if cardinality(v_dates) = 200 then
+        raise exception 'a series may have at most 200 occurrences' using errcode = '22023';
+      end if;
+      v_dates := v_dates || v_date;
+      exit periods when cardinality(v_dates) = v_count;
+    end loop;
+  end loop;
+
+  v_dates := array(
+    select d from unnest(v_dates) as d
+    where d <> all(coalesce(p_exdates, '{}'))
+    order by d
+  );
+  if cardinality(v_dates) = 0 then
+    raise exception 'the rule yields no dates' using errcode = '22023';
+  end if;
+  return v_dates;
+end
+$$;
+
+comment on function private.expand_rrule is 'Expands a canonical series rrule into its occurrence dates, minus exdates';
+
+-- 2) Create a series and its occurrences in one transaction, so a clash on any date books
+-- nothing. Each occurrence starts at p_start_time in p_timezone on a date the rule yields.
+-- Runs as the caller, so RLS still decides.
+drop function if exists public.create_appointment_series(uuid, uuid, uuid, text, text, date, time, integer, date[], text, text, text, date[]);
+
+create or replace function public.create_appointment_series(
+  p_practice_id       uuid,
+  p_client_id         uuid,
+  p_clinician_id      uuid,
+  p_rrule             text,
+  p_timezone          text,
+  p_start_date        date,
+  p_start_time        time,
+  p_duration_minutes  integer,
+  p_exdates           date[],
+  p_location_kind     text,
+  p_location          text,
+  p_telehealth_url    text
+)
+returns public.appointment_series
+language plpgsql
+security invoker
+set search_path = ''
+as $$
+declare
+  v_series public.appointment_series;
+  v_dates  date[];
+begin
+  if not exists (select 1 from pg_catalog.pg_timezone_names where name = p_timezone) then
+    raise exception 'unknown time zone: %', p_timezone using errcode = '22023';
+  end if;
+
+  v_dates := private.expand_rrule(p_rrule, p_start_date, p_exdates);
+
+  insert into public.appointment_series (
+    practice_id, client_id, clinician_id, rrule, timezone, start_date, start_time,
+    duration_minutes, exdates, location_kind, location, telehealth_url
+  )
+  values (
+    p_practice_id, p_client_id, p_clinician_id, p_rrule, p_timezone, p_start_date, p_start_time,
+    p_duration_minutes, coalesce(p_exdates, '{}'), p_location_kind, p_location, p_telehealth_url
+  )
+  returning * into v_series;
+
+  insert into public.appointments (
+    practice_id, client_id, clinician_id, series_id, starts_at, ends_at,
+    location_kind, location, telehealth_url
+  )
+  select
+    p_practice_id, p_client_id, p_clinician_id, v_series.id,
+    (d + p_start_time) at time zone p_timezone,
+    ((d + p_start_time) at time zone p_timezone) + make_interval(mins => p_duration_minutes),
+    p_location_kind, p_location, p_telehealth_url
+  from unnest(v_dates) as d;
+
+  return v_series;
+end
+$$;
+
+revoke execute on function public.create_appointment_series(uuid, uuid, uuid, text, text, date, time, integer, date[], text, text, text) from public, anon;
+grant execute on function public.create_appointment_series(uuid, uuid, uuid, text, text, date, time, integer, date[], text, text, text) to authenticated;
@@ -195,3 +195,40 @@ cross join u
 cross join unnest(array[u.u1, u.u2, u.u3, u.u4, u.u5, u.u6]) as uid
 where uid is not null
 on conflict (team_id, user_id) do nothing;
+
+-- Clients shown on the dashboard
+with p as (select id as practice_id from public.practices where name = 'Test Therapy Practice')
+insert into public.clients (
+  practice_id, mrn, first_name, last_name, date_of_birth, phone,
+  address_line1, city, state, postal_code,
+  emergency_contact_name, emergency_contact_relationship, emergency_contact_phone
+)
+select p.practice_id, c.mrn, c.first_name, c.last_name, c.date_of_birth, c.phone,
+       c.address_line1, c.city, c.state, c.postal_code,
+       c.ec_name, c.ec_relationship, c.ec_phone
+from p
+cross join (values
+  ('SEED0001', 'Michael', 'Chen', date '1988-04-12', '+15555550101',
+   '12 Harbor Lane', 'Portland', 'OR', '97201', 'Lily Chen', 'Spouse', '+15555550102'),
+  ('SEED0002', 'Emma', 'Rodriguez', date '1995-09-30', '+15555550103',
+   '480 Pine Street', 'Portland', 'OR', '97205', 'Ana Rodriguez', 'Mother', '+15555550104')
+) as c(mrn, first_name, last_name, date_of_birth, phone, address_line1, city, state,
+       postal_code, ec_name, ec_relationship, ec_phone)
+on conflict (practice_id, mrn) do nothing;
+
+-- Caseloads: Michael Chen with the Supervision Alpha team, Emma Rodriguez with Beta
+with p as (select id as practice_id from public.practices where name = 'Test Therapy Practice'),
+a as (
+  select *
+  from (values
+    ('SEED0001', 'clinician1@example.com', 'supervisor1@example.com', 'Supervision Alpha'),
+    ('SEED0002', 'clinician3@example.com', 'supervisor2@example.com', 'Supervision Beta')
+  ) as v(mrn, primary_email, supervising_email, team_name)
+)
+update public.clients c
+set primary_clinician_id = (select id from auth.users where email = a.primary_email),
+    supervising_clinician_id = (select id from auth.users where email = a.supervising_email),
+    team_id = (select t.id from public.teams t where t.practice_id = p.practice_id and t.name = a.team_name)
+from p, a
+where c.practice_id = p.practice_id
+  and c.mrn = a.mrn;
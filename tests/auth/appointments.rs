@@ -0,0 +1,563 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::{Value, json};
+use time::{Date, Duration, Month, OffsetDateTime, format_description::well_known::Rfc3339};
+
+use crate::helpers::{TestApp, body};
+
+const PASSWORD: &str = "Password123!";
+
+async fn client_id_with_mrn(app: &TestApp, token: &str, practice_id: &str, mrn: &str) -> String {
+    body(app.get_clients(token, practice_id, &[("mrn", mrn)]).await).await["clients"][0]["id"]
+        .as_str()
+        .expect("Seeded client not found")
+        .to_string()
+}
+
+/// A weekday morning far enough out, and random enough, that earlier runs against the
+/// same database do not collide with it.
+fn free_morning() -> OffsetDateTime {
+    let monday = Date::from_calendar_date(2031, Month::January, 6).unwrap()
+        + Duration::weeks(rand::random_range(0..5000));
+    monday
+        .with_hms(9, 0, 0)
+        .unwrap()
+        .assume_offset(time::UtcOffset::from_hms(-6, 0, 0).unwrap())
+}
+
+fn rfc3339(at: OffsetDateTime) -> String {
+    at.format(&Rfc3339).unwrap()
+}
+
+fn parse(at: &Value) -> OffsetDateTime {
+    OffsetDateTime::parse(at.as_str().unwrap(), &Rfc3339).unwrap()
+}
+
+fn in_person(client_id: &str, clinician_id: &str, starts_at: OffsetDateTime) -> Value {
+    json!({
+        "client_id": client_id,
+        "clinician_id": clinician_id,
+        "starts_at": rfc3339(starts_at),
+        "duration_minutes": 50,
+        "location_kind": "in_person",
+        "location": "Room 2",
+    })
+}
+
+#[tokio::test]
+async fn scheduler_books_for_any_clinician_and_double_booking_is_rejected() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let scheduler = app.signin_token("scheduler1@example.com", PASSWORD).await;
+    let clinician1 = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&scheduler).await;
+    let clinician1_id = app.own_user_id(&clinician1, &practice_id).await;
+    let michael_id = client_id_with_mrn(&app, &scheduler, &practice_id, "SEED0001").await;
+    let emma_id = client_id_with_mrn(&app, &scheduler, &practice_id, "SEED0002").await;
+    let starts_at = free_morning();
+
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &in_person(&michael_id, &clinician1_id, starts_at),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let appointment = body(response).await["appointment"].clone();
+    assert_eq!(appointment["status"], "scheduled");
+    assert_eq!(appointment["client"]["mrn"], "SEED0001");
+    assert_eq!(parse(&appointment["starts_at"]), starts_at);
+    assert_eq!(
+        parse(&appointment["ends_at"]),
+        starts_at + Duration::minutes(50)
+    );
+
+    // Overlapping the last 20 minutes, even with another client, is a double booking.
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &in_person(&emma_id, &clinician1_id, starts_at + Duration::minutes(30)),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 409);
+    assert_eq!(body(response).await["code"], "clinician_double_booked");
+
+    // Back to back is fine.
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &in_person(&emma_id, &clinician1_id, starts_at + Duration::minutes(50)),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    // The clinician's day shows both, including Emma, who is outside their caseload.
+    let day = [
+        ("from", rfc3339(starts_at - Duration::hours(3))),
+        ("to", rfc3339(starts_at + Duration::hours(12))),
+    ];
+    let query: Vec<(&str, &str)> = day.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    let response = app
+        .get_appointments(&clinician1, &practice_id, &query)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let appointments = body(response).await["appointments"].clone();
+    let appointments = appointments.as_array().unwrap();
+    assert_eq!(appointments.len(), 2);
+    assert_eq!(appointments[0]["client"]["mrn"], "SEED0001");
+    assert!(appointments[1]["client"].is_null());
+
+    // Cancelling frees the slot.
+    let response = app
+        .patch_appointment(
+            &scheduler,
+            &practice_id,
+            appointment["id"].as_str().unwrap(),
+            &json!({ "status": "cancelled", "cancellation_reason": "Client unwell" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let cancelled = body(response).await["appointment"].clone();
+    assert_eq!(cancelled["status"], "cancelled");
+    assert_eq!(cancelled["cancellation_reason"], "Client unwell");
+    assert!(cancelled["status_changed_at"].is_string());
+
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &in_person(&michael_id, &clinician1_id, starts_at),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+}
+
+#[tokio::test]
+async fn clinicians_book_only_themselves_and_their_caseload() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let scheduler = app.signin_token("scheduler1@example.com", PASSWORD).await;
+    let clinician1 = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let clinician3 = app.signin_token("clinician3@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&clinician1).await;
+    let clinician3_id = app.own_user_id(&clinician3, &practice_id).await;
+    let michael_id = client_id_with_mrn(&app, &scheduler, &practice_id, "SEED0001").await;
+    let emma_id = client_id_with_mrn(&app, &scheduler, &practice_id, "SEED0002").await;
+    let starts_at = free_morning();
+
+    // Without clinician_id the caller books for themselves.
+    let response = app
+        .post_appointment(
+            &clinician1,
+            &practice_id,
+            &json!({
+                "client_id": michael_id,
+                "starts_at": rfc3339(starts_at),
+                "duration_minutes": 50,
+                "location_kind": "telehealth",
+                "telehealth_url": "https://video.example.com/room/42",
+            }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let appointment = body(response).await["appointment"].clone();
+    assert_eq!(appointment["location_kind"], "telehealth");
+    assert!(appointment["location"].is_null());
+
+    let response = app
+        .post_appointment(
+            &clinician1,
+            &practice_id,
+            &in_person(&michael_id, &clinician3_id, starts_at + Duration::hours(2)),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(body(response).await["code"], "appointment_forbidden");
+
+    let response = app
+        .post_appointment(
+            &clinician1,
+            &practice_id,
+            &json!({
+                "client_id": emma_id,
+                "starts_at": rfc3339(starts_at + Duration::hours(2)),
+                "duration_minutes": 50,
+                "location_kind": "in_person",
+            }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    // Someone else's appointment with a client outside the caseload stays hidden.
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &in_person(&emma_id, &clinician3_id, starts_at),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let emmas_id = body(response).await["appointment"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = app
+        .patch_appointment(
+            &clinician1,
+            &practice_id,
+            &emmas_id,
+            &json!({ "status": "checked_in" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    // Only clinicians and supervisors can be booked.
+    let scheduler_id = app.own_user_id(&scheduler, &practice_id).await;
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &in_person(&michael_id, &scheduler_id, starts_at),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(
+        body(response).await["code"],
+        "invalid_appointment_participant"
+    );
+}
+
+#[tokio::test]
+async fn status_moves_forward_and_closed_appointments_stay_put() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let scheduler = app.signin_token("scheduler1@example.com", PASSWORD).await;
+    let clinician1 = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&clinician1).await;
+    let clinician1_id = app.own_user_id(&clinician1, &practice_id).await;
+    let michael_id = client_id_with_mrn(&app, &scheduler, &practice_id, "SEED0001").await;
+    let starts_at = free_morning();
+
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &in_person(&michael_id, &clinician1_id, starts_at),
+        )
+        .await;
+    let id = body(response).await["appointment"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Rescheduling a scheduled appointment moves both ends.
+    let moved = starts_at + Duration::hours(1);
+    let response = app
+        .patch_appointment(
+            &scheduler,
+            &practice_id,
+            &id,
+            &json!({ "starts_at": rfc3339(moved), "duration_minutes": 30 }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let appointment = body(response).await["appointment"].clone();
+    assert_eq!(parse(&appointment["starts_at"]), moved);
+    assert_eq!(
+        parse(&appointment["ends_at"]),
+        moved + Duration::minutes(30)
+    );
+    assert_eq!(appointment["is_exception"], false);
+
+    for status in ["checked_in", "completed"] {
+        let response = app
+            .patch_appointment(&clinician1, &practice_id, &id, &json!({ "status": status }))
+            .await;
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(body(response).await["appointment"]["status"], status);
+    }
+
+    let response = app
+        .patch_appointment(
+            &clinician1,
+            &practice_id,
+            &id,
+            &json!({ "status": "no_show" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 409);
+    assert_eq!(body(response).await["code"], "invalid_status_transition");
+
+    let response = app
+        .patch_appointment(
+            &scheduler,
+            &practice_id,
+            &id,
+            &json!({ "location_kind": "in_person", "location": "Room 5" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 409);
+    assert_eq!(body(response).await["code"], "appointment_not_scheduled");
+
+    let response = app
+        .patch_appointment(
+            &scheduler,
+            &practice_id,
+            &id,
+            &json!({ "status": "scheduled" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 409);
+}
+
+#[tokio::test]
+async fn series_keeps_local_time_across_dst_and_cancels_the_rest() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let scheduler = app.signin_token("scheduler1@example.com", PASSWORD).await;
+    let clinician1 = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&clinician1).await;
+    let clinician1_id = app.own_user_id(&clinician1, &practice_id).await;
+    let michael_id = client_id_with_mrn(&app, &scheduler, &practice_id, "SEED0001").await;
+
+    // Six Thursdays from mid-February always cross the second Sunday of March.
+    let year = rand::random_range(2031..2400);
+    let minute = 5 * rand::random_range(0..12);
+    let response = app
+        .post_appointment_series(
+            &scheduler,
+            &practice_id,
+            &json!({
+                "client_id": michael_id,
+                "clinician_id": clinician1_id,
+                "rrule": "FREQ=WEEKLY;BYDAY=TH;COUNT=6",
+                "timezone": "America/Chicago",
+                "start_date": format!("{year}-02-15"),
+                "start_time": format!("10:{minute:02}"),
+                "duration_minutes": 45,
+                "location_kind": "in_person",
+                "location": "Room 1",
+            }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let series = body(response).await["series"].clone();
+    assert_eq!(series["rrule"], "FREQ=WEEKLY;INTERVAL=1;BYDAY=TH;COUNT=6");
+    let occurrences = series["appointments"].as_array().unwrap().clone();
+    assert_eq!(occurrences.len(), 6);
+
+    let first = parse(&occurrences[0]["starts_at"]).to_offset(time::UtcOffset::UTC);
+    let last = parse(&occurrences[5]["starts_at"]).to_offset(time::UtcOffset::UTC);
+    assert_eq!(first.weekday(), time::Weekday::Thursday);
+    assert_eq!((first.hour(), first.minute()), (16, minute));
+    assert_eq!((last.hour(), last.minute()), (15, minute));
+
+    // Moving one occurrence makes it an exception.
+    let moved_id = occurrences[1]["id"].as_str().unwrap();
+    let moved = parse(&occurrences[1]["starts_at"]) + Duration::hours(2);
+    let response = app
+        .patch_appointment(
+            &scheduler,
+            &practice_id,
+            moved_id,
+            &json!({ "starts_at": rfc3339(moved), "duration_minutes": 45 }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(body(response).await["appointment"]["is_exception"], true);
+
+    // Check the third in, then cancel from it on: it is kept, the rest are cancelled.
+    let third_id = occurrences[2]["id"].as_str().unwrap();
+    let response = app
+        .patch_appointment(
+            &clinician1,
+            &practice_id,
+            third_id,
+            &json!({ "status": "checked_in" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let series_id = series["id"].as_str().unwrap();
+    let response = app
+        .post_cancel_appointment_series(
+            &scheduler,
+            &practice_id,
+            series_id,
+            &json!({ "from": occurrences[2]["starts_at"], "reason": "Treatment ended" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let series = body(response).await["series"].clone();
+    assert!(series["cancelled_from"].is_string());
+    let statuses: Vec<&str> = series["appointments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            "scheduled",
+            "scheduled",
+            "checked_in",
+            "cancelled",
+            "cancelled",
+            "cancelled"
+        ]
+    );
+
+    let response = app
+        .get_appointment_series(&clinician1, &practice_id, series_id)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn series_that_clashes_on_any_date_books_nothing() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let scheduler = app.signin_token("scheduler1@example.com", PASSWORD).await;
+    let clinician1 = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&clinician1).await;
+    let clinician1_id = app.own_user_id(&clinician1, &practice_id).await;
+    let michael_id = client_id_with_mrn(&app, &scheduler, &practice_id, "SEED0001").await;
+    let monday = free_morning();
+
+    // Block the third Monday.
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &in_person(&michael_id, &clinician1_id, monday + Duration::weeks(2)),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let series = json!({
+        "client_id": michael_id,
+        "clinician_id": clinician1_id,
+        "rrule": "FREQ=WEEKLY;COUNT=4",
+        "timezone": "Etc/GMT+6",
+        "start_date": monday.date().to_string(),
+        "start_time": "09:00",
+        "duration_minutes": 50,
+        "location_kind": "in_person",
+    });
+    let response = app
+        .post_appointment_series(&scheduler, &practice_id, &series)
+        .await;
+    assert_eq!(response.status().as_u16(), 409);
+    assert_eq!(body(response).await["code"], "clinician_double_booked");
+
+    let range = [
+        ("from", rfc3339(monday - Duration::days(1))),
+        ("to", rfc3339(monday + Duration::weeks(4))),
+        ("clinician_id", clinician1_id.clone()),
+    ];
+    let query: Vec<(&str, &str)> = range.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    let response = app.get_appointments(&scheduler, &practice_id, &query).await;
+    assert_eq!(
+        body(response).await["appointments"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Skipping the blocked date books the other three.
+    let mut series = series;
+    series["exdates"] = json!([(monday + Duration::weeks(2)).date().to_string()]);
+    let response = app
+        .post_appointment_series(&scheduler, &practice_id, &series)
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    assert_eq!(
+        body(response).await["series"]["appointments"]
+            .as_array()
+            .unwrap()
+            .len(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn malformed_bookings_and_queries_are_rejected() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let scheduler = app.signin_token("scheduler1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&scheduler).await;
+    let michael_id = client_id_with_mrn(&app, &scheduler, &practice_id, "SEED0001").await;
+
+    let series = |rrule: &str, timezone: &str| {
+        json!({
+            "client_id": michael_id,
+            "rrule": rrule,
+            "timezone": timezone,
+            "start_date": "2031-03-03",
+            "start_time": "09:00",
+            "duration_minutes": 50,
+            "location_kind": "in_person",
+        })
+    };
+    for rrule in [
+        "FREQ=WEEKLY",
+        "FREQ=HOURLY;COUNT=3",
+        "FREQ=DAILY;BYDAY=MO;COUNT=3",
+        "FREQ=DAILY;COUNT=500",
+        "FREQ=WEEKLY;COUNT=3;UNTIL=20310401",
+        "FREQ=MONTHLY;UNTIL=20400101",
+    ] {
+        let response = app
+            .post_appointment_series(&scheduler, &practice_id, &series(rrule, "America/Chicago"))
+            .await;
+        assert_eq!(response.status().as_u16(), 400, "{rrule}");
+        assert_eq!(body(response).await["code"], "invalid_recurrence_rule");
+    }
+
+    let response = app
+        .post_appointment_series(
+            &scheduler,
+            &practice_id,
+            &series("FREQ=WEEKLY;COUNT=3", "Mars/Olympus_Mons"),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = app
+        .post_appointment(
+            &scheduler,
+            &practice_id,
+            &json!({
+                "client_id": michael_id,
+                "starts_at": "2031-03-03T09:00:00-06:00",
+                "duration_minutes": 50,
+                "location_kind": "telehealth",
+                "telehealth_url": "http://video.example.com/room",
+            }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(body(response).await["code"], "invalid_location");
+
+    for query in [
+        vec![("from", "2031-03-01T00:00:00Z")],
+        vec![("from", "2031-03-01"), ("to", "2031-03-02")],
+        vec![
+            ("from", "2031-03-01T00:00:00Z"),
+            ("to", "2031-09-01T00:00:00Z"),
+        ],
+    ] {
+        let response = app.get_appointments(&scheduler, &practice_id, &query).await;
+        assert_eq!(response.status().as_u16(), 400, "{query:?}");
+    }
+}
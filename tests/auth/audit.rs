@@ -0,0 +1,144 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, body};
+
+#[tokio::test]
+async fn team_changes_are_paginated_with_diffs() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let name = format!("Audited Team {}", Uuid::new_v4());
+    let new_name = format!("Audited Rename {}", Uuid::new_v4());
+
+    let created = body(app.post_team(&token, &practice_id, &name).await).await;
+    let team_id = created
+        .pointer("/team/id")
+        .and_then(Value::as_str)
+        .unwrap()
+        .to_string();
+    app.patch_team(&token, &practice_id, &team_id, &new_name)
+        .await;
+
+    let first_response = app
+        .get_audit_log(
+            &token,
+            &practice_id,
+            &[
+                ("table_name", "teams"),
+                ("row_id", &team_id),
+                ("limit", "1"),
+            ],
+        )
+        .await;
+    assert_eq!(first_response.status().as_u16(), 200);
+    let first_page = body(first_response).await;
+    assert_eq!(
+        first_page.pointer("/entries/0/operation").unwrap(),
+        "UPDATE"
+    );
+    assert_eq!(
+        first_page
+            .pointer("/entries/0/changes/name/before")
+            .unwrap(),
+        &name
+    );
+    assert_eq!(
+        first_page.pointer("/entries/0/changes/name/after").unwrap(),
+        &new_name
+    );
+    let cursor = first_page
+        .get("next_cursor")
+        .and_then(Value::as_i64)
+        .expect("next_cursor missing on a partial page")
+        .to_string();
+
+    let second_page = body(
+        app.get_audit_log(
+            &token,
+            &practice_id,
+            &[
+                ("table_name", "teams"),
+                ("row_id", &team_id),
+                ("limit", "1"),
+                ("cursor", &cursor),
+            ],
+        )
+        .await,
+    )
+    .await;
+    assert_eq!(
+        second_page.pointer("/entries/0/operation").unwrap(),
+        "INSERT"
+    );
+    assert!(
+        second_page
+            .pointer("/entries/0/before_data")
+            .unwrap()
+            .is_null()
+    );
+    assert!(second_page.get("next_cursor").unwrap().is_null());
+
+    let updates = body(
+        app.get_audit_log(
+            &token,
+            &practice_id,
+            &[("row_id", &team_id), ("operation", "update")],
+        )
+        .await,
+    )
+    .await;
+    assert_eq!(
+        updates
+            .get("entries")
+            .and_then(Value::as_array)
+            .unwrap()
+            .len(),
+        1
+    );
+
+    app.delete_team(&token, &practice_id, &team_id).await;
+}
+
+#[tokio::test]
+async fn clinician_sees_no_audit_entries() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let response = app.get_audit_log(&token, &practice_id, &[]).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body = body(response).await;
+    assert!(
+        body.get("entries")
+            .and_then(Value::as_array)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn invalid_audit_filters_return_400() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    for query in [
+        [("operation", "TRUNCATE")],
+        [("table_name", "auth.users")],
+        [("limit", "0")],
+        [("from", "not-a-date")],
+    ] {
+        let response = app.get_audit_log(&token, &practice_id, &query).await;
+        assert_eq!(response.status().as_u16(), 400, "query {query:?}");
+    }
+}
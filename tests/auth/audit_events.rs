@@ -0,0 +1,83 @@
+use breeze_ehr::{
+    domain::types::audit::{AuditOutcome, AuthAuditAction},
+    utils::tracing::init_tracing,
+};
+use serde_json::Value;
+
+use crate::helpers::TestApp;
+
+#[tokio::test]
+async fn failed_signin_is_recorded_with_client_ip() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app
+        .post_signin_from(
+            "clinician1@example.com",
+            "WrongPassword123!",
+            "203.0.113.7, 10.0.0.1",
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let events = app.audit_events().await;
+    assert_eq!(events.len(), 1);
+    let event = &events[0];
+    assert_eq!(event.action, AuthAuditAction::Signin);
+    assert_eq!(event.outcome, AuditOutcome::Failure);
+    assert_eq!(event.actor_user_id, None);
+    assert_eq!(event.target.as_deref(), Some("clinician1@example.com"));
+    assert_eq!(event.request_id, "audit-test-request");
+    assert_eq!(event.client_ip.as_deref(), Some("203.0.113.7"));
+    assert!(event.reason.is_some());
+}
+
+#[tokio::test]
+async fn signin_lookup_and_signout_are_recorded_with_actor() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let signin_response = app
+        .post_signin_from("clinician1@example.com", "Password123!", "198.51.100.2")
+        .await;
+    assert_eq!(signin_response.status().as_u16(), 200);
+    let body: Value = signin_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let token = body.get("token").and_then(Value::as_str).unwrap();
+
+    let lookup_response = app
+        .post_retrieve_user_id(token, "clinician1@example.com")
+        .await;
+    let user_id = lookup_response
+        .json::<Value>()
+        .await
+        .expect("Failed to parse response body")
+        .get("user_id")
+        .and_then(Value::as_str)
+        .unwrap()
+        .to_string();
+
+    app.post_signout(token).await;
+
+    let events = app.audit_events().await;
+    let actions: Vec<_> = events.iter().map(|e| e.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            AuthAuditAction::Signin,
+            AuthAuditAction::RetrieveUserId,
+            AuthAuditAction::Signout,
+        ]
+    );
+    assert!(events.iter().all(|e| e.outcome == AuditOutcome::Success));
+    assert!(
+        events
+            .iter()
+            .all(|e| e.actor_user_id.as_deref() == Some(user_id.as_str()))
+    );
+    assert_eq!(events[0].client_ip.as_deref(), Some("198.51.100.2"));
+    // Requests without a forwarded header fall back to the socket peer.
+    assert_eq!(events[1].client_ip.as_deref(), Some("127.0.0.1"));
+}
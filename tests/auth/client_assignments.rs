@@ -0,0 +1,170 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::json;
+
+use crate::helpers::{TestApp, body};
+
+const PASSWORD: &str = "Password123!";
+
+async fn visible_mrns(app: &TestApp, token: &str, practice_id: &str) -> Vec<String> {
+    let response = app.get_clients(token, practice_id, &[]).await;
+    assert_eq!(response.status().as_u16(), 200);
+    body(response).await["clients"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["mrn"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn team_id_named(app: &TestApp, token: &str, practice_id: &str, name: &str) -> String {
+    body(app.get_teams(token, practice_id).await).await["teams"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["name"] == name)
+        .and_then(|t| t["id"].as_str())
+        .expect("Seeded team not found")
+        .to_string()
+}
+
+#[tokio::test]
+async fn clinicians_see_only_their_caseload_while_wider_roles_see_all() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let clinician1 = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let clinician3 = app.signin_token("clinician3@example.com", PASSWORD).await;
+    let supervisor = app.signin_token("supervisor2@example.com", PASSWORD).await;
+    let scheduler = app.signin_token("scheduler1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&clinician1).await;
+
+    let mrns = visible_mrns(&app, &clinician1, &practice_id).await;
+    assert!(mrns.contains(&"SEED0001".to_string()));
+    assert!(!mrns.contains(&"SEED0002".to_string()));
+
+    let mrns = visible_mrns(&app, &clinician3, &practice_id).await;
+    assert!(mrns.contains(&"SEED0002".to_string()));
+    assert!(!mrns.contains(&"SEED0001".to_string()));
+
+    for token in [&supervisor, &scheduler] {
+        let mrns = visible_mrns(&app, token, &practice_id).await;
+        assert!(mrns.contains(&"SEED0001".to_string()));
+        assert!(mrns.contains(&"SEED0002".to_string()));
+    }
+
+    // Outside the caseload a client looks like it does not exist.
+    let response = app
+        .get_clients(&scheduler, &practice_id, &[("mrn", "SEED0002")])
+        .await;
+    let emma_id = body(response).await["clients"][0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = app.get_client(&clinician1, &practice_id, &emma_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+    let response = app
+        .patch_client(
+            &clinician1,
+            &practice_id,
+            &emma_id,
+            &json!({ "preferred_name": "Em" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn assignments_move_clients_between_caseloads() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner = app.signin_token("owner1@example.com", PASSWORD).await;
+    let clinician2 = app.signin_token("clinician2@example.com", PASSWORD).await;
+    let clinician3 = app.signin_token("clinician3@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&owner).await;
+    let clinician2_id = app.own_user_id(&clinician2, &practice_id).await;
+    let beta_id = team_id_named(&app, &owner, &practice_id, "Supervision Beta").await;
+
+    let response = app
+        .post_client(
+            &owner,
+            &practice_id,
+            &json!({ "first_name": "Riley", "last_name": "Assign", "date_of_birth": "1992-03-03" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let client = body(response).await["client"].clone();
+    assert!(client["primary_clinician_id"].is_null());
+    let client_id = client["id"].as_str().unwrap().to_string();
+
+    let response = app.get_client(&clinician2, &practice_id, &client_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    let response = app
+        .put_client_assignment(
+            &owner,
+            &practice_id,
+            &client_id,
+            &json!({ "primary_clinician_id": clinician2_id }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let response = app.get_client(&clinician2, &practice_id, &client_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    // Clinicians work their caseload but cannot reassign it.
+    let response = app
+        .put_client_assignment(
+            &clinician2,
+            &practice_id,
+            &client_id,
+            &json!({ "primary_clinician_id": clinician2_id, "team_id": beta_id }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(body(response).await["code"], "client_forbidden");
+
+    let response = app
+        .put_client_assignment(
+            &owner,
+            &practice_id,
+            &client_id,
+            &json!({ "supervising_clinician_id": clinician2_id }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(body(response).await["code"], "invalid_client_assignment");
+
+    let response = app
+        .put_client_assignment(
+            &owner,
+            &practice_id,
+            &client_id,
+            &json!({ "team_id": beta_id }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let response = app.get_client(&clinician3, &practice_id, &client_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let response = app.get_client(&clinician2, &practice_id, &client_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    let response = app.delete_client(&owner, &practice_id, &client_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    // A clinician's own new client lands in their caseload.
+    let response = app
+        .post_client(
+            &clinician2,
+            &practice_id,
+            &json!({ "first_name": "Quinn", "last_name": "Self", "date_of_birth": "1985-12-12" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let client = body(response).await["client"].clone();
+    assert_eq!(client["primary_clinician_id"], clinician2_id.as_str());
+    let response = app
+        .delete_client(&owner, &practice_id, client["id"].as_str().unwrap())
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+}
@@ -0,0 +1,202 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, body};
+
+const PASSWORD: &str = "Password123!";
+
+/// An MRN no other test run will have used.
+fn unique_mrn() -> String {
+    format!("T-{}", &Uuid::new_v4().simple().to_string()[..12]).to_uppercase()
+}
+
+#[tokio::test]
+async fn members_see_seeded_clients_and_search_by_name_dob_and_mrn() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let response = app
+        .get_clients(&token, &practice_id, &[("name", "mich ch")])
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let clients = body(response).await["clients"].clone();
+    assert!(
+        clients
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|c| c["mrn"] == "SEED0001" && c["last_name"] == "Chen")
+    );
+
+    let response = app
+        .get_clients(
+            &token,
+            &practice_id,
+            &[("mrn", "seed0001"), ("status", "active")],
+        )
+        .await;
+    let clients = body(response).await["clients"].clone();
+    assert_eq!(clients.as_array().unwrap().len(), 1);
+    assert_eq!(clients[0]["first_name"], "Michael");
+
+    // Wildcards in the name are matched literally.
+    let response = app
+        .get_clients(&token, &practice_id, &[("name", "%")])
+        .await;
+    assert!(
+        body(response).await["clients"]
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+
+    let response = app
+        .get_clients(&token, &practice_id, &[("date_of_birth", "1990-02-30")])
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(body(response).await["code"], "invalid_date_of_birth");
+}
+
+#[tokio::test]
+async fn client_lifecycle_create_update_discharge_delete() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let clinician = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let owner = app.signin_token("owner1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&owner).await;
+    let mrn = unique_mrn();
+
+    let response = app
+        .post_client(
+            &clinician,
+            &practice_id,
+            &json!({
+                "mrn": mrn.to_lowercase(),
+                "first_name": " Jordan ",
+                "last_name": "Lee",
+                "date_of_birth": "1988-07-14",
+                "phone": "(555) 201-0199",
+                "address_line1": "12 Elm St",
+                "city": "Springfield",
+                "state": "il",
+                "postal_code": "62704",
+                "emergency_contact_name": "Sam Lee",
+                "emergency_contact_relationship": "Sibling",
+                "emergency_contact_phone": "555-201-0100",
+            }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let client = body(response).await["client"].clone();
+    assert_eq!(client["mrn"], mrn.as_str());
+    assert_eq!(client["first_name"], "Jordan");
+    assert_eq!(client["phone"], "+15552010199");
+    assert_eq!(client["state"], "IL");
+    assert_eq!(client["emergency_contact_phone"], "+15552010100");
+    assert_eq!(client["status"], "active");
+    let client_id = client["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .post_client(
+            &clinician,
+            &practice_id,
+            &json!({
+                "mrn": mrn,
+                "first_name": "Other",
+                "last_name": "Client",
+                "date_of_birth": "1990-01-01",
+            }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 409);
+    assert_eq!(body(response).await["code"], "mrn_taken");
+
+    // Clearing the address clears every part of it.
+    let response = app
+        .patch_client(
+            &clinician,
+            &practice_id,
+            &client_id,
+            &json!({ "address_line1": "", "phone": "", "status": "discharged" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let client = body(response).await["client"].clone();
+    assert!(client["city"].is_null() && client["postal_code"].is_null());
+    assert!(client["phone"].is_null());
+    assert_eq!(client["status"], "discharged");
+    assert!(client["discharged_at"].is_string());
+
+    let response = app
+        .patch_client(
+            &clinician,
+            &practice_id,
+            &client_id,
+            &json!({ "city": "Springfield" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(body(response).await["code"], "invalid_address");
+
+    let response = app
+        .delete_client(&clinician, &practice_id, &client_id)
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(body(response).await["code"], "client_forbidden");
+
+    let response = app.delete_client(&owner, &practice_id, &client_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.get_client(&owner, &practice_id, &client_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+    assert_eq!(body(response).await["code"], "client_not_found");
+}
+
+#[tokio::test]
+async fn create_client_rejects_invalid_demographics() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("clinician1@example.com", PASSWORD).await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let client = |overrides: Value| {
+        let mut body = json!({
+            "first_name": "Casey",
+            "last_name": "Invalid",
+            "date_of_birth": "1995-05-05",
+        });
+        body.as_object_mut()
+            .unwrap()
+            .extend(overrides.as_object().unwrap().clone());
+        body
+    };
+
+    for (overrides, code) in [
+        (
+            json!({ "date_of_birth": "2999-01-01" }),
+            "invalid_date_of_birth",
+        ),
+        (json!({ "first_name": "  " }), "invalid_client_name"),
+        (json!({ "phone": "call me" }), "invalid_phone_number"),
+        (json!({ "mrn": "no spaces" }), "invalid_mrn"),
+        (
+            json!({ "address_line1": "1 Main St", "city": "Town", "state": "ZZ", "postal_code": "12345" }),
+            "invalid_address",
+        ),
+        (
+            json!({ "emergency_contact_name": "Pat" }),
+            "invalid_emergency_contact",
+        ),
+    ] {
+        let response = app
+            .post_client(&token, &practice_id, &client(overrides))
+            .await;
+        assert_eq!(response.status().as_u16(), 400);
+        assert_eq!(body(response).await["code"], code);
+    }
+}
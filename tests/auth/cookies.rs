@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use breeze_ehr::utils::tracing::init_tracing;
+use reqwest::{Method, RequestBuilder};
+use serde_json::{Value, json};
+
+use crate::helpers::TestApp;
+
+const PASSWORD: &str = "Password123!";
+
+/// A browser's view of the session: the cookies it holds, keyed by name.
+#[derive(Default)]
+struct Browser {
+    cookies: HashMap<String, String>,
+}
+
+impl Browser {
+    /// Applies `Set-Cookie` headers, returning the raw headers for attribute checks.
+    fn store(&mut self, response: &reqwest::Response) -> Vec<String> {
+        let raw: Vec<String> = response
+            .headers()
+            .get_all("set-cookie")
+            .iter()
+            .map(|v| v.to_str().unwrap().to_string())
+            .collect();
+        for header in &raw {
+            let (name, value) = header
+                .split(';')
+                .next()
+                .and_then(|pair| pair.split_once('='))
+                .unwrap();
+            if header.contains("Max-Age=0") {
+                self.cookies.remove(name);
+            } else {
+                self.cookies.insert(name.to_string(), value.to_string());
+            }
+        }
+        raw
+    }
+
+    fn csrf(&self) -> &str {
+        &self.cookies["breeze_csrf_token"]
+    }
+
+    fn request(&self, app: &TestApp, method: Method, path: &str) -> RequestBuilder {
+        let cookie = self
+            .cookies
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+        app.http_client
+            .request(method, format!("http://{}{path}", &app.address))
+            .header("Cookie", cookie)
+    }
+}
+
+async fn cookie_signin(app: &TestApp, browser: &mut Browser, email: &str) -> Value {
+    let response = browser
+        .request(app, Method::POST, "/api/auth/signin")
+        .json(&json!({ "email": email, "password": PASSWORD, "use_cookie": true }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status().as_u16(), 200);
+
+    let set_cookies = browser.store(&response);
+    for name in ["breeze_access_token", "breeze_refresh_token"] {
+        let header = set_cookies
+            .iter()
+            .find(|h| h.starts_with(&format!("{name}=")))
+            .unwrap_or_else(|| panic!("{name} not set"));
+        assert!(header.contains("HttpOnly"));
+        assert!(header.contains("Secure"));
+        assert!(header.contains("SameSite=Strict"));
+    }
+    let csrf = set_cookies
+        .iter()
+        .find(|h| h.starts_with("breeze_csrf_token="))
+        .expect("CSRF cookie not set");
+    assert!(!csrf.contains("HttpOnly"));
+
+    response.json().await.expect("Failed to parse body")
+}
+
+#[tokio::test]
+async fn cookie_signin_keeps_tokens_out_of_the_body() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let mut browser = Browser::default();
+
+    let body = cookie_signin(&app, &mut browser, &email).await;
+    assert!(body.get("token").is_none());
+    assert!(body.get("refresh_token").is_none());
+    assert_eq!(body["csrf_token"].as_str(), Some(browser.csrf()));
+
+    // Reads need no CSRF token.
+    let response = browser
+        .request(&app, Method::GET, "/api/auth/mfa/factors")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    // Bearer signins are unchanged.
+    let response = app.post_signin(&email, PASSWORD).await;
+    assert!(response.headers().get("set-cookie").is_none());
+    let body: Value = response.json().await.unwrap();
+    assert!(body["token"].as_str().is_some());
+}
+
+#[tokio::test]
+async fn cookie_writes_require_matching_csrf_header() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let mut browser = Browser::default();
+    cookie_signin(&app, &mut browser, &email).await;
+
+    let enroll = |csrf: Option<&str>| {
+        let mut request = browser
+            .request(&app, Method::POST, "/api/auth/mfa/enroll")
+            .json(&json!({ "friendly_name": "Work phone" }));
+        if let Some(csrf) = csrf {
+            request = request.header("X-CSRF-Token", csrf);
+        }
+        request.send()
+    };
+
+    for csrf in [None, Some("forged")] {
+        let response = enroll(csrf).await.unwrap();
+        assert_eq!(response.status().as_u16(), 403);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["code"], "csrf_token_mismatch");
+    }
+
+    let response = enroll(Some(browser.csrf())).await.unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+}
+
+#[tokio::test]
+async fn cookie_refresh_rotates_and_signout_clears_cookies() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let mut browser = Browser::default();
+    cookie_signin(&app, &mut browser, &email).await;
+    let first_refresh = browser.cookies["breeze_refresh_token"].clone();
+
+    let response = browser
+        .request(&app, Method::POST, "/api/auth/refresh")
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = browser
+        .request(&app, Method::POST, "/api/auth/refresh")
+        .header("X-CSRF-Token", browser.csrf())
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    browser.store(&response);
+    assert_ne!(browser.cookies["breeze_refresh_token"], first_refresh);
+
+    let response = browser
+        .request(&app, Method::POST, "/api/auth/signout")
+        .header("X-CSRF-Token", browser.csrf())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    browser.store(&response);
+    assert!(browser.cookies.is_empty());
+
+    let response = browser
+        .request(&app, Method::GET, "/api/auth/mfa/factors")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+}
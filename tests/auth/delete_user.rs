@@ -57,3 +57,99 @@ async fn delete_user_returns_200() {
     let delete_response = app.delete_user(token, user_id).await;
     assert_eq!(delete_response.status().as_u16(), 200);
 }
+
+#[tokio::test]
+async fn clinician_cannot_delete_another_user() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&owner_token).await;
+    let target_token = app
+        .signin_token("clinician2@example.com", "Password123!")
+        .await;
+    let target_id = app.own_user_id(&target_token, &practice_id).await;
+
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+    let response = app.delete_user(&token, &target_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(body.get("code").unwrap(), "insufficient_role");
+}
+
+#[tokio::test]
+async fn admin_cannot_delete_an_owner() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let owner_id = app
+        .post_retrieve_user_id(&owner_token, "owner1@example.com")
+        .await
+        .json::<serde_json::Value>()
+        .await
+        .expect("Failed to parse response body")
+        .get("user_id")
+        .and_then(|v| v.as_str())
+        .expect("user_id not found in response")
+        .to_string();
+
+    let admin_token = app.signin_token("admin1@example.com", "Password123!").await;
+    let response = app.delete_user(&admin_token, &owner_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn owning_a_self_created_practice_grants_nothing_over_other_accounts() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let seeded_practice_id = app.seeded_practice_id(&owner_token).await;
+    let target_token = app
+        .signin_token("clinician2@example.com", "Password123!")
+        .await;
+    let target_id = app.own_user_id(&target_token, &seeded_practice_id).await;
+
+    // Anyone may create a practice and so become its owner.
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+    let response = app
+        .post_practice(&token, &format!("Own Practice {}", Uuid::new_v4()))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let practice_id = response
+        .json::<serde_json::Value>()
+        .await
+        .expect("Failed to parse response body")
+        .pointer("/practice/id")
+        .and_then(|v| v.as_str())
+        .expect("practice id not found in response")
+        .to_string();
+
+    let response = app
+        .insert_membership_directly(&token, &practice_id, &target_id)
+        .await;
+    assert!(
+        matches!(response.status().as_u16(), 401 | 403),
+        "Direct membership insert returned {}",
+        response.status()
+    );
+
+    let response = app.delete_user(&token, &target_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app
+        .post_retrieve_user_id(&token, "clinician2@example.com")
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app.delete_practice(&token, &practice_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
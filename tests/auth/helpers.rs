@@ -1,49 +1,993 @@
-use breeze_ehr::{App, utils::config::AppConfig};
+use breeze_ehr::{
+    App, AppBuilder,
+    domain::{
+        interfaces::auth_service::AuthService,
+        types::{audit::AuthAuditEvent, email::Email},
+    },
+    services::{
+        in_memory_audit_sink::InMemoryAuditSink, in_memory_auth_service::InMemoryAuthService,
+        in_memory_email_sender::InMemoryEmailSender,
+        in_memory_identity_provider_service::InMemoryIdentityProviderService,
+        in_memory_membership_service::InMemoryMembershipService,
+        in_memory_sms_gateway::InMemorySmsGateway,
+    },
+    utils::config::AppConfig,
+};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use reqwest::redirect::Policy;
+use secrecy::ExposeSecret;
 use serde_json::{Value, json};
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::RwLock;
 
 pub struct TestApp {
     pub address: String,
     pub mailpit_url: String,
     pub http_client: reqwest::Client,
+    pub audit_sink: Arc<RwLock<InMemoryAuditSink>>,
+    /// Set for apps started with [`TestApp::in_memory`].
+    pub memory: Option<MemoryBackend>,
+}
+
+/// Handles on the services behind a memory-backend test app.
+pub struct MemoryBackend {
+    pub auth_service: Arc<RwLock<InMemoryAuthService>>,
+    pub email_sender: Arc<RwLock<InMemoryEmailSender>>,
+    pub identity_provider_service: Arc<RwLock<InMemoryIdentityProviderService>>,
+    pub membership_service: Arc<RwLock<InMemoryMembershipService>>,
+    pub sms_gateway: Arc<RwLock<InMemorySmsGateway>>,
 }
 
 impl TestApp {
     pub async fn new() -> Self {
         let settings = AppConfig::for_tests();
+        let audit_sink = Arc::new(RwLock::new(InMemoryAuditSink::default()));
+        let app = App::builder(settings.clone())
+            .audit_sink(audit_sink.clone())
+            .build();
+
+        Self::spawn(app, audit_sink, None).await
+    }
+
+    /// Runs against the memory backend, so no Supabase or Mailpit is needed.
+    pub async fn in_memory() -> Self {
+        Self::in_memory_with(|builder| builder).await
+    }
+
+    /// [`TestApp::in_memory`] with extra services swapped in through the builder.
+    pub async fn in_memory_with(configure: impl FnOnce(AppBuilder) -> AppBuilder) -> Self {
+        let settings = AppConfig::for_memory_tests();
+        let audit_sink = Arc::new(RwLock::new(InMemoryAuditSink::default()));
+        let membership_service = Arc::new(RwLock::new(InMemoryMembershipService::default()));
+        let sms_gateway = Arc::new(RwLock::new(InMemorySmsGateway::default()));
+        let memory = MemoryBackend {
+            auth_service: Arc::new(RwLock::new(
+                InMemoryAuthService::new(
+                    settings.jwt_issuer.clone(),
+                    settings.jwt_audience.clone(),
+                    settings.supabase_jwt_secret.clone(),
+                )
+                .with_sms_gateway(sms_gateway.clone()),
+            )),
+            email_sender: Arc::new(RwLock::new(InMemoryEmailSender::default())),
+            identity_provider_service: Arc::new(RwLock::new(InMemoryIdentityProviderService::new(
+                membership_service.clone(),
+            ))),
+            membership_service,
+            sms_gateway,
+        };
+        let builder = App::builder(settings)
+            .audit_sink(audit_sink.clone())
+            .auth_service(memory.auth_service.clone())
+            .email_sender(memory.email_sender.clone())
+            .identity_provider_service(memory.identity_provider_service.clone())
+            .membership_service(memory.membership_service.clone())
+            .sms_gateway(memory.sms_gateway.clone());
+        let app = configure(builder).build();
+
+        Self::spawn(app, audit_sink, Some(memory)).await
+    }
+
+    async fn spawn(
+        app: App,
+        audit_sink: Arc<RwLock<InMemoryAuditSink>>,
+        memory: Option<MemoryBackend>,
+    ) -> Self {
         let http_client = reqwest::Client::builder()
             .redirect(Policy::none())
             .build()
             .expect("Failed to build HTTP client");
+        let address = app.config.app_address.clone();
+        let mailpit_url = app.config.mailpit_url.clone();
+
+        // Spawn the server
+        tokio::spawn(async move { app.run().await.expect("Failed to start test server") });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        Self {
+            address,
+            mailpit_url,
+            http_client,
+            audit_sink,
+            memory,
+        }
+    }
+
+    pub fn memory(&self) -> &MemoryBackend {
+        self.memory
+            .as_ref()
+            .expect("Only available on apps started with TestApp::in_memory")
+    }
+
+    /// Signs up through the API on a memory-backend app and confirms the address the way
+    /// the email link would. Returns the email and user id.
+    pub async fn memory_user(&self, password: &str) -> (String, String) {
+        let address = format!("memory-user+{}@example.com", uuid::Uuid::new_v4());
+        let response = self.post_signup(&address, password, None).await;
+        assert_eq!(response.status().as_u16(), 201);
+
+        let email = Email::new(address.clone()).expect("Invalid test email");
+        let auth_service = self.memory().auth_service.read().await;
+        auth_service
+            .confirm_email(&email)
+            .await
+            .expect("Failed to confirm email");
+        let user_id = auth_service
+            .retrieve_user_id(&email)
+            .await
+            .expect("User not found");
+
+        (address, user_id)
+    }
+
+    pub async fn audit_events(&self) -> Vec<AuthAuditEvent> {
+        self.audit_sink.read().await.events().await
+    }
+
+    pub async fn health_check(&self) -> reqwest::Response {
+        self.http_client
+            .get(format!("http://{}/api/health", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_user(&self, token: &str, user_id: &str) -> reqwest::Response {
+        let request_body = json!({ "user_id": user_id });
+        self.http_client
+            .delete(format!("http://{}/api/auth/delete_user", &self.address))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_forgot_password(&self, email: &str) -> reqwest::Response {
+        let request_body = json!({ "email": email });
+        self.http_client
+            .post(format!("http://{}/api/auth/forgot_password", &self.address))
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_magic_link(&self, email: &str) -> reqwest::Response {
+        let request_body = json!({ "email": email });
+        self.http_client
+            .post(format!("http://{}/api/auth/magic_link", &self.address))
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_verify_otp(&self, body: &Value) -> reqwest::Response {
+        self.http_client
+            .post(format!("http://{}/api/auth/verify_otp", &self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_me(&self, token: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!("http://{}/api/auth/me", &self.address))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn patch_me(&self, token: &str, body: &Value) -> reqwest::Response {
+        self.http_client
+            .patch(format!("http://{}/api/auth/me", &self.address))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_mfa_enroll(&self, token: &str, friendly_name: &str) -> reqwest::Response {
+        let request_body = json!({ "friendly_name": friendly_name });
+        self.http_client
+            .post(format!("http://{}/api/auth/mfa/enroll", &self.address))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_mfa_enroll_phone(
+        &self,
+        token: &str,
+        phone: &str,
+        friendly_name: &str,
+    ) -> reqwest::Response {
+        let request_body = json!({ "phone": phone, "friendly_name": friendly_name });
+        self.http_client
+            .post(format!(
+                "http://{}/api/auth/mfa/enroll/phone",
+                &self.address
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Posts `body` to the Send SMS hook with Standard Webhooks headers for `signature`.
+    pub async fn post_send_sms_hook(
+        &self,
+        body: &str,
+        timestamp: i64,
+        signature: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .post(format!("http://{}/api/hooks/send_sms", &self.address))
+            .header("content-type", "application/json")
+            .header("webhook-id", "msg_test")
+            .header("webhook-timestamp", timestamp.to_string())
+            .header("webhook-signature", signature)
+            .body(body.to_string())
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_session_idle(&self, token: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!("http://{}/api/auth/session/idle", &self.address))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_sessions(&self, token: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!("http://{}/api/auth/sessions", &self.address))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_sessions(&self, token: &str) -> reqwest::Response {
+        self.http_client
+            .delete(format!("http://{}/api/auth/sessions", &self.address))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_session(&self, token: &str, session_id: &str) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/auth/sessions/{}",
+                &self.address, session_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_mfa_factors(&self, token: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!("http://{}/api/auth/mfa/factors", &self.address))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_mfa_factor(&self, token: &str, factor_id: &str) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/auth/mfa/factors/{}",
+                &self.address, factor_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_mfa_challenge(&self, token: &str, factor_id: &str) -> reqwest::Response {
+        let request_body = json!({ "factor_id": factor_id });
+        self.http_client
+            .post(format!("http://{}/api/auth/mfa/challenge", &self.address))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_mfa_verify(
+        &self,
+        token: &str,
+        factor_id: &str,
+        challenge_id: &str,
+        code: &str,
+    ) -> reqwest::Response {
+        let request_body = json!({
+            "factor_id": factor_id,
+            "challenge_id": challenge_id,
+            "code": code,
+        });
+        self.http_client
+            .post(format!("http://{}/api/auth/mfa/verify", &self.address))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_refresh(&self, refresh_token: &str) -> reqwest::Response {
+        let request_body = json!({ "refresh_token": refresh_token });
+        self.http_client
+            .post(format!("http://{}/api/auth/refresh", &self.address))
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_reset_password(&self, token: &str, password: &str) -> reqwest::Response {
+        let request_body = json!({ "token": token, "password": password });
+        self.http_client
+            .post(format!("http://{}/api/auth/reset_password", &self.address))
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Writes a membership straight to PostgREST as the caller, the way a client holding
+    /// its own access token could, bypassing the API.
+    pub async fn insert_membership_directly(
+        &self,
+        token: &str,
+        practice_id: &str,
+        user_id: &str,
+    ) -> reqwest::Response {
+        let settings = AppConfig::for_tests();
+        self.http_client
+            .post(format!(
+                "{}/rest/v1/practice_memberships",
+                settings.supabase_url
+            ))
+            .header("apikey", settings.supabase_anon_key.expose_secret())
+            .bearer_auth(token)
+            .json(&json!({ "practice_id": practice_id, "user_id": user_id }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    /// Updates the caller's `user_metadata` straight through GoTrue, bypassing the API.
+    pub async fn update_user_metadata_directly(
+        &self,
+        token: &str,
+        data: &Value,
+    ) -> reqwest::Response {
+        let settings = AppConfig::for_tests();
+        self.http_client
+            .put(format!("{}/auth/v1/user", settings.supabase_url))
+            .header("apikey", settings.supabase_anon_key.expose_secret())
+            .bearer_auth(token)
+            .json(&json!({ "data": data }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_retrieve_user_id(&self, token: &str, email: &str) -> reqwest::Response {
+        let request_body = json!({ "email": email });
+        self.http_client
+            .post(format!(
+                "http://{}/api/auth/retrieve_user_id",
+                &self.address
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_signin(&self, email: &str, password: &str) -> reqwest::Response {
+        let request_body = json!({ "email": email, "password": password });
+        self.http_client
+            .post(format!("http://{}/api/auth/signin", &self.address))
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_signin_from(
+        &self,
+        email: &str,
+        password: &str,
+        forwarded_for: &str,
+    ) -> reqwest::Response {
+        let request_body = json!({ "email": email, "password": password });
+        self.http_client
+            .post(format!("http://{}/api/auth/signin", &self.address))
+            .header("X-Forwarded-For", forwarded_for)
+            .header("X-Request-Id", "audit-test-request")
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
 
-        let app = App::new(settings.clone());
+    pub async fn post_signout(&self, token: &str) -> reqwest::Response {
+        self.http_client
+            .post(format!("http://{}/api/auth/signout", &self.address))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_signup(
+        &self,
+        email: &str,
+        password: &str,
+        redirect_to: Option<&str>,
+    ) -> reqwest::Response {
+        let mut request_body = json!({ "email": email, "password": password });
+
+        if let Some(redirect) = redirect_to {
+            request_body["redirect_to"] = json!(redirect);
+        }
+
+        self.http_client
+            .post(format!("http://{}/api/auth/signup", &self.address))
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_signup_from(
+        &self,
+        email: &str,
+        password: &str,
+        forwarded_for: &str,
+    ) -> reqwest::Response {
+        let request_body = json!({ "email": email, "password": password });
+        self.http_client
+            .post(format!("http://{}/api/auth/signup", &self.address))
+            .header("X-Forwarded-For", forwarded_for)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_practices(&self, token: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!("http://{}/api/practices", &self.address))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_practice(&self, token: &str, name: &str) -> reqwest::Response {
+        let request_body = json!({ "name": name });
+        self.http_client
+            .post(format!("http://{}/api/practices", &self.address))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_practice(&self, token: &str, practice_id: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn patch_practice(
+        &self,
+        token: &str,
+        practice_id: &str,
+        name: &str,
+    ) -> reqwest::Response {
+        let request_body = json!({ "name": name });
+        self.http_client
+            .patch(format!(
+                "http://{}/api/practices/{}",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_practice(&self, token: &str, practice_id: &str) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/practices/{}",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn put_passwordless_signin(
+        &self,
+        token: &str,
+        practice_id: &str,
+        allowed: bool,
+    ) -> reqwest::Response {
+        let request_body = json!({ "allowed": allowed });
+        self.http_client
+            .put(format!(
+                "http://{}/api/practices/{}/passwordless_signin",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_identity_providers(
+        &self,
+        token: &str,
+        practice_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}/identity_providers",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_identity_provider(
+        &self,
+        token: &str,
+        practice_id: &str,
+        body: &Value,
+    ) -> reqwest::Response {
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/identity_providers",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_identity_provider(
+        &self,
+        token: &str,
+        practice_id: &str,
+        provider_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/practices/{}/identity_providers/{}",
+                &self.address, practice_id, provider_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_approve_identity_provider(
+        &self,
+        token: &str,
+        practice_id: &str,
+        provider_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/identity_providers/{}/approve",
+                &self.address, practice_id, provider_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_sso_start(
+        &self,
+        provider_id: &str,
+        redirect_to: Option<&str>,
+    ) -> reqwest::Response {
+        let mut request = self.http_client.get(format!(
+            "http://{}/api/auth/sso/{}/start",
+            &self.address, provider_id
+        ));
+        if let Some(redirect_to) = redirect_to {
+            request = request.query(&[("redirect_to", redirect_to)]);
+        }
+        request.send().await.expect("Failed to execute request.")
+    }
+
+    /// `query` is what the provider appended to the callback URL; `login_cookie` is the
+    /// cookie set by the start request, if the browser still has it.
+    pub async fn get_sso_callback(
+        &self,
+        query: &str,
+        login_cookie: Option<&str>,
+    ) -> reqwest::Response {
+        let mut request = self.http_client.get(format!(
+            "http://{}/api/auth/sso/callback?{}",
+            &self.address, query
+        ));
+        if let Some(cookie) = login_cookie {
+            request = request.header(reqwest::header::COOKIE, cookie);
+        }
+        request.send().await.expect("Failed to execute request.")
+    }
+
+    pub async fn post_sso_link_start(
+        &self,
+        token: &str,
+        provider_id: &str,
+        redirect_to: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .post(format!(
+                "http://{}/api/auth/sso/{}/link",
+                &self.address, provider_id
+            ))
+            .bearer_auth(token)
+            .query(&[("redirect_to", redirect_to)])
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_sso_link(
+        &self,
+        token: &str,
+        body: &Value,
+        login_cookie: Option<&str>,
+    ) -> reqwest::Response {
+        let mut request = self
+            .http_client
+            .post(format!("http://{}/api/auth/sso/link", &self.address))
+            .bearer_auth(token)
+            .json(body);
+        if let Some(cookie) = login_cookie {
+            request = request.header(reqwest::header::COOKIE, cookie);
+        }
+        request.send().await.expect("Failed to execute request.")
+    }
+
+    pub async fn get_members(&self, token: &str, practice_id: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}/members",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn patch_membership(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        is_active: bool,
+    ) -> reqwest::Response {
+        let request_body = json!({ "is_active": is_active });
+        self.http_client
+            .patch(format!(
+                "http://{}/api/practices/{}/members/{}",
+                &self.address, practice_id, membership_id
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_member_role(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        role: &str,
+    ) -> reqwest::Response {
+        let request_body = json!({ "role": role });
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/members/{}/roles",
+                &self.address, practice_id, membership_id
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_member_role(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+        role: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/practices/{}/members/{}/roles/{}",
+                &self.address, practice_id, membership_id, role
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_member_sessions(
+        &self,
+        token: &str,
+        practice_id: &str,
+        membership_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/practices/{}/members/{}/sessions",
+                &self.address, practice_id, membership_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_invitations(&self, token: &str, practice_id: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}/invitations",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        email: &str,
+        roles: &[&str],
+    ) -> reqwest::Response {
+        let request_body = json!({ "email": email, "roles": roles });
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/invitations",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_resend_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        invitation_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/invitations/{}/resend",
+                &self.address, practice_id, invitation_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_invitation(
+        &self,
+        token: &str,
+        practice_id: &str,
+        invitation_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/practices/{}/invitations/{}",
+                &self.address, practice_id, invitation_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_preview_invitation(&self, invite_token: &str) -> reqwest::Response {
+        let request_body = json!({ "token": invite_token });
+        self.http_client
+            .post(format!("http://{}/api/invitations/preview", &self.address))
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_accept_invitation(
+        &self,
+        invite_token: &str,
+        password: &str,
+    ) -> reqwest::Response {
+        let request_body = json!({ "token": invite_token, "password": password });
+        self.http_client
+            .post(format!("http://{}/api/invitations/accept", &self.address))
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_teams(&self, token: &str, practice_id: &str) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}/teams",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_team(&self, token: &str, practice_id: &str, name: &str) -> reqwest::Response {
+        let request_body = json!({ "name": name });
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/teams",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
 
-        // Spawn the server
-        tokio::spawn(async move { app.run().await.expect("Failed to start test server") });
-        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    pub async fn get_team(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}/teams/{}",
+                &self.address, practice_id, team_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
 
-        Self {
-            address: settings.app_address,
-            mailpit_url: settings.mailpit_url,
-            http_client,
-        }
+    pub async fn patch_team(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        name: &str,
+    ) -> reqwest::Response {
+        let request_body = json!({ "name": name });
+        self.http_client
+            .patch(format!(
+                "http://{}/api/practices/{}/teams/{}",
+                &self.address, practice_id, team_id
+            ))
+            .bearer_auth(token)
+            .json(&request_body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
     }
 
-    pub async fn health_check(&self) -> reqwest::Response {
+    pub async fn delete_team(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+    ) -> reqwest::Response {
         self.http_client
-            .get(format!("http://{}/api/health", &self.address))
+            .delete(format!(
+                "http://{}/api/practices/{}/teams/{}",
+                &self.address, practice_id, team_id
+            ))
+            .bearer_auth(token)
             .send()
             .await
             .expect("Failed to execute request.")
     }
 
-    pub async fn delete_user(&self, token: &str, user_id: &str) -> reqwest::Response {
+    pub async fn post_team_member(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        user_id: &str,
+    ) -> reqwest::Response {
         let request_body = json!({ "user_id": user_id });
         self.http_client
-            .delete(format!("http://{}/api/auth/delete_user", &self.address))
+            .post(format!(
+                "http://{}/api/practices/{}/teams/{}/members",
+                &self.address, practice_id, team_id
+            ))
             .bearer_auth(token)
             .json(&request_body)
             .send()
@@ -51,59 +995,326 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
-    pub async fn post_retrieve_user_id(&self, token: &str, email: &str) -> reqwest::Response {
-        let request_body = json!({ "email": email });
+    pub async fn delete_team_member(
+        &self,
+        token: &str,
+        practice_id: &str,
+        team_id: &str,
+        user_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/practices/{}/teams/{}/members/{}",
+                &self.address, practice_id, team_id, user_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_clients(
+        &self,
+        token: &str,
+        practice_id: &str,
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}/clients",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        body: &Value,
+    ) -> reqwest::Response {
         self.http_client
             .post(format!(
-                "http://{}/api/auth/retrieve_user_id",
-                &self.address
+                "http://{}/api/practices/{}/clients",
+                &self.address, practice_id
             ))
             .bearer_auth(token)
-            .json(&request_body)
+            .json(body)
             .send()
             .await
             .expect("Failed to execute request.")
     }
 
-    pub async fn post_signin(&self, email: &str, password: &str) -> reqwest::Response {
-        let request_body = json!({ "email": email, "password": password });
+    pub async fn get_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+    ) -> reqwest::Response {
         self.http_client
-            .post(format!("http://{}/api/auth/signin", &self.address))
-            .json(&request_body)
+            .get(format!(
+                "http://{}/api/practices/{}/clients/{}",
+                &self.address, practice_id, client_id
+            ))
+            .bearer_auth(token)
             .send()
             .await
             .expect("Failed to execute request.")
     }
 
-    pub async fn post_signout(&self, token: &str) -> reqwest::Response {
+    pub async fn patch_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+        body: &Value,
+    ) -> reqwest::Response {
         self.http_client
-            .post(format!("http://{}/api/auth/signout", &self.address))
+            .patch(format!(
+                "http://{}/api/practices/{}/clients/{}",
+                &self.address, practice_id, client_id
+            ))
             .bearer_auth(token)
+            .json(body)
             .send()
             .await
             .expect("Failed to execute request.")
     }
 
-    pub async fn post_signup(
+    pub async fn put_client_assignment(
         &self,
-        email: &str,
-        password: &str,
-        redirect_to: Option<&str>,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+        body: &Value,
     ) -> reqwest::Response {
-        let mut request_body = json!({ "email": email, "password": password });
+        self.http_client
+            .put(format!(
+                "http://{}/api/practices/{}/clients/{}/assignment",
+                &self.address, practice_id, client_id
+            ))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
 
-        if let Some(redirect) = redirect_to {
-            request_body["redirect_to"] = json!(redirect);
-        }
+    pub async fn delete_client(
+        &self,
+        token: &str,
+        practice_id: &str,
+        client_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .delete(format!(
+                "http://{}/api/practices/{}/clients/{}",
+                &self.address, practice_id, client_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
 
+    pub async fn get_appointments(
+        &self,
+        token: &str,
+        practice_id: &str,
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
         self.http_client
-            .post(format!("http://{}/api/auth/signup", &self.address))
-            .json(&request_body)
+            .get(format!(
+                "http://{}/api/practices/{}/appointments",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_appointment(
+        &self,
+        token: &str,
+        practice_id: &str,
+        body: &Value,
+    ) -> reqwest::Response {
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/appointments",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn patch_appointment(
+        &self,
+        token: &str,
+        practice_id: &str,
+        appointment_id: &str,
+        body: &Value,
+    ) -> reqwest::Response {
+        self.http_client
+            .patch(format!(
+                "http://{}/api/practices/{}/appointments/{}",
+                &self.address, practice_id, appointment_id
+            ))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        body: &Value,
+    ) -> reqwest::Response {
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/appointment_series",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        series_id: &str,
+    ) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}/appointment_series/{}",
+                &self.address, practice_id, series_id
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_cancel_appointment_series(
+        &self,
+        token: &str,
+        practice_id: &str,
+        series_id: &str,
+        body: &Value,
+    ) -> reqwest::Response {
+        self.http_client
+            .post(format!(
+                "http://{}/api/practices/{}/appointment_series/{}/cancel",
+                &self.address, practice_id, series_id
+            ))
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_audit_log(
+        &self,
+        token: &str,
+        practice_id: &str,
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
+        self.http_client
+            .get(format!(
+                "http://{}/api/practices/{}/audit",
+                &self.address, practice_id
+            ))
+            .bearer_auth(token)
+            .query(query)
             .send()
             .await
             .expect("Failed to execute request.")
     }
 
+    /// User id behind `token` for callers who only see their own membership row, i.e. not
+    /// owners or admins.
+    pub async fn own_user_id(&self, token: &str, practice_id: &str) -> String {
+        let body: Value = self
+            .get_members(token, practice_id)
+            .await
+            .json()
+            .await
+            .expect("Failed to parse members response body");
+
+        body.pointer("/members/0/user_id")
+            .and_then(Value::as_str)
+            .expect("No membership visible for caller")
+            .to_string()
+    }
+
+    /// Membership id of the first member holding `role`, as seen by `token`.
+    pub async fn membership_id_with_role(
+        &self,
+        token: &str,
+        practice_id: &str,
+        role: &str,
+    ) -> String {
+        let body: Value = self
+            .get_members(token, practice_id)
+            .await
+            .json()
+            .await
+            .expect("Failed to parse members response body");
+
+        body.get("members")
+            .and_then(Value::as_array)
+            .and_then(|members| {
+                members.iter().find(|m| {
+                    m.get("roles")
+                        .and_then(Value::as_array)
+                        .is_some_and(|roles| roles.iter().any(|r| r.as_str() == Some(role)))
+                })
+            })
+            .and_then(|m| m.get("membership_id"))
+            .and_then(Value::as_str)
+            .expect("No member with that role found")
+            .to_string()
+    }
+
+    /// Id of the practice created by `supabase/seeds/after_users.sql`.
+    pub async fn seeded_practice_id(&self, token: &str) -> String {
+        let body: Value = self
+            .get_practices(token)
+            .await
+            .json()
+            .await
+            .expect("Failed to parse practices response body");
+
+        body.get("practices")
+            .and_then(Value::as_array)
+            .and_then(|practices| {
+                practices.iter().find(|p| {
+                    p.get("name").and_then(Value::as_str) == Some("Test Therapy Practice")
+                })
+            })
+            .and_then(|p| p.get("id"))
+            .and_then(Value::as_str)
+            .expect("Seeded practice not found; run scripts/dev-reset.sh")
+            .to_string()
+    }
+
     pub async fn clear_mailpit_messages(&self) {
         self.http_client
             .delete(format!("{}/api/v1/messages", self.mailpit_url))
@@ -160,21 +1371,138 @@ impl TestApp {
             )
         })
     }
+
+    /// Reads the newest email sent to `email` and returns the `token` query parameter of its link.
+    pub async fn email_link_token(&self, email: &str) -> String {
+        let link = self.verification_link(email).await;
+        reqwest::Url::parse(&link)
+            .expect("Email link is not a valid URL")
+            .query_pairs()
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+            .unwrap_or_else(|| panic!("No token found in email link {link}"))
+    }
+
+    /// Waits for a magic-link email to `email` and returns its code and link `token_hash`.
+    pub async fn magic_link_email(&self, email: &str) -> (String, String) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let normalized_email = email.to_ascii_lowercase();
+
+        while Instant::now() < deadline {
+            let messages: Value = self
+                .http_client
+                .get(format!("{}/api/v1/messages", self.mailpit_url))
+                .send()
+                .await
+                .expect("Failed to execute request.")
+                .json()
+                .await
+                .expect("Failed to parse Mailpit response body");
+
+            if let Some(message_id) = find_message_id(&messages, &normalized_email) {
+                let message: Value = self
+                    .http_client
+                    .get(format!(
+                        "{}/api/v1/message/{}",
+                        self.mailpit_url, message_id
+                    ))
+                    .send()
+                    .await
+                    .expect("Failed to execute request.")
+                    .json()
+                    .await
+                    .expect("Failed to parse Mailpit message response");
+
+                // The newest message may still be the signup confirmation.
+                let token_hash =
+                    find_verification_link(&message)
+                        .filter(|link| link.contains("type=magiclink"))
+                        .and_then(|link| {
+                            reqwest::Url::parse(&link).ok()?.query_pairs().find_map(
+                                |(key, value)| (key == "token_hash").then(|| value.into_owned()),
+                            )
+                        });
+                let code = ["Text", "HTML"].iter().find_map(|field| {
+                    let body = message.get(field).and_then(Value::as_str)?;
+                    OTP_CODE_REGEX.find(body).map(|m| m.as_str().to_string())
+                });
+                if let (Some(code), Some(token_hash)) = (code, token_hash) {
+                    return (code, token_hash);
+                }
+            }
+
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+
+        panic!("No magic-link email found for {email}")
+    }
+
+    /// Signs up `email`, follows the confirmation link and returns the new user's access token.
+    pub async fn signup_confirmed_user(&self, email: &str, password: &str) -> String {
+        let signup_response = self.post_signup(email, password, None).await;
+        assert_eq!(signup_response.status().as_u16(), 201);
+
+        let verification_url = self.verification_link(email).await;
+        let verify_response = self
+            .http_client
+            .get(&verification_url)
+            .send()
+            .await
+            .expect("Failed to follow verification link");
+        assert!(
+            verify_response.status().is_success() || verify_response.status().is_redirection(),
+            "Unexpected verification status {}",
+            verify_response.status()
+        );
+
+        self.signin_token(email, password).await
+    }
+
+    pub async fn signin_token(&self, email: &str, password: &str) -> String {
+        let response = self.post_signin(email, password).await;
+        assert_eq!(response.status().as_u16(), 200);
+
+        let body: Value = response
+            .json()
+            .await
+            .expect("Failed to parse signin response body");
+        body.get("token")
+            .and_then(Value::as_str)
+            .expect("token not found in response")
+            .to_string()
+    }
+
+    /// Removes a user created by a test, looking the id up through the API.
+    pub async fn cleanup_user(&self, token: &str, email: &str) {
+        let response = self.post_retrieve_user_id(token, email).await;
+        assert_eq!(response.status().as_u16(), 200);
+        let body: Value = response
+            .json()
+            .await
+            .expect("Failed to parse response body");
+        let user_id = body
+            .get("user_id")
+            .and_then(Value::as_str)
+            .expect("user_id not found in response");
+
+        let delete_response = self.delete_user(token, user_id).await;
+        assert_eq!(delete_response.status().as_u16(), 200);
+    }
 }
 
 fn find_message_id(messages: &Value, email: &str) -> Option<String> {
     const RECIPIENT_KEYS: [&str; 2] = ["To", "to"];
 
-    if let Some(arr) = messages.as_array() {
-        if let Some(id) = find_in_messages(arr, email, &RECIPIENT_KEYS) {
-            return Some(id);
-        }
+    if let Some(arr) = messages.as_array()
+        && let Some(id) = find_in_messages(arr, email, &RECIPIENT_KEYS)
+    {
+        return Some(id);
     }
 
-    if let Some(arr) = messages.get("messages").and_then(Value::as_array) {
-        if let Some(id) = find_in_messages(arr, email, &RECIPIENT_KEYS) {
-            return Some(id);
-        }
+    if let Some(arr) = messages.get("messages").and_then(Value::as_array)
+        && let Some(id) = find_in_messages(arr, email, &RECIPIENT_KEYS)
+    {
+        return Some(id);
     }
 
     None
@@ -208,9 +1536,32 @@ fn find_in_messages(messages: &[Value], email: &str, recipient_keys: &[&str]) ->
     })
 }
 
+static OTP_CODE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b\d{6}\b").expect("valid OTP code regex"));
+
 static LINK_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"https?://[^\s"<>]+"#).expect("valid verification link regex"));
 
+pub async fn body(response: reqwest::Response) -> Value {
+    response
+        .json()
+        .await
+        .expect("Failed to parse response body")
+}
+
+/// The `code` of an error response.
+pub async fn error_code(response: reqwest::Response) -> String {
+    body(response).await["code"]
+        .as_str()
+        .expect("error code missing")
+        .to_string()
+}
+
+pub async fn assert_error(response: reqwest::Response, status: u16, code: &str) {
+    assert_eq!(response.status().as_u16(), status);
+    assert_eq!(error_code(response).await, code);
+}
+
 pub fn find_link_in_html(html: &str) -> Option<String> {
     LINK_REGEX.find(html).map(|m| {
         let cleaned = m.as_str().trim_end_matches(['"', ')']);
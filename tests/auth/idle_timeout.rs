@@ -0,0 +1,133 @@
+use std::{sync::Arc, time::Duration};
+
+use breeze_ehr::{
+    domain::{
+        error::app_error::{AppError, AppResult},
+        interfaces::session_activity_tracker::SessionActivityTracker,
+        types::session::SessionActivity,
+    },
+    utils::tracing::init_tracing,
+};
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use crate::helpers::TestApp;
+
+const PASSWORD: &str = "Password123!";
+
+/// An activity store that can't be reached.
+struct UnreachableTracker;
+
+#[async_trait::async_trait]
+impl SessionActivityTracker for UnreachableTracker {
+    async fn get(&self, _session_id: &str) -> AppResult<Option<SessionActivity>> {
+        Err(AppError::internal(anyhow::anyhow!(
+            "session_activity unreachable"
+        )))
+    }
+
+    async fn list_for_user(&self, _user_id: &str) -> AppResult<Vec<SessionActivity>> {
+        Err(AppError::internal(anyhow::anyhow!(
+            "session_activity unreachable"
+        )))
+    }
+
+    async fn save(&self, _activity: &SessionActivity) -> AppResult<()> {
+        Err(AppError::internal(anyhow::anyhow!(
+            "session_activity unreachable"
+        )))
+    }
+
+    async fn remove(&self, _session_id: &str) -> AppResult<()> {
+        Err(AppError::internal(anyhow::anyhow!(
+            "session_activity unreachable"
+        )))
+    }
+}
+
+async fn app_with_idle_timeout(secs: i64) -> TestApp {
+    TestApp::in_memory_with(|builder| builder.session_idle_timeout_secs(secs)).await
+}
+
+async fn assert_idle_timeout(response: reqwest::Response) {
+    assert_eq!(response.status().as_u16(), 401);
+    let body: Value = response.json().await.expect("Failed to parse body");
+    assert_eq!(body["code"], "session_idle_timeout");
+}
+
+#[tokio::test]
+async fn idle_session_is_rejected_even_after_refresh() {
+    init_tracing("info");
+    let app = app_with_idle_timeout(2).await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+
+    let response = app.post_signin(&email, PASSWORD).await;
+    let session: Value = response.json().await.unwrap();
+    let token = session["token"].as_str().unwrap();
+    let refresh_token = session["refresh_token"].as_str().unwrap();
+
+    let response = app.get_mfa_factors(token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.get_session_idle(token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let status: Value = response.json().await.unwrap();
+    assert_eq!(status["idle_timeout_secs"], 2);
+    assert!(status["seconds_remaining"].as_i64().unwrap() <= 2);
+
+    tokio::time::sleep(Duration::from_secs(3)).await;
+
+    assert_idle_timeout(app.get_session_idle(token).await).await;
+    assert_idle_timeout(app.get_mfa_factors(token).await).await;
+
+    // Refreshing mints a token for the same session, which stays dead.
+    let response = app.post_refresh(refresh_token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let refreshed: Value = response.json().await.unwrap();
+    assert_idle_timeout(
+        app.get_mfa_factors(refreshed["token"].as_str().unwrap())
+            .await,
+    )
+    .await;
+
+    let token = app.signin_token(&email, PASSWORD).await;
+    let response = app.get_mfa_factors(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn activity_extends_session_but_polling_does_not() {
+    init_tracing("info");
+    let app = app_with_idle_timeout(4).await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&email, PASSWORD).await;
+
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        let response = app.get_mfa_factors(&token).await;
+        assert_eq!(response.status().as_u16(), 200);
+    }
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let response = app.get_session_idle(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    assert_idle_timeout(app.get_session_idle(&token).await).await;
+}
+
+#[tokio::test]
+async fn requests_are_refused_when_the_activity_store_is_down() {
+    init_tracing("info");
+    let app = TestApp::in_memory_with(|builder| {
+        builder.session_activity_tracker(Arc::new(RwLock::new(UnreachableTracker)))
+    })
+    .await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&email, PASSWORD).await;
+
+    let response = app.get_mfa_factors(&token).await;
+    assert_eq!(response.status().as_u16(), 500);
+    let response = app.get_session_idle(&token).await;
+    assert_eq!(response.status().as_u16(), 500);
+}
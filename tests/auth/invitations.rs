@@ -0,0 +1,328 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, error_code};
+
+async fn invitation_id(response: reqwest::Response) -> String {
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    body.pointer("/invitation/id")
+        .and_then(Value::as_str)
+        .expect("invitation id not found in response")
+        .to_string()
+}
+
+#[tokio::test]
+async fn invited_user_accepts_and_joins_practice() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&owner_token).await;
+    let email = format!("invitee+{}@example.com", Uuid::new_v4());
+    let password = "InviteePass123!";
+
+    let response = app
+        .post_invitation(&owner_token, &practice_id, &email, &["biller"])
+        .await;
+    invitation_id(response).await;
+
+    let invite_token = app.email_link_token(&email).await;
+
+    let preview_response = app.post_preview_invitation(&invite_token).await;
+    assert_eq!(preview_response.status().as_u16(), 200);
+    let preview: Value = preview_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        preview.pointer("/invitation/practice_name").unwrap(),
+        "Test Therapy Practice"
+    );
+    assert_eq!(preview.pointer("/invitation/status").unwrap(), "pending");
+
+    let accept_response = app.post_accept_invitation(&invite_token, password).await;
+    assert_eq!(accept_response.status().as_u16(), 200);
+    let accept_body: Value = accept_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        accept_body.get("practice_id").unwrap().as_str().unwrap(),
+        practice_id
+    );
+    let token = accept_body
+        .get("token")
+        .and_then(Value::as_str)
+        .expect("token not found in response")
+        .to_string();
+
+    let members: Value = app
+        .get_members(&token, &practice_id)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let roles = members
+        .pointer("/members/0/roles")
+        .and_then(Value::as_array)
+        .unwrap();
+    assert_eq!(roles, &vec![Value::from("biller")]);
+
+    // Links are single use.
+    let reuse_response = app.post_accept_invitation(&invite_token, password).await;
+    assert_eq!(reuse_response.status().as_u16(), 409);
+    assert_eq!(
+        error_code(reuse_response).await,
+        "invitation_already_accepted"
+    );
+
+    app.cleanup_user(&token, &email).await;
+}
+
+#[tokio::test]
+async fn existing_user_accepts_with_current_password() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let email = format!("existing-invitee+{}@example.com", Uuid::new_v4());
+    let password = "ExistingPass123!";
+    app.signup_confirmed_user(&email, password).await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&owner_token).await;
+
+    let response = app
+        .post_invitation(&owner_token, &practice_id, &email, &["clinician"])
+        .await;
+    invitation_id(response).await;
+    let invite_token = app.email_link_token(&email).await;
+
+    // Guesses count towards the same lockout as signin.
+    let mut locked_response = None;
+    for _ in 0..10 {
+        let response = app
+            .post_accept_invitation(&invite_token, "WrongPass123!")
+            .await;
+        match response.status().as_u16() {
+            401 => continue,
+            429 => {
+                locked_response = Some(response);
+                break;
+            }
+            status => panic!("Unexpected status {status}"),
+        }
+    }
+    let locked_response = locked_response.expect("Wrong passwords never locked the account");
+    let retry_after: u64 = locked_response
+        .headers()
+        .get("Retry-After")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .expect("Retry-After header missing");
+    assert_eq!(error_code(locked_response).await, "account_locked");
+    tokio::time::sleep(std::time::Duration::from_secs(retry_after + 1)).await;
+
+    let accept_response = app.post_accept_invitation(&invite_token, password).await;
+    assert_eq!(accept_response.status().as_u16(), 200);
+    let accept_body: Value = accept_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let token = accept_body
+        .get("token")
+        .and_then(Value::as_str)
+        .unwrap()
+        .to_string();
+
+    assert_eq!(app.seeded_practice_id(&token).await, practice_id);
+
+    app.cleanup_user(&token, &email).await;
+}
+
+#[tokio::test]
+async fn resend_rotates_token_and_revoke_closes_invitation() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&owner_token).await;
+    let email = format!("resend-invitee+{}@example.com", Uuid::new_v4());
+
+    let response = app
+        .post_invitation(&owner_token, &practice_id, &email, &["scheduler"])
+        .await;
+    let invitation_id = invitation_id(response).await;
+    let first_token = app.email_link_token(&email).await;
+
+    let resend_response = app
+        .post_resend_invitation(&owner_token, &practice_id, &invitation_id)
+        .await;
+    assert_eq!(resend_response.status().as_u16(), 200);
+    let second_token = app.email_link_token(&email).await;
+    assert_ne!(first_token, second_token);
+
+    let stale_response = app.post_preview_invitation(&first_token).await;
+    assert_eq!(stale_response.status().as_u16(), 404);
+
+    let listed: Value = app
+        .get_invitations(&owner_token, &practice_id)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert!(
+        listed
+            .get("invitations")
+            .and_then(Value::as_array)
+            .unwrap()
+            .iter()
+            .any(|i| i.get("id").and_then(Value::as_str) == Some(invitation_id.as_str()))
+    );
+
+    let revoke_response = app
+        .delete_invitation(&owner_token, &practice_id, &invitation_id)
+        .await;
+    assert_eq!(revoke_response.status().as_u16(), 200);
+
+    let accept_response = app
+        .post_accept_invitation(&second_token, "InviteePass123!")
+        .await;
+    assert_eq!(accept_response.status().as_u16(), 400);
+    assert_eq!(error_code(accept_response).await, "invitation_revoked");
+
+    let revoke_again_response = app
+        .delete_invitation(&owner_token, &practice_id, &invitation_id)
+        .await;
+    assert_eq!(revoke_again_response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn duplicate_pending_invitation_returns_409() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&owner_token).await;
+    let email = format!("duplicate-invitee+{}@example.com", Uuid::new_v4());
+
+    let response = app
+        .post_invitation(&owner_token, &practice_id, &email, &["biller"])
+        .await;
+    let invitation_id = invitation_id(response).await;
+
+    let duplicate_response = app
+        .post_invitation(&owner_token, &practice_id, &email, &["clinician"])
+        .await;
+    assert_eq!(duplicate_response.status().as_u16(), 409);
+    assert_eq!(error_code(duplicate_response).await, "invitation_pending");
+
+    app.delete_invitation(&owner_token, &practice_id, &invitation_id)
+        .await;
+}
+
+#[tokio::test]
+async fn inviting_existing_member_returns_409() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&owner_token).await;
+
+    let response = app
+        .post_invitation(
+            &owner_token,
+            &practice_id,
+            "clinician3@example.com",
+            &["biller"],
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 409);
+    assert_eq!(error_code(response).await, "already_member");
+}
+
+#[tokio::test]
+async fn admin_cannot_invite_owner() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("admin1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let email = format!("would-be-owner+{}@example.com", Uuid::new_v4());
+
+    let response = app
+        .post_invitation(&token, &practice_id, &email, &["owner"])
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(error_code(response).await, "cannot_invite");
+}
+
+#[tokio::test]
+async fn clinician_cannot_invite() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician4@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let email = format!("uninvited+{}@example.com", Uuid::new_v4());
+
+    let response = app
+        .post_invitation(&token, &practice_id, &email, &["biller"])
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(error_code(response).await, "insufficient_role");
+
+    let list_response = app.get_invitations(&token, &practice_id).await;
+    assert_eq!(list_response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn role_guard_reads_practice_from_path() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let response = app.get_invitations(&token, &practice_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    // Owning one practice says nothing about another.
+    let response = app
+        .get_invitations(&token, &Uuid::new_v4().to_string())
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(error_code(response).await, "insufficient_role");
+}
+
+#[tokio::test]
+async fn invitation_without_roles_returns_400() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let response = app
+        .post_invitation(&token, &practice_id, "nobody@example.com", &[])
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn accept_with_unknown_token_returns_404() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app
+        .post_accept_invitation(&"ab".repeat(32), "InviteePass123!")
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+    assert_eq!(error_code(response).await, "invitation_not_found");
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use breeze_ehr::{
+    domain::{
+        error::app_error::{AppError, AppResult},
+        interfaces::login_attempt_tracker::LoginAttemptTracker,
+        types::{email::Email, login_attempt::LockoutPolicy},
+    },
+    services::in_memory_login_attempt_tracker::InMemoryLoginAttemptTracker,
+    utils::tracing::init_tracing,
+};
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use crate::helpers::TestApp;
+
+const PASSWORD: &str = "Password123!";
+const WRONG_PASSWORD: &str = "WrongPass123!";
+
+/// Lock on the third failure, long enough that the test can't outlast it.
+fn strict() -> LockoutPolicy {
+    LockoutPolicy {
+        free_attempts: 2,
+        max_failures: 3,
+        base_delay_secs: 60,
+        lockout_secs: 600,
+        window_secs: 600,
+    }
+}
+
+fn lenient() -> LockoutPolicy {
+    LockoutPolicy {
+        free_attempts: 100,
+        max_failures: 1000,
+        ..strict()
+    }
+}
+
+async fn app_with(account: LockoutPolicy, client: LockoutPolicy) -> TestApp {
+    let tracker = Arc::new(RwLock::new(InMemoryLoginAttemptTracker::new(
+        account, client,
+    )));
+    TestApp::in_memory_with(|builder| builder.login_attempt_tracker(tracker)).await
+}
+
+/// An attempt store that can't be reached.
+struct UnreachableTracker;
+
+#[async_trait::async_trait]
+impl LoginAttemptTracker for UnreachableTracker {
+    async fn check(&self, _email: &Email, _client_ip: Option<&str>) -> AppResult<()> {
+        Err(AppError::internal(anyhow::anyhow!(
+            "login_attempts unreachable"
+        )))
+    }
+
+    async fn record_failure(&self, _email: &Email, _client_ip: Option<&str>) -> AppResult<()> {
+        Err(AppError::internal(anyhow::anyhow!(
+            "login_attempts unreachable"
+        )))
+    }
+
+    async fn record_success(&self, _email: &Email) -> AppResult<()> {
+        Err(AppError::internal(anyhow::anyhow!(
+            "login_attempts unreachable"
+        )))
+    }
+}
+
+#[tokio::test]
+async fn repeated_failures_lock_account_with_429_and_retry_after() {
+    init_tracing("info");
+    let app = app_with(strict(), lenient()).await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let (other_email, _) = app.memory_user(PASSWORD).await;
+
+    for _ in 0..3 {
+        let response = app.post_signin(&email, WRONG_PASSWORD).await;
+        assert_eq!(response.status().as_u16(), 401);
+    }
+
+    // Even the right password is refused while the lock holds.
+    let response = app.post_signin(&email, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 429);
+    let retry_after: u64 = response
+        .headers()
+        .get("Retry-After")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .expect("Retry-After header missing");
+    assert!(retry_after > 0 && retry_after <= 600);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse signin response body");
+    assert_eq!(
+        body.get("code").unwrap().as_str().unwrap(),
+        "account_locked"
+    );
+
+    // Email case doesn't open a second counter.
+    let response = app.post_signin(&email.to_uppercase(), PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 429);
+
+    let response = app.post_signin(&other_email, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let events = app.audit_events().await;
+    assert!(events.iter().any(|e| {
+        e.target.as_deref() == Some(email.as_str())
+            && e.reason
+                .as_deref()
+                .is_some_and(|r| r.contains("Too many failed sign-in attempts"))
+    }));
+}
+
+#[tokio::test]
+async fn failures_from_one_ip_lock_that_ip_across_accounts() {
+    init_tracing("info");
+    let app = app_with(lenient(), strict()).await;
+    let (first, _) = app.memory_user(PASSWORD).await;
+    let (second, _) = app.memory_user(PASSWORD).await;
+    let (third, _) = app.memory_user(PASSWORD).await;
+
+    for email in [&first, &second, &first] {
+        let response = app
+            .post_signin_from(email, WRONG_PASSWORD, "203.0.113.7")
+            .await;
+        assert_eq!(response.status().as_u16(), 401);
+    }
+
+    let response = app.post_signin_from(&third, PASSWORD, "203.0.113.7").await;
+    assert_eq!(response.status().as_u16(), 429);
+
+    let response = app.post_signin_from(&third, PASSWORD, "198.51.100.4").await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn successful_signin_resets_account_failures() {
+    init_tracing("info");
+    let app = app_with(strict(), lenient()).await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+
+    for password in [
+        WRONG_PASSWORD,
+        WRONG_PASSWORD,
+        PASSWORD,
+        WRONG_PASSWORD,
+        WRONG_PASSWORD,
+    ] {
+        let response = app.post_signin(&email, password).await;
+        let expected = if password == PASSWORD { 200 } else { 401 };
+        assert_eq!(response.status().as_u16(), expected);
+    }
+
+    let response = app.post_signin(&email, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn only_wrong_passwords_count_towards_the_lock() {
+    init_tracing("info");
+    let app = app_with(strict(), lenient()).await;
+    let address = format!("unconfirmed+{}@example.com", uuid::Uuid::new_v4());
+    let response = app.post_signup(&address, PASSWORD, None).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    // Right password, unconfirmed address: refused, but not a guess.
+    for _ in 0..4 {
+        let response = app.post_signin(&address, PASSWORD).await;
+        assert_eq!(response.status().as_u16(), 401);
+    }
+
+    app.memory()
+        .auth_service
+        .read()
+        .await
+        .confirm_email(&Email::new(address.clone()).unwrap())
+        .await
+        .unwrap();
+    let response = app.post_signin(&address, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn signin_is_refused_when_the_attempt_store_is_down() {
+    init_tracing("info");
+    let app = TestApp::in_memory_with(|builder| {
+        builder.login_attempt_tracker(Arc::new(RwLock::new(UnreachableTracker)))
+    })
+    .await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+
+    let response = app.post_signin(&email, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 500);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert!(body.get("token").is_none());
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use breeze_ehr::{
+    domain::types::{audit::AuthAuditAction, email::Email},
+    utils::tracing::init_tracing,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, assert_error, body};
+
+const PASSWORD: &str = "Password123!";
+const MAGIC_LINK_MESSAGE: &str =
+    "If an account exists for this email, a signin link and code have been sent.";
+
+async fn memory_otp(app: &TestApp, address: &str) -> (String, String) {
+    let email = Email::new(address.to_string()).expect("Invalid test email");
+    app.memory()
+        .auth_service
+        .read()
+        .await
+        .magic_link_otp(&email)
+        .await
+        .expect("No magic-link code outstanding")
+}
+
+#[tokio::test]
+async fn memory_magic_link_code_signs_in_once() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, user_id) = app.memory_user(PASSWORD).await;
+
+    let response = app.post_magic_link(&address).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(body(response).await["message"], MAGIC_LINK_MESSAGE);
+    let (code, _) = memory_otp(&app, &address).await;
+
+    let response = app
+        .post_verify_otp(&json!({ "email": address, "token": code }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let token = body(response).await["token"]
+        .as_str()
+        .expect("token not found in response")
+        .to_string();
+
+    let response = app.get_me(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(body(response).await["user"]["user_id"], user_id.as_str());
+
+    let response = app
+        .post_verify_otp(&json!({ "email": address, "token": code }))
+        .await;
+    assert_error(response, 401, "invalid_otp").await;
+
+    let events = app.audit_events().await;
+    assert!(events.iter().any(|e| e.action == AuthAuditAction::Signin
+        && e.actor_user_id.as_deref() == Some(user_id.as_str())));
+}
+
+#[tokio::test]
+async fn memory_magic_link_token_hash_signs_in_and_replaces_earlier_links() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, _) = app.memory_user(PASSWORD).await;
+
+    app.post_magic_link(&address).await;
+    let (_, first_hash) = memory_otp(&app, &address).await;
+    app.post_magic_link(&address).await;
+    let (_, second_hash) = memory_otp(&app, &address).await;
+    assert_ne!(first_hash, second_hash);
+
+    let response = app
+        .post_verify_otp(&json!({ "token_hash": first_hash }))
+        .await;
+    assert_error(response, 401, "invalid_otp").await;
+
+    let response = app
+        .post_verify_otp(&json!({ "token_hash": second_hash, "use_cookie": true }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let session = body(response).await;
+    assert!(session.get("token").is_none());
+    assert!(session.get("csrf_token").is_some());
+}
+
+#[tokio::test]
+async fn memory_magic_link_hides_unknown_accounts_and_rejects_bad_requests() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, _) = app.memory_user(PASSWORD).await;
+
+    let unknown = format!("nobody+{}@example.com", Uuid::new_v4());
+    let response = app.post_magic_link(&unknown).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(body(response).await["message"], MAGIC_LINK_MESSAGE);
+
+    let response = app
+        .post_verify_otp(&json!({ "email": address, "token": "000000" }))
+        .await;
+    assert_error(response, 401, "invalid_otp").await;
+
+    let response = app.post_verify_otp(&json!({ "email": address })).await;
+    assert_error(response, 400, "invalid_input").await;
+
+    let response = app
+        .post_verify_otp(&json!({ "email": address, "token": "000000", "token_hash": "abc" }))
+        .await;
+    assert_error(response, 400, "invalid_input").await;
+}
+
+#[tokio::test]
+async fn magic_link_email_signs_in_end_to_end() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+    let address = format!("magic-link+{}@example.com", Uuid::new_v4());
+    let password_token = app.signup_confirmed_user(&address, PASSWORD).await;
+
+    // Supabase won't email the same user twice within a second.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let response = app.post_magic_link(&address).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let (code, token_hash) = app.magic_link_email(&address).await;
+
+    let response = app
+        .post_verify_otp(&json!({ "email": address, "token": code }))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let token = body(response).await["token"]
+        .as_str()
+        .expect("token not found in response")
+        .to_string();
+
+    let response = app.get_me(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(body(response).await["user"]["email"], address.as_str());
+
+    // The link and the code are the same one-time secret.
+    let response = app
+        .post_verify_otp(&json!({ "token_hash": token_hash }))
+        .await;
+    assert_error(response, 401, "invalid_otp").await;
+
+    app.cleanup_user(&password_token, &address).await;
+}
+
+#[tokio::test]
+async fn practice_can_disable_passwordless_signin() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+    let address = format!("magic-link+{}@example.com", Uuid::new_v4());
+    let token = app.signup_confirmed_user(&address, PASSWORD).await;
+
+    let response = app
+        .post_practice(&token, &format!("Passwordless Practice {}", Uuid::new_v4()))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let practice = body(response).await["practice"].clone();
+    assert_eq!(practice["passwordless_signin"], true);
+    let practice_id = practice["id"].as_str().expect("practice id").to_string();
+
+    let response = app
+        .put_passwordless_signin(&token, &practice_id, false)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        body(response).await["practice"]["passwordless_signin"],
+        false
+    );
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    app.post_magic_link(&address).await;
+    let (_, token_hash) = app.magic_link_email(&address).await;
+
+    let response = app
+        .post_verify_otp(&json!({ "token_hash": token_hash }))
+        .await;
+    assert_error(response, 403, "passwordless_signin_disabled").await;
+
+    // Password signin is unaffected.
+    let token = app.signin_token(&address, PASSWORD).await;
+
+    let response = app.delete_practice(&token, &practice_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    app.cleanup_user(&token, &address).await;
+}
@@ -1,7 +1,31 @@
+pub mod appointments;
+pub mod audit;
+pub mod audit_events;
+pub mod client_assignments;
+pub mod clients;
+pub mod cookies;
 pub mod delete_user;
 pub mod health;
 pub mod helpers;
+pub mod idle_timeout;
+pub mod invitations;
+pub mod jwks;
+pub mod lockout;
+pub mod magic_link;
+pub mod members;
+pub mod memory;
+pub mod mfa;
+pub mod password_policy;
+pub mod practices;
+pub mod profile;
+pub mod rate_limit;
+pub mod refresh;
+pub mod reset_password;
 pub mod retrieve_user_id;
+pub mod sessions;
 pub mod signin;
 pub mod signout;
 pub mod signup;
+pub mod sms_mfa;
+pub mod sso;
+pub mod teams;
@@ -0,0 +1,322 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::Value;
+
+use crate::helpers::TestApp;
+
+#[tokio::test]
+async fn owner_lists_members_with_roles() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let response = app.get_members(&token, &practice_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let members = body.get("members").and_then(Value::as_array).unwrap();
+    assert!(members.len() > 1);
+    assert!(members.iter().any(|m| {
+        m.get("roles")
+            .and_then(Value::as_array)
+            .is_some_and(|roles| roles.iter().any(|r| r.as_str() == Some("owner")))
+    }));
+}
+
+#[tokio::test]
+async fn clinician_only_sees_own_membership() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let response = app.get_members(&token, &practice_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        body.get("members").and_then(Value::as_array).unwrap().len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn owner_adds_and_removes_role() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let clinician_token = app
+        .signin_token("clinician6@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&clinician_token).await;
+    let membership_id = app
+        .membership_id_with_role(&clinician_token, &practice_id, "clinician")
+        .await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+
+    let add_response = app
+        .post_member_role(&token, &practice_id, &membership_id, "biller")
+        .await;
+    assert_eq!(add_response.status().as_u16(), 201);
+    let add_body: Value = add_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let roles = add_body
+        .pointer("/member/roles")
+        .and_then(Value::as_array)
+        .unwrap();
+    assert!(roles.iter().any(|r| r.as_str() == Some("biller")));
+    assert!(roles.iter().any(|r| r.as_str() == Some("clinician")));
+
+    let duplicate_response = app
+        .post_member_role(&token, &practice_id, &membership_id, "biller")
+        .await;
+    assert_eq!(duplicate_response.status().as_u16(), 409);
+
+    let remove_response = app
+        .delete_member_role(&token, &practice_id, &membership_id, "biller")
+        .await;
+    assert_eq!(remove_response.status().as_u16(), 200);
+
+    let missing_response = app
+        .delete_member_role(&token, &practice_id, &membership_id, "biller")
+        .await;
+    assert_eq!(missing_response.status().as_u16(), 404);
+    let missing_body: Value = missing_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        missing_body.get("code").unwrap().as_str().unwrap(),
+        "role_not_assigned"
+    );
+}
+
+#[tokio::test]
+async fn add_unknown_role_returns_400() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let membership_id = app
+        .membership_id_with_role(&token, &practice_id, "clinician")
+        .await;
+
+    let response = app
+        .post_member_role(&token, &practice_id, &membership_id, "janitor")
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(body.get("code").unwrap().as_str().unwrap(), "invalid_role");
+}
+
+#[tokio::test]
+async fn admin_cannot_grant_owner_role() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("admin1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let membership_id = app
+        .membership_id_with_role(&token, &practice_id, "clinician")
+        .await;
+
+    let response = app
+        .post_member_role(&token, &practice_id, &membership_id, "owner")
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        body.get("code").unwrap().as_str().unwrap(),
+        "cannot_manage_role"
+    );
+}
+
+#[tokio::test]
+async fn clinician_cannot_grant_roles_to_self() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician2@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let membership_id = app
+        .membership_id_with_role(&token, &practice_id, "clinician")
+        .await;
+
+    let response = app
+        .post_member_role(&token, &practice_id, &membership_id, "admin")
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn admin_cannot_deactivate_owner() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("admin1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let membership_id = app
+        .membership_id_with_role(&token, &practice_id, "owner")
+        .await;
+
+    let response = app
+        .patch_membership(&token, &practice_id, &membership_id, false)
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        body.get("code").unwrap().as_str().unwrap(),
+        "cannot_manage_membership"
+    );
+}
+
+#[tokio::test]
+async fn owner_deactivates_and_reactivates_membership() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let clinician_token = app
+        .signin_token("clinician5@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&clinician_token).await;
+    let membership_id = app
+        .membership_id_with_role(&clinician_token, &practice_id, "clinician")
+        .await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+
+    let deactivate_response = app
+        .patch_membership(&token, &practice_id, &membership_id, false)
+        .await;
+    assert_eq!(deactivate_response.status().as_u16(), 200);
+    let deactivate_body: Value = deactivate_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        deactivate_body.pointer("/member/is_active"),
+        Some(&Value::Bool(false))
+    );
+
+    // Inactive members drop out of the practice entirely.
+    let practices: Value = app
+        .get_practices(&clinician_token)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert!(
+        !practices
+            .get("practices")
+            .and_then(Value::as_array)
+            .unwrap()
+            .iter()
+            .any(|p| p.get("id").and_then(Value::as_str) == Some(practice_id.as_str()))
+    );
+
+    let reactivate_response = app
+        .patch_membership(&token, &practice_id, &membership_id, true)
+        .await;
+    assert_eq!(reactivate_response.status().as_u16(), 200);
+    let reactivate_body: Value = reactivate_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        reactivate_body.pointer("/member/is_active"),
+        Some(&Value::Bool(true))
+    );
+}
+
+#[tokio::test]
+async fn owner_of_a_self_created_practice_cannot_revoke_outsiders_sessions() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let seeded_practice_id = app.seeded_practice_id(&owner_token).await;
+    let target_token = app
+        .signin_token("clinician2@example.com", "Password123!")
+        .await;
+    let target_id = app.own_user_id(&target_token, &seeded_practice_id).await;
+    let members: Value = app
+        .get_members(&owner_token, &seeded_practice_id)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let target_membership_id = members["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["user_id"] == target_id.as_str())
+        .and_then(|m| m["membership_id"].as_str())
+        .expect("target membership not listed")
+        .to_string();
+
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+    let response = app
+        .post_practice(&token, &format!("Own Practice {}", uuid::Uuid::new_v4()))
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let practice_id = response
+        .json::<Value>()
+        .await
+        .expect("Failed to parse response body")
+        .pointer("/practice/id")
+        .and_then(Value::as_str)
+        .expect("practice id not found in response")
+        .to_string();
+
+    // Enrolling the target in the new practice is what would make them "a member".
+    let response = app
+        .insert_membership_directly(&token, &practice_id, &target_id)
+        .await;
+    assert!(
+        matches!(response.status().as_u16(), 401 | 403),
+        "Direct membership insert returned {}",
+        response.status()
+    );
+
+    let response = app
+        .delete_member_sessions(&token, &practice_id, &target_membership_id)
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+    let response = app
+        .delete_member_sessions(&token, &seeded_practice_id, &target_membership_id)
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app.get_members(&target_token, &seeded_practice_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.delete_practice(&token, &practice_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
@@ -0,0 +1,250 @@
+use breeze_ehr::{
+    domain::types::{email::Email, practice_role::PracticeRole},
+    utils::tracing::init_tracing,
+};
+use serde_json::Value;
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, body};
+
+const PASSWORD: &str = "Password123!";
+
+fn email(address: &str) -> Email {
+    Email::new(address.to_string()).expect("Invalid test email")
+}
+
+#[tokio::test]
+async fn memory_signin_requires_confirmation_and_signout_ends_session() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+
+    let address = format!("memory-user+{}@example.com", Uuid::new_v4());
+    let response = app.post_signup(&address, PASSWORD, None).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = app.post_signup(&address, PASSWORD, None).await;
+    assert_eq!(response.status().as_u16(), 409);
+
+    let response = app.post_signin(&address, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(
+        body(response).await.get("message").unwrap(),
+        "Email not confirmed"
+    );
+
+    app.memory()
+        .auth_service
+        .read()
+        .await
+        .confirm_email(&email(&address))
+        .await
+        .expect("Failed to confirm email");
+
+    let response = app.post_signin(&address, "WrongPass123!").await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(
+        body(response).await.get("message").unwrap(),
+        "Invalid login credentials"
+    );
+
+    let token = app.signin_token(&address, PASSWORD).await;
+
+    let response = app.post_retrieve_user_id(&token, &address).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(body(response).await.get("user_id").is_some());
+
+    let response = app.post_signout(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    // Access tokens stay valid until they expire, but the session behind them is gone.
+    let response = app.post_signout(&token).await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn memory_refresh_rotates_tokens() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, _) = app.memory_user(PASSWORD).await;
+
+    let response = app.post_signin(&address, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let signin = body(response).await;
+    let refresh_token = signin.get("refresh_token").and_then(Value::as_str).unwrap();
+
+    let response = app.post_refresh(refresh_token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let refreshed = body(response).await;
+    assert_ne!(refreshed.get("refresh_token"), signin.get("refresh_token"));
+    assert!(refreshed.get("token").and_then(Value::as_str).is_some());
+
+    let response = app.post_refresh(refresh_token).await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(
+        body(response).await.get("code").unwrap(),
+        "invalid_refresh_token"
+    );
+}
+
+#[tokio::test]
+async fn memory_password_reset_replaces_password_once() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, _) = app.memory_user(PASSWORD).await;
+
+    let response = app.post_forgot_password(&address).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let recovery_token = app
+        .memory()
+        .auth_service
+        .read()
+        .await
+        .recovery_token(&email(&address))
+        .await
+        .expect("No recovery token issued");
+
+    let new_password = "NewPassword456!";
+    let response = app.post_reset_password(&recovery_token, new_password).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.post_signin(&address, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 401);
+    let response = app.post_signin(&address, new_password).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app
+        .post_reset_password(&recovery_token, "Another789!")
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(
+        body(response).await.get("code").unwrap(),
+        "invalid_recovery_token"
+    );
+}
+
+#[tokio::test]
+async fn memory_totp_verification_elevates_session_to_aal2() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, _) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&address, PASSWORD).await;
+
+    let response = app.post_mfa_enroll(&token, "Work phone").await;
+    assert_eq!(response.status().as_u16(), 201);
+    let enrollment = body(response).await;
+    let factor_id = enrollment.get("factor_id").and_then(Value::as_str).unwrap();
+    let secret = enrollment.get("secret").and_then(Value::as_str).unwrap();
+
+    let response = app.post_mfa_challenge(&token, factor_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let challenge_id = body(response).await["challenge_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let code = TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        Secret::Encoded(secret.to_string()).to_bytes().unwrap(),
+    )
+    .unwrap()
+    .generate_current()
+    .unwrap();
+    let response = app
+        .post_mfa_verify(&token, factor_id, &challenge_id, &code)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let aal2_token = body(response).await["token"].as_str().unwrap().to_string();
+
+    let response = app.get_mfa_factors(&token).await;
+    assert_eq!(body(response).await["factors"][0]["status"], "verified");
+
+    let response = app.delete_mfa_factor(&token, factor_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let response = app.delete_mfa_factor(&aal2_token, factor_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn memory_delete_user_takes_a_platform_admin() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (admin_email, admin_id) = app.memory_user(PASSWORD).await;
+    let (clinician_email, clinician_id) = app.memory_user(PASSWORD).await;
+    let (operator_email, _) = app.memory_user(PASSWORD).await;
+
+    let practice_id = Uuid::new_v4().to_string();
+    let memberships = app.memory().membership_service.read().await;
+    memberships
+        .add_member(
+            &practice_id,
+            &admin_id,
+            &[PracticeRole::Owner, PracticeRole::Admin],
+        )
+        .await
+        .unwrap();
+    memberships
+        .add_member(&practice_id, &clinician_id, &[PracticeRole::Clinician])
+        .await
+        .unwrap();
+    drop(memberships);
+
+    let clinician_token = app.signin_token(&clinician_email, PASSWORD).await;
+    let response = app.delete_user(&clinician_token, &admin_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    // Practice roles stop at the practice; accounts are global.
+    let admin_token = app.signin_token(&admin_email, PASSWORD).await;
+    let response = app.delete_user(&admin_token, &clinician_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+    let response = app
+        .post_retrieve_user_id(&admin_token, &clinician_email)
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    app.memory()
+        .auth_service
+        .read()
+        .await
+        .grant_platform_admin(&email(&operator_email))
+        .await
+        .unwrap();
+    let operator_token = app.signin_token(&operator_email, PASSWORD).await;
+    let response = app
+        .post_retrieve_user_id(&operator_token, &clinician_email)
+        .await;
+    assert_eq!(body(response).await["user_id"], clinician_id.as_str());
+    let response = app.delete_user(&operator_token, &clinician_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.post_signin(&clinician_email, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let events = app.audit_events().await;
+    assert!(
+        events
+            .iter()
+            .any(|e| e.target.as_deref() == Some(clinician_id.as_str()))
+    );
+}
+
+#[tokio::test]
+async fn memory_postgres_only_routes_fail_fast() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&email, PASSWORD).await;
+
+    let response = app.get_practices(&token).await;
+    assert_eq!(response.status().as_u16(), 503);
+    assert_eq!(body(response).await["code"], "memory_backend_unsupported");
+
+    let response = app.post_practice(&token, "Memory Practice").await;
+    assert_eq!(response.status().as_u16(), 503);
+    assert_eq!(body(response).await["code"], "memory_backend_unsupported");
+}
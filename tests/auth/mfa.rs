@@ -0,0 +1,203 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::Value;
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+use crate::helpers::TestApp;
+
+fn current_totp_code(secret: &str) -> String {
+    let secret = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .expect("TOTP secret is not valid base32");
+    TOTP::new(Algorithm::SHA1, 6, 1, 30, secret)
+        .expect("Failed to build TOTP generator")
+        .generate_current()
+        .expect("Failed to generate TOTP code")
+}
+
+async fn enroll(app: &TestApp, token: &str) -> (String, String) {
+    let response = app.post_mfa_enroll(token, "Work phone").await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse enroll response body");
+    assert!(
+        body.get("uri")
+            .and_then(Value::as_str)
+            .unwrap()
+            .starts_with("otpauth://totp/")
+    );
+    assert!(
+        body.get("qr_code")
+            .and_then(Value::as_str)
+            .unwrap()
+            .contains("svg")
+    );
+
+    let factor_id = body.get("factor_id").and_then(Value::as_str).unwrap();
+    let secret = body.get("secret").and_then(Value::as_str).unwrap();
+    (factor_id.to_string(), secret.to_string())
+}
+
+async fn challenge(app: &TestApp, token: &str, factor_id: &str) -> String {
+    let response = app.post_mfa_challenge(token, factor_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse challenge response body");
+    body.get("challenge_id")
+        .and_then(Value::as_str)
+        .expect("challenge_id not found in response")
+        .to_string()
+}
+
+#[tokio::test]
+async fn mfa_factors_with_invalid_token_returns_401() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app.get_mfa_factors("invalid_token").await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn enroll_totp_lists_unverified_factor_and_unenrolls_with_200() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    app.clear_mailpit_messages().await;
+
+    let email = format!("new-user+{}@example.com", Uuid::new_v4());
+    let password = "StrongPass123!";
+    let token = app.signup_confirmed_user(&email, password).await;
+
+    let (factor_id, _) = enroll(&app, &token).await;
+
+    let factors_response = app.get_mfa_factors(&token).await;
+    assert_eq!(factors_response.status().as_u16(), 200);
+    let factors_body: Value = factors_response
+        .json()
+        .await
+        .expect("Failed to parse factors response body");
+    let factors = factors_body
+        .get("factors")
+        .and_then(Value::as_array)
+        .unwrap();
+    assert_eq!(factors.len(), 1);
+    assert_eq!(factors[0].get("id").unwrap().as_str().unwrap(), factor_id);
+    assert_eq!(
+        factors[0].get("status").unwrap().as_str().unwrap(),
+        "unverified"
+    );
+
+    // Unverified factors can be removed from an AAL1 session.
+    let unenroll_response = app.delete_mfa_factor(&token, &factor_id).await;
+    assert_eq!(unenroll_response.status().as_u16(), 200);
+
+    app.cleanup_user(&token, &email).await;
+}
+
+#[tokio::test]
+async fn verify_with_wrong_code_returns_401() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    app.clear_mailpit_messages().await;
+
+    let email = format!("new-user+{}@example.com", Uuid::new_v4());
+    let password = "StrongPass123!";
+    let token = app.signup_confirmed_user(&email, password).await;
+
+    let (factor_id, _) = enroll(&app, &token).await;
+    let challenge_id = challenge(&app, &token, &factor_id).await;
+
+    let response = app
+        .post_mfa_verify(&token, &factor_id, &challenge_id, "000000")
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse verify response body");
+    assert_eq!(
+        body.get("code").unwrap().as_str().unwrap(),
+        "invalid_mfa_code"
+    );
+
+    app.cleanup_user(&token, &email).await;
+}
+
+#[tokio::test]
+async fn verified_factor_requires_aal2_sessions() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    app.clear_mailpit_messages().await;
+
+    let email = format!("new-user+{}@example.com", Uuid::new_v4());
+    let password = "StrongPass123!";
+    let token = app.signup_confirmed_user(&email, password).await;
+
+    let (factor_id, secret) = enroll(&app, &token).await;
+    let challenge_id = challenge(&app, &token, &factor_id).await;
+
+    let verify_response = app
+        .post_mfa_verify(
+            &token,
+            &factor_id,
+            &challenge_id,
+            &current_totp_code(&secret),
+        )
+        .await;
+    assert_eq!(verify_response.status().as_u16(), 200);
+    let verify_body: Value = verify_response
+        .json()
+        .await
+        .expect("Failed to parse verify response body");
+    let aal2_token = verify_body
+        .get("token")
+        .and_then(Value::as_str)
+        .expect("token not found in response");
+
+    // A fresh password signin is only AAL1, which no longer gets past the guard.
+    let response = app.post_signin(&email, password).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let signin_body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse signin response body");
+    assert_eq!(signin_body["mfa_required"], true);
+    let aal1_token = signin_body["token"].as_str().unwrap();
+
+    let response = app.get_me(aal1_token).await;
+    assert_eq!(response.status().as_u16(), 403);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse me response body");
+    assert_eq!(body.get("code").unwrap().as_str().unwrap(), "mfa_required");
+
+    let response = app.delete_mfa_factor(aal1_token, &factor_id).await;
+    assert_eq!(response.status().as_u16(), 403);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse unenroll response body");
+    assert_eq!(body.get("code").unwrap().as_str().unwrap(), "mfa_required");
+
+    // It can still step up.
+    let response = app.get_mfa_factors(aal1_token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.get_me(aal2_token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.delete_mfa_factor(aal2_token, &factor_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    app.cleanup_user(aal2_token, &email).await;
+}
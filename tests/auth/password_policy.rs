@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use breeze_ehr::{
+    domain::{
+        interfaces::auth_service::AuthService,
+        types::{email::Email, password::Password, password_policy::PasswordPolicy},
+    },
+    utils::tracing::init_tracing,
+};
+use serde_json::{Value, json};
+use sha1::{Digest, Sha1};
+use uuid::Uuid;
+
+use crate::helpers::TestApp;
+
+async fn violation_codes(response: reqwest::Response) -> Vec<String> {
+    assert_eq!(response.status().as_u16(), 400);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(body["code"], "weak_password");
+    body["violations"]
+        .as_array()
+        .expect("violations missing")
+        .iter()
+        .map(|v| {
+            assert!(v["message"].as_str().is_some_and(|m| !m.is_empty()));
+            v["code"].as_str().unwrap().to_string()
+        })
+        .collect()
+}
+
+fn address(local: &str) -> String {
+    format!("{local}+{}@example.com", Uuid::new_v4())
+}
+
+#[tokio::test]
+async fn memory_weak_password_lists_every_broken_rule() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+
+    let response = app.post_signup(&address("new-user"), "weak", None).await;
+    assert_eq!(
+        violation_codes(response).await,
+        [
+            "too_short",
+            "missing_uppercase",
+            "missing_digit",
+            "missing_symbol"
+        ]
+    );
+
+    let response = app
+        .post_signup(&address("jordan"), "Jordan-2024!", None)
+        .await;
+    assert_eq!(violation_codes(response).await, ["contains_email"]);
+
+    let response = app
+        .post_signup(&address("other"), "Jordan-2024!", None)
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+}
+
+#[tokio::test]
+async fn memory_default_policy_matches_gotrue() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+
+    // Only ASCII letters count towards the case rules.
+    let response = app
+        .post_signup(&address("accents"), "ÉÉÉ-ééé-2024!", None)
+        .await;
+    assert_eq!(
+        violation_codes(response).await,
+        ["missing_lowercase", "missing_uppercase"]
+    );
+
+    // A space or `§` is not one of GoTrue's symbols.
+    let response = app
+        .post_signup(&address("space"), "Correct Horse 2024", None)
+        .await;
+    assert_eq!(violation_codes(response).await, ["missing_symbol"]);
+    let response = app
+        .post_signup(&address("section"), "Paragraph§2024", None)
+        .await;
+    assert_eq!(violation_codes(response).await, ["missing_symbol"]);
+
+    // 40 characters, but 76 bytes: past what bcrypt reads.
+    let long = format!("Aa1!{}", "é".repeat(36));
+    let response = app.post_signup(&address("long"), &long, None).await;
+    assert_eq!(violation_codes(response).await, ["too_long"]);
+
+    let response = app
+        .post_signup(&address("symbols"), "Backslash\\2024", None)
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+}
+
+#[tokio::test]
+async fn memory_configured_policy_counts_bytes_and_screens_breaches() {
+    init_tracing("info");
+    let breached = format!(
+        "# sample\n{:X}:52\n",
+        Sha1::digest("Summer-2024!".as_bytes())
+    );
+    let policy = PasswordPolicy {
+        min_length: 10,
+        require_lowercase: true,
+        breached: Arc::new(PasswordPolicy::parse_breached(&breached).unwrap()),
+        ..PasswordPolicy::default()
+    };
+    let app = TestApp::in_memory_with(|builder| builder.password_policy(policy)).await;
+
+    let response = app
+        .post_signup(&address("breach"), "Summer-2024!", None)
+        .await;
+    assert_eq!(violation_codes(response).await, ["breached"]);
+
+    // One byte short.
+    let response = app.post_signup(&address("short"), "Shrt-24!x", None).await;
+    assert_eq!(violation_codes(response).await, ["too_short"]);
+    // Seven characters, but ten bytes.
+    let response = app.post_signup(&address("bytes"), "Ab1!ééé", None).await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = app
+        .post_signup(&address("upper"), "ALLCAPS-2024!", None)
+        .await;
+    assert_eq!(violation_codes(response).await, ["missing_lowercase"]);
+
+    let (email, _) = app.memory_user("Autumn-2024!").await;
+    let token = app.signin_token(&email, "Autumn-2024!").await;
+    let response = app
+        .patch_me(
+            &token,
+            &json!({ "password": "Summer-2024!", "current_password": "Autumn-2024!" }),
+        )
+        .await;
+    assert_eq!(violation_codes(response).await, ["breached"]);
+}
+
+#[tokio::test]
+async fn memory_signin_accepts_passwords_set_under_an_older_policy() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+
+    let email = address("legacy");
+    app.memory()
+        .auth_service
+        .read()
+        .await
+        .create_confirmed_user(
+            &Email::new(email.clone()).unwrap(),
+            &Password::existing("legacy".to_string()),
+        )
+        .await
+        .expect("Failed to create user");
+
+    let response = app.post_signin(&email, "legacy").await;
+    assert_eq!(response.status().as_u16(), 200);
+}
@@ -0,0 +1,150 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::TestApp;
+
+#[tokio::test]
+async fn list_practices_returns_member_practices() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+
+    let response = app.get_practices(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let practices = body.get("practices").and_then(Value::as_array).unwrap();
+    assert!(
+        practices
+            .iter()
+            .any(|p| p.get("name").unwrap().as_str().unwrap() == "Test Therapy Practice")
+    );
+}
+
+#[tokio::test]
+async fn practice_lifecycle_create_get_rename_delete() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let name = format!("Lifecycle Practice {}", Uuid::new_v4());
+
+    let create_response = app.post_practice(&token, &name).await;
+    assert_eq!(create_response.status().as_u16(), 201);
+    let create_body: Value = create_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let practice_id = create_body
+        .pointer("/practice/id")
+        .and_then(Value::as_str)
+        .expect("practice id not found in response")
+        .to_string();
+
+    let get_response = app.get_practice(&token, &practice_id).await;
+    assert_eq!(get_response.status().as_u16(), 200);
+
+    let new_name = format!("Renamed Practice {}", Uuid::new_v4());
+    let rename_response = app.patch_practice(&token, &practice_id, &new_name).await;
+    assert_eq!(rename_response.status().as_u16(), 200);
+    let rename_body: Value = rename_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        rename_body
+            .pointer("/practice/name")
+            .unwrap()
+            .as_str()
+            .unwrap(),
+        new_name
+    );
+
+    let delete_response = app.delete_practice(&token, &practice_id).await;
+    assert_eq!(delete_response.status().as_u16(), 200);
+
+    let get_response = app.get_practice(&token, &practice_id).await;
+    assert_eq!(get_response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn create_practice_with_blank_name_returns_400() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+
+    let response = app.post_practice(&token, "   ").await;
+    assert_eq!(response.status().as_u16(), 400);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        body.get("code").unwrap().as_str().unwrap(),
+        "invalid_practice_name"
+    );
+}
+
+#[tokio::test]
+async fn get_practice_with_malformed_id_returns_400() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+
+    let response = app.get_practice(&token, "not-a-uuid").await;
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn clinician_cannot_rename_or_delete_practice() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let rename_response = app
+        .patch_practice(&token, &practice_id, "Hijacked Practice")
+        .await;
+    assert_eq!(rename_response.status().as_u16(), 403);
+    let body: Value = rename_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        body.get("code").unwrap().as_str().unwrap(),
+        "practice_forbidden"
+    );
+
+    let delete_response = app.delete_practice(&token, &practice_id).await;
+    assert_eq!(delete_response.status().as_u16(), 403);
+}
+
+#[tokio::test]
+async fn non_member_gets_404_for_practice() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&owner_token).await;
+
+    app.clear_mailpit_messages().await;
+    let email = format!("new-user+{}@example.com", Uuid::new_v4());
+    let token = app.signup_confirmed_user(&email, "StrongPass123!").await;
+
+    let response = app.get_practice(&token, &practice_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    app.cleanup_user(&token, &email).await;
+}
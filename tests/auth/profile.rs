@@ -0,0 +1,201 @@
+use breeze_ehr::{
+    domain::types::{audit::AuthAuditAction, email::Email},
+    utils::tracing::init_tracing,
+};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, assert_error, body};
+
+const PASSWORD: &str = "Password123!";
+const NEW_PASSWORD: &str = "NewPassword456!";
+
+#[tokio::test]
+async fn memory_user_updates_profile_metadata() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, user_id) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&email, PASSWORD).await;
+
+    let response = app.get_me(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let user = body(response).await["user"].clone();
+    assert_eq!(user["user_id"], user_id.as_str());
+    assert_eq!(user["email"], email.as_str());
+    assert_eq!(user["display_name"], Value::Null);
+    assert_eq!(user["credentials"], json!([]));
+
+    let response = app
+        .patch_me(
+            &token,
+            &json!({
+                "display_name": "  Dana Reyes ",
+                "credentials": ["LCSW", "PhD", "lcsw"],
+                "npi": "1234567893",
+                "phone": "(555) 123-4567",
+            }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let user = body(response).await["user"].clone();
+    assert_eq!(user["display_name"], "Dana Reyes");
+    assert_eq!(user["credentials"], json!(["LCSW", "PhD"]));
+    assert_eq!(user["npi"], "1234567893");
+    assert_eq!(user["phone"], "+15551234567");
+
+    // Omitted fields stay, and an empty string clears.
+    let response = app.patch_me(&token, &json!({ "phone": "" })).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let response = app.get_me(&token).await;
+    let user = body(response).await["user"].clone();
+    assert_eq!(user["phone"], Value::Null);
+    assert_eq!(user["npi"], "1234567893");
+
+    let response = app.patch_me(&token, &json!({ "npi": "1234567890" })).await;
+    assert_error(response, 400, "invalid_npi").await;
+    let response = app
+        .patch_me(&token, &json!({ "phone": "555-CALL-NOW" }))
+        .await;
+    assert_error(response, 400, "invalid_phone_number").await;
+    let response = app
+        .patch_me(&token, &json!({ "credentials": ["<script>"] }))
+        .await;
+    assert_error(response, 400, "invalid_input").await;
+
+    let events = app.audit_events().await;
+    assert!(
+        events
+            .iter()
+            .any(|e| e.action == AuthAuditAction::UpdateUser
+                && e.actor_user_id.as_deref() == Some(user_id.as_str()))
+    );
+}
+
+#[tokio::test]
+async fn memory_password_change_requires_current_password() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&email, PASSWORD).await;
+
+    let response = app
+        .patch_me(&token, &json!({ "password": NEW_PASSWORD }))
+        .await;
+    assert_error(response, 400, "invalid_input").await;
+
+    let response = app
+        .patch_me(
+            &token,
+            &json!({ "password": NEW_PASSWORD, "current_password": "WrongPassword1!" }),
+        )
+        .await;
+    assert_error(response, 403, "invalid_current_password").await;
+
+    let response = app
+        .patch_me(
+            &token,
+            &json!({ "password": "weak", "current_password": PASSWORD }),
+        )
+        .await;
+    assert_error(response, 400, "weak_password").await;
+
+    let response = app
+        .patch_me(
+            &token,
+            &json!({ "password": NEW_PASSWORD, "current_password": PASSWORD }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.post_signin(&email, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 401);
+    let response = app.post_signin(&email, NEW_PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn memory_email_change_waits_for_confirmation() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+    let (taken_email, _) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&email, PASSWORD).await;
+    let new_email = format!("renamed+{}@example.com", Uuid::new_v4());
+
+    let response = app
+        .patch_me(
+            &token,
+            &json!({ "email": taken_email, "current_password": PASSWORD }),
+        )
+        .await;
+    assert_error(response, 409, "email_already_in_use").await;
+
+    let response = app
+        .patch_me(&token, &json!({ "email": "not-an-email" }))
+        .await;
+    assert_error(response, 400, "invalid_email").await;
+
+    let response = app
+        .patch_me(
+            &token,
+            &json!({ "email": new_email, "current_password": PASSWORD }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let user = body(response).await["user"].clone();
+    assert_eq!(user["email"], email.as_str());
+    assert_eq!(user["pending_email"], new_email.as_str());
+
+    // The old address keeps working until the new one is confirmed.
+    let response = app.post_signin(&email, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    app.memory()
+        .auth_service
+        .read()
+        .await
+        .confirm_email_change(&Email::new(new_email.clone()).unwrap())
+        .await
+        .expect("Failed to confirm email change");
+
+    let response = app.get_me(&token).await;
+    let user = body(response).await["user"].clone();
+    assert_eq!(user["email"], new_email.as_str());
+    assert_eq!(user["pending_email"], Value::Null);
+    let response = app.post_signin(&new_email, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn profile_ignores_metadata_written_around_the_api() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+    let address = format!("profile+{}@example.com", Uuid::new_v4());
+    let token = app.signup_confirmed_user(&address, PASSWORD).await;
+
+    let response = app
+        .patch_me(
+            &token,
+            &json!({ "npi": "1234567893", "phone": "5551234567" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    // Users can always write their own `user_metadata`, so the profile mustn't come from it.
+    let response = app
+        .update_user_metadata_directly(
+            &token,
+            &json!({ "npi": "0000000000", "phone": "not a phone", "display_name": "Dr. Nobody" }),
+        )
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.get_me(&token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let user = body(response).await["user"].clone();
+    assert_eq!(user["npi"], "1234567893");
+    assert_eq!(user["phone"], "+15551234567");
+    assert_eq!(user["display_name"], Value::Null);
+
+    app.cleanup_user(&token, &address).await;
+}
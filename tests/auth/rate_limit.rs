@@ -0,0 +1,118 @@
+use breeze_ehr::{
+    domain::types::rate_limit::{RateLimitConfig, RateLimitPolicy, RouteRateLimit},
+    utils::tracing::init_tracing,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::TestApp;
+
+const PASSWORD: &str = "Password123!";
+
+fn header(response: &reqwest::Response, name: &str) -> u64 {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| panic!("{name} header missing"))
+}
+
+fn policy(spec: &str) -> RateLimitPolicy {
+    RateLimitPolicy::parse(spec).expect("Invalid test policy")
+}
+
+async fn app_with(rate_limits: RateLimitConfig) -> TestApp {
+    TestApp::in_memory_with(|builder| builder.rate_limits(rate_limits)).await
+}
+
+#[tokio::test]
+async fn route_policy_limits_anonymous_callers_per_ip() {
+    init_tracing("info");
+    let app = app_with(RateLimitConfig {
+        routes: vec![RouteRateLimit::parse("POST /api/auth/signup=2/1h").unwrap()],
+        ..RateLimitConfig::default()
+    })
+    .await;
+    let address = || format!("rate-limit+{}@example.com", Uuid::new_v4());
+
+    for remaining in [1, 0] {
+        let response = app
+            .post_signup_from(&address(), PASSWORD, "203.0.113.7")
+            .await;
+        assert_eq!(response.status().as_u16(), 201);
+        assert_eq!(header(&response, "RateLimit-Limit"), 2);
+        assert_eq!(header(&response, "RateLimit-Remaining"), remaining);
+        assert!(header(&response, "RateLimit-Reset") <= 3600);
+    }
+
+    let response = app
+        .post_signup_from(&address(), PASSWORD, "203.0.113.7")
+        .await;
+    assert_eq!(response.status().as_u16(), 429);
+    assert_eq!(header(&response, "RateLimit-Remaining"), 0);
+    let retry_after = header(&response, "Retry-After");
+    assert!(retry_after > 0 && retry_after <= 1800);
+    let body: Value = response.json().await.expect("Failed to parse body");
+    assert_eq!(body.get("code").unwrap().as_str().unwrap(), "rate_limited");
+
+    let response = app
+        .post_signup_from(&address(), PASSWORD, "198.51.100.4")
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+
+    // Other routes draw on the default policy, not the signup bucket.
+    let response = app.health_check().await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        header(&response, "RateLimit-Limit"),
+        RateLimitConfig::default().anonymous.limit as u64
+    );
+}
+
+#[tokio::test]
+async fn authenticated_callers_are_limited_per_user() {
+    init_tracing("info");
+    let app = app_with(RateLimitConfig {
+        anonymous: policy("100/1m"),
+        authenticated: policy("3/1h"),
+        routes: Vec::new(),
+        enabled: true,
+    })
+    .await;
+    let (first_email, _) = app.memory_user(PASSWORD).await;
+    let (second_email, _) = app.memory_user(PASSWORD).await;
+    let first = app.signin_token(&first_email, PASSWORD).await;
+    let second = app.signin_token(&second_email, PASSWORD).await;
+
+    for _ in 0..3 {
+        let response = app.get_mfa_factors(&first).await;
+        assert_eq!(response.status().as_u16(), 200);
+    }
+    let response = app.get_mfa_factors(&first).await;
+    assert_eq!(response.status().as_u16(), 429);
+
+    // Same IP, different user: a separate bucket.
+    let response = app.get_mfa_factors(&second).await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(header(&response, "RateLimit-Remaining"), 2);
+
+    // A forged token is counted against the caller's IP instead.
+    let response = app.get_mfa_factors("not-a-jwt").await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(header(&response, "RateLimit-Limit"), 100);
+}
+
+#[tokio::test]
+async fn disabled_rate_limits_add_no_headers() {
+    init_tracing("info");
+    let app = app_with(RateLimitConfig {
+        enabled: false,
+        ..RateLimitConfig::default()
+    })
+    .await;
+
+    let response = app.health_check().await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(response.headers().get("RateLimit-Limit").is_none());
+}
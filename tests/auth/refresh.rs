@@ -0,0 +1,136 @@
+use breeze_ehr::utils::{config::AppConfig, tracing::init_tracing};
+use jsonwebtoken::{EncodingKey, Header, encode};
+use secrecy::ExposeSecret;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::helpers::TestApp;
+
+fn expired_token() -> String {
+    let config = AppConfig::for_tests();
+    let secret = config.supabase_jwt_secret.expose_secret();
+    let key = EncodingKey::from_base64_secret(secret)
+        .unwrap_or_else(|_| EncodingKey::from_secret(secret.as_bytes()));
+
+    let exp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 3600;
+    let claims = json!({
+        "sub": Uuid::new_v4().to_string(),
+        "aud": "authenticated",
+        "role": "authenticated",
+        "exp": exp,
+    });
+
+    encode(&Header::default(), &claims, &key).expect("Failed to encode test token")
+}
+
+#[tokio::test]
+async fn signin_returns_refresh_token_and_expiry() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app.post_signin("owner1@example.com", "Password123!").await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert!(body.get("token").and_then(Value::as_str).is_some());
+    assert!(body.get("refresh_token").and_then(Value::as_str).is_some());
+    assert!(body.get("expires_in").and_then(Value::as_i64).unwrap() > 0);
+    assert!(body.get("expires_at").and_then(Value::as_i64).unwrap() > 0);
+}
+
+#[tokio::test]
+async fn refresh_with_valid_refresh_token_returns_new_session() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    app.clear_mailpit_messages().await;
+
+    let email = format!("new-user+{}@example.com", Uuid::new_v4());
+    let password = "StrongPass123!";
+    app.signup_confirmed_user(&email, password).await;
+
+    let signin_body: Value = app
+        .post_signin(&email, password)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse signin response body");
+    let refresh_token = signin_body
+        .get("refresh_token")
+        .and_then(Value::as_str)
+        .expect("refresh_token not found in response");
+
+    let response = app.post_refresh(refresh_token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse refresh response body");
+    let token = body
+        .get("token")
+        .and_then(Value::as_str)
+        .expect("token not found in response");
+    let new_refresh_token = body
+        .get("refresh_token")
+        .and_then(Value::as_str)
+        .expect("refresh_token not found in response");
+    assert_ne!(new_refresh_token, refresh_token);
+
+    app.cleanup_user(token, &email).await;
+}
+
+#[tokio::test]
+async fn refresh_with_invalid_refresh_token_returns_401() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app.post_refresh("not-a-refresh-token").await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        body.get("code").unwrap().as_str().unwrap(),
+        "invalid_refresh_token"
+    );
+}
+
+#[tokio::test]
+async fn expired_access_token_returns_401_expired_token() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app.post_signout(&expired_token()).await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(body.get("code").unwrap().as_str().unwrap(), "expired_token");
+}
+
+#[tokio::test]
+async fn malformed_access_token_returns_401_invalid_token() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app.post_signout("invalid_token").await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(body.get("code").unwrap().as_str().unwrap(), "invalid_token");
+}
@@ -0,0 +1,113 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use uuid::Uuid;
+
+use crate::helpers::TestApp;
+
+#[tokio::test]
+async fn forgot_password_with_invalid_email_returns_400() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app.post_forgot_password("invalid-email").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+    let response_body: serde_json::Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        response_body.get("message").unwrap().as_str().unwrap(),
+        "Invalid email format"
+    );
+}
+
+#[tokio::test]
+async fn forgot_password_for_unknown_email_returns_200() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let email = format!("unknown-user+{}@example.com", Uuid::new_v4());
+    let response = app.post_forgot_password(&email).await;
+
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn reset_password_with_invalid_token_returns_401() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app
+        .post_reset_password("not-a-recovery-token", "NewStrongPass123!")
+        .await;
+
+    assert_eq!(response.status().as_u16(), 401);
+    let response_body: serde_json::Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        response_body.get("code").unwrap().as_str().unwrap(),
+        "invalid_recovery_token"
+    );
+}
+
+#[tokio::test]
+async fn reset_password_with_weak_password_returns_400() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let response = app.post_reset_password("abc123", "weak").await;
+
+    assert_eq!(response.status().as_u16(), 400);
+    let response_body: serde_json::Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        response_body.get("code").unwrap().as_str().unwrap(),
+        "weak_password"
+    );
+}
+
+#[tokio::test]
+async fn forgot_password_email_allows_reset_and_signin_with_new_password() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    app.clear_mailpit_messages().await;
+
+    let email = format!("new-user+{}@example.com", Uuid::new_v4());
+    let old_password = "StrongPass123!";
+    let new_password = "EvenStronger456!";
+
+    app.signup_confirmed_user(&email, old_password).await;
+    app.clear_mailpit_messages().await;
+
+    let forgot_response = app.post_forgot_password(&email).await;
+    assert_eq!(forgot_response.status().as_u16(), 200);
+
+    let recovery_token = app.email_link_token(&email).await;
+
+    let reset_response = app.post_reset_password(&recovery_token, new_password).await;
+    assert_eq!(reset_response.status().as_u16(), 200);
+
+    let old_signin_response = app.post_signin(&email, old_password).await;
+    assert_eq!(old_signin_response.status().as_u16(), 401);
+
+    let token = app.signin_token(&email, new_password).await;
+
+    // Recovery tokens are single use.
+    let reuse_response = app.post_reset_password(&recovery_token, old_password).await;
+    assert_eq!(reuse_response.status().as_u16(), 401);
+    let reuse_body: serde_json::Value = reuse_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        reuse_body.get("code").unwrap().as_str().unwrap(),
+        "expired_recovery_token"
+    );
+
+    app.cleanup_user(&token, &email).await;
+}
@@ -29,3 +29,23 @@ async fn retrieve_user_id_returns_200() {
         .expect("Failed to parse response body");
     assert!(response_body.get("user_id").is_some());
 }
+
+#[tokio::test]
+async fn clinician_cannot_look_up_other_users() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+
+    let own_response = app
+        .post_retrieve_user_id(&token, "clinician1@example.com")
+        .await;
+    assert_eq!(own_response.status().as_u16(), 200);
+
+    for email in ["clinician2@example.com", "nobody@example.com"] {
+        let response = app.post_retrieve_user_id(&token, email).await;
+        assert_eq!(response.status().as_u16(), 403, "lookup of {email}");
+    }
+}
@@ -0,0 +1,166 @@
+use breeze_ehr::{
+    domain::types::{audit::AuthAuditAction, practice_role::PracticeRole},
+    utils::tracing::init_tracing,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, body};
+
+const PASSWORD: &str = "Password123!";
+
+async fn assert_revoked(response: reqwest::Response) {
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(body(response).await["code"], "session_revoked");
+}
+
+/// The caller's session list, with the id of the session marked `current`.
+async fn sessions(app: &TestApp, token: &str) -> (Vec<Value>, String) {
+    let response = app.get_sessions(token).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let sessions = body(response).await["sessions"]
+        .as_array()
+        .expect("sessions should be an array")
+        .clone();
+    let current = sessions
+        .iter()
+        .find(|s| s["current"] == true)
+        .expect("current session should be listed")["session_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    (sessions, current)
+}
+
+#[tokio::test]
+async fn user_lists_and_revokes_one_session() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, user_id) = app.memory_user(PASSWORD).await;
+
+    let laptop = app.signin_token(&email, PASSWORD).await;
+    let response = app.post_signin(&email, PASSWORD).await;
+    let phone_session = body(response).await;
+    let phone = phone_session["token"].as_str().unwrap();
+    let phone_refresh = phone_session["refresh_token"].as_str().unwrap();
+
+    let (_, phone_id) = sessions(&app, phone).await;
+    let (listed, laptop_id) = sessions(&app, &laptop).await;
+    assert_eq!(listed.len(), 2);
+    assert_ne!(laptop_id, phone_id);
+    assert!(listed.iter().all(|s| s["ip_address"] == "127.0.0.1"));
+    assert!(listed.iter().all(|s| s["last_seen_at"].as_i64().is_some()));
+
+    let response = app.delete_session(&laptop, &phone_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_revoked(app.get_mfa_factors(phone).await).await;
+    let response = app.post_refresh(phone_refresh).await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    let (listed, _) = sessions(&app, &laptop).await;
+    assert_eq!(listed.len(), 1);
+
+    // Another user's session can't be revoked, or even confirmed to exist.
+    let (other_email, _) = app.memory_user(PASSWORD).await;
+    let other = app.signin_token(&other_email, PASSWORD).await;
+    let response = app.delete_session(&other, &laptop_id).await;
+    assert_eq!(response.status().as_u16(), 404);
+    assert_eq!(body(response).await["code"], "session_not_found");
+
+    let events = app.audit_events().await;
+    assert!(
+        events
+            .iter()
+            .any(|e| e.action == AuthAuditAction::RevokeSessions
+                && e.actor_user_id.as_deref() == Some(user_id.as_str())
+                && e.target.as_deref() == Some(phone_id.as_str()))
+    );
+}
+
+#[tokio::test]
+async fn user_revokes_all_sessions() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (email, _) = app.memory_user(PASSWORD).await;
+
+    let laptop = app.signin_token(&email, PASSWORD).await;
+    let phone = app.signin_token(&email, PASSWORD).await;
+    let response = app.get_mfa_factors(&phone).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app.delete_sessions(&laptop).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    assert_revoked(app.get_mfa_factors(&laptop).await).await;
+    assert_revoked(app.get_mfa_factors(&phone).await).await;
+
+    let token = app.signin_token(&email, PASSWORD).await;
+    let (listed, _) = sessions(&app, &token).await;
+    assert_eq!(listed.len(), 1);
+}
+
+#[tokio::test]
+async fn practice_admin_revokes_member_sessions() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (owner_email, owner_id) = app.memory_user(PASSWORD).await;
+    let (admin_email, admin_id) = app.memory_user(PASSWORD).await;
+    let (clinician_email, clinician_id) = app.memory_user(PASSWORD).await;
+
+    let practice_id = Uuid::new_v4().to_string();
+    let memberships = app.memory().membership_service.read().await;
+    let owner = memberships
+        .add_member(&practice_id, &owner_id, &[PracticeRole::Owner])
+        .await
+        .unwrap();
+    memberships
+        .add_member(&practice_id, &admin_id, &[PracticeRole::Admin])
+        .await
+        .unwrap();
+    let clinician = memberships
+        .add_member(&practice_id, &clinician_id, &[PracticeRole::Clinician])
+        .await
+        .unwrap();
+    drop(memberships);
+
+    let owner_token = app.signin_token(&owner_email, PASSWORD).await;
+    let admin_token = app.signin_token(&admin_email, PASSWORD).await;
+    let clinician_token = app.signin_token(&clinician_email, PASSWORD).await;
+    let response = app.get_mfa_factors(&clinician_token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app
+        .delete_member_sessions(&clinician_token, &practice_id, &owner.membership_id)
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(body(response).await["code"], "insufficient_role");
+
+    let response = app
+        .delete_member_sessions(&admin_token, &practice_id, &owner.membership_id)
+        .await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(body(response).await["code"], "cannot_manage_membership");
+    let response = app.get_mfa_factors(&owner_token).await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = app
+        .delete_member_sessions(&admin_token, &practice_id, &Uuid::new_v4().to_string())
+        .await;
+    assert_eq!(response.status().as_u16(), 404);
+
+    let response = app
+        .delete_member_sessions(&admin_token, &practice_id, &clinician.membership_id)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_revoked(app.get_mfa_factors(&clinician_token).await).await;
+
+    let events = app.audit_events().await;
+    assert!(
+        events
+            .iter()
+            .any(|e| e.action == AuthAuditAction::RevokeSessions
+                && e.actor_user_id.as_deref() == Some(admin_id.as_str())
+                && e.target.as_deref() == Some(clinician_id.as_str()))
+    );
+}
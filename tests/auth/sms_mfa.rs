@@ -0,0 +1,253 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use breeze_ehr::utils::{clock::unix_now, tracing::init_tracing};
+use hmac::{Hmac, Mac};
+use secrecy::SecretString;
+use sha2::Sha256;
+
+use crate::helpers::{TestApp, body};
+
+const PASSWORD: &str = "Password123!";
+const HOOK_KEY: &[u8] = b"send-sms-hook-test-key";
+
+/// The six-digit code in the last text sent to `phone`.
+async fn texted_code(app: &TestApp, phone: &str) -> String {
+    let sent = app.memory().sms_gateway.read().await.sent().await;
+    let sms = sent
+        .iter()
+        .rev()
+        .find(|sms| sms.to == phone)
+        .expect("No text sent to this number");
+    sms.body
+        .split(|c: char| !c.is_ascii_digit())
+        .find(|word| word.len() == 6)
+        .expect("No code in text")
+        .to_string()
+}
+
+/// Challenges and verifies `factor_id` with `token`, returning the AAL2 token.
+async fn step_up(app: &TestApp, token: &str, factor_id: &str, phone: &str) -> String {
+    let response = app.post_mfa_challenge(token, factor_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let challenge_id = body(response).await["challenge_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let code = texted_code(app, phone).await;
+
+    let response = app
+        .post_mfa_verify(token, factor_id, &challenge_id, &code)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    body(response).await["token"].as_str().unwrap().to_string()
+}
+
+fn sign(id: &str, timestamp: i64, payload: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(HOOK_KEY).unwrap();
+    mac.update(format!("{id}.{timestamp}.{payload}").as_bytes());
+    format!("v1,{}", STANDARD.encode(mac.finalize().into_bytes()))
+}
+
+#[tokio::test]
+async fn memory_sms_factor_texts_single_use_code_and_elevates_to_aal2() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, _) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&address, PASSWORD).await;
+
+    let response = app
+        .post_mfa_enroll_phone(&token, "(555) 201-0123", "Mobile")
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let enrollment = body(response).await;
+    assert_eq!(enrollment["phone"], "+15552010123");
+    let factor_id = enrollment["factor_id"].as_str().unwrap().to_string();
+
+    let response = app.post_mfa_challenge(&token, &factor_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let challenge_id = body(response).await["challenge_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let code = texted_code(&app, "+15552010123").await;
+
+    let response = app
+        .post_mfa_verify(&token, &factor_id, &challenge_id, &code)
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    let aal2_token = body(response).await["token"].as_str().unwrap().to_string();
+
+    let response = app.get_mfa_factors(&token).await;
+    let factor = &body(response).await["factors"][0];
+    assert_eq!(factor["factor_type"], "phone");
+    assert_eq!(factor["phone"], "+15552010123");
+    assert_eq!(factor["status"], "verified");
+
+    // The code went with its challenge.
+    let response = app
+        .post_mfa_verify(&aal2_token, &factor_id, &challenge_id, &code)
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(body(response).await["code"], "invalid_mfa_code");
+
+    let response = app.delete_mfa_factor(&aal2_token, &factor_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn memory_verified_factor_requires_aal2_for_every_session() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, _) = app.memory_user(PASSWORD).await;
+
+    let response = app.post_signin(&address, PASSWORD).await;
+    assert_eq!(body(response).await["mfa_required"], false);
+    let earlier_token = app.signin_token(&address, PASSWORD).await;
+    assert_eq!(app.get_me(&earlier_token).await.status().as_u16(), 200);
+
+    let token = app.signin_token(&address, PASSWORD).await;
+    let response = app
+        .post_mfa_enroll_phone(&token, "+15552010123", "Mobile")
+        .await;
+    let factor_id = body(response).await["factor_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let aal2_token = step_up(&app, &token, &factor_id, "+15552010123").await;
+    assert_eq!(app.get_me(&aal2_token).await.status().as_u16(), 200);
+
+    // Sessions already in use are held back too.
+    let response = app.get_me(&earlier_token).await;
+    assert_eq!(response.status().as_u16(), 403);
+    assert_eq!(body(response).await["code"], "mfa_required");
+
+    let response = app.post_signin(&address, PASSWORD).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let signin = body(response).await;
+    assert_eq!(signin["mfa_required"], true);
+    let aal1_token = signin["token"].as_str().unwrap().to_string();
+
+    for response in [
+        app.get_me(&aal1_token).await,
+        app.get_sessions(&aal1_token).await,
+        app.post_mfa_enroll(&aal1_token, "Another").await,
+    ] {
+        assert_eq!(response.status().as_u16(), 403);
+        assert_eq!(body(response).await["code"], "mfa_required");
+    }
+    assert_eq!(
+        app.get_mfa_factors(&aal1_token).await.status().as_u16(),
+        200
+    );
+
+    // The first number's code is still throttled, so step up with a second one.
+    let response = app
+        .post_mfa_enroll_phone(&aal2_token, "+15552010124", "Desk")
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let second_factor_id = body(response).await["factor_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = app.post_mfa_challenge(&aal1_token, &factor_id).await;
+    assert_eq!(response.status().as_u16(), 429);
+    let stepped_up = step_up(&app, &aal1_token, &second_factor_id, "+15552010124").await;
+    assert_eq!(app.get_me(&stepped_up).await.status().as_u16(), 200);
+
+    // Without a verified factor left, AAL1 is enough again.
+    for id in [&factor_id, &second_factor_id] {
+        let response = app.delete_mfa_factor(&stepped_up, id).await;
+        assert_eq!(response.status().as_u16(), 200);
+    }
+    assert_eq!(app.get_me(&earlier_token).await.status().as_u16(), 200);
+    let response = app.post_signin(&address, PASSWORD).await;
+    assert_eq!(body(response).await["mfa_required"], false);
+}
+
+#[tokio::test]
+async fn memory_sms_challenge_throttles_resends_and_rejects_wrong_codes() {
+    init_tracing("info");
+    let app = TestApp::in_memory().await;
+    let (address, _) = app.memory_user(PASSWORD).await;
+    let token = app.signin_token(&address, PASSWORD).await;
+
+    let response = app
+        .post_mfa_enroll_phone(&token, "not a phone", "Mobile")
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = app
+        .post_mfa_enroll_phone(&token, "+44 7700 900123", "Mobile")
+        .await;
+    assert_eq!(response.status().as_u16(), 201);
+    let factor_id = body(response).await["factor_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = app.post_mfa_challenge(&token, &factor_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let challenge_id = body(response).await["challenge_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = app.post_mfa_challenge(&token, &factor_id).await;
+    assert_eq!(response.status().as_u16(), 429);
+    let retry_after: u64 = response
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .expect("Missing Retry-After header");
+    assert!(retry_after > 0 && retry_after <= 30);
+    assert_eq!(body(response).await["code"], "sms_resend_throttled");
+    assert_eq!(app.memory().sms_gateway.read().await.sent().await.len(), 1);
+
+    let code = texted_code(&app, "+447700900123").await;
+    let wrong = if code == "000000" { "111111" } else { "000000" };
+    let response = app
+        .post_mfa_verify(&token, &factor_id, &challenge_id, wrong)
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(body(response).await["code"], "invalid_mfa_code");
+
+    // A wrong guess spends the challenge too.
+    let response = app
+        .post_mfa_verify(&token, &factor_id, &challenge_id, &code)
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn send_sms_hook_delivers_only_signed_calls() {
+    init_tracing("info");
+    let secret = format!("v1,whsec_{}", STANDARD.encode(HOOK_KEY));
+    let app =
+        TestApp::in_memory_with(|builder| builder.send_sms_hook_secret(SecretString::from(secret)))
+            .await;
+
+    let payload = r#"{"user":{"id":"u1","phone":"15552010123"},"sms":{"otp":"482913"}}"#;
+    let now = unix_now();
+
+    let response = app
+        .post_send_sms_hook(payload, now, &sign("msg_test", now, payload))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(texted_code(&app, "+15552010123").await, "482913");
+
+    let tampered = payload.replace("482913", "000000");
+    let response = app
+        .post_send_sms_hook(&tampered, now, &sign("msg_test", now, payload))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+    assert_eq!(body(response).await["code"], "invalid_hook_signature");
+
+    let stale = now - 3600;
+    let response = app
+        .post_send_sms_hook(payload, stale, &sign("msg_test", stale, payload))
+        .await;
+    assert_eq!(response.status().as_u16(), 401);
+
+    assert_eq!(app.memory().sms_gateway.read().await.sent().await.len(), 1);
+}
@@ -0,0 +1,207 @@
+use breeze_ehr::utils::tracing::init_tracing;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, error_code};
+
+async fn team_id(response: reqwest::Response) -> String {
+    assert_eq!(response.status().as_u16(), 201);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    body.pointer("/team/id")
+        .and_then(Value::as_str)
+        .expect("team id not found in response")
+        .to_string()
+}
+
+#[tokio::test]
+async fn list_teams_returns_seeded_teams() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician1@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let response = app.get_teams(&token, &practice_id).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert!(
+        body.get("teams")
+            .and_then(Value::as_array)
+            .unwrap()
+            .iter()
+            .any(|t| t.get("name").and_then(Value::as_str) == Some("Operations"))
+    );
+}
+
+#[tokio::test]
+async fn team_lifecycle_create_rename_delete() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let name = format!("Lifecycle Team {}", Uuid::new_v4());
+
+    let team_id = team_id(app.post_team(&token, &practice_id, &name).await).await;
+
+    // Uniqueness is case-insensitive, matching `idx_teams_name_unique`.
+    let duplicate_response = app
+        .post_team(&token, &practice_id, &name.to_uppercase())
+        .await;
+    assert_eq!(duplicate_response.status().as_u16(), 409);
+    assert_eq!(error_code(duplicate_response).await, "team_name_taken");
+
+    let new_name = format!("Renamed Team {}", Uuid::new_v4());
+    let rename_response = app
+        .patch_team(&token, &practice_id, &team_id, &new_name)
+        .await;
+    assert_eq!(rename_response.status().as_u16(), 200);
+    let rename_body: Value = rename_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(rename_body.pointer("/team/name").unwrap(), &new_name);
+
+    let rename_to_seeded_response = app
+        .patch_team(&token, &practice_id, &team_id, "operations")
+        .await;
+    assert_eq!(rename_to_seeded_response.status().as_u16(), 409);
+
+    let delete_response = app.delete_team(&token, &practice_id, &team_id).await;
+    assert_eq!(delete_response.status().as_u16(), 200);
+
+    let get_response = app.get_team(&token, &practice_id, &team_id).await;
+    assert_eq!(get_response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn supervisor_adds_and_removes_team_member() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let owner_token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&owner_token).await;
+    let team_id = team_id(
+        app.post_team(
+            &owner_token,
+            &practice_id,
+            &format!("Roster Team {}", Uuid::new_v4()),
+        )
+        .await,
+    )
+    .await;
+
+    let clinician_token = app
+        .signin_token("clinician2@example.com", "Password123!")
+        .await;
+    let clinician_id = app.own_user_id(&clinician_token, &practice_id).await;
+
+    let supervisor_token = app
+        .signin_token("supervisor1@example.com", "Password123!")
+        .await;
+
+    let add_response = app
+        .post_team_member(&supervisor_token, &practice_id, &team_id, &clinician_id)
+        .await;
+    assert_eq!(add_response.status().as_u16(), 201);
+    let add_body: Value = add_response
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    assert_eq!(
+        add_body.pointer("/team/members/0/user_id").unwrap(),
+        &clinician_id
+    );
+
+    let duplicate_response = app
+        .post_team_member(&supervisor_token, &practice_id, &team_id, &clinician_id)
+        .await;
+    assert_eq!(duplicate_response.status().as_u16(), 409);
+    assert_eq!(error_code(duplicate_response).await, "already_on_team");
+
+    let remove_response = app
+        .delete_team_member(&supervisor_token, &practice_id, &team_id, &clinician_id)
+        .await;
+    assert_eq!(remove_response.status().as_u16(), 200);
+
+    let remove_again_response = app
+        .delete_team_member(&supervisor_token, &practice_id, &team_id, &clinician_id)
+        .await;
+    assert_eq!(remove_again_response.status().as_u16(), 404);
+    assert_eq!(error_code(remove_again_response).await, "not_on_team");
+
+    app.delete_team(&owner_token, &practice_id, &team_id).await;
+}
+
+#[tokio::test]
+async fn adding_non_member_returns_400() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app.signin_token("owner1@example.com", "Password123!").await;
+    let practice_id = app.seeded_practice_id(&token).await;
+    let team_id = team_id(
+        app.post_team(
+            &token,
+            &practice_id,
+            &format!("Outsider Team {}", Uuid::new_v4()),
+        )
+        .await,
+    )
+    .await;
+
+    let response = app
+        .post_team_member(&token, &practice_id, &team_id, &Uuid::new_v4().to_string())
+        .await;
+    assert_eq!(response.status().as_u16(), 400);
+    assert_eq!(error_code(response).await, "not_practice_member");
+
+    app.delete_team(&token, &practice_id, &team_id).await;
+}
+
+#[tokio::test]
+async fn clinician_cannot_create_or_staff_teams() {
+    init_tracing("info");
+    let app = TestApp::new().await;
+
+    let token = app
+        .signin_token("clinician3@example.com", "Password123!")
+        .await;
+    let practice_id = app.seeded_practice_id(&token).await;
+
+    let create_response = app
+        .post_team(
+            &token,
+            &practice_id,
+            &format!("Rogue Team {}", Uuid::new_v4()),
+        )
+        .await;
+    assert_eq!(create_response.status().as_u16(), 403);
+    assert_eq!(error_code(create_response).await, "team_forbidden");
+
+    let teams: Value = app
+        .get_teams(&token, &practice_id)
+        .await
+        .json()
+        .await
+        .expect("Failed to parse response body");
+    let team_id = teams
+        .pointer("/teams/0/id")
+        .and_then(Value::as_str)
+        .unwrap()
+        .to_string();
+    let user_id = app.own_user_id(&token, &practice_id).await;
+
+    let add_response = app
+        .post_team_member(&token, &practice_id, &team_id, &user_id)
+        .await;
+    assert_eq!(add_response.status().as_u16(), 403);
+}